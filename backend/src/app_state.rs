@@ -7,12 +7,17 @@ use std::{env, sync::Arc};
 
 use backend_shared::profanities_checker::ProfanitiesChecker;
 
-use crate::integration::{chat::ChatIntegration, discord::DiscordIntegration};
+use crate::integration::{
+    chat::ChatIntegration, discord::DiscordIntegration, oauth::OAuthIntegration,
+};
 
 pub use crate::{
     db::pool::DbPool,
     email::EmailService,
-    game::{data::master_store::MasterStore, sessions::SessionsStore},
+    game::{
+        challenges::ChallengesStore, data::master_store::MasterStore,
+        maintenance::MaintenanceStore, sessions::SessionsStore,
+    },
 };
 
 #[derive(Clone)]
@@ -22,8 +27,11 @@ pub struct AppState {
     pub email_service: EmailService,
     pub master_store: MasterStore,
     pub sessions_store: SessionsStore,
+    pub challenges_store: ChallengesStore,
+    pub maintenance_store: MaintenanceStore,
     pub discord_integration: DiscordIntegration,
     pub chat_integration: ChatIntegration,
+    pub oauth_integration: OAuthIntegration,
     pub profanities_checker: Arc<ProfanitiesChecker>,
 }
 
@@ -68,6 +76,20 @@ impl AppSettings {
     }
 }
 
+#[cfg(test)]
+impl AppSettings {
+    pub fn for_tests() -> Self {
+        Self {
+            jwt_encoding_key: EncodingKey::from_secret(b"test_jwt_secret"),
+            jwt_decoding_key: DecodingKey::from_secret(b"test_jwt_secret"),
+            aes_key: Aes256Gcm::new_from_slice(&[7u8; 32]).expect("failed to create AES key"),
+            hash_key: "test_hash_key".into(),
+            frontend_url: "http://localhost:8080".into(),
+            game_start_at_utc: None,
+        }
+    }
+}
+
 impl FromRef<AppState> for AppSettings {
     fn from_ref(app_state: &AppState) -> AppSettings {
         app_state.app_settings.clone()
@@ -93,6 +115,16 @@ impl FromRef<AppState> for SessionsStore {
         app_state.sessions_store.clone()
     }
 }
+impl FromRef<AppState> for ChallengesStore {
+    fn from_ref(app_state: &AppState) -> ChallengesStore {
+        app_state.challenges_store.clone()
+    }
+}
+impl FromRef<AppState> for MaintenanceStore {
+    fn from_ref(app_state: &AppState) -> MaintenanceStore {
+        app_state.maintenance_store.clone()
+    }
+}
 impl FromRef<AppState> for DiscordIntegration {
     fn from_ref(app_state: &AppState) -> DiscordIntegration {
         app_state.discord_integration.clone()
@@ -103,6 +135,11 @@ impl FromRef<AppState> for ChatIntegration {
         app_state.chat_integration.clone()
     }
 }
+impl FromRef<AppState> for OAuthIntegration {
+    fn from_ref(app_state: &AppState) -> OAuthIntegration {
+        app_state.oauth_integration.clone()
+    }
+}
 impl FromRef<AppState> for Arc<ProfanitiesChecker> {
     fn from_ref(app_state: &AppState) -> Arc<ProfanitiesChecker> {
         app_state.profanities_checker.clone()
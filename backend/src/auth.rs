@@ -17,13 +17,14 @@ use base64::{
     Engine as _, alphabet,
     engine::{self, general_purpose},
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 use headers::{Authorization, authorization::Bearer};
 use jsonwebtoken::{Header, TokenData, Validation, decode, encode};
-use rand::RngCore;
+use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, TOTP};
 
 use shared::types::Email;
 
@@ -38,8 +39,19 @@ use crate::{
 const B64_ENGINE: engine::GeneralPurpose =
     engine::GeneralPurpose::new(&alphabet::URL_SAFE, general_purpose::NO_PAD);
 const REFRESH_COOKIE_NAME: &str = "refresh_token";
+const OAUTH_NONCE_COOKIE_NAME: &str = "oauth_nonce";
 const ACCESS_TOKEN_DURATION: Duration = Duration::minutes(15);
 const REFRESH_TOKEN_DURATION: Duration = Duration::days(30);
+const OAUTH_STATE_DURATION: Duration = Duration::minutes(10);
+const TOTP_CHALLENGE_DURATION: Duration = Duration::minutes(5);
+const TOTP_ISSUER: &str = "Grind to Rust";
+const TOTP_STEP: u64 = 30;
+const TOTP_SECRET_LENGTH: usize = 20;
+const TOTP_MAX_FAILED_ATTEMPTS: i64 = 5;
+const TOTP_LOCKOUT_DURATION: Duration = Duration::minutes(15);
+const RECOVERY_CODES_AMOUNT: usize = 10;
+const RECOVERY_CODE_CHARSET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+const RECOVERY_CODE_LENGTH: usize = 10;
 
 pub async fn verify_captcha(token: &str) -> anyhow::Result<bool> {
     // TODO: move to app_settings
@@ -71,6 +83,7 @@ pub struct Claims {
 pub enum TokenKind {
     Access,
     Refresh,
+    TotpChallenge,
 }
 
 pub struct AuthTokens {
@@ -78,12 +91,49 @@ pub struct AuthTokens {
     pub refresh_token: String,
 }
 
+pub enum SignInOutcome {
+    SignedIn(AuthTokens),
+    /// First factor is verified, the challenge must be completed with a TOTP code
+    TotpRequired(String),
+}
+
+/// Shown to the user when enrolling an authenticator app
+pub struct TotpProvisioning {
+    pub provisioning_uri: String,
+    pub qr_code: String,
+    pub secret: String,
+}
+
+/// What to do with the identity once the provider redirects back
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OAuthIntent {
+    SignIn { accepted_terms: bool },
+    Link { user_id: UserId },
+}
+
+/// Authorized state of an OAuth flow started by this browser
+pub struct OAuthState {
+    pub intent: OAuthIntent,
+    /// Also checked against the OpenID Connect ID token
+    pub nonce: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OAuthStateClaims {
+    exp: usize,
+    iat: usize,
+    provider: String,
+    nonce: String,
+    intent: OAuthIntent,
+}
+
 pub async fn sign_in(
     app_settings: &AppSettings,
     db_pool: &db::DbPool,
     username: &str,
     password: &str,
-) -> Result<AuthTokens, AppError> {
+) -> Result<SignInOutcome, AppError> {
     let (user, password_hash_opt) = db::users::auth_user(db_pool, username)
         .await?
         .ok_or_else(|| AppError::Unauthorized("incorrect username or password".to_string()))?;
@@ -94,10 +144,7 @@ pub async fn sign_in(
     // TODO: Track activity logs, etc.
 
     if verify_password(password, &password_hash) {
-        db::users::update_last_login(db_pool, &user.user_id)
-            .await
-            .unwrap_or_else(|e| tracing::error!("couldn't update user last login: {e}"));
-        Ok(auth_tokens(app_settings, user)?)
+        sign_in_user(app_settings, db_pool, user).await
     } else {
         Err(AppError::Unauthorized(
             "incorrect username or password".to_string(),
@@ -105,6 +152,156 @@ pub async fn sign_in(
     }
 }
 
+/// Issue tokens for an already authenticated user, unless a second factor is required
+pub async fn sign_in_user(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    user: User,
+) -> Result<SignInOutcome, AppError> {
+    if db::user_totp::is_totp_enabled(db_pool, &user.user_id).await? {
+        return Ok(SignInOutcome::TotpRequired(encode_jwt(
+            app_settings,
+            user,
+            TokenKind::TotpChallenge,
+            TOTP_CHALLENGE_DURATION,
+        )?));
+    }
+
+    Ok(SignInOutcome::SignedIn(
+        issue_tokens(app_settings, db_pool, user).await?,
+    ))
+}
+
+pub async fn sign_in_totp(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    challenge: &str,
+    code: &str,
+) -> Result<AuthTokens, AppError> {
+    let user = authorize_jwt(app_settings, challenge, TokenKind::TotpChallenge)
+        .ok_or_else(|| AppError::Unauthorized("sign-in expired, please retry".to_string()))?;
+
+    verify_totp(app_settings, db_pool, &user.user_id, code).await?;
+
+    issue_tokens(app_settings, db_pool, user).await
+}
+
+async fn issue_tokens(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    user: User,
+) -> Result<AuthTokens, AppError> {
+    db::users::update_last_login(db_pool, &user.user_id)
+        .await
+        .unwrap_or_else(|e| tracing::error!("couldn't update user last login: {e}"));
+    Ok(auth_tokens(app_settings, user)?)
+}
+
+/// Sensitive actions must be confirmed with a TOTP or recovery code when enabled
+pub async fn verify_sensitive_action(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    user_id: &UserId,
+    code: Option<&str>,
+) -> Result<(), AppError> {
+    if !db::user_totp::is_totp_enabled(db_pool, user_id).await? {
+        return Ok(());
+    }
+
+    match code {
+        Some(code) => verify_totp(app_settings, db_pool, user_id, code).await,
+        None => Err(AppError::TotpRequired),
+    }
+}
+
+/// Accept either a TOTP code or a single use recovery code
+pub async fn verify_totp(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    user_id: &UserId,
+    code: &str,
+) -> Result<(), AppError> {
+    let totp_entry = db::user_totp::read_user_totp(db_pool, user_id)
+        .await?
+        .filter(|totp_entry| totp_entry.enabled_at.is_some())
+        .ok_or_else(|| AppError::UserError("two-factor authentication is not enabled".into()))?;
+
+    verify_totp_entry(app_settings, db_pool, &totp_entry, code).await
+}
+
+/// Also used to confirm pending enrolments, so that they share the lockout
+pub async fn verify_totp_entry(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    totp_entry: &db::user_totp::UserTotpEntry,
+    code: &str,
+) -> Result<(), AppError> {
+    if is_totp_locked(totp_entry, Utc::now()) {
+        return Err(AppError::UserError(
+            "too many invalid codes, please try again later".into(),
+        ));
+    }
+
+    let code = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+
+    let verified = if code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()) {
+        check_totp_code(app_settings, db_pool, totp_entry, &code).await?
+    } else {
+        db::user_totp::use_recovery_code(
+            db_pool,
+            &totp_entry.user_id,
+            &hash_content(app_settings, &code),
+        )
+        .await?
+    };
+
+    if !verified {
+        db::user_totp::record_totp_failure(db_pool, &totp_entry.user_id).await?;
+        return Err(AppError::UserError("invalid two-factor code".into()));
+    }
+
+    Ok(())
+}
+
+fn is_totp_locked(totp_entry: &db::user_totp::UserTotpEntry, now: DateTime<Utc>) -> bool {
+    totp_entry.failed_attempts >= TOTP_MAX_FAILED_ATTEMPTS
+        && totp_entry
+            .last_failed_at
+            .clone()
+            .is_some_and(|last_failed_at| {
+                now - DateTime::from(last_failed_at) < TOTP_LOCKOUT_DURATION
+            })
+}
+
+/// Codes are accepted one step early or late for clock drift, but only once
+async fn check_totp_code(
+    app_settings: &AppSettings,
+    db_pool: &db::DbPool,
+    totp_entry: &db::user_totp::UserTotpEntry,
+    code: &str,
+) -> anyhow::Result<bool> {
+    let totp = totp(
+        decrypt_content(app_settings, &totp_entry.secret_crypt)?,
+        String::new(),
+    )?;
+
+    let Some(step) = find_totp_step(&totp, code, Utc::now().timestamp() as u64) else {
+        return Ok(false);
+    };
+
+    Ok(db::user_totp::record_totp_success(db_pool, &totp_entry.user_id, step as i64).await?)
+}
+
+fn find_totp_step(totp: &TOTP, code: &str, timestamp: u64) -> Option<u64> {
+    let current_step = timestamp / TOTP_STEP;
+    (current_step.saturating_sub(1)..=current_step + 1)
+        .find(|step| totp.check(code, step * TOTP_STEP))
+}
+
 pub async fn authorization_middleware(
     State(state): State<AppState>,
     TypedHeader(Authorization(bearer)): TypedHeader<Authorization<Bearer>>,
@@ -118,12 +315,33 @@ pub async fn authorization_middleware(
     Ok(next.run(req).await)
 }
 
+/// Must be layered after `authorization_middleware`
+pub async fn admin_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response<Body>, AppError> {
+    let user = req
+        .extensions()
+        .get::<User>()
+        .ok_or_else(|| AppError::Unauthorized("invalid token".to_string()))?;
+
+    match db::users::read_user(&state.db_pool, &user.user_id).await? {
+        Some(user) if user.is_admin => Ok(next.run(req).await),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
 pub fn refresh_cookie_from_headers(headers: &axum::http::HeaderMap) -> Option<String> {
+    cookie_from_headers(headers, REFRESH_COOKIE_NAME)
+}
+
+fn cookie_from_headers(headers: &axum::http::HeaderMap, cookie_name: &str) -> Option<String> {
     let cookie = headers.get(COOKIE)?.to_str().ok()?;
 
     cookie.split(';').find_map(|part| {
         let (name, value) = part.trim().split_once('=')?;
-        (name == REFRESH_COOKIE_NAME).then(|| value.to_string())
+        (name == cookie_name).then(|| value.to_string())
     })
 }
 
@@ -192,25 +410,92 @@ fn encode_jwt(
     )?)
 }
 
+/// Signed state round-tripped through the identity provider, along its nonce.
+/// The nonce must be stored in the browser starting the flow, see [`oauth_nonce_cookie`].
+pub fn oauth_state(
+    app_settings: &AppSettings,
+    provider: &str,
+    intent: OAuthIntent,
+) -> anyhow::Result<(String, String)> {
+    let now = Utc::now();
+    let nonce = generate_token();
+
+    let state = encode(
+        &Header::default(),
+        &OAuthStateClaims {
+            exp: (now + OAUTH_STATE_DURATION).timestamp() as usize,
+            iat: now.timestamp() as usize,
+            provider: provider.to_string(),
+            nonce: nonce.clone(),
+            intent,
+        },
+        &app_settings.jwt_encoding_key,
+    )?;
+
+    Ok((state, nonce))
+}
+
+/// The state is only valid in the browser that started the flow, to prevent login CSRF
+pub fn authorize_oauth_state(
+    app_settings: &AppSettings,
+    token: &str,
+    provider: &str,
+    headers: &axum::http::HeaderMap,
+) -> Option<OAuthState> {
+    let nonce = cookie_from_headers(headers, OAUTH_NONCE_COOKIE_NAME)?;
+
+    decode(
+        token,
+        &app_settings.jwt_decoding_key,
+        &Validation::default(),
+    )
+    .ok()
+    .filter(|token_data: &TokenData<OAuthStateClaims>| {
+        token_data.claims.provider == provider && token_data.claims.nonce == nonce
+    })
+    .map(|token_data| OAuthState {
+        intent: token_data.claims.intent,
+        nonce: token_data.claims.nonce,
+    })
+}
+
+pub fn oauth_nonce_cookie(nonce: &str) -> String {
+    format!(
+        "{OAUTH_NONCE_COOKIE_NAME}={nonce}; HttpOnly; Path=/; {}; Max-Age={}",
+        cookie_same_site(),
+        OAUTH_STATE_DURATION.num_seconds()
+    )
+}
+
+pub fn expired_oauth_nonce_cookie() -> String {
+    format!(
+        "{OAUTH_NONCE_COOKIE_NAME}=; HttpOnly; Path=/; {}; Max-Age=0",
+        cookie_same_site()
+    )
+}
+
 pub fn refresh_cookie(token: &str) -> String {
-    let same_site = if cfg!(debug_assertions) {
-        "SameSite=Lax"
-    } else {
-        "SameSite=None; Secure"
-    };
     format!(
-        "{REFRESH_COOKIE_NAME}={token}; HttpOnly; Path=/; {same_site}; Max-Age={}",
+        "{REFRESH_COOKIE_NAME}={token}; HttpOnly; Path=/; {}; Max-Age={}",
+        cookie_same_site(),
         REFRESH_TOKEN_DURATION.num_seconds()
     )
 }
 
 pub fn expired_refresh_cookie() -> String {
-    let same_site = if cfg!(debug_assertions) {
+    format!(
+        "{REFRESH_COOKIE_NAME}=; HttpOnly; Path=/; {}; Max-Age=0",
+        cookie_same_site()
+    )
+}
+
+// The frontend is served from another site in production
+fn cookie_same_site() -> &'static str {
+    if cfg!(debug_assertions) {
         "SameSite=Lax"
     } else {
         "SameSite=None; Secure"
-    };
-    format!("{REFRESH_COOKIE_NAME}=; HttpOnly; Path=/; {same_site}; Max-Age=0")
+    }
 }
 
 pub fn hash_password(password: &str) -> anyhow::Result<String> {
@@ -238,14 +523,22 @@ impl From<db::users::UserEntry> for User {
     }
 }
 
-#[allow(deprecated)]
 pub fn encrypt_email(app_settings: &AppSettings, email: &str) -> anyhow::Result<Vec<u8>> {
+    encrypt_content(app_settings, email.as_bytes())
+}
+
+pub fn decrypt_email(app_settings: &AppSettings, data: &[u8]) -> anyhow::Result<Email> {
+    Email::try_new(String::from_utf8(decrypt_content(app_settings, data)?)?)
+}
+
+#[allow(deprecated)]
+fn encrypt_content(app_settings: &AppSettings, content: &[u8]) -> anyhow::Result<Vec<u8>> {
     let mut nonce = [0u8; 12];
     rand::rng().fill_bytes(&mut nonce);
 
     let ciphertext = app_settings
         .aes_key
-        .encrypt(Nonce::from_slice(&nonce), email.as_bytes())
+        .encrypt(Nonce::from_slice(&nonce), content)
         .map_err(|_| anyhow!("failed to encrypt"))?;
 
     let mut combined = Vec::with_capacity(nonce.len() + ciphertext.len());
@@ -256,17 +549,66 @@ pub fn encrypt_email(app_settings: &AppSettings, email: &str) -> anyhow::Result<
 }
 
 #[allow(deprecated)]
-pub fn decrypt_email(app_settings: &AppSettings, data: &[u8]) -> anyhow::Result<Email> {
+fn decrypt_content(app_settings: &AppSettings, data: &[u8]) -> anyhow::Result<Vec<u8>> {
     let (nonce_bytes, ciphertext) = data.split_at_checked(12).ok_or(anyhow!("invalid data"))?;
 
-    Email::try_new(String::from_utf8(
-        app_settings
-            .aes_key
-            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
-            .map_err(|_| anyhow!("failed to decrypt"))?,
+    app_settings
+        .aes_key
+        .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+        .map_err(|_| anyhow!("failed to decrypt"))
+}
+
+/// Return the encrypted secret to store along its provisioning
+pub fn generate_totp_secret(
+    app_settings: &AppSettings,
+    username: &str,
+) -> anyhow::Result<(Vec<u8>, TotpProvisioning)> {
+    let mut secret = vec![0u8; TOTP_SECRET_LENGTH];
+    rand::rng().fill_bytes(&mut secret);
+
+    let secret_crypt = encrypt_content(app_settings, &secret)?;
+    let totp = totp(secret, username.to_string())?;
+
+    Ok((
+        secret_crypt,
+        TotpProvisioning {
+            provisioning_uri: totp.get_url(),
+            qr_code: totp.get_qr_base64().map_err(|e| anyhow!(e))?,
+            secret: totp.get_secret_base32(),
+        },
+    ))
+}
+
+fn totp(secret: Vec<u8>, account_name: String) -> anyhow::Result<TOTP> {
+    Ok(TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        TOTP_STEP,
+        secret,
+        Some(TOTP_ISSUER.to_string()),
+        account_name,
     )?)
 }
 
+/// Return the codes to show once to the user, and their hashes to store
+pub fn generate_recovery_codes(app_settings: &AppSettings) -> (Vec<String>, Vec<Vec<u8>>) {
+    let mut rng = rand::rng();
+
+    (0..RECOVERY_CODES_AMOUNT)
+        .map(|_| {
+            let code: String = (0..RECOVERY_CODE_LENGTH)
+                .map(|_| {
+                    RECOVERY_CODE_CHARSET[rng.random_range(0..RECOVERY_CODE_CHARSET.len())] as char
+                })
+                .collect();
+            let code_hash = hash_content(app_settings, &code);
+            let (head, tail) = code.split_at(RECOVERY_CODE_LENGTH / 2);
+            (format!("{head}-{tail}"), code_hash)
+        })
+        .unzip()
+}
+
 pub fn hash_content(app_settings: &AppSettings, email: &str) -> Vec<u8> {
     let mut hasher = Sha256::new();
     hasher.update(&app_settings.hash_key);
@@ -280,3 +622,230 @@ pub fn generate_token() -> String {
 
     B64_ENGINE.encode(token_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totp_entry(
+        failed_attempts: i64,
+        last_failed_at: Option<DateTime<Utc>>,
+    ) -> db::user_totp::UserTotpEntry {
+        db::user_totp::UserTotpEntry {
+            user_id: UserId::nil(),
+            secret_crypt: Vec::new(),
+            enabled_at: Some(Utc::now().into()),
+            last_used_step: 0,
+            failed_attempts,
+            last_failed_at: last_failed_at.map(Into::into),
+            created_at: Utc::now().into(),
+        }
+    }
+
+    fn nonce_cookie_headers(nonce: &str) -> axum::http::HeaderMap {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            format!("other=value; {OAUTH_NONCE_COOKIE_NAME}={nonce}")
+                .parse()
+                .unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_oauth_state_bound_to_browser() {
+        let app_settings = AppSettings::for_tests();
+        let intent = OAuthIntent::SignIn {
+            accepted_terms: false,
+        };
+        let (state, nonce) = oauth_state(&app_settings, "stub", intent).unwrap();
+
+        let oauth_state =
+            authorize_oauth_state(&app_settings, &state, "stub", &nonce_cookie_headers(&nonce))
+                .unwrap();
+        assert!(oauth_state.intent == intent);
+        assert_eq!(oauth_state.nonce, nonce);
+
+        // Callback replayed in another browser
+        assert!(
+            authorize_oauth_state(&app_settings, &state, "stub", &Default::default()).is_none()
+        );
+        assert!(
+            authorize_oauth_state(
+                &app_settings,
+                &state,
+                "stub",
+                &nonce_cookie_headers("other_nonce")
+            )
+            .is_none()
+        );
+        assert!(
+            authorize_oauth_state(
+                &app_settings,
+                &state,
+                "other",
+                &nonce_cookie_headers(&nonce)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_totp_window_skew() {
+        let totp = totp(vec![7; TOTP_SECRET_LENGTH], "tester".into()).unwrap();
+        let timestamp = 1_700_000_000;
+        let current_step = timestamp / TOTP_STEP;
+
+        for step in [current_step - 1, current_step, current_step + 1] {
+            let code = totp.generate(step * TOTP_STEP);
+            assert_eq!(find_totp_step(&totp, &code, timestamp), Some(step));
+        }
+
+        for step in [current_step - 2, current_step + 2] {
+            let code = totp.generate(step * TOTP_STEP);
+            assert_eq!(find_totp_step(&totp, &code, timestamp), None);
+        }
+    }
+
+    #[test]
+    fn test_totp_lockout() {
+        let now = Utc::now();
+
+        assert!(!is_totp_locked(&totp_entry(0, None), now));
+        assert!(!is_totp_locked(
+            &totp_entry(TOTP_MAX_FAILED_ATTEMPTS - 1, Some(now)),
+            now
+        ));
+        assert!(is_totp_locked(
+            &totp_entry(TOTP_MAX_FAILED_ATTEMPTS, Some(now - Duration::minutes(1))),
+            now
+        ));
+        assert!(!is_totp_locked(
+            &totp_entry(
+                TOTP_MAX_FAILED_ATTEMPTS,
+                Some(now - TOTP_LOCKOUT_DURATION - Duration::minutes(1))
+            ),
+            now
+        ));
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn enable_test_totp(
+        app_settings: &AppSettings,
+        db_pool: &db::DbPool,
+    ) -> (UserId, TOTP, Vec<String>) {
+        let user_id = db::users::create_user(db_pool, "tester", None, None, None, &Utc::now(), 1)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (secret_crypt, _) = generate_totp_secret(app_settings, "tester").unwrap();
+        db::user_totp::upsert_pending_totp(db_pool, &user_id, &secret_crypt)
+            .await
+            .unwrap();
+        db::user_totp::enable_user_totp(db_pool, &user_id)
+            .await
+            .unwrap();
+
+        let (recovery_codes, code_hashes) = generate_recovery_codes(app_settings);
+        let mut tx = db_pool.begin().await.unwrap();
+        db::user_totp::replace_recovery_codes(&mut tx, &user_id, &code_hashes)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let totp = totp(
+            decrypt_content(app_settings, &secret_crypt).unwrap(),
+            String::new(),
+        )
+        .unwrap();
+        (user_id, totp, recovery_codes)
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_check_totp_code_single_use() {
+        let app_settings = AppSettings::for_tests();
+        let db_pool = db::pool::create_test_pool().await;
+        let (user_id, totp, _) = enable_test_totp(&app_settings, &db_pool).await;
+
+        let totp_entry = db::user_totp::read_user_totp(&db_pool, &user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let code = totp.generate_current().unwrap();
+
+        assert!(
+            check_totp_code(&app_settings, &db_pool, &totp_entry, &code)
+                .await
+                .unwrap()
+        );
+        // Replayed code must be rejected
+        assert!(
+            !check_totp_code(&app_settings, &db_pool, &totp_entry, &code)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_recovery_code_single_use() {
+        let app_settings = AppSettings::for_tests();
+        let db_pool = db::pool::create_test_pool().await;
+        let (user_id, _, recovery_codes) = enable_test_totp(&app_settings, &db_pool).await;
+
+        let recovery_code = recovery_codes[0].to_uppercase();
+        assert!(
+            verify_totp(&app_settings, &db_pool, &user_id, &recovery_code)
+                .await
+                .is_ok()
+        );
+        assert!(
+            verify_totp(&app_settings, &db_pool, &user_id, &recovery_code)
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            db::user_totp::count_recovery_codes(&db_pool, &user_id)
+                .await
+                .unwrap(),
+            RECOVERY_CODES_AMOUNT as i64 - 1
+        );
+
+        let code_hash = hash_content(&app_settings, &recovery_codes[1].replace('-', ""));
+        assert!(
+            db::user_totp::use_recovery_code(&db_pool, &user_id, &code_hash)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !db::user_totp::use_recovery_code(&db_pool, &user_id, &code_hash)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_totp_lockout_rejects_valid_code() {
+        let app_settings = AppSettings::for_tests();
+        let db_pool = db::pool::create_test_pool().await;
+        let (user_id, totp, _) = enable_test_totp(&app_settings, &db_pool).await;
+
+        for _ in 0..TOTP_MAX_FAILED_ATTEMPTS {
+            assert!(
+                verify_totp(&app_settings, &db_pool, &user_id, "invalid-code")
+                    .await
+                    .is_err()
+            );
+        }
+
+        let code = totp.generate_current().unwrap();
+        assert!(matches!(
+            verify_totp(&app_settings, &db_pool, &user_id, &code).await,
+            Err(AppError::UserError(err)) if err.contains("too many")
+        ));
+    }
+}
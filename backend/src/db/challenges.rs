@@ -0,0 +1,24 @@
+use shared::data::user::UserCharacterId;
+
+use crate::db::pool::DbExecutor;
+
+/// Return whether the reward was claimed, or false if it was already claimed before
+pub async fn claim_challenge_reward<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+    challenge_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO challenges_rewards (character_id, challenge_id)
+        VALUES ($1, $2)
+        ON CONFLICT(character_id, challenge_id) DO NOTHING
+        "#,
+        character_id,
+        challenge_id,
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -1,8 +1,8 @@
 use sqlx::FromRow;
 
 use shared::data::{
-    passive::PassivesTreeAscension, player::PlayerInventory, skill_mastery::PlayerSkillMasteries,
-    temple::PlayerBenedictions, user::UserCharacterId,
+    atlas::AtlasState, passive::PassivesTreeAscension, player::PlayerInventory,
+    skill_mastery::PlayerSkillMasteries, temple::PlayerBenedictions, user::UserCharacterId,
 };
 
 use crate::{
@@ -180,6 +180,55 @@ async fn upsert_character_benedictions_data<'c>(
     Ok(())
 }
 
+pub async fn save_character_atlas<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+    atlas_state: &AtlasState,
+) -> anyhow::Result<()> {
+    Ok(
+        upsert_character_atlas_data(executor, character_id, rmp_serde::to_vec(atlas_state)?)
+            .await?,
+    )
+}
+
+async fn upsert_character_atlas_data<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+    atlas_data: Vec<u8>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE characters_data SET
+            data_version = $2,
+            atlas_data = $3, 
+            updated_at = CURRENT_TIMESTAMP
+        WHERE character_id = $1",
+        character_id,
+        DATA_VERSION,
+        atlas_data
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn load_character_atlas<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+) -> anyhow::Result<AtlasState> {
+    let atlas_data = sqlx::query_scalar!(
+        "SELECT atlas_data FROM characters_data WHERE character_id = $1",
+        character_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .flatten();
+
+    Ok(atlas_data
+        .and_then(|atlas_data| rmp_serde::from_slice::<AtlasState>(&atlas_data).ok())
+        .unwrap_or_default())
+}
+
 // TODO: Split
 pub async fn load_character_data<'c>(
     executor: impl DbExecutor<'c>,
@@ -220,6 +269,35 @@ pub async fn load_character_data<'c>(
     }
 }
 
+pub struct CharacterInventoryDataEntry {
+    pub character_id: UserCharacterId,
+    pub inventory_data: Vec<u8>,
+}
+
+/// Inventories ordered by character, starting after the given one
+pub async fn read_characters_inventory_data<'c>(
+    executor: impl DbExecutor<'c>,
+    after_character_id: Option<&UserCharacterId>,
+    limit: i64,
+) -> Result<Vec<CharacterInventoryDataEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        CharacterInventoryDataEntry,
+        r#"
+        SELECT
+            character_id as "character_id: UserCharacterId",
+            inventory_data
+         FROM characters_data
+         WHERE $1 IS NULL OR character_id > $1
+         ORDER BY character_id
+         LIMIT $2
+         "#,
+        after_character_id,
+        limit
+    )
+    .fetch_all(executor)
+    .await
+}
+
 async fn read_character_data<'c>(
     executor: impl DbExecutor<'c>,
     character_id: &UserCharacterId,
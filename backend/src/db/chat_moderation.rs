@@ -0,0 +1,162 @@
+use sqlx::{FromRow, types::JsonValue};
+
+use shared::data::user::UserId;
+use shared_chat::moderation::ChatReportId;
+
+use super::{pool::DbExecutor, utc_datetime::UtcDateTime};
+
+#[derive(Debug, FromRow)]
+pub struct ChatReportEntry {
+    pub report_id: ChatReportId,
+    pub reporter_id: UserId,
+    pub reported_user_id: UserId,
+    pub reason: String,
+    pub context_messages: JsonValue,
+
+    pub created_at: UtcDateTime,
+    pub resolved_at: Option<UtcDateTime>,
+    pub resolved_by: Option<UserId>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct ChatModerationLogEntry {
+    pub log_id: i64,
+    pub moderator_id: UserId,
+    pub target_user_id: Option<UserId>,
+    pub action: String,
+    pub details: JsonValue,
+
+    pub created_at: UtcDateTime,
+}
+
+pub async fn create_report<'c>(
+    executor: impl DbExecutor<'c>,
+    reporter_id: &UserId,
+    reported_user_id: &UserId,
+    reason: &str,
+    context_messages: JsonValue,
+) -> Result<ChatReportId, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        INSERT INTO chat_reports (reporter_id, reported_user_id, reason, context_messages)
+        VALUES ($1, $2, $3, $4)
+        RETURNING report_id
+        "#,
+        reporter_id,
+        reported_user_id,
+        reason,
+        context_messages
+    )
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn read_reports<'c>(
+    executor: impl DbExecutor<'c>,
+    include_resolved: bool,
+    skip: i64,
+    limit: i64,
+) -> Result<Vec<ChatReportEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        ChatReportEntry,
+        r#"
+        SELECT
+            report_id,
+            reporter_id as "reporter_id: UserId",
+            reported_user_id as "reported_user_id: UserId",
+            reason,
+            context_messages as "context_messages: JsonValue",
+            created_at,
+            resolved_at as "resolved_at?: UtcDateTime",
+            resolved_by as "resolved_by?: UserId"
+        FROM chat_reports
+        WHERE $1 OR resolved_at IS NULL
+        ORDER BY created_at DESC
+        LIMIT $2
+        OFFSET $3
+        "#,
+        include_resolved,
+        limit,
+        skip
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Return the reported user if the report was not resolved yet
+pub async fn resolve_report<'c>(
+    executor: impl DbExecutor<'c>,
+    report_id: ChatReportId,
+    resolved_by: &UserId,
+) -> Result<Option<UserId>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        UPDATE chat_reports
+        SET
+            resolved_at = CURRENT_TIMESTAMP,
+            resolved_by = $2
+        WHERE
+            report_id = $1
+            AND resolved_at IS NULL
+        RETURNING
+            reported_user_id as "reported_user_id: UserId"
+        "#,
+        report_id,
+        resolved_by
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn create_moderation_log<'c>(
+    executor: impl DbExecutor<'c>,
+    moderator_id: &UserId,
+    target_user_id: Option<&UserId>,
+    action: &str,
+    details: JsonValue,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        INSERT INTO chat_moderation_logs (moderator_id, target_user_id, action, details)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        moderator_id,
+        target_user_id,
+        action,
+        details
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn read_moderation_logs<'c>(
+    executor: impl DbExecutor<'c>,
+    target_user_id: Option<&UserId>,
+    skip: i64,
+    limit: i64,
+) -> Result<Vec<ChatModerationLogEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        ChatModerationLogEntry,
+        r#"
+        SELECT
+            log_id,
+            moderator_id as "moderator_id: UserId",
+            target_user_id as "target_user_id?: UserId",
+            action,
+            details as "details: JsonValue",
+            created_at
+        FROM chat_moderation_logs
+        WHERE $1 IS NULL OR target_user_id = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        OFFSET $3
+        "#,
+        target_user_id,
+        limit,
+        skip
+    )
+    .fetch_all(executor)
+    .await
+}
@@ -8,7 +8,7 @@ use shared::data::{
 use crate::constants::DATA_VERSION;
 
 use super::{
-    pool::{Database, DbPool},
+    pool::{Database, DbExecutor, DbPool},
     utc_datetime::UtcDateTime,
 };
 
@@ -48,7 +48,7 @@ pub async fn get_leaderboard(
                     ORDER BY lb.area_level DESC, lb.elapsed_time ASC, lb.updated_at ASC
                 ) AS area_rank
             FROM leaderboard lb
-            WHERE lb.realm_id = $2
+            WHERE lb.realm_id = $2 AND lb.area_id NOT LIKE 'challenge:%'
         )
         SELECT
             r.realm_id,
@@ -120,6 +120,26 @@ pub async fn get_area_leaderboard(
     .await
 }
 
+pub async fn read_character_area_level<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+    realm_id: &RealmId,
+    area_id: &str,
+) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT area_level AS "area_level: i32"
+        FROM leaderboard
+        WHERE character_id = $1 AND realm_id = $2 AND area_id = $3
+        "#,
+        character_id,
+        realm_id,
+        area_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
 /// Return whether the result is a new global high score in the realm
 pub async fn update_leaderboard<'c>(
     executor: &mut sqlx::Transaction<'c, Database>,
@@ -182,3 +202,38 @@ pub async fn update_leaderboard<'c>(
 
     Ok(count == 0)
 }
+
+/// Return the rank and level of the character in the area leaderboard
+pub async fn read_character_area_rank<'c>(
+    executor: impl DbExecutor<'c>,
+    character_id: &UserCharacterId,
+    realm_id: &RealmId,
+    area_id: &str,
+) -> Result<Option<(i64, i32)>, sqlx::Error> {
+    let entry = sqlx::query!(
+        r#"
+        WITH ranked AS (
+            SELECT
+                lb.character_id,
+                lb.area_level,
+                ROW_NUMBER() OVER (
+                    ORDER BY lb.area_level DESC, lb.elapsed_time ASC, lb.updated_at ASC
+                ) AS area_rank
+            FROM leaderboard lb
+            WHERE lb.realm_id = $2 AND lb.area_id = $3
+        )
+        SELECT
+            area_rank   AS "area_rank!: i64",
+            area_level  AS "area_level!: i32"
+        FROM ranked
+        WHERE character_id = $1
+        "#,
+        character_id,
+        realm_id,
+        area_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(entry.map(|entry| (entry.area_rank, entry.area_level)))
+}
@@ -243,10 +243,14 @@ impl From<OldItemModifiers> for ItemModifiers {
                             })
                             .collect(),
                         item_level: affix.item_level,
+                        triggers: Vec::new(),
                     })
                     .collect(),
                 quality: value.quality,
                 upgrade_level: value.upgrade_level,
+                corrupted: false,
+                socketed_runes: Default::default(),
+                base_version: 0,
             };
         }
 
@@ -258,6 +262,9 @@ impl From<OldItemModifiers> for ItemModifiers {
             affixes: value.affixes.into_iter().map(Into::into).collect(),
             quality: value.quality,
             upgrade_level: value.upgrade_level,
+            corrupted: false,
+            socketed_runes: Default::default(),
+            base_version: 0,
         }
     }
 }
@@ -286,6 +293,7 @@ impl From<OldItemAffix> for ItemAffix {
             tier: value.tier,
             effects: value.effects.into_iter().map(Into::into).collect(),
             item_level: value.item_level,
+            triggers: Vec::new(),
         }
     }
 }
@@ -1,6 +1,8 @@
+pub mod challenges;
 pub mod characters;
 pub mod characters_builds;
 pub mod characters_data;
+pub mod chat_moderation;
 pub mod game_instances;
 pub mod game_sessions;
 pub mod game_stats;
@@ -9,8 +11,11 @@ pub mod market;
 pub mod migrations;
 pub mod password_reset;
 pub mod pool;
+pub mod query_metrics;
 pub mod stash_items;
 pub mod stashes;
+pub mod user_identities;
+pub mod user_totp;
 pub mod users;
 pub mod utc_datetime;
 
@@ -53,3 +53,24 @@ pub async fn migrate(db_pool: &DbPool) -> Result<(), sqlx::Error> {
         .await?;
     Ok(())
 }
+
+/// In memory database with all migrations applied, kept on a single connection
+#[cfg(all(test, feature = "sqlite"))]
+pub async fn create_test_pool() -> DbPool {
+    let db_pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("failed to create test database");
+
+    Migrator::new(std::path::Path::new("../migrations/sqlite"))
+        .await
+        .expect("failed to load migrations")
+        .run(&db_pool)
+        .await
+        .expect("failed to run migrations");
+
+    db_pool
+}
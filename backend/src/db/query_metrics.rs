@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    filter::{LevelFilter, Targets},
+    layer::{Context, Layer},
+};
+
+/// Record the latency of every database query, from the statements logged by sqlx
+pub struct QueryMetricsLayer;
+
+impl QueryMetricsLayer {
+    /// sqlx only times the queries when their logs are enabled, so listen to all of them
+    /// independently of the log filter
+    pub fn filter() -> Targets {
+        Targets::new().with_target("sqlx::query", LevelFilter::TRACE)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for QueryMetricsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = ElapsedVisitor::default();
+        event.record(&mut visitor);
+        if let Some(elapsed_secs) = visitor.elapsed_secs {
+            metrics::histogram!("db_query_duration_seconds").record(elapsed_secs);
+        }
+    }
+}
+
+#[derive(Default)]
+struct ElapsedVisitor {
+    elapsed_secs: Option<f64>,
+}
+
+impl Visit for ElapsedVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+}
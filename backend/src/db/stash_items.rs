@@ -307,6 +307,38 @@ pub async fn read_stash_item<'c>(
     .await
 }
 
+pub struct StashItemDataEntry {
+    pub stash_item_id: StashItemId,
+    pub item_data: JsonValue,
+}
+
+/// Stored items ordered by id, starting after the given one
+pub async fn read_stash_items_data<'c>(
+    executor: impl DbExecutor<'c>,
+    after_stash_item_id: Option<StashItemId>,
+    limit: i64,
+) -> Result<Vec<StashItemDataEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        StashItemDataEntry,
+        r#"
+        SELECT 
+            stash_item_id,
+            item_data as "item_data: JsonValue"
+        FROM 
+            stash_items
+        WHERE 
+            deleted_at is NULL
+            AND ($1 IS NULL OR stash_item_id > $1)
+        ORDER BY stash_item_id
+        LIMIT $2
+        "#,
+        after_stash_item_id,
+        limit
+    )
+    .fetch_all(executor)
+    .await
+}
+
 pub async fn read_stash_items<'c>(
     executor: impl DbExecutor<'c>,
     stash_id: StashId,
@@ -0,0 +1,130 @@
+use sqlx::FromRow;
+
+use shared::data::user::{User, UserId};
+
+use super::{pool::DbExecutor, utc_datetime::UtcDateTime};
+
+#[derive(Debug, FromRow)]
+pub struct UserIdentityEntry {
+    pub provider: String,
+    pub subject: String,
+    pub user_id: UserId,
+    pub provider_username: Option<String>,
+
+    pub created_at: UtcDateTime,
+}
+
+/// Return false if the identity or the provider is already linked
+pub async fn create_user_identity<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+    provider: &str,
+    subject: &str,
+    provider_username: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    let res = sqlx::query!(
+        r#"
+        INSERT INTO user_identities (provider, subject, user_id, provider_username)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        provider,
+        subject,
+        user_id,
+        provider_username
+    )
+    .execute(executor)
+    .await;
+
+    match res {
+        Ok(_) => Ok(true),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn read_identity_user<'c>(
+    executor: impl DbExecutor<'c>,
+    provider: &str,
+    subject: &str,
+) -> Result<Option<User>, sqlx::Error> {
+    Ok(sqlx::query!(
+        r#"
+        SELECT
+            users.user_id as "user_id: UserId",
+            users.username
+        FROM user_identities
+        INNER JOIN users ON users.user_id = user_identities.user_id
+        WHERE
+            user_identities.provider = $1
+            AND user_identities.subject = $2
+            AND users.deleted_at IS NULL
+        "#,
+        provider,
+        subject
+    )
+    .fetch_optional(executor)
+    .await?
+    .map(|record| User {
+        user_id: record.user_id,
+        username: record.username.unwrap_or("someone".into()),
+    }))
+}
+
+pub async fn read_user_identities<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<Vec<UserIdentityEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        UserIdentityEntry,
+        r#"
+        SELECT
+            provider,
+            subject,
+            user_id as "user_id: UserId",
+            provider_username,
+            created_at
+        FROM user_identities
+        WHERE user_id = $1
+        ORDER BY created_at
+        "#,
+        user_id
+    )
+    .fetch_all(executor)
+    .await
+}
+
+pub async fn delete_user_identity<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+    provider: &str,
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query!(
+        r#"
+        DELETE FROM user_identities
+        WHERE user_id = $1 AND provider = $2
+        "#,
+        user_id,
+        provider
+    )
+    .execute(executor)
+    .await?
+    .rows_affected()
+        > 0)
+}
+
+pub async fn delete_all_user_identities<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM user_identities
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
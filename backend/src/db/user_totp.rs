@@ -0,0 +1,245 @@
+use sqlx::{FromRow, Transaction};
+
+use shared::data::user::UserId;
+
+use super::{
+    pool::{Database, DbExecutor},
+    utc_datetime::UtcDateTime,
+};
+
+#[derive(Debug, FromRow)]
+pub struct UserTotpEntry {
+    pub user_id: UserId,
+    pub secret_crypt: Vec<u8>,
+    pub enabled_at: Option<UtcDateTime>,
+
+    pub last_used_step: i64,
+    pub failed_attempts: i64,
+    pub last_failed_at: Option<UtcDateTime>,
+
+    pub created_at: UtcDateTime,
+}
+
+/// Replace any pending enrolment, return false if TOTP is already enabled.
+/// Failed attempts are kept so that enrolling again doesn't lift a lockout.
+pub async fn upsert_pending_totp<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+    secret_crypt: &[u8],
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query!(
+        r#"
+        INSERT INTO user_totp (user_id, secret_crypt)
+        VALUES ($1, $2)
+        ON CONFLICT(user_id) DO UPDATE
+        SET
+            secret_crypt = EXCLUDED.secret_crypt,
+            last_used_step = 0,
+            created_at = CURRENT_TIMESTAMP
+        WHERE user_totp.enabled_at IS NULL
+        "#,
+        user_id,
+        secret_crypt
+    )
+    .execute(executor)
+    .await?
+    .rows_affected()
+        > 0)
+}
+
+pub async fn read_user_totp<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<Option<UserTotpEntry>, sqlx::Error> {
+    sqlx::query_as!(
+        UserTotpEntry,
+        r#"
+        SELECT
+            user_id as "user_id: UserId",
+            secret_crypt,
+            enabled_at as "enabled_at?: UtcDateTime",
+            last_used_step,
+            failed_attempts,
+            last_failed_at as "last_failed_at?: UtcDateTime",
+            created_at
+        FROM user_totp
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn is_totp_enabled<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query_scalar!(
+        r#"
+        SELECT user_id as "user_id: UserId"
+        FROM user_totp
+        WHERE user_id = $1 AND enabled_at IS NOT NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .is_some())
+}
+
+pub async fn enable_user_totp<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE user_totp
+        SET enabled_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Only accept steps more recent than the last used one, so that codes cannot be replayed
+pub async fn record_totp_success<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+    used_step: i64,
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query!(
+        r#"
+        UPDATE user_totp
+        SET
+            last_used_step = $2,
+            failed_attempts = 0,
+            last_failed_at = NULL
+        WHERE user_id = $1 AND last_used_step < $2
+        "#,
+        user_id,
+        used_step
+    )
+    .execute(executor)
+    .await?
+    .rows_affected()
+        > 0)
+}
+
+pub async fn record_totp_failure<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        UPDATE user_totp
+        SET
+            failed_attempts = failed_attempts + 1,
+            last_failed_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_user_totp<'c>(
+    executor: &mut Transaction<'c, Database>,
+    user_id: &UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM user_recovery_codes
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **executor)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM user_totp
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **executor)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn replace_recovery_codes<'c>(
+    executor: &mut Transaction<'c, Database>,
+    user_id: &UserId,
+    code_hashes: &[Vec<u8>],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        DELETE FROM user_recovery_codes
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .execute(&mut **executor)
+    .await?;
+
+    for code_hash in code_hashes {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            VALUES ($1, $2)
+            "#,
+            user_id,
+            code_hash
+        )
+        .execute(&mut **executor)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Recovery codes are single use, return false if the code was not found
+pub async fn use_recovery_code<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+    code_hash: &[u8],
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query!(
+        r#"
+        DELETE FROM user_recovery_codes
+        WHERE user_id = $1 AND code_hash = $2
+        "#,
+        user_id,
+        code_hash
+    )
+    .execute(executor)
+    .await?
+    .rows_affected()
+        > 0)
+}
+
+pub async fn count_recovery_codes<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM user_recovery_codes
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(executor)
+    .await
+}
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use sqlx::{FromRow, Transaction};
+use sqlx::{FromRow, QueryBuilder, Transaction};
 
 use shared::data::user::{User, UserId};
 
@@ -34,12 +34,12 @@ pub struct UserUpdate {
     pub password_hash: Option<String>,
 }
 
-pub async fn create_user(
-    db_pool: &DbPool,
+pub async fn create_user<'c>(
+    executor: impl DbExecutor<'c>,
     username: &str,
     email_crypt: Option<&[u8]>,
     email_hash: Option<&[u8]>,
-    password_hash: &str,
+    password_hash: Option<&str>,
     terms_accepted_at: &DateTime<Utc>,
     max_characters: i16,
 ) -> Result<Option<uuid::Uuid>, sqlx::Error> {
@@ -58,7 +58,7 @@ pub async fn create_user(
         terms_accepted_at,
         max_characters
     )
-    .execute(db_pool)
+    .execute(executor)
     .await;
 
     match res {
@@ -95,6 +95,40 @@ pub async fn read_user(
     .await
 }
 
+/// Deleted users are skipped
+pub async fn read_users(
+    db_pool: &DbPool,
+    user_ids: &[UserId],
+) -> Result<Vec<UserEntry>, sqlx::Error> {
+    if user_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut query_builder = QueryBuilder::<Database>::new(
+        r#"
+        SELECT
+            user_id,
+            username,
+            email_crypt,
+            terms_accepted_at,
+            is_admin,
+            max_characters,
+            chat_badge,
+            last_login_at,
+            created_at,
+            updated_at,
+            deleted_at
+         FROM users WHERE deleted_at IS NULL AND user_id IN ("#,
+    );
+    let mut separated = query_builder.separated(", ");
+    for user_id in user_ids {
+        separated.push_bind(user_id);
+    }
+    separated.push_unseparated(")");
+
+    query_builder.build_query_as().fetch_all(db_pool).await
+}
+
 pub async fn read_user_by_email(
     db_pool: &DbPool,
     email_hash: &[u8],
@@ -167,6 +201,23 @@ pub async fn auth_user(
     }))
 }
 
+/// Accounts created through an identity provider have no password
+pub async fn has_password<'c>(
+    executor: impl DbExecutor<'c>,
+    user_id: &UserId,
+) -> Result<bool, sqlx::Error> {
+    Ok(sqlx::query_scalar!(
+        r#"
+        SELECT password_hash FROM users WHERE user_id = $1 AND deleted_at IS NULL
+        "#,
+        user_id
+    )
+    .fetch_optional(executor)
+    .await?
+    .flatten()
+    .is_some())
+}
+
 pub async fn update_last_login(db_pool: &DbPool, user_id: &UserId) -> Result<(), sqlx::Error> {
     sqlx::query!(
         r#"
@@ -243,6 +294,11 @@ pub async fn delete_user(db_pool: &DbPool, user_id: &UserId) -> Result<(), sqlx:
         super::characters::delete_character(db_pool, &character.character_id).await?;
     }
 
+    let mut tx = db_pool.begin().await?;
+
+    super::user_identities::delete_all_user_identities(&mut *tx, user_id).await?;
+    super::user_totp::delete_user_totp(&mut tx, user_id).await?;
+
     sqlx::query!(
         r#"
         UPDATE users
@@ -257,8 +313,10 @@ pub async fn delete_user(db_pool: &DbPool, user_id: &UserId) -> Result<(), sqlx:
         "#,
         user_id
     )
-    .execute(db_pool)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     Ok(())
 }
@@ -0,0 +1,22 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+
+use shared::data::challenge::ActiveChallenge;
+
+/// Currently active challenges, by schedule id
+#[derive(Debug, Clone, Default)]
+pub struct ChallengesStore {
+    pub active_challenges: Arc<DashMap<String, ActiveChallenge>>,
+}
+
+impl ChallengesStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, schedule_id: &str) -> Option<ActiveChallenge> {
+        self.active_challenges
+            .get(schedule_id)
+            .map(|entry| entry.value().clone())
+    }
+}
@@ -1,11 +1,18 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
-use shared::data::{area::AreaSpecs, chance::ChanceRange};
+use shared::data::{
+    area::{AreaLevel, AreaSpecs, BossDrop, BossDropType, BossDrops},
+    chance::{Chance, ChanceRange},
+};
 
 use crate::game::utils::json::LoadJsonFromFile;
 
-use super::{loot_table::LootTable, master_store::LootTablesStore};
+use super::{
+    items_store::{ItemId, ItemsStore},
+    loot_table::LootTable,
+    master_store::LootTablesStore,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AreaBlueprintSchema {
@@ -31,6 +38,32 @@ pub struct BossBlueprint {
     #[serde(default)]
     pub interval: Option<u16>,
     pub spawns: Vec<MonsterWaveSpawnBlueprint>,
+    #[serde(default)]
+    pub loot: Option<BossLootBlueprint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BossLootBlueprint {
+    /// Replace the area loot tables when rolling the boss reward
+    #[serde(default)]
+    pub loot_tables: Vec<String>,
+    #[serde(skip)]
+    pub loot_table: LootTable,
+
+    /// Always dropped on kill
+    #[serde(default)]
+    pub guaranteed_drops: Vec<ItemId>,
+    #[serde(default)]
+    pub unique_drops: Vec<BossUniqueDrop>,
+    /// Only dropped the first time the character kills the boss
+    #[serde(default)]
+    pub first_kill_drops: Vec<ItemId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BossUniqueDrop {
+    pub item_id: ItemId,
+    pub chance: Chance,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -64,6 +97,26 @@ impl AreaBlueprint {
             })
             .collect::<Result<_>>()?;
 
+        let mut bosses = schema.bosses;
+        for boss_loot in bosses.iter_mut().filter_map(|boss| boss.loot.as_mut()) {
+            boss_loot.loot_table = LootTable {
+                area_specific: false,
+                entries: boss_loot
+                    .loot_tables
+                    .iter()
+                    .map(|t| {
+                        loot_tables_store
+                            .get(t)
+                            .map(|t| t.entries.clone())
+                            .ok_or(anyhow::format_err!("missing boss loot table '{:?}'", t))
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            };
+        }
+
         Ok(Self {
             loot_table: LootTable {
                 area_specific: false,
@@ -83,8 +136,58 @@ impl AreaBlueprint {
             },
             reward_loot_table: None,
             specs: schema.specs,
-            bosses: schema.bosses,
+            bosses,
             waves: schema.waves,
         })
     }
 }
+
+impl BossBlueprint {
+    pub fn is_available(&self, area_level: AreaLevel) -> bool {
+        area_level >= self.level
+            && (area_level - self.level).is_multiple_of(self.interval.unwrap_or(AreaLevel::MAX))
+    }
+
+    /// List all items specific to the boss, for display purposes
+    pub fn possible_drops(&self, items_store: &ItemsStore) -> Option<BossDrops> {
+        let boss_loot = self.loot.as_ref()?;
+
+        let drops = boss_loot
+            .loot_table
+            .entries
+            .iter()
+            .map(|entry| (&entry.item_id, BossDropType::LootTable))
+            .chain(
+                boss_loot
+                    .guaranteed_drops
+                    .iter()
+                    .map(|item_id| (item_id, BossDropType::Guaranteed)),
+            )
+            .chain(
+                boss_loot
+                    .unique_drops
+                    .iter()
+                    .map(|drop| (&drop.item_id, BossDropType::Chance(drop.chance.value.get()))),
+            )
+            .chain(
+                boss_loot
+                    .first_kill_drops
+                    .iter()
+                    .map(|item_id| (item_id, BossDropType::FirstKill)),
+            )
+            .filter_map(|(item_id, drop_type)| {
+                items_store.content.get(item_id).map(|base| BossDrop {
+                    item_id: item_id.clone(),
+                    name: base.name.clone(),
+                    drop_type,
+                })
+            })
+            .collect();
+
+        Some(BossDrops {
+            level: self.level,
+            interval: self.interval,
+            drops,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use shared::data::{forge::CorruptionOutcome, item_affix::ItemAffixBlueprint};
+
+use crate::game::utils::{json::LoadJsonFromFile, rng::RandomWeighted};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CorruptionTable {
+    pub outcomes: Vec<CorruptionOutcomeEntry>,
+    #[serde(default)]
+    pub implicit_affixes: Vec<ItemAffixBlueprint>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CorruptionOutcomeEntry {
+    pub outcome: CorruptionOutcome,
+    pub weight: u64,
+}
+
+impl RandomWeighted for CorruptionOutcomeEntry {
+    fn random_weight(&self) -> u64 {
+        self.weight
+    }
+}
+
+impl LoadJsonFromFile for CorruptionTable {}
@@ -3,6 +3,7 @@ use shared::data::{
     chance::ChanceRange,
     character::CharacterState,
     character_status::StatusMap,
+    minion::{MinionSpecs, MinionState},
     monster::{MonsterSpecs, MonsterState},
     passive::{PassivesTreeAscension, PassivesTreeState},
     player::{CharacterSpecs, PlayerBaseSpecs, PlayerSpecs, PlayerState},
@@ -35,8 +36,10 @@ impl DataInit<&CharacterSpecs> for CharacterState {
         CharacterState {
             life: specs.character_attrs.max_life.get().into(),
             mana: specs.character_attrs.max_mana.get().into(),
+            ward: specs.character_attrs.max_ward.get().into(),
 
             statuses: StatusMap::default(),
+            status_immunities: Default::default(),
             skills_states: specs
                 .skills_specs
                 .iter()
@@ -53,6 +56,7 @@ impl DataInit<&CharacterSpecs> for CharacterState {
             monitored_conditions: Default::default(),
             repeated_skills: Default::default(),
             resurrected: false,
+            ward_recharge_elapsed: 0.0,
         }
     }
 }
@@ -105,6 +109,15 @@ impl DataInit<&MonsterSpecs> for MonsterState {
     }
 }
 
+impl DataInit<&MinionSpecs> for MinionState {
+    fn init(specs: &MinionSpecs) -> Self {
+        MinionState {
+            character_state: CharacterState::init(&specs.character_specs),
+            elapsed_lifetime: Default::default(),
+        }
+    }
+}
+
 // impl DataInit<BaseSkillSpecs> for SkillSpecs {
 //     fn init(specs: BaseSkillSpecs) -> Self {
 //         Self {
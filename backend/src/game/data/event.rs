@@ -5,6 +5,7 @@ use shared::data::{
     character::CharacterId,
     character_status::StatusId,
     skill::{DamageType, RestoreType, SkillRange, SkillType},
+    stat_effect::StatEffect,
     values::NonNegative,
 };
 
@@ -21,7 +22,9 @@ pub enum GameEvent {
     WaveCompleted(AreaLevel),
     ThreatIncreased(ThreatLevel),
     StatusApplied(StatusEvent),
+    StatusRemoved(StatusEvent),
     Restored(RestoreEvent), // MaximumLife(CharacterId),
+    Summoned(SummonEvent),
 }
 
 #[derive(Debug, Clone)]
@@ -72,6 +75,17 @@ pub struct RestoreEvent {
     pub value: NonNegative,
 }
 
+#[derive(Debug, Clone)]
+pub struct SummonEvent {
+    pub source: CharacterId,
+    pub skill_id: String,
+
+    pub minion_id: String,
+    pub lifetime: NonNegative,
+    pub max_minions: u8,
+    pub minion_effects: Vec<StatEffect>,
+}
+
 #[derive(Debug, Default)]
 pub struct EventsQueue {
     events: Vec<GameEvent>,
@@ -12,6 +12,8 @@ use crate::game::utils::json::LoadJsonFromFile;
 #[serde(rename_all = "snake_case")]
 pub enum ManifestCategory {
     Passives,
+    Atlas,
+    Challenges,
     Benedictions,
     Skills,
     SkillMasteries,
@@ -20,8 +22,10 @@ pub enum ManifestCategory {
     ItemAffixes,
     ItemAdjectives,
     ItemNouns,
+    ItemSets,
     Loot,
     GambleTable,
+    CorruptionTable,
     Monsters,
     Areas,
 }
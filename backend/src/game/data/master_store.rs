@@ -9,7 +9,10 @@ use std::{
 };
 
 use shared::data::{
+    atlas::AtlasSpecs,
+    challenge::ChallengeSchedule,
     character_status::{StatusEffectType, StatusSpecs},
+    item_set::{ItemSetId, ItemSetSpecs},
     monster::MonsterSpecs,
     passive::PassivesTreeSpecs,
     skill::{BaseSkillSpecs, SkillEffectType},
@@ -28,6 +31,7 @@ use super::{
 
 use crate::game::{
     data::{
+        corruption_table::CorruptionTable,
         loot_table::{GambleTable, GambleTableBlueprint},
         manifest::ManifestCategory,
     },
@@ -37,17 +41,22 @@ use crate::game::{
 // TODO: Load from zip/dat file and compress at build time for prod release
 
 pub type PassivesStore = HashMap<String, PassivesTreeSpecs>;
+pub type AtlasStore = HashMap<String, AtlasSpecs>;
+pub type ChallengeSchedulesStore = HashMap<String, ChallengeSchedule>;
 pub type BenedictionsStore = IndexMap<String, BenedictionsCategory>;
 pub type SkillsStore = HashMap<String, BaseSkillSpecs>;
 pub type SkillMasteriesStore = IndexMap<String, SkillMasterySpecs>;
 pub type StatusesStore = IndexedStore<String, StatusSpecs>;
 pub type MonstersSpecsStore = HashMap<String, BaseMonsterSpecs>;
+pub type ItemSetsStore = HashMap<ItemSetId, ItemSetSpecs>;
 pub type LootTablesStore = HashMap<String, LootTable>;
 pub type AreaBlueprintStore = HashMap<String, AreaBlueprint>;
 
 #[derive(Debug, Clone)]
 pub struct MasterStore {
     pub passives_store: Arc<PassivesStore>,
+    pub atlas_store: Arc<AtlasStore>,
+    pub challenge_schedules_store: Arc<ChallengeSchedulesStore>,
     pub benedictions_store: Arc<BenedictionsStore>,
     pub skills_store: Arc<SkillsStore>,
     pub skill_masteries_store: Arc<SkillMasteriesStore>,
@@ -56,8 +65,10 @@ pub struct MasterStore {
     pub item_affixes_table: Arc<ItemAffixesTable>,
     pub item_adjectives_table: Arc<ItemAdjectivesTable>,
     pub item_nouns_table: Arc<ItemNounsTable>,
+    pub item_sets_store: Arc<ItemSetsStore>,
     pub loot_tables_store: Arc<LootTablesStore>,
     pub gamble_table: Arc<GambleTable>,
+    pub corruption_table: Arc<CorruptionTable>,
     pub monster_specs_store: Arc<MonstersSpecsStore>,
     pub area_blueprints_store: Arc<AreaBlueprintStore>,
 }
@@ -67,7 +78,10 @@ impl LoadJsonFromFile for BaseSkillSpecs {}
 impl LoadJsonFromFile for SkillMasterySpecs {}
 impl LoadJsonFromFile for StatusSpecs {}
 impl LoadJsonFromFile for PassivesTreeSpecs {}
+impl LoadJsonFromFile for AtlasSpecs {}
+impl LoadJsonFromFile for ChallengeSchedule {}
 impl LoadJsonFromFile for BenedictionsCategory {}
+impl LoadJsonFromFile for ItemSetSpecs {}
 
 impl MasterStore {
     pub async fn load_from_folder(
@@ -78,6 +92,8 @@ impl MasterStore {
 
         let (
             passives_store,
+            atlas_store,
+            challenge_schedules_store,
             benedictions_store,
             skills_store,
             skill_masteries_store,
@@ -86,11 +102,15 @@ impl MasterStore {
             item_affixes_table,
             item_adjectives_table,
             item_nouns_table,
+            item_sets_store,
             loot_tables_store,
             gamble_tables_store,
+            corruption_tables_store,
             monster_specs_store,
         ) = tokio::join!(
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::Passives)),
+            join_load_and_merge_tables(manifest.get_resources(ManifestCategory::Atlas)),
+            join_load_and_merge_tables(manifest.get_resources(ManifestCategory::Challenges)),
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::Benedictions)),
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::Skills)),
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::SkillMasteries)),
@@ -101,8 +121,10 @@ impl MasterStore {
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::ItemAffixes)),
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::ItemAdjectives)),
             join_load_and_merge_tables(manifest.get_resources(ManifestCategory::ItemNouns)),
+            join_load_and_merge_tables(manifest.get_resources(ManifestCategory::ItemSets)),
             join_load_and_map(manifest.get_resources(ManifestCategory::Loot)),
             join_load_and_map(manifest.get_resources(ManifestCategory::GambleTable)),
+            join_load_and_map(manifest.get_resources(ManifestCategory::CorruptionTable)),
             join_load_and_map(manifest.get_resources(ManifestCategory::Monsters)),
         );
 
@@ -142,10 +164,18 @@ impl MasterStore {
             item_rarity: gamble_table_blueprint.item_rarity,
         };
 
+        let mut corruption_tables_store: HashMap<String, CorruptionTable> =
+            corruption_tables_store?;
+        let corruption_table = corruption_tables_store
+            .remove("0_corruption_table.json")
+            .expect("missing 'corruption_table.json'");
+
         // TODO: Pre attack indexed keys?
 
         let master_store = MasterStore {
             passives_store: Arc::new(passives_store?),
+            atlas_store: Arc::new(atlas_store?),
+            challenge_schedules_store: Arc::new(challenge_schedules_store?),
             benedictions_store: Arc::new(benedictions_store?),
             skills_store: Arc::new(skills_store?),
             skill_masteries_store: Arc::new(skill_masteries_store?),
@@ -157,8 +187,10 @@ impl MasterStore {
             item_affixes_table: Arc::new(item_affixes_table?),
             item_adjectives_table: Arc::new(item_adjectives_table?),
             item_nouns_table: Arc::new(item_nouns_table?),
+            item_sets_store: Arc::new(item_sets_store?),
             loot_tables_store: Arc::new(loot_tables_store),
             gamble_table: Arc::new(gamble_table),
+            corruption_table: Arc::new(corruption_table),
             monster_specs_store: Arc::new(monster_specs_store?),
             area_blueprints_store: Arc::new(area_blueprints_store),
         };
@@ -236,6 +268,114 @@ fn verify_store_integrity(master_store: &MasterStore) -> Result<()> {
         }
     }
 
+    for (item_id, item) in &master_store.items_store.content {
+        if let Some(item_set) = &item.item_set
+            && !master_store.item_sets_store.contains_key(item_set)
+        {
+            errors.push(anyhow!(
+                "Missing item set '{}' referenced by item '{}'",
+                item_set,
+                item_id
+            ));
+        }
+    }
+
+    for atlas_specs in master_store.atlas_store.values() {
+        for node_specs in atlas_specs.nodes.values() {
+            if !master_store
+                .area_blueprints_store
+                .contains_key(&node_specs.area_id)
+            {
+                errors.push(anyhow!(
+                    "Missing area '{}' referenced by atlas node '{}'",
+                    node_specs.area_id,
+                    node_specs.name
+                ));
+            }
+        }
+
+        for connection in atlas_specs.connections.iter() {
+            for node_id in [connection.from, connection.to] {
+                if !atlas_specs.nodes.contains_key(&node_id) {
+                    errors.push(anyhow!("Missing atlas node '{}' in connection", node_id));
+                }
+            }
+        }
+    }
+
+    for (schedule_id, challenge_specs) in master_store
+        .challenge_schedules_store
+        .iter()
+        .flat_map(|(schedule_id, schedule)| schedule.rotation.iter().map(move |c| (schedule_id, c)))
+    {
+        if !master_store
+            .area_blueprints_store
+            .contains_key(&challenge_specs.area_id)
+        {
+            errors.push(anyhow!(
+                "Missing area '{}' referenced by challenge '{}' in schedule '{}'",
+                challenge_specs.area_id,
+                challenge_specs.name,
+                schedule_id
+            ));
+        }
+
+        if !master_store
+            .items_store
+            .content
+            .get(&challenge_specs.map_item_id)
+            .map(|item| item.map_specs.is_some())
+            .unwrap_or_default()
+        {
+            errors.push(anyhow!(
+                "Missing map item '{}' referenced by challenge '{}'",
+                challenge_specs.map_item_id,
+                challenge_specs.name
+            ));
+        }
+
+        for map_affix in challenge_specs.map_affixes.iter() {
+            if !master_store
+                .item_affixes_table
+                .iter()
+                .any(|a| a.family == map_affix.family && a.tier == map_affix.tier)
+            {
+                errors.push(anyhow!(
+                    "Missing affix '{}' tier {} referenced by challenge '{}'",
+                    map_affix.family,
+                    map_affix.tier,
+                    challenge_specs.name
+                ));
+            }
+        }
+    }
+
+    for (area_id, boss_loot) in
+        master_store
+            .area_blueprints_store
+            .iter()
+            .flat_map(|(area_id, area)| {
+                area.bosses
+                    .iter()
+                    .filter_map(move |boss| boss.loot.as_ref().map(|loot| (area_id, loot)))
+            })
+    {
+        for item_id in boss_loot
+            .guaranteed_drops
+            .iter()
+            .chain(boss_loot.first_kill_drops.iter())
+            .chain(boss_loot.unique_drops.iter().map(|drop| &drop.item_id))
+        {
+            if !master_store.items_store.content.contains_key(item_id) {
+                errors.push(anyhow!(
+                    "Missing item '{}' referenced by boss loot of area '{}'",
+                    item_id,
+                    area_id
+                ));
+            }
+        }
+    }
+
     for spawn in master_store.area_blueprints_store.values().flat_map(|w| {
         w.bosses
             .iter()
@@ -304,6 +444,29 @@ fn verify_store_integrity(master_store: &MasterStore) -> Result<()> {
                 .iter()
                 .flat_map(|trigger| trigger.trigger_effect.effects.iter())
         }))
+        .chain(
+            master_store
+                .item_sets_store
+                .values()
+                .flat_map(|item_set| item_set.bonuses.iter())
+                .flat_map(|bonus| bonus.triggers.iter())
+                .flat_map(|trigger| trigger.trigger_effect.effects.iter()),
+        )
+        .chain(
+            master_store
+                .item_affixes_table
+                .iter()
+                .flat_map(|affix| affix.triggers.iter())
+                .flat_map(|trigger| trigger.trigger_effect.effects.iter()),
+        )
+        .chain(
+            master_store
+                .atlas_store
+                .values()
+                .flat_map(|atlas_specs| atlas_specs.modifiers_tree.nodes.values())
+                .flat_map(|node| node.triggers.iter())
+                .flat_map(|trigger| trigger.trigger_effect.effects.iter()),
+        )
         .chain(
             master_store
                 .statuses_store
@@ -1,4 +1,5 @@
 pub mod area;
+pub mod corruption_table;
 pub mod event;
 pub mod indexed_store;
 pub mod inventory_data;
@@ -4,10 +4,13 @@ use std::{collections::HashMap, time::Duration};
 
 use shared::data::{
     area::{AreaLevel, AreaSpecs, AreaState, AreaThreat},
+    atlas::AtlasState,
+    challenge::ActiveChallenge,
     character::CharacterId,
     game_stats::GameStats,
     item::ItemSpecs,
     loot::QueuedLoot,
+    minion::{MinionSpecs, MinionState},
     monster::{MonsterSpecs, MonsterState},
     passive::{PassivesTreeSpecs, PassivesTreeState},
     player::{
@@ -20,7 +23,11 @@ use shared::data::{
 };
 
 use crate::game::{
-    data::{DataInit, area::AreaBlueprint, master_store},
+    data::{
+        DataInit,
+        area::{AreaBlueprint, BossBlueprint},
+        master_store,
+    },
     systems::{
         area_controller, passives_controller, player_controller::PlayerController, player_updater,
         skills_updater,
@@ -33,6 +40,7 @@ pub struct GameInstanceData {
     pub realm: Realm,
     pub area_id: String,
     pub map_item: Option<ItemSpecs>,
+    pub challenge: Option<ActiveChallenge>,
 
     pub area_blueprint: AreaBlueprint,
     pub area_specs: AreaSpecs,
@@ -43,6 +51,8 @@ pub struct GameInstanceData {
     pub passives_tree_specs: PassivesTreeSpecs,
     pub passives_tree_state: LazySyncer<PassivesTreeState>,
 
+    pub atlas_state: AtlasState,
+
     pub player_base_specs: LazySyncer<PlayerBaseSpecs>,
     pub skill_mastery_skill_specs: HashMap<String, SkillSpecs>,
     pub player_specs: LazySyncer<PlayerSpecs>,
@@ -60,6 +70,11 @@ pub struct GameInstanceData {
     pub monster_specs: Vec<MonsterSpecs>, // Only use internally, not shared
     pub monster_states: Vec<MonsterState>,
     pub monster_wave_delay: Duration,
+    pub boss_blueprint: Option<BossBlueprint>,
+
+    pub minion_base_specs: LazySyncer<Vec<MinionSpecs>>,
+    pub minion_specs: Vec<MinionSpecs>, // Only use internally, not shared
+    pub minion_states: Vec<MinionState>,
 
     pub wave_completed: bool,
     pub new_wave: bool,
@@ -95,6 +110,11 @@ pub struct SavedGameData {
 
     end_quest: bool,
     quest_rewards: Option<QuestRewards>,
+
+    #[serde(default)]
+    atlas_state: AtlasState,
+    #[serde(default)]
+    challenge: Option<ActiveChallenge>,
 }
 
 impl std::ops::Deref for SavedGameData {
@@ -116,6 +136,7 @@ impl GameInstanceData {
         max_power_shard_level_ever: AreaLevel,
         passives_tree_id: &str,
         mut passives_tree_state: PassivesTreeState,
+        atlas_state: AtlasState,
         mut player_resources: PlayerResources,
         player_base_specs: PlayerBaseSpecs,
         player_inventory: PlayerInventory,
@@ -128,10 +149,17 @@ impl GameInstanceData {
             .cloned()
             .ok_or_else(|| anyhow::anyhow!("couldn't load area: {}", area_id))?;
 
+        let atlas_specs = master_store
+            .atlas_store
+            .get("default")
+            .ok_or_else(|| anyhow::anyhow!("couldn't load atlas"))?;
+
         let area_specs = area_controller::init_area_specs(
             &master_store.loot_tables_store,
             &mut area_blueprint,
             &map_item,
+            atlas_specs,
+            &atlas_state,
         );
 
         if area_specs.hidden
@@ -184,6 +212,7 @@ impl GameInstanceData {
         let player_specs = player_updater::update_player_specs(
             &master_store.skill_masteries_store,
             &master_store.statuses_store,
+            &master_store.item_sets_store,
             &player_base_specs,
             // &player_specs,
             &player_state,
@@ -203,6 +232,7 @@ impl GameInstanceData {
             realm,
             area_id,
             map_item,
+            challenge: None,
             area_specs,
             area_state: LazySyncer::new(area_state),
             area_blueprint,
@@ -212,6 +242,8 @@ impl GameInstanceData {
             passives_tree_specs,
             passives_tree_state: LazySyncer::new(passives_tree_state),
 
+            atlas_state,
+
             player_resources: LazySyncer::new(player_resources),
             player_state,
             player_controller,
@@ -226,6 +258,11 @@ impl GameInstanceData {
             monster_specs: Vec::new(),
             monster_states: Vec::new(),
             monster_wave_delay: Default::default(),
+            boss_blueprint: None,
+
+            minion_base_specs: LazySyncer::new(Vec::new()),
+            minion_specs: Vec::new(),
+            minion_states: Vec::new(),
 
             wave_completed: false,
             new_wave: false,
@@ -261,6 +298,8 @@ impl GameInstanceData {
             auto_progress: self.area_state.read().auto_progress,
             end_quest: self.end_quest,
             quest_rewards: self.quest_rewards.read().clone(),
+            atlas_state: self.atlas_state,
+            challenge: self.challenge,
         })?)
     }
 
@@ -286,6 +325,8 @@ impl GameInstanceData {
             auto_progress,
             end_quest,
             quest_rewards,
+            atlas_state,
+            challenge,
         } = rmp_serde::from_slice::<SavedGameData>(bytes)?;
 
         let mut s = Self::init_from_store(
@@ -297,6 +338,7 @@ impl GameInstanceData {
             max_power_shard_level_ever,
             &passives_tree_id,
             passives_tree_state,
+            atlas_state,
             player_resources,
             player_base_specs,
             player_inventory,
@@ -304,6 +346,7 @@ impl GameInstanceData {
             player_controller,
         )?;
 
+        s.challenge = challenge;
         s.area_state.mutate().area_level = area_level;
         s.area_state.mutate().max_area_level = max_area_level;
         s.area_state.mutate().last_champion_spawn = last_champion_spawn;
@@ -323,6 +366,7 @@ impl GameInstanceData {
         self.player_specs.mutate();
         self.player_inventory.mutate();
         self.monster_base_specs.mutate();
+        self.minion_base_specs.mutate();
         self.queued_loot.mutate();
         self.quest_rewards.mutate();
     }
@@ -334,6 +378,10 @@ impl GameInstanceData {
                 .monster_states
                 .get(index)
                 .map(|monster_state| &monster_state.character_state),
+            CharacterId::Minion(index) => self
+                .minion_states
+                .get(index)
+                .map(|minion_state| &minion_state.character_state),
         }
     }
 
@@ -344,6 +392,10 @@ impl GameInstanceData {
                 .monster_specs
                 .get(index)
                 .map(|monster_specs| &monster_specs.character_specs),
+            CharacterId::Minion(index) => self
+                .minion_specs
+                .get(index)
+                .map(|minion_specs| &minion_specs.character_specs),
         }
     }
 }
@@ -1,10 +1,15 @@
 use anyhow::Result;
+use chrono::Utc;
+use std::time::Instant;
 
 use shared::{
     computations,
     constants::{self, RUSH_MODE_SPEED_MULTIPLIER},
     data::{realms::Realm, user::UserCharacterId},
-    messages::server::{ErrorMessage, ErrorType, ServerMessage},
+    messages::server::{
+        ErrorMessage, ErrorType, MaintenanceMessage, ScheduledMaintenance, ServerDownMessage,
+        ServerMessage,
+    },
 };
 
 use super::{
@@ -12,6 +17,8 @@ use super::{
     game_data::GameInstanceData,
     game_inputs, game_orchestrator, game_sync,
     game_timer::GameTimer,
+    maintenance::MaintenanceStore,
+    systems::atlas_controller,
 };
 
 use crate::{
@@ -27,12 +34,14 @@ pub struct GameInstance<'a> {
     chat_integration: ChatIntegration,
     master_store: MasterStore,
     sessions_store: SessionsStore,
+    maintenance_store: MaintenanceStore,
     character_id: &'a UserCharacterId,
     game_data: &'a mut GameInstanceData,
     events_queue: EventsQueue,
 }
 
 impl<'a> GameInstance<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         client_conn: &'a mut WebSocketConnection,
         character_id: &'a UserCharacterId,
@@ -41,6 +50,7 @@ impl<'a> GameInstance<'a> {
         chat_integration: ChatIntegration,
         master_store: MasterStore,
         sessions_store: SessionsStore,
+        maintenance_store: MaintenanceStore,
     ) -> Self {
         GameInstance {
             client_conn,
@@ -49,6 +59,7 @@ impl<'a> GameInstance<'a> {
             chat_integration,
             master_store,
             sessions_store,
+            maintenance_store,
             game_data,
 
             events_queue: EventsQueue::new(),
@@ -72,6 +83,7 @@ impl<'a> GameInstance<'a> {
         .await?;
 
         let mut game_timer = GameTimer::new();
+        let mut announced_maintenance = None;
         loop {
             if !self.game_data.end_quest {
                 game_orchestrator::reset_entities(self.game_data).await;
@@ -99,6 +111,7 @@ impl<'a> GameInstance<'a> {
                 1
             };
             for _ in 0..tick_multiplier {
+                let tick_start = Instant::now();
                 game_orchestrator::tick(
                     &mut self.events_queue,
                     self.game_data,
@@ -106,21 +119,82 @@ impl<'a> GameInstance<'a> {
                     elapsed_time,
                 )
                 .await?;
+                metrics::histogram!("game_tick_duration_seconds")
+                    .record(tick_start.elapsed().as_secs_f64());
             }
 
             match game_sync::sync_update_game(self.client_conn, self.game_data).await {
                 Ok(true) => {}
-                Ok(false) => tracing::debug!("skipping sync update while previous send is pending"),
+                Ok(false) => {
+                    metrics::counter!("game_sync_skipped_total").increment(1);
+                    tracing::debug!("skipping sync update while previous send is pending")
+                }
                 Err(e) => {
                     tracing::warn!("failed to sync client: {}", e);
                     break;
                 }
             }
 
-            if game_timer.should_autosave() {
+            let save_requested = self
+                .sessions_store
+                .sessions_saving
+                .remove(self.character_id)
+                .is_some();
+            if game_timer.should_autosave() || save_requested {
                 self.auto_save();
             }
 
+            if let Some(mut active_session) = self
+                .sessions_store
+                .active_sessions
+                .get_mut(self.character_id)
+            {
+                active_session.area_level = self.game_data.area_state.read().area_level;
+                active_session.last_active = Instant::now();
+            }
+
+            // Warn the player as soon as a maintenance is scheduled, or cancelled
+            let maintenance = self
+                .maintenance_store
+                .get()
+                .filter(|maintenance| Utc::now() < maintenance.ends_at);
+            if maintenance != announced_maintenance {
+                self.client_conn
+                    .send(
+                        &MaintenanceMessage {
+                            scheduled: maintenance.map(|maintenance| ScheduledMaintenance {
+                                starts_at: maintenance.starts_at,
+                                expected_launch_time: maintenance.ends_at,
+                            }),
+                        }
+                        .into(),
+                    )
+                    .await
+                    .unwrap_or_else(|_| tracing::warn!("failed to send maintenance message"));
+                announced_maintenance = maintenance;
+            }
+
+            // Save before exiting so clients can resume even if the server is restarting
+            if let Some(expected_launch_time) = self.maintenance_store.down_until() {
+                save_progress(
+                    self.db_pool.clone(),
+                    *self.character_id,
+                    self.game_data.clone(),
+                )
+                .await;
+                self.client_conn
+                    .send(
+                        &ServerDownMessage {
+                            expected_launch_time,
+                        }
+                        .into(),
+                    )
+                    .await
+                    .unwrap_or_else(|_| tracing::warn!("failed to send server-down message"));
+                tracing::debug!("game session '{}' drained", self.character_id);
+                return Ok(());
+            }
+
             if self
                 .sessions_store
                 .sessions_stealing
@@ -164,17 +238,7 @@ impl<'a> GameInstance<'a> {
             *self.character_id,
             self.game_data.clone(), // TODO: Do something else, like only copy the necessary data
         );
-        tokio::spawn(async move {
-            auto_save_impl(db_pool, character_id, game_data)
-                .await
-                .unwrap_or_else(|e| {
-                    tracing::error!(
-                        "failed to save character progress '{}': {}",
-                        character_id,
-                        e
-                    )
-                });
-        });
+        tokio::spawn(save_progress(db_pool, character_id, game_data));
     }
 
     async fn terminate_quest(&self) -> Result<()> {
@@ -257,8 +321,37 @@ impl<'a> GameInstance<'a> {
                 .await?;
             }
 
+            if self.game_data.map_item.is_some()
+                && self.game_data.challenge.is_none()
+                && let Some(atlas_specs) = self.master_store.atlas_store.get("default")
+            {
+                let mut atlas_state =
+                    db::characters_data::load_character_atlas(&mut *tx, self.character_id).await?;
+                if atlas_controller::complete_atlas_nodes(
+                    atlas_specs,
+                    &mut atlas_state,
+                    &self.game_data.area_id,
+                    self.game_data.area_state.read().max_area_level,
+                ) {
+                    db::characters_data::save_character_atlas(
+                        &mut *tx,
+                        self.character_id,
+                        &atlas_state,
+                    )
+                    .await?;
+                }
+            }
+
             if self.game_data.area_state.read().max_area_level > 0 {
                 let realm_id = self.game_data.realm.realm_id();
+                // Challenges have their own leaderboard for each period
+                let (leaderboard_id, leaderboard_name) = match &self.game_data.challenge {
+                    Some(challenge) => (challenge.challenge_id(), &challenge.specs.name),
+                    None => (
+                        self.game_data.area_id.clone(),
+                        &self.game_data.area_specs.name,
+                    ),
+                };
                 if let Err(err) = db::game_stats::save_game_stats(
                     &mut *tx,
                     self.character_id,
@@ -274,7 +367,7 @@ impl<'a> GameInstance<'a> {
                     &mut tx,
                     self.character_id,
                     &realm_id,
-                    &self.game_data.area_id,
+                    &leaderboard_id,
                     self.game_data.area_state.read().max_area_level as i32,
                     self.game_data
                         .game_stats
@@ -302,7 +395,7 @@ impl<'a> GameInstance<'a> {
                                         .name,
                                     realm_label,
                                     self.game_data.area_state.read().max_area_level,
-                                    self.game_data.area_specs.name,
+                                    leaderboard_name,
                                 ),
                                 None,
                             )
@@ -331,6 +424,24 @@ impl<'a> GameInstance<'a> {
     }
 }
 
+async fn save_progress(
+    db_pool: DbPool,
+    character_id: UserCharacterId,
+    game_data: GameInstanceData,
+) {
+    let save_start = Instant::now();
+    if let Err(e) = auto_save_impl(db_pool, character_id, game_data).await {
+        metrics::counter!("game_autosave_failures_total").increment(1);
+        tracing::error!(
+            "failed to save character progress '{}': {}",
+            character_id,
+            e
+        );
+    }
+    metrics::histogram!("game_autosave_duration_seconds")
+        .record(save_start.elapsed().as_secs_f64());
+}
+
 async fn auto_save_impl(
     db_pool: DbPool,
     character_id: UserCharacterId,
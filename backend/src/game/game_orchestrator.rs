@@ -14,8 +14,8 @@ use super::{
     },
     game_data::GameInstanceData,
     systems::{
-        area_controller, events_resolver, monsters_controller, monsters_updater, monsters_wave,
-        player_updater,
+        area_controller, events_resolver, minions_controller, minions_updater, monsters_controller,
+        monsters_updater, monsters_wave, player_updater,
     },
     utils::LazySyncer,
 };
@@ -29,6 +29,7 @@ pub async fn reset_entities(game_data: &mut GameInstanceData) {
 
     player_updater::reset_player(&mut game_data.player_state);
     monsters_updater::reset_monsters(&mut game_data.monster_states);
+    minions_updater::reset_minions(&mut game_data.minion_states);
 }
 
 pub async fn tick(
@@ -63,6 +64,7 @@ pub async fn tick(
         *game_data.player_specs.mutate() = player_updater::update_player_specs(
             &master_store.skill_masteries_store,
             &master_store.statuses_store,
+            &master_store.item_sets_store,
             game_data.player_base_specs.read(),
             // game_data.player_specs.read(),
             &game_data.player_state,
@@ -101,6 +103,33 @@ pub async fn tick(
         }
     }
 
+    if game_data
+        .minion_states
+        .iter()
+        .any(|m| m.character_state.dirty_specs)
+    {
+        for (i, ((base_specs, minion_specs), minion_state)) in game_data
+            .minion_base_specs
+            .read()
+            .iter()
+            .zip(game_data.minion_specs.iter_mut())
+            .zip(game_data.minion_states.iter_mut())
+            .enumerate()
+        {
+            if minion_state.character_state.dirty_specs {
+                minion_state.character_state.dirty_specs = false;
+                minions_updater::update_minion_specs(
+                    &master_store.statuses_store,
+                    CharacterId::Minion(i),
+                    base_specs,
+                    minion_specs,
+                    minion_state,
+                    &game_data.area_threat,
+                );
+            }
+        }
+    }
+
     control_entities(events_queue, game_data, master_store).await?;
     events_resolver::resolve_events(events_queue, game_data, master_store).await;
     // Some effects need to chain before next tick (eg. retaliate bleed)
@@ -146,6 +175,21 @@ async fn control_entities(
         })
         .collect();
 
+    let mut minions_still_alive: Vec<_> = game_data
+        .minion_specs
+        .iter()
+        .zip(game_data.minion_states.iter_mut())
+        .enumerate()
+        .filter(|(_, (_, m))| m.character_state.is_alive)
+        .map(|(i, (x, y))| {
+            (
+                CharacterId::Minion(i),
+                (&x.character_specs, &mut y.character_state),
+            )
+        })
+        .collect();
+
+    let prevent_attack = game_data.new_wave;
     game_data.player_controller.control_player(
         &master_store.statuses_store,
         events_queue,
@@ -154,12 +198,25 @@ async fn control_entities(
         game_data.player_specs.read(),
         game_data.player_inventory.read(),
         &mut game_data.player_state,
+        &mut minions_still_alive,
         &mut monsters_still_alive,
-        game_data.new_wave,
+        prevent_attack,
     );
     game_data.new_wave = false;
 
     let wave_completed = monsters_still_alive.is_empty();
+
+    minions_controller::control_minions(
+        &master_store.statuses_store,
+        events_queue,
+        &game_data.minion_specs,
+        &mut game_data.minion_states,
+        game_data.player_specs.read(),
+        &mut game_data.player_state,
+        &game_data.monster_specs,
+        &mut game_data.monster_states,
+        prevent_attack,
+    );
     if wave_completed || game_data.area_state.read().going_back != 0 {
         game_data.area_threat.cooldown = Default::default();
         if wave_completed
@@ -181,17 +238,23 @@ async fn control_entities(
                 area_state.going_back = 0;
             }
 
-            let (monster_specs, monster_states) = monsters_wave::generate_monsters_wave(
-                &master_store.monster_specs_store,
-                &master_store.statuses_store,
-                &game_data.area_blueprint.waves,
-                &game_data.area_blueprint.bosses,
-                &game_data.area_specs,
-                game_data.area_state.mutate(),
-            )?;
+            let (monster_specs, monster_states, boss_blueprint) =
+                monsters_wave::generate_monsters_wave(
+                    &master_store.monster_specs_store,
+                    &master_store.statuses_store,
+                    &game_data.area_blueprint.waves,
+                    &game_data.area_blueprint.bosses,
+                    &game_data.area_specs,
+                    game_data.area_state.mutate(),
+                    game_data
+                        .challenge
+                        .as_ref()
+                        .map(|challenge| challenge.seed()),
+                )?;
             game_data.monster_base_specs = LazySyncer::new(monster_specs.clone());
             game_data.monster_specs = monster_specs;
             game_data.monster_states = monster_states;
+            game_data.boss_blueprint = boss_blueprint;
             game_data.new_wave = true;
 
             game_data.area_threat = AreaThreat {
@@ -217,6 +280,8 @@ async fn control_entities(
             &mut game_data.monster_states,
             game_data.player_specs.read(),
             &mut game_data.player_state,
+            &game_data.minion_specs,
+            &mut game_data.minion_states,
         );
     }
 
@@ -278,6 +343,14 @@ async fn update_entities(
         &mut game_data.monster_states,
         &game_data.area_threat,
     );
+    minions_updater::update_minion_states(
+        &master_store.statuses_store,
+        events_queue,
+        elapsed_time,
+        &game_data.minion_specs,
+        &mut game_data.minion_states,
+        &game_data.area_threat,
+    );
 }
 
 fn respawn_player(game_data: &mut GameInstanceData, master_store: &MasterStore) {
@@ -285,9 +358,14 @@ fn respawn_player(game_data: &mut GameInstanceData, master_store: &MasterStore)
     game_data.monster_specs.clear();
     game_data.monster_states.clear();
 
+    game_data.minion_base_specs.mutate().clear();
+    game_data.minion_specs.clear();
+    game_data.minion_states.clear();
+
     *game_data.player_specs.mutate() = player_updater::update_player_specs(
         &master_store.skill_masteries_store,
         &master_store.statuses_store,
+        &master_store.item_sets_store,
         game_data.player_base_specs.read(),
         // game_data.player_specs.read(),
         &game_data.player_state,
@@ -62,6 +62,8 @@ pub async fn sync_update_game(
         player_stamina: game_data.player_stamina,
         monster_specs: game_data.monster_base_specs.sync(),
         monster_states: game_data.monster_states.clone(),
+        minion_specs: game_data.minion_base_specs.sync(),
+        minion_states: game_data.minion_states.clone(),
         queued_loot: game_data.queued_loot.sync(),
         game_stats: game_data.game_stats.clone(),
         quest_rewards: game_data.quest_rewards.sync(),
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Maintenance {
+    /// Game sessions are closed once started
+    pub starts_at: DateTime<Utc>,
+    /// Sent to the clients as expected launch time
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Scheduled server maintenance, set by the admins, and graceful drain before restarts
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceStore {
+    maintenance: Arc<Mutex<Option<Maintenance>>>,
+    draining_until: Arc<Mutex<Option<DateTime<Utc>>>>,
+}
+
+impl MaintenanceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self) -> Option<Maintenance> {
+        *self.maintenance.lock().unwrap()
+    }
+
+    pub fn schedule(&self, maintenance: Maintenance) {
+        *self.maintenance.lock().unwrap() = Some(maintenance);
+    }
+
+    /// Return the cancelled maintenance, if any
+    pub fn cancel(&self) -> Option<Maintenance> {
+        self.maintenance.lock().unwrap().take()
+    }
+
+    /// Close all game sessions now, clients should reconnect at the given time
+    pub fn drain(&self, reconnect_at: DateTime<Utc>) {
+        *self.draining_until.lock().unwrap() = Some(reconnect_at);
+    }
+
+    /// Expected end of the maintenance or drain if one is ongoing
+    pub fn down_until(&self) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        let maintenance_until = self
+            .get()
+            .filter(|maintenance| maintenance.starts_at <= now && now < maintenance.ends_at)
+            .map(|maintenance| maintenance.ends_at);
+        let draining_until = self
+            .draining_until
+            .lock()
+            .unwrap()
+            .filter(|draining_until| now < *draining_until);
+
+        maintenance_until.max(draining_until)
+    }
+}
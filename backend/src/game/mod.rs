@@ -1,3 +1,4 @@
+pub mod challenges;
 pub mod data;
 pub mod game_data;
 pub mod game_inputs;
@@ -5,6 +6,7 @@ pub mod game_instance;
 pub mod game_orchestrator;
 pub mod game_sync;
 pub mod game_timer;
+pub mod maintenance;
 pub mod sessions;
 pub mod systems;
 pub mod utils;
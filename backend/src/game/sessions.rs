@@ -1,6 +1,7 @@
 use dashmap::{DashMap, DashSet};
 use std::{sync::Arc, time::Instant};
 
+use shared::data::area::AreaLevel;
 pub use shared::data::user::UserCharacterId;
 
 use super::game_data::GameInstanceData;
@@ -11,6 +12,10 @@ pub struct SessionsStore {
     pub sessions_stealing: Arc<DashSet<UserCharacterId>>,
     // pub sessions: Arc<Mutex<HashMap<UserCharacterId, Session>>>,
     // pub sessions_stealing: Arc<Mutex<HashSet<UserCharacterId>>>,
+    /// Sessions currently played, kept up to date by their game instance
+    pub active_sessions: Arc<DashMap<UserCharacterId, ActiveSession>>,
+    /// Active sessions that must be saved on their next tick
+    pub sessions_saving: Arc<DashSet<UserCharacterId>>,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +26,13 @@ pub struct Session {
     pub game_data: Box<GameInstanceData>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ActiveSession {
+    pub area_id: String,
+    pub area_level: AreaLevel,
+    pub last_active: Instant,
+}
+
 impl Default for SessionsStore {
     fn default() -> Self {
         Self::new()
@@ -32,6 +44,8 @@ impl SessionsStore {
         Self {
             sessions: Default::default(),
             sessions_stealing: Default::default(),
+            active_sessions: Default::default(),
+            sessions_saving: Default::default(),
         }
     }
 }
@@ -2,9 +2,11 @@ use shared::{
     constants::{MAX_AREA_LEVEL, MAX_POWER_SHARD_LEVEL_BASE},
     data::{
         area::{AreaLevel, AreaSpecs, AreaState},
+        atlas::{AtlasSpecs, AtlasState},
         item::ItemSpecs,
         item_affix::AffixEffectScope,
         modifier::ModifiableValue,
+        passive,
         stat_effect::{EffectsMap, StatType},
     },
 };
@@ -22,6 +24,8 @@ pub fn init_area_specs(
     loot_tables_store: &LootTablesStore,
     area_blueprint: &mut AreaBlueprint,
     map_item: &Option<ItemSpecs>,
+    atlas_specs: &AtlasSpecs,
+    atlas_state: &AtlasState,
 ) -> AreaSpecs {
     let mut area_specs = area_blueprint.specs.clone();
 
@@ -53,7 +57,31 @@ pub fn init_area_specs(
                 .cloned();
         }
 
-        for trigger_specs in map_item.base.triggers.iter() {
+        area_specs.player_effects = map_item
+            .modifiers
+            .aggregate_effects(AffixEffectScope::Player, false);
+
+        for trigger_specs in map_item.base.triggers.iter().chain(
+            map_item
+                .modifiers
+                .affixes
+                .iter()
+                .flat_map(|affix| affix.triggers.iter()),
+        ) {
+            area_specs.triggers.push(
+                trigger_specs.trigger.clone(),
+                trigger_specs.trigger_effect.clone(),
+                None,
+            );
+        }
+
+        // Atlas modifiers only apply to maps
+        for trigger_specs in atlas_state
+            .purchased_modifiers
+            .iter()
+            .filter_map(|node_id| atlas_specs.modifiers_tree.nodes.get(node_id))
+            .flat_map(|node_specs| node_specs.triggers.iter())
+        {
             area_specs.triggers.push(
                 trigger_specs.trigger.clone(),
                 trigger_specs.trigger_effect.clone(),
@@ -71,6 +99,13 @@ pub fn init_area_specs(
                 map_item
                     .modifiers
                     .aggregate_effects(AffixEffectScope::Global, false),
+            ))
+            .chain(std::iter::once(
+                passive::generate_effects_map_from_passives(
+                    &atlas_specs.modifiers_tree,
+                    &Default::default(),
+                    &atlas_state.purchased_modifiers,
+                ),
             )),
         )
     } else {
@@ -112,6 +147,7 @@ fn compute_area_specs(area_specs: &mut AreaSpecs) -> f64 {
             StatType::ItemLevel => area_specs.item_level_modifier.apply_effect(&effect),
             StatType::GemsFind => area_specs.gems_find.apply_effect(&effect),
             StatType::GoldFind => area_specs.gold_find.apply_effect(&effect),
+            StatType::ChampionChance => area_specs.champion_chance.apply_effect(&effect),
             StatType::PowerLevel => area_specs.power_level.apply_effect(&effect),
             _ => {}
         }
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use shared::data::{
+    area::AreaLevel,
+    atlas::{AtlasSpecs, AtlasState},
+    passive::PurchasedNodes,
+    user::UserCharacterId,
+};
+use sqlx::Transaction;
+
+use crate::{
+    app_state::MasterStore,
+    db::{self, pool::Database},
+    rest::AppError,
+};
+
+/// Complete all unlocked atlas nodes of the area if the reached level is high enough.
+/// Return whether any new node was completed.
+pub fn complete_atlas_nodes(
+    atlas_specs: &AtlasSpecs,
+    atlas_state: &mut AtlasState,
+    area_id: &str,
+    area_level: AreaLevel,
+) -> bool {
+    let completed_nodes: Vec<_> = atlas_specs
+        .nodes
+        .iter()
+        .filter(|(node_id, node_specs)| {
+            node_specs.area_id == area_id
+                && area_level >= node_specs.completion_level
+                && !atlas_state.completed_nodes.contains(*node_id)
+                && atlas_specs.is_node_unlocked(atlas_state, node_id)
+        })
+        .map(|(node_id, _)| *node_id)
+        .collect();
+
+    let completed = !completed_nodes.is_empty();
+    atlas_state.completed_nodes.extend(completed_nodes);
+    completed
+}
+
+pub async fn update_atlas_modifiers(
+    tx: &mut Transaction<'_, Database>,
+    master_store: &MasterStore,
+    character_id: &UserCharacterId,
+    purchased_modifiers: &PurchasedNodes,
+) -> Result<AtlasState, AppError> {
+    let atlas_specs = master_store
+        .atlas_store
+        .get("default")
+        .ok_or(anyhow::anyhow!("atlas not found"))?;
+
+    let mut atlas_state =
+        db::characters_data::load_character_atlas(&mut **tx, character_id).await?;
+    atlas_state.purchased_modifiers = purchased_modifiers.clone();
+
+    validate_atlas_modifiers(atlas_specs, &atlas_state)?;
+
+    db::characters_data::save_character_atlas(&mut **tx, character_id, &atlas_state).await?;
+
+    Ok(atlas_state)
+}
+
+pub fn validate_atlas_modifiers(
+    atlas_specs: &AtlasSpecs,
+    atlas_state: &AtlasState,
+) -> Result<(), AppError> {
+    if atlas_specs.available_points(atlas_state) < 0 {
+        return Err(AppError::UserError("not enough atlas points".to_string()));
+    }
+
+    let modifiers_tree = &atlas_specs.modifiers_tree;
+    let mut allocated_nodes = HashSet::new();
+
+    for node_id in atlas_state.purchased_modifiers.iter() {
+        let node_specs = modifiers_tree
+            .nodes
+            .get(node_id)
+            .ok_or(AppError::UserError(
+                "invalid atlas modifiers: missing node".into(),
+            ))?;
+
+        if !node_specs.root_node
+            && !modifiers_tree.connections.iter().any(|connection| {
+                (connection.from == *node_id && allocated_nodes.contains(&connection.to))
+                    || (connection.to == *node_id && allocated_nodes.contains(&connection.from))
+            })
+        {
+            return Err(AppError::UserError(
+                "invalid atlas modifiers: missing connection".into(),
+            ));
+        }
+
+        allocated_nodes.insert(*node_id);
+    }
+
+    Ok(())
+}
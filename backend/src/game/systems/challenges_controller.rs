@@ -0,0 +1,222 @@
+use chrono::{DateTime, Utc};
+use sqlx::Transaction;
+
+use shared::data::{
+    challenge::ActiveChallenge, item::ItemSpecs, realms::RealmId, user::UserCharacterId,
+};
+
+use crate::{
+    app_state::MasterStore,
+    db::{self, characters::CharacterResources, pool::Database},
+    game::{
+        challenges::ChallengesStore,
+        data::master_store::ChallengeSchedulesStore,
+        systems::{items_controller, loot_generator},
+        utils::rng,
+    },
+    rest::AppError,
+};
+
+/// Compute the challenge of each schedule for the period containing `now`
+pub fn compute_active_challenges(
+    challenge_schedules_store: &ChallengeSchedulesStore,
+    now: DateTime<Utc>,
+) -> Vec<ActiveChallenge> {
+    challenge_schedules_store
+        .iter()
+        .filter(|(_, schedule)| !schedule.rotation.is_empty())
+        .map(|(schedule_id, schedule)| {
+            let period_index = schedule.period.period_index(now);
+            ActiveChallenge {
+                schedule_id: schedule_id.clone(),
+                period: schedule.period,
+                period_index,
+                ends_at: schedule.period.period_end(period_index),
+                specs: schedule.rotation
+                    [period_index.rem_euclid(schedule.rotation.len() as i64) as usize]
+                    .clone(),
+            }
+        })
+        .collect()
+}
+
+/// Update the active challenges, return the ones that just started
+pub fn rotate_challenges(
+    master_store: &MasterStore,
+    challenges_store: &ChallengesStore,
+    now: DateTime<Utc>,
+) -> Vec<ActiveChallenge> {
+    compute_active_challenges(&master_store.challenge_schedules_store, now)
+        .into_iter()
+        .filter(|challenge| {
+            let is_new = challenges_store
+                .active_challenges
+                .get(&challenge.schedule_id)
+                .map(|active| active.period_index != challenge.period_index)
+                .unwrap_or(true);
+
+            if is_new {
+                challenges_store
+                    .active_challenges
+                    .insert(challenge.schedule_id.clone(), challenge.clone());
+            }
+
+            is_new
+        })
+        .collect()
+}
+
+/// Generate the map item of the challenge, identical for every player
+pub fn generate_challenge_map(
+    master_store: &MasterStore,
+    challenge: &ActiveChallenge,
+) -> Option<ItemSpecs> {
+    let base = master_store
+        .items_store
+        .content
+        .get(&challenge.specs.map_item_id)?
+        .clone();
+
+    let affixes: Vec<_> = challenge
+        .specs
+        .map_affixes
+        .iter()
+        .map(|map_affix| (map_affix.family.as_str(), map_affix.tier))
+        .collect();
+
+    let mut map_item = loot_generator::roll_item_with_affixes(
+        challenge.specs.map_item_id.clone(),
+        base,
+        challenge.specs.map_level,
+        &affixes,
+        &master_store.item_affixes_table,
+        &master_store.item_adjectives_table,
+        &master_store.item_nouns_table,
+        &mut rng::fixed_seed(challenge.seed()),
+        &master_store.items_store.signature_key,
+    );
+    map_item.modifiers.name = challenge.specs.name.clone();
+    items_controller::sign_item_specs(&mut map_item, &master_store.items_store.signature_key);
+
+    Some(map_item)
+}
+
+pub async fn claim_challenge_reward(
+    tx: &mut Transaction<'_, Database>,
+    challenges_store: &ChallengesStore,
+    character_id: &UserCharacterId,
+    realm_id: &RealmId,
+    schedule_id: &str,
+) -> Result<CharacterResources, AppError> {
+    let challenge = challenges_store
+        .get(schedule_id)
+        .ok_or(AppError::NotFound)?;
+    let challenge_id = challenge.challenge_id();
+
+    let area_level = db::leaderboard::read_character_area_level(
+        &mut **tx,
+        character_id,
+        realm_id,
+        &challenge_id,
+    )
+    .await?
+    .unwrap_or_default();
+
+    if area_level < challenge.specs.completion_level as i32 {
+        return Err(AppError::UserError("challenge not completed".to_string()));
+    }
+
+    if !db::challenges::claim_challenge_reward(&mut **tx, character_id, &challenge_id).await? {
+        return Err(AppError::UserError("reward already claimed".to_string()));
+    }
+
+    Ok(db::characters::update_character_resources(
+        &mut **tx,
+        character_id,
+        challenge.specs.reward.gems,
+        challenge.specs.reward.shards,
+        0.0,
+        0.0,
+    )
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use shared::data::challenge::{ChallengePeriod, ChallengeSchedule, ChallengeSpecs};
+
+    use super::*;
+
+    fn schedules_store(period: ChallengePeriod) -> ChallengeSchedulesStore {
+        ChallengeSchedulesStore::from([(
+            "schedule".to_string(),
+            ChallengeSchedule {
+                period,
+                rotation: ["first", "second", "third"]
+                    .into_iter()
+                    .map(|name| ChallengeSpecs {
+                        name: name.into(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            },
+        )])
+    }
+
+    fn active_challenge(store: &ChallengeSchedulesStore, now: DateTime<Utc>) -> ActiveChallenge {
+        compute_active_challenges(store, now).pop().unwrap()
+    }
+
+    #[test]
+    fn test_weekly_period_starts_on_monday() {
+        let store = schedules_store(ChallengePeriod::Weekly);
+        // 2024-01-01 is a Monday
+        let monday = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        let challenge = active_challenge(&store, monday);
+        assert_eq!(challenge.ends_at, monday + Duration::days(7));
+        assert_eq!(
+            active_challenge(&store, monday + Duration::days(6) + Duration::hours(23)).period_index,
+            challenge.period_index
+        );
+        assert_eq!(
+            active_challenge(&store, monday - Duration::seconds(1)).period_index,
+            challenge.period_index - 1
+        );
+    }
+
+    #[test]
+    fn test_rotation_loops_over_schedule() {
+        let store = schedules_store(ChallengePeriod::Daily);
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+
+        let names: Vec<_> = (0..6)
+            .map(|day| {
+                active_challenge(&store, now + Duration::days(day))
+                    .specs
+                    .name
+            })
+            .collect();
+
+        assert_eq!(names[..3], names[3..]);
+        assert_ne!(names[0], names[1]);
+        assert_ne!(names[1], names[2]);
+        assert_ne!(names[0], names[2]);
+
+        let challenge = active_challenge(&store, now);
+        assert_eq!(
+            challenge.ends_at,
+            Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_rotation_is_skipped() {
+        let store =
+            ChallengeSchedulesStore::from([("empty".to_string(), ChallengeSchedule::default())]);
+
+        assert!(compute_active_challenges(&store, Utc::now()).is_empty());
+    }
+}
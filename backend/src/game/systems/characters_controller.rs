@@ -5,7 +5,7 @@ use shared::{
     constants::ARMOR_FACTOR,
     data::{
         character::{CharacterAttrs, CharacterId, CharacterState},
-        character_status::{StatusEffectType, StatusId},
+        character_status::{StatusEffectType, StatusId, StatusSpecs, StatusState},
         item::SkillRange,
         player::CharacterSpecs,
         skill::{DamageType, RestoreModifier, RestoreType, SkillType},
@@ -57,6 +57,8 @@ pub fn attack_character(
         &target_specs.character_attrs,
         &mut target_state.life,
         &mut target_state.mana,
+        &mut target_state.ward,
+        &mut target_state.ward_recharge_elapsed,
         &damage,
         skill_type,
         is_blocked,
@@ -101,10 +103,15 @@ pub fn attack_character(
     !is_blocked
 }
 
+/// Damage is taken from the ward first, then from mana and life.
+/// Any damage taken delays the ward recharge.
+#[allow(clippy::too_many_arguments)]
 pub fn damage_character(
     character_attrs: &CharacterAttrs,
     life: &mut NonNegative,
     mana: &mut NonNegative,
+    ward: &mut NonNegative,
+    ward_recharge_elapsed: &mut f64,
     damage: &HashMap<DamageType, NonNegative>,
     skill_type: SkillType,
     is_blocked: bool,
@@ -129,13 +136,18 @@ pub fn damage_character(
         return 0.0;
     }
 
+    let take_from_ward = ward.get().min(amount);
+    let remaining_amount = amount - take_from_ward;
+
     let take_from_mana = mana
         .get()
-        .min(amount * (character_attrs.take_from_mana_before_life.get() as f64 * 0.01));
-    let take_from_life: f64 = amount - take_from_mana;
+        .min(remaining_amount * (character_attrs.take_from_mana_before_life.get() as f64 * 0.01));
+    let take_from_life: f64 = remaining_amount - take_from_mana;
 
+    *ward -= take_from_ward.into();
     *mana -= take_from_mana.into();
     *life -= take_from_life.into();
+    *ward_recharge_elapsed = 0.0;
 
     amount
 }
@@ -264,6 +276,7 @@ pub fn resuscitate_character(target: &mut Target) -> bool {
 
     target_state.is_alive = true;
     target_state.life = target_specs.character_attrs.max_life.get().into();
+    target_state.ward = target_specs.character_attrs.max_ward.get().into();
     target_state.resurrected = true;
 
     // TODO: Check if we needed that?
@@ -284,6 +297,7 @@ pub fn kill_character(target: &mut Target) -> bool {
     let (_, (_, target_state)) = target;
     target_state.life = 0.0.into();
     target_state.mana = 0.0.into();
+    target_state.ward = 0.0.into();
 
     true
 }
@@ -330,7 +344,10 @@ pub fn should_apply_status(
 ) -> bool {
     let (_, (_, target_state)) = target;
 
-    if duration.get() <= 0.1 || !target_state.is_alive {
+    if duration.get() <= 0.1
+        || !target_state.is_alive
+        || target_state.status_immunities.contains_key(status_id)
+    {
         return false;
     }
 
@@ -398,7 +415,10 @@ pub fn apply_status(
     let factor = (1.0 - status_resistance * 0.01).clamp(0.0, 1.0);
     let duration = duration * factor;
 
-    if duration.get() <= 0.1 || !target_state.is_alive {
+    if duration.get() <= 0.1
+        || !target_state.is_alive
+        || target_state.status_immunities.contains_key(&status_id)
+    {
         return false;
     }
 
@@ -465,15 +485,102 @@ pub fn apply_status(
         return false;
     }
 
-    if status_specs.effects.iter().any(|status_effect| {
-        matches!(
-            status_effect.status_effect_type,
-            StatusEffectType::StatModifier { .. } | StatusEffectType::Trigger { .. }
-        )
-    }) {
+    if affects_specs(status_specs) {
         target_state.dirty_specs = true;
     }
 
+    if applied {
+        for status_filter in status_specs.excludes.iter() {
+            for (removed_status_id, removed_stacks) in remove_statuses(
+                statuses_store,
+                target_state,
+                status_filter,
+                Some(&status_id),
+            ) {
+                register_status_removed(
+                    statuses_store,
+                    events_queue,
+                    *target_id,
+                    attacker,
+                    removed_status_id,
+                    &removed_stacks,
+                    skill_type,
+                    skill_id,
+                    trigger_depth,
+                );
+            }
+        }
+
+        for status_consumption in status_specs.consumes.iter() {
+            let mut burst_damage: HashMap<DamageType, NonNegative> = HashMap::new();
+
+            for (removed_status_id, removed_stacks) in remove_statuses(
+                statuses_store,
+                target_state,
+                &status_consumption.status_filter,
+                Some(&status_id),
+            ) {
+                if let Some(removed_status_specs) = statuses_store.get(&removed_status_id) {
+                    for status_effect in removed_status_specs.effects.iter() {
+                        if let StatusEffectType::DamageOverTime { damage_type, .. } =
+                            status_effect.status_effect_type
+                        {
+                            // Deal the remaining damage over time at once
+                            let remaining_damage: f64 = removed_stacks
+                                .iter()
+                                .map(|status_state| {
+                                    status_effect.computed_value(status_state.value).get()
+                                        * status_state.duration.get()
+                                })
+                                .sum();
+                            *burst_damage.entry(damage_type).or_default() +=
+                                (remaining_damage * status_consumption.burst_factor.get() * 0.01)
+                                    .into();
+                        }
+                    }
+                }
+
+                register_status_removed(
+                    statuses_store,
+                    events_queue,
+                    *target_id,
+                    attacker,
+                    removed_status_id,
+                    &removed_stacks,
+                    skill_type,
+                    skill_id,
+                    trigger_depth,
+                );
+            }
+
+            if burst_damage.is_empty() {
+                continue;
+            }
+
+            let CharacterState {
+                life,
+                mana,
+                ward,
+                ward_recharge_elapsed,
+                ..
+            } = &mut **target_state;
+            if damage_character(
+                &target_specs.character_attrs,
+                life,
+                mana,
+                ward,
+                ward_recharge_elapsed,
+                &burst_damage,
+                skill_type,
+                false,
+                Default::default(),
+            ) > 0.0
+            {
+                target_state.just_hurt = true;
+            }
+        }
+    }
+
     events_queue.register_event(GameEvent::StatusApplied(StatusEvent {
         source: attacker,
         target: *target_id,
@@ -528,6 +635,129 @@ fn compute_effect_weight(
     }
 }
 
+/// Return whether any status was removed
+#[allow(clippy::too_many_arguments)]
+pub fn cleanse_statuses(
+    statuses_store: &StatusesStore,
+    events_queue: &mut EventsQueue,
+    target: &mut Target,
+    attacker: CharacterId,
+    status_filter: &StatStatusFilter,
+    skill_type: SkillType,
+    skill_id: &str,
+    trigger_depth: u8,
+) -> bool {
+    let (target_id, (_, target_state)) = target;
+
+    let removed_statuses = remove_statuses(statuses_store, target_state, status_filter, None);
+    let cleansed = !removed_statuses.is_empty();
+
+    for (removed_status_id, removed_stacks) in removed_statuses {
+        register_status_removed(
+            statuses_store,
+            events_queue,
+            *target_id,
+            attacker,
+            removed_status_id,
+            &removed_stacks,
+            skill_type,
+            skill_id,
+            trigger_depth,
+        );
+    }
+
+    cleansed
+}
+
+fn remove_statuses(
+    statuses_store: &StatusesStore,
+    target_state: &mut CharacterState,
+    status_filter: &StatStatusFilter,
+    except_status_id: Option<&StatusId>,
+) -> Vec<(StatusId, Vec<StatusState>)> {
+    let removed_status_ids: Vec<_> = target_state
+        .statuses
+        .keys()
+        .filter(|status_id| Some(*status_id) != except_status_id)
+        .filter(|status_id| {
+            statuses_store
+                .get(status_id)
+                .map(|status_specs| {
+                    status_filter.is_match_with_status(
+                        status_id,
+                        status_specs.damage_type,
+                        status_specs.debuff,
+                    )
+                })
+                .unwrap_or_default()
+        })
+        .cloned()
+        .collect();
+
+    removed_status_ids
+        .into_iter()
+        .filter_map(|status_id| {
+            let status_stacks = target_state.statuses.remove(&status_id)?;
+            if statuses_store
+                .get(&status_id)
+                .map(affects_specs)
+                .unwrap_or_default()
+            {
+                target_state.dirty_specs = true;
+            }
+            Some((status_id, status_stacks))
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn register_status_removed(
+    statuses_store: &StatusesStore,
+    events_queue: &mut EventsQueue,
+    target_id: CharacterId,
+    attacker: CharacterId,
+    status_id: StatusId,
+    status_stacks: &[StatusState],
+    skill_type: SkillType,
+    skill_id: &str,
+    trigger_depth: u8,
+) {
+    let Some(status_specs) = statuses_store.get(&status_id) else {
+        return;
+    };
+
+    events_queue.register_event(GameEvent::StatusRemoved(StatusEvent {
+        source: attacker,
+        target: target_id,
+        skill_type,
+        damage_type: status_specs.damage_type,
+        debuff: status_specs.debuff,
+        value: status_stacks
+            .iter()
+            .map(|status_state| status_state.value.get())
+            .sum::<f64>()
+            .into(),
+        duration: status_stacks
+            .iter()
+            .map(|status_state| status_state.duration)
+            .max_by(|a, b| a.get().total_cmp(&b.get()))
+            .unwrap_or_default(),
+        status_id,
+        is_evaded: false,
+        skill_id: skill_id.into(),
+        trigger_depth,
+    }));
+}
+
+fn affects_specs(status_specs: &StatusSpecs) -> bool {
+    status_specs.effects.iter().any(|status_effect| {
+        matches!(
+            status_effect.status_effect_type,
+            StatusEffectType::StatModifier { .. } | StatusEffectType::Trigger { .. }
+        )
+    })
+}
+
 pub fn refresh_status_cooldown(
     statuses_store: &StatusesStore,
     target: &mut Target,
@@ -595,3 +825,165 @@ pub fn reset_buff_statuses(statuses_store: &StatusesStore, character_state: &mut
             .unwrap_or_default()
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses_store() -> StatusesStore {
+        serde_json::from_value(serde_json::json!({
+            "burn": {
+                "name": "Burn",
+                "icon": "statuses/burning.svg",
+                "debuff": true,
+                "damage_type": "Fire",
+                "duration": 4.0,
+                "max_stacks": 1,
+                "effects": [
+                    {
+                        "DamageOverTime": { "damage_type": "Fire" },
+                        "value": 100,
+                        "modifier": "Percent"
+                    }
+                ]
+            },
+            "detonate": {
+                "name": "Detonate",
+                "icon": "statuses/burning.svg",
+                "debuff": true,
+                "duration": 1.0,
+                "max_stacks": 1,
+                "effects": [],
+                "consumes": [{ "status_id": "burn", "burst_factor": 50 }]
+            }
+        }))
+        .unwrap()
+    }
+
+    fn apply(
+        statuses_store: &StatusesStore,
+        character_specs: &CharacterSpecs,
+        character_state: &mut CharacterState,
+        status_id: &str,
+        value: f64,
+        duration: f64,
+    ) -> bool {
+        apply_status(
+            statuses_store,
+            &mut EventsQueue::default(),
+            &mut (CharacterId::Monster(0), (character_specs, character_state)),
+            CharacterId::Player,
+            &status_id.into(),
+            SkillType::Spell,
+            NonNegative::new(value),
+            NonNegative::new(duration),
+            NonNegative::default(),
+            1,
+            false,
+            "skill",
+            0,
+        )
+    }
+
+    fn hit(
+        character_attrs: &CharacterAttrs,
+        life: &mut NonNegative,
+        ward: &mut NonNegative,
+        ward_recharge_elapsed: &mut f64,
+        amount: f64,
+    ) -> f64 {
+        damage_character(
+            character_attrs,
+            life,
+            &mut NonNegative::default(),
+            ward,
+            ward_recharge_elapsed,
+            &HashMap::from([(DamageType::Fire, NonNegative::new(amount))]),
+            SkillType::Spell,
+            false,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn test_ward_absorbs_damage_before_life() {
+        let character_attrs = CharacterAttrs::default();
+        let (mut life, mut ward) = (NonNegative::new(100.0), NonNegative::new(30.0));
+        let mut ward_recharge_elapsed = 5.0;
+
+        hit(
+            &character_attrs,
+            &mut life,
+            &mut ward,
+            &mut ward_recharge_elapsed,
+            20.0,
+        );
+        assert_eq!(ward.get(), 10.0);
+        assert_eq!(life.get(), 100.0);
+        assert_eq!(ward_recharge_elapsed, 0.0);
+
+        hit(
+            &character_attrs,
+            &mut life,
+            &mut ward,
+            &mut ward_recharge_elapsed,
+            20.0,
+        );
+        assert_eq!(ward.get(), 0.0);
+        assert_eq!(life.get(), 90.0);
+    }
+
+    #[test]
+    fn test_no_damage_keeps_ward_recharging() {
+        let character_attrs = CharacterAttrs::default();
+        let (mut life, mut ward) = (NonNegative::new(100.0), NonNegative::new(30.0));
+        let mut ward_recharge_elapsed = 5.0;
+
+        hit(
+            &character_attrs,
+            &mut life,
+            &mut ward,
+            &mut ward_recharge_elapsed,
+            0.0,
+        );
+        assert_eq!(ward.get(), 30.0);
+        assert_eq!(ward_recharge_elapsed, 5.0);
+    }
+
+    #[test]
+    fn test_consumed_damage_over_time_bursts() {
+        let statuses_store = statuses_store();
+        let character_specs = CharacterSpecs::default();
+        let mut character_state = CharacterState {
+            life: NonNegative::new(100.0),
+            is_alive: true,
+            ..Default::default()
+        };
+
+        assert!(apply(
+            &statuses_store,
+            &character_specs,
+            &mut character_state,
+            "burn",
+            10.0,
+            4.0
+        ));
+        assert!(apply(
+            &statuses_store,
+            &character_specs,
+            &mut character_state,
+            "detonate",
+            1.0,
+            1.0
+        ));
+
+        // Half of the 40 remaining burn damage is dealt at once
+        assert_eq!(character_state.life.get(), 80.0);
+        assert!(character_state.just_hurt);
+        assert!(
+            !character_state
+                .statuses
+                .contains_key(&statuses_store.id_with_key("burn".into()))
+        );
+    }
+}
@@ -69,6 +69,11 @@ pub fn update_character_state(
         .get()
         .min(character_specs.character_attrs.max_mana.get())
         .into();
+    character_state.ward = character_state
+        .ward
+        .get()
+        .min(character_specs.character_attrs.max_ward.get())
+        .into();
 
     if character_state.life.get() < 0.5 {
         character_state.life = 0.0.into();
@@ -94,6 +99,8 @@ pub fn update_character_state(
         true,
     );
 
+    recharge_ward(elapsed_time_f64, character_specs, character_state);
+
     for monitored_condition in character_state.monitored_conditions.values_mut() {
         monitored_condition.duration += elapsed_time_f64;
     }
@@ -141,9 +148,35 @@ pub fn update_character_state(
             elapsed_time,
             &mut character_state.repeated_skills,
         )
+    } else {
+        // Stuns interrupt any ongoing cast or channel
+        skills_updater::interrupt_skills(&mut character_state.skills_states);
     }
 }
 
+fn recharge_ward(
+    elapsed_time_f64: f64,
+    character_specs: &CharacterSpecs,
+    character_state: &mut CharacterState,
+) {
+    let character_attrs = &character_specs.character_attrs;
+    let max_ward = character_attrs.max_ward.get();
+
+    if max_ward <= 0.0 {
+        return;
+    }
+
+    character_state.ward_recharge_elapsed += elapsed_time_f64;
+    if character_state.ward_recharge_elapsed < character_attrs.ward_recharge_delay.get() {
+        return;
+    }
+
+    character_state.ward = (character_state.ward.get()
+        + elapsed_time_f64 * *character_attrs.ward_recharge * max_ward * 0.01)
+        .clamp(0.0, max_ward)
+        .into();
+}
+
 pub fn reset_character(character_state: &mut CharacterState) {
     character_state.just_hurt = false;
     character_state.just_hurt_crit = false;
@@ -259,6 +292,11 @@ fn compute_character_specs(
             StatType::LifeRegen => character_attrs.life_regen.apply_effect(effect),
             StatType::Mana => character_attrs.max_mana.apply_effect(effect),
             StatType::ManaRegen => character_attrs.mana_regen.apply_effect(effect),
+            StatType::Ward => character_attrs.max_ward.apply_effect(effect),
+            StatType::WardRecharge => character_attrs.ward_recharge.apply_effect(effect),
+            StatType::WardRechargeDelay => {
+                character_attrs.ward_recharge_delay.apply_effect(effect)
+            }
             StatType::Armor(armor_type) => match armor_type {
                 Some(armor_type) => {
                     for damage_type in DamageType::iter() {
@@ -456,12 +494,17 @@ fn compute_character_specs(
             | StatType::SkillTargetModifier { .. }
             | StatType::SkillRepeat { .. }
             | StatType::SkillEffectModifier { .. }
+            | StatType::DamageConversion { .. }
+            | StatType::MinionLifetime(_)
+            | StatType::MaxMinions(_)
+            | StatType::MinionModifier { .. }
             | StatType::TriggerEffectModifier { .. } => {}
             // Other
             StatType::ItemRarity
             | StatType::ItemAreaChance
             | StatType::ItemLevel
             | StatType::GemsFind
+            | StatType::ChampionChance
             | StatType::PowerLevel
             | StatType::Description(_)
             | StatType::Description2(_) => {}
@@ -639,3 +682,29 @@ pub fn extend_triggers_from_skills_and_statuses(
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::data::values::NonNegative;
+
+    use super::*;
+
+    #[test]
+    fn test_ward_recharges_after_delay() {
+        let mut character_specs = CharacterSpecs::default();
+        character_specs.character_attrs.max_ward = NonNegative::new(100.0).into();
+        character_specs.character_attrs.ward_recharge = 20.0.into();
+        character_specs.character_attrs.ward_recharge_delay = NonNegative::new(2.0).into();
+        let mut character_state = CharacterState::default();
+
+        recharge_ward(1.0, &character_specs, &mut character_state);
+        assert_eq!(character_state.ward.get(), 0.0);
+
+        recharge_ward(1.5, &character_specs, &mut character_state);
+        assert_eq!(character_state.ward.get(), 30.0);
+
+        // Capped to the maximum ward
+        recharge_ward(10.0, &character_specs, &mut character_state);
+        assert_eq!(character_state.ward.get(), 100.0);
+    }
+}
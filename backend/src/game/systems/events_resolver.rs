@@ -16,7 +16,7 @@ use shared::{
 
 use crate::game::{
     data::{
-        event::{EventsQueue, GameEvent, HitEvent, RestoreEvent, StatusEvent},
+        event::{EventsQueue, GameEvent, HitEvent, RestoreEvent, StatusEvent, SummonEvent},
         master_store::MasterStore,
     },
     game_data::GameInstanceData,
@@ -28,7 +28,8 @@ use crate::game::{
 };
 
 use super::{
-    loot_controller, loot_generator, player_controller, triggers_controller::TriggerContext,
+    loot_controller, loot_generator, minions_controller, player_controller,
+    triggers_controller::TriggerContext,
 };
 
 pub async fn resolve_events(
@@ -60,12 +61,26 @@ pub async fn resolve_events(
             GameEvent::ThreatIncreased(threat_level) => {
                 handle_threat_increased_event(&mut trigger_contexts, game_data, *threat_level)
             }
-            GameEvent::StatusApplied(status_event) => {
-                handle_status_event(&mut trigger_contexts, game_data, master_store, status_event)
-            }
+            GameEvent::StatusApplied(status_event) => handle_status_event(
+                &mut trigger_contexts,
+                game_data,
+                master_store,
+                status_event,
+                false,
+            ),
+            GameEvent::StatusRemoved(status_event) => handle_status_event(
+                &mut trigger_contexts,
+                game_data,
+                master_store,
+                status_event,
+                true,
+            ),
             GameEvent::Restored(restore_event) => {
                 handle_restore_event(&mut trigger_contexts, game_data, restore_event)
             }
+            GameEvent::Summoned(summon_event) => {
+                handle_summon_event(game_data, master_store, summon_event)
+            }
         }
     }
 
@@ -95,6 +110,13 @@ fn handle_hit_event<'a>(
             .map(|(idx, monster_specs)| {
                 (CharacterId::Monster(idx), &monster_specs.character_specs)
             }),
+    )
+    .chain(
+        game_data
+            .minion_specs
+            .iter()
+            .enumerate()
+            .map(|(idx, minion_specs)| (CharacterId::Minion(idx), &minion_specs.character_specs)),
     );
 
     for (character_id, character_specs) in characters {
@@ -121,6 +143,17 @@ fn handle_hit_event<'a>(
                         None => continue,
                     }
                 }
+
+                CharacterId::Minion(idx) => {
+                    match game_data
+                        .minion_specs
+                        .get(idx)
+                        .zip(game_data.minion_states.get(idx))
+                    {
+                        Some((specs, state)) => (&specs.character_specs, &state.character_state),
+                        None => continue,
+                    }
+                }
             };
 
             if !compare_options(&hit_trigger.skill_type, &Some(hit_event.skill_type))
@@ -189,6 +222,7 @@ fn handle_status_event<'a>(
     game_data: &mut GameInstanceData,
     master_store: &MasterStore,
     status_event: &'a StatusEvent,
+    removed: bool,
 ) {
     let characters = iter::once((
         CharacterId::Player,
@@ -202,19 +236,31 @@ fn handle_status_event<'a>(
             .map(|(idx, monster_specs)| {
                 (CharacterId::Monster(idx), &monster_specs.character_specs)
             }),
+    )
+    .chain(
+        game_data
+            .minion_specs
+            .iter()
+            .enumerate()
+            .map(|(idx, minion_specs)| (CharacterId::Minion(idx), &minion_specs.character_specs)),
     );
 
     for (character_id, character_specs) in characters {
         for (trigger, owned_triggers) in character_specs.triggers.iter() {
             match trigger {
-                EventTrigger::OnApplyStatus(_) if status_event.source == character_id => {}
-                EventTrigger::OnReceiveStatus(_) if status_event.target == character_id => {}
+                EventTrigger::OnApplyStatus(_)
+                    if !removed && status_event.source == character_id => {}
+                EventTrigger::OnReceiveStatus(_)
+                    if !removed && status_event.target == character_id => {}
+                EventTrigger::OnRemoveStatus(_)
+                    if removed && status_event.source == character_id => {}
                 _ => continue,
             };
 
             let status_trigger = match trigger {
                 EventTrigger::OnApplyStatus(status_trigger)
-                | EventTrigger::OnReceiveStatus(status_trigger) => status_trigger,
+                | EventTrigger::OnReceiveStatus(status_trigger)
+                | EventTrigger::OnRemoveStatus(status_trigger) => status_trigger,
                 _ => continue,
             };
 
@@ -234,6 +280,17 @@ fn handle_status_event<'a>(
                         None => continue,
                     }
                 }
+
+                CharacterId::Minion(idx) => {
+                    match game_data
+                        .minion_specs
+                        .get(idx)
+                        .zip(game_data.minion_states.get(idx))
+                    {
+                        Some((specs, state)) => (&specs.character_specs, &state.character_state),
+                        None => continue,
+                    }
+                }
             };
 
             if !compare_options(&status_trigger.skill_type, &Some(status_event.skill_type))
@@ -300,6 +357,13 @@ fn handle_restore_event<'a>(
             .map(|(idx, monster_specs)| {
                 (CharacterId::Monster(idx), &monster_specs.character_specs)
             }),
+    )
+    .chain(
+        game_data
+            .minion_specs
+            .iter()
+            .enumerate()
+            .map(|(idx, minion_specs)| (CharacterId::Minion(idx), &minion_specs.character_specs)),
     );
 
     for (character_id, character_specs) in characters {
@@ -421,7 +485,7 @@ fn handle_kill_event(
                 .enumerate()
             {
                 let event_target_type = match target {
-                    CharacterId::Player => TargetType::Enemy,
+                    CharacterId::Player | CharacterId::Minion(_) => TargetType::Enemy,
                     CharacterId::Monster(event_target_idx) => {
                         if event_target_idx == idx {
                             TargetType::Me
@@ -455,9 +519,36 @@ fn handle_kill_event(
         CharacterId::Player => {
             game_data.game_stats.player_deaths += 1;
         }
+        CharacterId::Minion(_) => {}
     }
 }
 
+fn handle_summon_event(
+    game_data: &mut GameInstanceData,
+    master_store: &MasterStore,
+    summon_event: &SummonEvent,
+) {
+    // Only the player can have minions for now
+    if summon_event.source != CharacterId::Player {
+        return;
+    }
+
+    let area_state = game_data.area_state.read();
+    let minion_level = area_state
+        .area_level
+        .saturating_add(*game_data.area_specs.power_level);
+
+    minions_controller::summon_minion(
+        &master_store.monster_specs_store,
+        &master_store.statuses_store,
+        game_data.minion_base_specs.mutate(),
+        &mut game_data.minion_specs,
+        &mut game_data.minion_states,
+        summon_event,
+        minion_level,
+    );
+}
+
 fn handle_area_completed_event(
     game_data: &mut GameInstanceData,
     master_store: &MasterStore,
@@ -485,6 +576,7 @@ fn handle_area_completed_event(
     }
 
     let new_max = area_state.area_level > area_state.max_area_level;
+    let first_time = area_state.area_level > area_state.max_area_level_ever;
 
     if new_max {
         game_data.game_stats.elapsed_time_at_max_level = game_data.game_stats.elapsed_time;
@@ -492,9 +584,19 @@ fn handle_area_completed_event(
         area_state.max_area_level_ever = area_state.max_area_level_ever.max(area_state.area_level);
     }
 
-    if !game_data.area_blueprint.loot_table.entries.is_empty() {
+    let boss = game_data
+        .boss_blueprint
+        .as_ref()
+        .filter(|_| is_boss_level && area_state.is_boss);
+    let boss_loot = boss.and_then(|boss| boss.loot.as_ref());
+    let loot_table = boss_loot
+        .map(|boss_loot| &boss_loot.loot_table)
+        .filter(|loot_table| !loot_table.entries.is_empty())
+        .unwrap_or(&game_data.area_blueprint.loot_table);
+
+    if !loot_table.entries.is_empty() {
         match loot_generator::generate_loot(
-            &game_data.area_blueprint.loot_table,
+            loot_table,
             &master_store.items_store,
             &master_store.item_affixes_table,
             &master_store.item_adjectives_table,
@@ -530,6 +632,34 @@ fn handle_area_completed_event(
         }
     }
 
+    if let Some(boss) = boss
+        && let Some(boss_loot) = &boss.loot
+    {
+        // First kill is the first time the boss appears for the character in the area
+        let first_kill = first_time && area_state.area_level == boss.level;
+        for item_specs in loot_generator::generate_boss_drops(
+            boss_loot,
+            first_kill,
+            &master_store.items_store,
+            &master_store.item_affixes_table,
+            &master_store.item_adjectives_table,
+            &master_store.item_nouns_table,
+            power_level,
+            *game_data.area_specs.power_level + *game_data.area_specs.item_level_modifier,
+            *game_data.area_specs.loot_rarity * ITEM_REWARDS_BOSS_FACTOR,
+            game_data.player_specs.read().gold_find.get(),
+        ) {
+            for item_specs in loot_controller::drop_loot(game_data.queued_loot.mutate(), item_specs)
+            {
+                player_controller::sell_item(
+                    game_data.player_resources.mutate(),
+                    &item_specs,
+                    game_data.realm.is_ssf(),
+                );
+            }
+        }
+    }
+
     area_state.waves_done = 1;
     if area_state.auto_progress {
         area_state.area_level = area_state
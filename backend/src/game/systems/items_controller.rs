@@ -1,9 +1,11 @@
 use strum::IntoEnumIterator;
 
+use backend_shared::signature::{self, HmacKey};
 use shared::{
     constants::{MAX_POWER_SHARD_LEVEL_BASE, POWER_SHARD_LEVELS_NEEDED},
     data::{
         area::AreaLevel,
+        forge,
         item::{
             ArmorSpecs, ItemBase, ItemModifiers, ItemRarity, ItemSlot, ItemSpecs, MapSpecs,
             WeaponSpecs,
@@ -19,7 +21,10 @@ use shared::{
 };
 
 use crate::{
-    game::data::{items_store::ItemsStore, master_store::SkillsStore},
+    game::{
+        data::{items_store::ItemsStore, master_store::SkillsStore},
+        utils::rng::{self, RngSeed},
+    },
     rest::AppError,
 };
 
@@ -34,7 +39,8 @@ pub fn init_item_specs_from_store(
             create_item_specs(
                 base.clone(),
                 item_modifiers,
-                0.0, // &items_store.signature_key,
+                0.0,
+                &items_store.signature_key,
             )
         })
 }
@@ -43,7 +49,7 @@ pub fn create_item_specs(
     base: ItemBase,
     mut modifiers: ItemModifiers,
     gold_price: f64,
-    // signature_key: &HmacKey,
+    signature_key: &HmacKey,
 ) -> ItemSpecs {
     compute_upgrade_effects(&base, &mut modifiers);
 
@@ -53,11 +59,10 @@ pub fn create_item_specs(
     // TODO: convert local StatType::LifeOnHit(hit_trigger) to item linked trigger
     // TODO: compute triggers with local effects applied to it
 
-    ItemSpecs {
+    let mut item_specs = ItemSpecs {
         required_level: base.min_area_level.max(
             modifiers
-                .affixes
-                .iter()
+                .all_affixes()
                 .map(|affix| affix.item_level)
                 .max()
                 .unwrap_or_default(),
@@ -79,14 +84,29 @@ pub fn create_item_specs(
         modifiers,
         old_game: true,
         gold_price,
-        // signature: Default::default(),
-    }
+        signature: Default::default(),
+    };
+
+    sign_item_specs(&mut item_specs, signature_key);
 
-    // if let Ok(serialized_item_specs) = rmp_serde::to_vec(&item_specs) {
-    //     item_specs.signature = signature::compute_hmac(&serialized_item_specs, signature_key);
-    // }
+    item_specs
+}
 
-    // item_specs
+/// Must be called again whenever the item specs are modified after their creation
+pub fn sign_item_specs(item_specs: &mut ItemSpecs, signature_key: &HmacKey) {
+    if let Ok(serialized_item_specs) = serialize_unsigned_item_specs(item_specs) {
+        item_specs.signature = signature::compute_hmac(&serialized_item_specs, signature_key);
+    }
+}
+
+/// Payload covered by the signature, i.e. the item specs with an empty signature
+pub fn serialize_unsigned_item_specs(
+    item_specs: &ItemSpecs,
+) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    rmp_serde::to_vec(&ItemSpecs {
+        signature: Default::default(),
+        ..item_specs.clone()
+    })
 }
 
 fn compute_weapon_specs(
@@ -266,6 +286,7 @@ fn compute_upgrade_effects(base: &ItemBase, item_modifiers: &mut ItemModifiers)
                         .get(item_modifiers.upgrade_level.saturating_sub(1) as usize)
                         .copied()
                         .unwrap_or_default(),
+                    triggers: Vec::new(),
                 }
             }));
 
@@ -297,7 +318,7 @@ fn compute_upgrade_effects(base: &ItemBase, item_modifiers: &mut ItemModifiers)
     }
 }
 
-pub fn upgrade_item(item: &ItemSpecs) -> Result<ItemSpecs, AppError> {
+pub fn upgrade_item(item: &ItemSpecs, signature_key: &HmacKey) -> Result<ItemSpecs, AppError> {
     let available_upgrade_levels = item
         .base
         .upgrade_levels
@@ -312,7 +333,82 @@ pub fn upgrade_item(item: &ItemSpecs) -> Result<ItemSpecs, AppError> {
     let mut item_modifiers = item.modifiers.clone();
     item_modifiers.upgrade_level = item_modifiers.upgrade_level.saturating_add(1);
 
-    Ok(create_item_specs(item.base.clone(), item_modifiers, 0.0))
+    Ok(create_item_specs(
+        item.base.clone(),
+        item_modifiers,
+        0.0,
+        signature_key,
+    ))
+}
+
+pub fn socket_rune(
+    item: &ItemSpecs,
+    rune: &ItemSpecs,
+    signature_key: &HmacKey,
+) -> Result<ItemSpecs, AppError> {
+    match rune.base.rune_specs {
+        Some(ref rune_specs) if !rune_specs.root_node => {}
+        Some(_) => {
+            return Err(AppError::UserError(
+                "Anchor Runes can only be socketed into Passives Tree".into(),
+            ));
+        }
+        None => {
+            return Err(AppError::UserError(
+                "only Runes can be socketed into items".into(),
+            ));
+        }
+    }
+
+    if item.modifiers.socketed_runes.len() >= item.base.sockets as usize {
+        return Err(AppError::UserError("no free socket on item".into()));
+    }
+
+    if rune.modifiers.rarity == ItemRarity::Unique
+        && item
+            .modifiers
+            .socketed_runes
+            .iter()
+            .any(|socketed_rune| socketed_rune.base_item_id == rune.modifiers.base_item_id)
+    {
+        return Err(AppError::UserError(
+            "cannot socket twice the same Unique Rune".into(),
+        ));
+    }
+
+    let mut item_modifiers = item.modifiers.clone();
+    item_modifiers.socketed_runes.push(rune.modifiers.clone());
+
+    Ok(create_item_specs(
+        item.base.clone(),
+        item_modifiers,
+        0.0,
+        signature_key,
+    ))
+}
+
+/// Remove the rune from the given socket, returning the updated item and the removed rune
+pub fn unsocket_rune(
+    item: &ItemSpecs,
+    socket_index: usize,
+    signature_key: &HmacKey,
+) -> Result<(ItemSpecs, ItemModifiers), AppError> {
+    if socket_index >= item.modifiers.socketed_runes.len() {
+        return Err(AppError::NotFound);
+    }
+
+    let mut item_modifiers = item.modifiers.clone();
+    let rune = item_modifiers.socketed_runes.remove(socket_index);
+
+    Ok((
+        create_item_specs(item.base.clone(), item_modifiers, 0.0, signature_key),
+        rune,
+    ))
+}
+
+/// Roll whether the rune removed from its socket is destroyed in the process
+pub fn roll_rune_destroyed(seed: &mut RngSeed) -> bool {
+    rng::random_range_with_seed(0.0..1.0, seed).unwrap_or(1.0) < forge::UNSOCKET_RUNE_DESTROY_CHANCE
 }
 
 pub fn make_weapon_skill(
@@ -345,3 +441,243 @@ fn item_slot_to_skill_id(item_slot: ItemSlot) -> &'static str {
         ItemSlot::Ring => "ring_skill",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::data::{item::RuneSpecs, trigger::TriggerSpecs};
+
+    use super::*;
+
+    fn affix(name: &str, affix_type: AffixType) -> ItemAffix {
+        ItemAffix {
+            name: name.into(),
+            family: name.into(),
+            tags: Default::default(),
+            affix_type,
+            tier: 1,
+            effects: Vec::new(),
+            item_level: 1,
+            triggers: Vec::new(),
+        }
+    }
+
+    fn item_modifiers(
+        base_item_id: &str,
+        rarity: ItemRarity,
+        affixes: Vec<ItemAffix>,
+    ) -> ItemModifiers {
+        ItemModifiers {
+            base_item_id: base_item_id.into(),
+            name: base_item_id.into(),
+            rarity,
+            level: 10,
+            affixes,
+            quality: 0.0,
+            upgrade_level: 0,
+            corrupted: false,
+            socketed_runes: Vec::new(),
+            base_version: 0,
+        }
+    }
+
+    fn item(sockets: u8) -> ItemSpecs {
+        create_item_specs(
+            ItemBase {
+                name: "Sword".into(),
+                sockets,
+                ..Default::default()
+            },
+            item_modifiers(
+                "sword",
+                ItemRarity::Magic,
+                vec![affix("sharp", AffixType::Prefix)],
+            ),
+            0.0,
+            &[0; 32],
+        )
+    }
+
+    fn rune(base_item_id: &str, rarity: ItemRarity, root_node: bool) -> ItemSpecs {
+        create_item_specs(
+            ItemBase {
+                name: base_item_id.into(),
+                rune_specs: Some(RuneSpecs { root_node }),
+                ..Default::default()
+            },
+            item_modifiers(
+                base_item_id,
+                rarity,
+                vec![affix(base_item_id, AffixType::Unique)],
+            ),
+            0.0,
+            &[0; 32],
+        )
+    }
+
+    fn triggered_item(signature_key: &HmacKey) -> ItemSpecs {
+        let trigger: TriggerSpecs = serde_json::from_value(serde_json::json!({
+            "OnKill": {},
+            "trigger_id": "test_trigger",
+            "target": "Me",
+            "skill_type": "Spell",
+            "effects": [
+                {
+                    "FlatDamage": {
+                        "damage": {},
+                        "damage_factors": {"Physical": 1.0, "Fire": 0.5, "Storm": 0.5, "Poison": 0.5}
+                    },
+                    "ignore_stat_effects": [
+                        {"Damage": {}},
+                        {"StatusDuration": {"damage_type": "Any"}},
+                        {"StatusEscalation": {"damage_type": "Any"}},
+                        {"StatusFaster": {"damage_type": "Any"}},
+                        {"StatusPower": {"damage_type": "Any"}}
+                    ]
+                }
+            ]
+        }))
+        .unwrap();
+
+        create_item_specs(
+            ItemBase {
+                name: "Sword".into(),
+                triggers: vec![trigger],
+                ..Default::default()
+            },
+            item_modifiers(
+                "sword",
+                ItemRarity::Magic,
+                vec![affix("sharp", AffixType::Prefix)],
+            ),
+            0.0,
+            signature_key,
+        )
+    }
+
+    #[test]
+    fn test_signature_survives_serialization_roundtrip() {
+        let signature_key = [1; 32];
+        let item = triggered_item(&signature_key);
+        let payload = serialize_unsigned_item_specs(&item).unwrap();
+
+        // Items are sent to the client as JSON and linked back as MessagePack
+        let from_json: ItemSpecs =
+            serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+        let from_msgpack: ItemSpecs =
+            rmp_serde::from_slice(&rmp_serde::to_vec(&item).unwrap()).unwrap();
+
+        for item in [from_json, from_msgpack] {
+            let roundtrip_payload = serialize_unsigned_item_specs(&item).unwrap();
+            assert_eq!(roundtrip_payload, payload);
+            assert!(signature::verify_hmac(
+                &roundtrip_payload,
+                &item.signature,
+                &signature_key
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tampered_item_is_rejected() {
+        let signature_key = [1; 32];
+        let item = triggered_item(&signature_key);
+
+        let mut tampered_item = item.clone();
+        tampered_item.modifiers.level += 1;
+        assert!(!signature::verify_hmac(
+            &serialize_unsigned_item_specs(&tampered_item).unwrap(),
+            &tampered_item.signature,
+            &signature_key
+        ));
+
+        // Signed with another key
+        assert!(!signature::verify_hmac(
+            &serialize_unsigned_item_specs(&item).unwrap(),
+            &item.signature,
+            &[2; 32]
+        ));
+    }
+
+    #[test]
+    fn test_socket_rune_into_free_socket() {
+        let item = socket_rune(
+            &item(2),
+            &rune("fire_rune", ItemRarity::Normal, false),
+            &[0; 32],
+        )
+        .unwrap();
+
+        assert_eq!(item.modifiers.socketed_runes.len(), 1);
+        assert_eq!(item.modifiers.affixes.len(), 1);
+        assert_eq!(
+            item.modifiers
+                .all_affixes()
+                .map(|affix| affix.name.as_str())
+                .collect::<Vec<_>>(),
+            ["sharp", "fire_rune"]
+        );
+    }
+
+    #[test]
+    fn test_socket_rune_rejected() {
+        let fire_rune = rune("fire_rune", ItemRarity::Normal, false);
+
+        // No free socket
+        assert!(socket_rune(&item(0), &fire_rune, &[0; 32]).is_err());
+        let full_item = socket_rune(&item(1), &fire_rune, &[0; 32]).unwrap();
+        assert!(socket_rune(&full_item, &fire_rune, &[0; 32]).is_err());
+
+        // Not a socketable rune
+        assert!(socket_rune(&item(2), &item(0), &[0; 32]).is_err());
+        assert!(
+            socket_rune(
+                &item(2),
+                &rune("anchor_rune", ItemRarity::Normal, true),
+                &[0; 32]
+            )
+            .is_err()
+        );
+
+        // Same Unique Rune twice
+        let unique_rune = rune("unique_rune", ItemRarity::Unique, false);
+        let item = socket_rune(&item(2), &unique_rune, &[0; 32]).unwrap();
+        assert!(socket_rune(&item, &unique_rune, &[0; 32]).is_err());
+    }
+
+    #[test]
+    fn test_unsocket_rune() {
+        let item = socket_rune(
+            &item(2),
+            &rune("fire_rune", ItemRarity::Normal, false),
+            &[0; 32],
+        )
+        .unwrap();
+        let item = socket_rune(
+            &item,
+            &rune("cold_rune", ItemRarity::Normal, false),
+            &[0; 32],
+        )
+        .unwrap();
+
+        assert!(unsocket_rune(&item, 2, &[0; 32]).is_err());
+
+        let (item, rune) = unsocket_rune(&item, 0, &[0; 32]).unwrap();
+        assert_eq!(rune.base_item_id, "fire_rune");
+        assert_eq!(item.modifiers.socketed_runes.len(), 1);
+        assert_eq!(item.modifiers.socketed_runes[0].base_item_id, "cold_rune");
+        assert_eq!(item.modifiers.all_affixes().count(), 2);
+    }
+
+    #[test]
+    fn test_unsocketed_rune_destroy_chance() {
+        let mut seed = rng::fixed_seed(0);
+        let rolls = 10_000;
+
+        let destroyed = (0..rolls)
+            .filter(|_| roll_rune_destroyed(&mut seed))
+            .count();
+
+        let destroy_rate = destroyed as f64 / rolls as f64;
+        assert!((destroy_rate - forge::UNSOCKET_RUNE_DESTROY_CHANCE).abs() < 0.02);
+    }
+}
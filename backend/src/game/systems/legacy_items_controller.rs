@@ -0,0 +1,485 @@
+use std::fmt;
+
+use shared::data::{
+    item::{ItemModifiers, ItemRarity},
+    item_affix::{AffixType, ItemAffix, ItemAffixBlueprint},
+    user::UserCharacterId,
+};
+
+use crate::{
+    app_state::MasterStore,
+    db::{self, stash_items::StashItemId},
+    game::{
+        data::{
+            inventory_data::InventoryData,
+            items_store::{ItemAffixesTable, ItemsStore},
+        },
+        systems::loot_generator,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegacyItemIssue {
+    /// Stored data could not be decoded
+    Unreadable,
+    UnknownBase,
+    OutdatedUnique {
+        version: u16,
+        current_version: u16,
+    },
+    UniqueAffixesMismatch,
+    UnknownAffix {
+        family: String,
+        tier: u8,
+    },
+    /// Affix values outside of the current definition of its tier
+    AffixOutOfRange {
+        family: String,
+        tier: u8,
+    },
+}
+
+impl fmt::Display for LegacyItemIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LegacyItemIssue::Unreadable => write!(f, "unreadable data"),
+            LegacyItemIssue::UnknownBase => write!(f, "unknown base"),
+            LegacyItemIssue::OutdatedUnique {
+                version,
+                current_version,
+            } => write!(
+                f,
+                "outdated unique version {version} (current {current_version})"
+            ),
+            LegacyItemIssue::UniqueAffixesMismatch => write!(f, "unique affixes mismatch"),
+            LegacyItemIssue::UnknownAffix { family, tier } => {
+                write!(f, "unknown affix '{family}' tier {tier}")
+            }
+            LegacyItemIssue::AffixOutOfRange { family, tier } => {
+                write!(f, "affix '{family}' tier {tier} out of range")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegacyItemLocation {
+    Inventory(UserCharacterId),
+    Stash(StashItemId),
+}
+
+impl fmt::Display for LegacyItemLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LegacyItemLocation::Inventory(character_id) => {
+                write!(f, "inventory of character {character_id}")
+            }
+            LegacyItemLocation::Stash(stash_item_id) => write!(f, "stash item {stash_item_id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LegacyItemReport {
+    pub location: LegacyItemLocation,
+    pub base_item_id: String,
+    pub name: String,
+    pub issues: Vec<LegacyItemIssue>,
+}
+
+/// List what doesn't match the current items definitions anymore
+pub fn find_legacy_issues(
+    items_store: &ItemsStore,
+    affixes_table: &ItemAffixesTable,
+    item_modifiers: &ItemModifiers,
+) -> Vec<LegacyItemIssue> {
+    let Some(base) = items_store.content.get(&item_modifiers.base_item_id) else {
+        return vec![LegacyItemIssue::UnknownBase];
+    };
+
+    let mut issues = Vec::new();
+
+    if item_modifiers.rarity == ItemRarity::Unique {
+        if item_modifiers.base_version < base.version {
+            issues.push(LegacyItemIssue::OutdatedUnique {
+                version: item_modifiers.base_version,
+                current_version: base.version,
+            });
+        }
+
+        let unique_affixes: Vec<_> = item_modifiers
+            .affixes
+            .iter()
+            .filter(|affix| affix.affix_type == AffixType::Unique)
+            .collect();
+
+        if unique_affixes.len() != base.affixes.len()
+            || unique_affixes
+                .iter()
+                .zip(base.affixes.iter())
+                .any(|(affix, blueprint)| {
+                    affix.effects.iter().any(|effect| {
+                        effect.stat_effect.stat != blueprint.stat
+                            || effect.stat_effect.modifier != blueprint.modifier
+                            || effect.scope != blueprint.scope
+                    })
+                })
+        {
+            issues.push(LegacyItemIssue::UniqueAffixesMismatch);
+        }
+    }
+
+    issues.extend(
+        item_modifiers
+            .affixes
+            .iter()
+            .filter(|affix| matches!(affix.affix_type, AffixType::Prefix | AffixType::Suffix))
+            .filter_map(|affix| {
+                match loot_generator::find_affix_blueprint(
+                    base,
+                    &affix.family,
+                    affix.tier,
+                    affixes_table,
+                ) {
+                    None => Some(LegacyItemIssue::UnknownAffix {
+                        family: affix.family.clone(),
+                        tier: affix.tier,
+                    }),
+                    Some(affix_blueprint) if !is_affix_in_range(affix, affix_blueprint) => {
+                        Some(LegacyItemIssue::AffixOutOfRange {
+                            family: affix.family.clone(),
+                            tier: affix.tier,
+                        })
+                    }
+                    Some(_) => None,
+                }
+            }),
+    );
+
+    for rune in item_modifiers.socketed_runes.iter() {
+        issues.extend(find_legacy_issues(items_store, affixes_table, rune));
+    }
+
+    issues
+}
+
+fn is_affix_in_range(affix: &ItemAffix, affix_blueprint: &ItemAffixBlueprint) -> bool {
+    affix.effects.len() == affix_blueprint.effects.len()
+        && affix
+            .effects
+            .iter()
+            .zip(affix_blueprint.effects.iter())
+            .all(|(effect, blueprint)| {
+                // Rolled values are rounded
+                effect.stat_effect.stat == blueprint.stat
+                    && effect.stat_effect.modifier == blueprint.modifier
+                    && effect.scope == blueprint.scope
+                    && effect.stat_effect.value >= blueprint.value.min.floor()
+                    && effect.stat_effect.value <= blueprint.value.max.ceil()
+            })
+}
+
+/// Check a page of characters inventories.
+/// Returns the reports and the character to continue after, if there are more.
+pub async fn report_inventories_legacy_items(
+    db_pool: &db::DbPool,
+    master_store: &MasterStore,
+    after_character_id: Option<UserCharacterId>,
+    limit: i64,
+) -> anyhow::Result<(Vec<LegacyItemReport>, Option<UserCharacterId>)> {
+    let entries = db::characters_data::read_characters_inventory_data(
+        db_pool,
+        after_character_id.as_ref(),
+        limit,
+    )
+    .await?;
+    let next_character_id = entries
+        .last()
+        .filter(|_| entries.len() as i64 >= limit)
+        .map(|entry| entry.character_id);
+
+    let mut reports = Vec::new();
+    for entry in entries {
+        let location = LegacyItemLocation::Inventory(entry.character_id);
+        match rmp_serde::from_slice::<InventoryData>(&entry.inventory_data) {
+            Ok(inventory_data) => {
+                for item_modifiers in inventory_data
+                    .equipped
+                    .values()
+                    .chain(inventory_data.bag.iter())
+                {
+                    reports.extend(check_item(master_store, location.clone(), item_modifiers));
+                }
+            }
+            Err(_) => reports.push(unreadable_item(location)),
+        }
+    }
+
+    Ok((reports, next_character_id))
+}
+
+/// Check a page of stashed items.
+/// Returns the reports and the item to continue after, if there are more.
+pub async fn report_stash_legacy_items(
+    db_pool: &db::DbPool,
+    master_store: &MasterStore,
+    after_stash_item_id: Option<StashItemId>,
+    limit: i64,
+) -> anyhow::Result<(Vec<LegacyItemReport>, Option<StashItemId>)> {
+    let entries =
+        db::stash_items::read_stash_items_data(db_pool, after_stash_item_id, limit).await?;
+    let next_stash_item_id = entries
+        .last()
+        .filter(|_| entries.len() as i64 >= limit)
+        .map(|entry| entry.stash_item_id);
+
+    let reports = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let location = LegacyItemLocation::Stash(entry.stash_item_id);
+            match serde_json::from_value::<ItemModifiers>(entry.item_data) {
+                Ok(item_modifiers) => check_item(master_store, location, &item_modifiers),
+                Err(_) => Some(unreadable_item(location)),
+            }
+        })
+        .collect();
+
+    Ok((reports, next_stash_item_id))
+}
+
+fn unreadable_item(location: LegacyItemLocation) -> LegacyItemReport {
+    LegacyItemReport {
+        location,
+        base_item_id: Default::default(),
+        name: Default::default(),
+        issues: vec![LegacyItemIssue::Unreadable],
+    }
+}
+
+fn check_item(
+    master_store: &MasterStore,
+    location: LegacyItemLocation,
+    item_modifiers: &ItemModifiers,
+) -> Option<LegacyItemReport> {
+    let issues = find_legacy_issues(
+        &master_store.items_store,
+        &master_store.item_affixes_table,
+        item_modifiers,
+    );
+    (!issues.is_empty()).then(|| LegacyItemReport {
+        location,
+        base_item_id: item_modifiers.base_item_id.clone(),
+        name: item_modifiers.name.clone(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::data::{
+        chance::ChanceRange,
+        item::ItemBase,
+        item_affix::{AffixEffect, AffixEffectBlueprint, AffixEffectScope},
+        modifier::Modifier,
+        stat_effect::{StatEffect, StatType},
+    };
+
+    use super::*;
+
+    fn effect_blueprint() -> AffixEffectBlueprint {
+        AffixEffectBlueprint {
+            stat: StatType::GemsFind,
+            modifier: Modifier::Increased,
+            scope: AffixEffectScope::Global,
+            value: ChanceRange {
+                min: 10.0,
+                max: 20.0,
+                lucky_chance: Default::default(),
+            },
+            ignore_quality: true,
+            bypass_ignore: false,
+        }
+    }
+
+    fn affix(affix_type: AffixType, family: &str, tier: u8, value: f64) -> ItemAffix {
+        ItemAffix {
+            name: family.into(),
+            family: family.into(),
+            tags: Default::default(),
+            affix_type,
+            tier,
+            effects: vec![AffixEffect {
+                scope: AffixEffectScope::Global,
+                stat_effect: StatEffect {
+                    stat: StatType::GemsFind,
+                    modifier: Modifier::Increased,
+                    value,
+                    bypass_ignore: false,
+                },
+            }],
+            item_level: 1,
+            triggers: Vec::new(),
+        }
+    }
+
+    fn item_modifiers(
+        base_item_id: &str,
+        rarity: ItemRarity,
+        affixes: Vec<ItemAffix>,
+        base_version: u16,
+    ) -> ItemModifiers {
+        ItemModifiers {
+            base_item_id: base_item_id.into(),
+            name: base_item_id.into(),
+            rarity,
+            level: 10,
+            affixes,
+            quality: 0.0,
+            upgrade_level: 0,
+            corrupted: false,
+            socketed_runes: Vec::new(),
+            base_version,
+        }
+    }
+
+    fn stores() -> (ItemsStore, ItemAffixesTable) {
+        let items_store = ItemsStore {
+            content: [
+                (
+                    "sword".to_string(),
+                    ItemBase {
+                        name: "Sword".into(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "unique_sword".to_string(),
+                    ItemBase {
+                        name: "Unique Sword".into(),
+                        rarity: ItemRarity::Unique,
+                        affixes: vec![effect_blueprint()],
+                        version: 1,
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into(),
+            signature_key: [0; 32],
+        };
+        let affixes_table = vec![ItemAffixBlueprint {
+            name: "gems".into(),
+            family: "gems".into(),
+            tags: Default::default(),
+            affix_type: AffixType::Prefix,
+            tier: 1,
+            weight: 1,
+            restrictions: None,
+            item_level: 1,
+            effects: vec![effect_blueprint()],
+            triggers: Vec::new(),
+        }];
+        (items_store, affixes_table)
+    }
+
+    #[test]
+    fn test_clean_items_have_no_issues() {
+        let (items_store, affixes_table) = stores();
+
+        for item_modifiers in [
+            item_modifiers(
+                "sword",
+                ItemRarity::Magic,
+                vec![affix(AffixType::Prefix, "gems", 1, 15.0)],
+                0,
+            ),
+            item_modifiers(
+                "unique_sword",
+                ItemRarity::Unique,
+                vec![affix(AffixType::Unique, "Unique Sword", 1, 15.0)],
+                1,
+            ),
+        ] {
+            assert!(find_legacy_issues(&items_store, &affixes_table, &item_modifiers).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_unknown_base() {
+        let (items_store, affixes_table) = stores();
+
+        assert_eq!(
+            find_legacy_issues(
+                &items_store,
+                &affixes_table,
+                &item_modifiers("removed_sword", ItemRarity::Normal, Vec::new(), 0),
+            ),
+            [LegacyItemIssue::UnknownBase]
+        );
+    }
+
+    #[test]
+    fn test_unknown_or_out_of_range_affixes() {
+        let (items_store, affixes_table) = stores();
+        let rune = item_modifiers(
+            "sword",
+            ItemRarity::Magic,
+            vec![affix(AffixType::Suffix, "removed", 1, 15.0)],
+            0,
+        );
+        let mut item = item_modifiers(
+            "sword",
+            ItemRarity::Rare,
+            vec![
+                affix(AffixType::Prefix, "gems", 2, 15.0),
+                affix(AffixType::Prefix, "gems", 1, 30.0),
+            ],
+            0,
+        );
+        item.socketed_runes.push(rune);
+
+        assert_eq!(
+            find_legacy_issues(&items_store, &affixes_table, &item),
+            [
+                LegacyItemIssue::UnknownAffix {
+                    family: "gems".into(),
+                    tier: 2,
+                },
+                LegacyItemIssue::AffixOutOfRange {
+                    family: "gems".into(),
+                    tier: 1,
+                },
+                LegacyItemIssue::UnknownAffix {
+                    family: "removed".into(),
+                    tier: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outdated_unique_is_fixed_by_divine() {
+        let (items_store, affixes_table) = stores();
+        let base = &items_store.content["unique_sword"];
+        let mut item = item_modifiers(
+            "unique_sword",
+            ItemRarity::Unique,
+            vec![affix(AffixType::Unique, "Unique Sword", 1, 15.0)],
+            0,
+        );
+        item.affixes[0].effects[0].stat_effect.stat = StatType::ItemRarity;
+
+        assert_eq!(
+            find_legacy_issues(&items_store, &affixes_table, &item),
+            [
+                LegacyItemIssue::OutdatedUnique {
+                    version: 0,
+                    current_version: 1,
+                },
+                LegacyItemIssue::UniqueAffixesMismatch,
+            ]
+        );
+
+        assert!(loot_generator::divine_unique(base, &mut item));
+        assert!(find_legacy_issues(&items_store, &affixes_table, &item).is_empty());
+    }
+}
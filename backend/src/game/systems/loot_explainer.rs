@@ -0,0 +1,198 @@
+use shared::{
+    constants::ITEM_REWARDS_BOSS_FACTOR,
+    data::{
+        area::AreaLevel,
+        loot::{
+            AffixRollChance, AreaLootChances, ItemDropArea, ItemDropChance, ItemDropSource,
+            RarityChance,
+        },
+    },
+};
+
+use crate::{
+    app_state::MasterStore,
+    game::systems::{
+        area_controller,
+        loot_generator::{self, DEFAULT_LOOT_TEMPLATE},
+    },
+};
+
+/// Compute the loot chances of an area without map, when reaching a new area level
+pub fn explain_area_loot(
+    master_store: &MasterStore,
+    area_id: &str,
+    area_level: AreaLevel,
+    is_boss_level: bool,
+) -> Option<AreaLootChances> {
+    let mut area_blueprint = master_store.area_blueprints_store.get(area_id)?.clone();
+    let area_specs = area_controller::init_area_specs(
+        &master_store.loot_tables_store,
+        &mut area_blueprint,
+        &None,
+        &Default::default(),
+        &Default::default(),
+    );
+
+    let item_level = area_level
+        .saturating_add(*area_specs.power_level)
+        .saturating_add(*area_specs.item_level_modifier);
+    let loot_rarity = *area_specs.loot_rarity
+        * if is_boss_level {
+            ITEM_REWARDS_BOSS_FACTOR
+        } else {
+            1.0
+        };
+
+    let boss_loot_table = area_blueprint
+        .bosses
+        .iter()
+        .filter(|boss| is_boss_level && boss.is_available(area_level))
+        .filter_map(|boss| boss.loot.as_ref())
+        .map(|boss_loot| &boss_loot.loot_table)
+        .find(|loot_table| !loot_table.entries.is_empty());
+
+    let mut items: Vec<_> = loot_generator::base_items_drop_chances(
+        boss_loot_table.unwrap_or(&area_blueprint.loot_table),
+        &master_store.items_store,
+        &DEFAULT_LOOT_TEMPLATE,
+        item_level,
+        is_boss_level,
+        loot_rarity,
+    )
+    .into_iter()
+    .filter_map(|(item_id, chance)| {
+        master_store
+            .items_store
+            .content
+            .get(&item_id)
+            .map(|base| ItemDropChance {
+                name: base.name.clone(),
+                rarity: base.rarity,
+                item_id,
+                chance,
+            })
+    })
+    .collect();
+    items.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+
+    Some(AreaLootChances {
+        item_level,
+        rarity_chances: loot_generator::rarity_chances(&DEFAULT_LOOT_TEMPLATE, loot_rarity)
+            .into_iter()
+            .map(|(rarity, chance)| RarityChance { rarity, chance })
+            .collect(),
+        items,
+    })
+}
+
+pub fn explain_item_affixes(
+    master_store: &MasterStore,
+    item_id: &str,
+    item_level: AreaLevel,
+) -> Option<Vec<AffixRollChance>> {
+    let base = master_store.items_store.content.get(item_id)?;
+
+    Some(
+        loot_generator::affixes_roll_chances(base, item_level, &master_store.item_affixes_table)
+            .into_iter()
+            .map(|(affix_blueprint, chance)| AffixRollChance {
+                name: affix_blueprint.name.clone(),
+                family: affix_blueprint.family.clone(),
+                affix_type: affix_blueprint.affix_type,
+                tier: affix_blueprint.tier,
+                item_level: affix_blueprint.item_level,
+                chance,
+            })
+            .collect(),
+    )
+}
+
+/// List all the areas, bosses and maps that can drop the item
+pub fn find_item_drop_areas(master_store: &MasterStore, item_id: &str) -> Vec<ItemDropArea> {
+    let Some(base) = master_store.items_store.content.get(item_id) else {
+        return vec![];
+    };
+
+    let mut drop_areas = Vec::new();
+
+    for (area_id, area_blueprint) in master_store.area_blueprints_store.iter() {
+        // Loot levels are compared to the power level, convert them back to area levels
+        let power_level = (*area_blueprint.specs.power_level)
+            .saturating_add(*area_blueprint.specs.item_level_modifier);
+
+        for entry in area_blueprint
+            .loot_table
+            .entries
+            .iter()
+            .chain(area_blueprint.loot_table_area.entries.iter())
+            .filter(|entry| entry.item_id == item_id)
+        {
+            drop_areas.push(ItemDropArea {
+                area_id: Some(area_id.clone()),
+                source: ItemDropSource::Area,
+                min_area_level: entry
+                    .min_area_level
+                    .unwrap_or(base.min_area_level)
+                    .saturating_sub(power_level)
+                    .max(1),
+                max_area_level: entry
+                    .max_area_level
+                    .map(|level| level.saturating_sub(power_level)),
+                boss_only: entry.boss_only,
+            });
+        }
+
+        for boss in area_blueprint.bosses.iter() {
+            let Some(boss_loot) = &boss.loot else {
+                continue;
+            };
+
+            if boss_loot
+                .loot_table
+                .entries
+                .iter()
+                .map(|entry| &entry.item_id)
+                .chain(boss_loot.guaranteed_drops.iter())
+                .chain(boss_loot.first_kill_drops.iter())
+                .chain(boss_loot.unique_drops.iter().map(|drop| &drop.item_id))
+                .any(|boss_item_id| boss_item_id == item_id)
+            {
+                drop_areas.push(ItemDropArea {
+                    area_id: Some(area_id.clone()),
+                    source: ItemDropSource::Boss,
+                    min_area_level: boss.level,
+                    max_area_level: None,
+                    boss_only: true,
+                });
+            }
+        }
+    }
+
+    for (map_item_id, map_base) in master_store.items_store.content.iter() {
+        let Some(map_specs) = &map_base.map_specs else {
+            continue;
+        };
+
+        for entry in map_specs
+            .loot_tables
+            .iter()
+            .chain(map_specs.reward_loot_table.iter())
+            .filter_map(|loot_table_id| master_store.loot_tables_store.get(loot_table_id))
+            .flat_map(|loot_table| loot_table.entries.iter())
+            .filter(|entry| entry.item_id == item_id)
+        {
+            drop_areas.push(ItemDropArea {
+                area_id: map_specs.area_id.clone(),
+                source: ItemDropSource::Map {
+                    item_id: map_item_id.clone(),
+                    name: map_base.name.clone(),
+                },
+                min_area_level: entry.min_area_level.unwrap_or(base.min_area_level),
+                max_area_level: entry.max_area_level,
+                boss_only: entry.boss_only,
+            });
+        }
+    }
+
+    drop_areas
+}
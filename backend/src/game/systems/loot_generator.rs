@@ -1,12 +1,14 @@
+use indexmap::IndexMap;
 use std::collections::{BTreeSet, HashSet};
 
+use backend_shared::signature::HmacKey;
 use shared::{
     computations,
     constants::{MAX_ITEM_QUALITY, MAX_ITEM_QUALITY_PER_LEVEL},
     data::{
         area::AreaLevel,
         chance::ChanceRange,
-        forge::MAX_AFFIXES,
+        forge::{CorruptionOutcome, MAX_AFFIXES},
         item::{ItemBase, ItemCategory, ItemModifiers, ItemRarity, ItemSpecs},
         item_affix::{AffixEffect, AffixEffectBlueprint, AffixType, ItemAffix, ItemAffixBlueprint},
         stat_effect::StatEffect,
@@ -15,10 +17,12 @@ use shared::{
 
 use crate::game::{
     data::{
+        area::BossLootBlueprint,
+        corruption_table::CorruptionTable,
         items_store::{ItemAdjectivesTable, ItemAffixesTable, ItemNounsTable, ItemsStore},
         loot_table::{LootTable, LootTableEntry, RarityWeights},
     },
-    utils::rng::{self, RandomWeighted, Rollable, flip_coin},
+    utils::rng::{self, RandomWeighted, RngSeed, Rollable, flip_coin},
 };
 
 use super::items_controller;
@@ -88,17 +92,87 @@ pub fn generate_loot(
     )
 }
 
+/// Roll the specific drops of a boss, including first kill drops if requested
+#[allow(clippy::too_many_arguments)]
+pub fn generate_boss_drops(
+    boss_loot: &BossLootBlueprint,
+    first_kill: bool,
+    items_store: &ItemsStore,
+    affixes_table: &ItemAffixesTable,
+    adjectives_table: &ItemAdjectivesTable,
+    nouns_table: &ItemNounsTable,
+    level: AreaLevel,
+    power_level_modifier: AreaLevel,
+    loot_rarity: f64,
+    gold_find: f64,
+) -> Vec<ItemSpecs> {
+    boss_loot
+        .guaranteed_drops
+        .iter()
+        .chain(
+            boss_loot
+                .unique_drops
+                .iter()
+                .filter(|drop| drop.chance.roll())
+                .map(|drop| &drop.item_id),
+        )
+        .chain(boss_loot.first_kill_drops.iter().filter(|_| first_kill))
+        .filter_map(|item_id| {
+            let base = items_store.content.get(item_id)?.clone();
+            let rarity = if base.rarity == ItemRarity::Unique {
+                ItemRarity::Unique
+            } else {
+                roll_rarity(&RarityWeights::default(), loot_rarity)
+                    .clamp(base.rarity, ItemRarity::Rare)
+            };
+            Some(roll_item_stats(
+                item_id.clone(),
+                base,
+                rarity,
+                level,
+                power_level_modifier,
+                affixes_table,
+                adjectives_table,
+                nouns_table,
+                false,
+                gold_find,
+                &items_store.signature_key,
+            ))
+        })
+        .collect()
+}
+
 fn roll_rarity(weights: &RarityWeights, loot_rarity: f64) -> ItemRarity {
-    let factor = loot_rarity * 0.01;
+    pick_rarity(
+        weights,
+        loot_rarity,
+        rng::random_range(0.0..rarity_total_weight(weights)).unwrap_or(0.0),
+    )
+}
 
-    match rng::random_range(0.0..(weights.normal + weights.magic + weights.rare + weights.unique))
-        .unwrap_or(0.0)
-    {
-        r if r < (weights.unique) * factor => ItemRarity::Unique,
-        r if r < (weights.unique + weights.rare) * factor => ItemRarity::Rare,
-        r if r < (weights.unique + weights.rare + weights.magic) * factor => ItemRarity::Magic,
-        _ => ItemRarity::Normal,
-    }
+fn pick_rarity(weights: &RarityWeights, loot_rarity: f64, roll: f64) -> ItemRarity {
+    rarity_thresholds(weights, loot_rarity)
+        .into_iter()
+        .find(|(_, threshold)| roll < *threshold)
+        .map(|(rarity, _)| rarity)
+        .unwrap_or(ItemRarity::Normal)
+}
+
+fn rarity_total_weight(weights: &RarityWeights) -> f64 {
+    weights.normal + weights.magic + weights.rare + weights.unique
+}
+
+/// Cumulated roll thresholds under which each rarity is picked, Normal takes the remainder
+fn rarity_thresholds(weights: &RarityWeights, loot_rarity: f64) -> [(ItemRarity, f64); 3] {
+    let factor = loot_rarity * 0.01;
+    [
+        (ItemRarity::Unique, weights.unique * factor),
+        (ItemRarity::Rare, (weights.unique + weights.rare) * factor),
+        (
+            ItemRarity::Magic,
+            (weights.unique + weights.rare + weights.magic) * factor,
+        ),
+    ]
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -140,7 +214,7 @@ pub fn roll_item(
             nouns_table,
             loot_template.max_affixes,
             gold_find,
-            // &items_store.signature_key,
+            &items_store.signature_key,
         )
     })
 }
@@ -157,7 +231,7 @@ pub fn roll_item_stats(
     nouns_table: &ItemNounsTable,
     max_affixes: bool,
     gold_find: f64,
-    // signature_key: &HmacSignature,
+    signature_key: &HmacKey,
 ) -> ItemSpecs {
     let quality = if base.ignore_quality {
         0.0
@@ -176,6 +250,9 @@ pub fn roll_item_stats(
         quality,
         affixes: roll_unique_affixes(&base, quality),
         upgrade_level: 0,
+        corrupted: false,
+        socketed_runes: Vec::new(),
+        base_version: base.version,
     };
 
     let affixes_amount = if max_affixes {
@@ -219,7 +296,55 @@ pub fn roll_item_stats(
     ) * gold_find
         * 0.01;
 
-    items_controller::create_item_specs(base, modifiers, gold_price)
+    items_controller::create_item_specs(base, modifiers, gold_price, signature_key)
+}
+
+/// Create an item with a fixed list of affixes (family, tier), with values rolled from the seed
+#[allow(clippy::too_many_arguments)]
+pub fn roll_item_with_affixes(
+    base_item_id: String,
+    base: ItemBase,
+    level: AreaLevel,
+    affixes: &[(&str, u8)],
+    affixes_table: &ItemAffixesTable,
+    adjectives_table: &ItemAdjectivesTable,
+    nouns_table: &ItemNounsTable,
+    seed: &mut RngSeed,
+    signature_key: &HmacKey,
+) -> ItemSpecs {
+    let mut modifiers = ItemModifiers {
+        base_item_id,
+        name: base.name.clone(),
+        rarity: ItemRarity::Normal,
+        level,
+        quality: 0.0,
+        affixes: roll_unique_affixes(&base, 0.0),
+        upgrade_level: 0,
+        corrupted: false,
+        socketed_runes: Vec::new(),
+        base_version: base.version,
+    };
+
+    for (family, tier) in affixes {
+        if let Some(affix_blueprint) = find_affix_blueprint(&base, family, *tier, affixes_table) {
+            modifiers
+                .affixes
+                .push(affix_from_blueprint_with_seed(affix_blueprint, seed));
+        }
+    }
+
+    let affixes_amount = modifiers.count_nonunique_affixes();
+    if affixes_amount > 0 {
+        update_rarity(
+            &base,
+            &mut modifiers,
+            affixes_amount,
+            adjectives_table,
+            nouns_table,
+        );
+    }
+
+    items_controller::create_item_specs(base, modifiers, 0.0, signature_key)
 }
 
 fn roll_quality(min_item_level: AreaLevel, level: AreaLevel) -> f32 {
@@ -237,6 +362,37 @@ fn roll_base_item(
     is_boss_level: bool,
     is_unique: bool,
 ) -> Option<(String, ItemBase)> {
+    let items_available = base_items_pool(
+        loot_table,
+        items_store,
+        loot_template,
+        area_level,
+        is_boss_level,
+        is_unique,
+    );
+
+    if items_available.is_empty() {
+        tracing::warn!("No base items available for level {}", area_level);
+    }
+
+    rng::random_weighted_pick(&items_available).and_then(|loot_entry| {
+        items_store
+            .content
+            .get(&loot_entry.item_id)
+            .cloned()
+            .map(|item_base| (loot_entry.item_id.clone(), item_base))
+    })
+}
+
+/// Return the loot entries that can be picked for the given rarity
+fn base_items_pool<'a>(
+    loot_table: &'a LootTable,
+    items_store: &ItemsStore,
+    loot_template: &GenerateLootTemplate,
+    area_level: AreaLevel,
+    is_boss_level: bool,
+    is_unique: bool,
+) -> Vec<&'a LootTableEntry> {
     let items_available: Vec<_> = loot_table
         .entries
         .iter()
@@ -288,7 +444,7 @@ fn roll_base_item(
             .collect()
     };
 
-    let items_available = if loot_template.max_base && !is_unique {
+    if loot_template.max_base && !is_unique {
         let max_level = items_available
             .iter()
             .map(|l| {
@@ -312,19 +468,111 @@ fn roll_base_item(
             .collect()
     } else {
         items_available
-    };
+    }
+}
 
-    if items_available.is_empty() {
-        tracing::warn!("No base items available for level {}", area_level);
+/// Compute the chance of each rarity to be rolled, in percent
+pub fn rarity_chances(
+    loot_template: &GenerateLootTemplate,
+    loot_rarity: f64,
+) -> Vec<(ItemRarity, f64)> {
+    let weights = RarityWeights::default();
+    let total = rarity_total_weight(&weights);
+
+    let mut previous = 0.0;
+    let mut chances: Vec<_> = rarity_thresholds(&weights, loot_rarity)
+        .into_iter()
+        .map(|(rarity, threshold)| (rarity, (threshold / total).min(1.0)))
+        .chain([(ItemRarity::Normal, 1.0)])
+        .map(|(rarity, cumulated)| {
+            let chance = (cumulated - previous).max(0.0);
+            previous = cumulated.max(previous);
+            (rarity, chance * 100.0)
+        })
+        .collect();
+
+    if !loot_template.allow_unique {
+        let unique_chance = std::mem::take(&mut chances[0].1);
+        chances[1].1 += unique_chance;
     }
 
-    rng::random_weighted_pick(&items_available).and_then(|loot_entry| {
-        items_store
-            .content
-            .get(&loot_entry.item_id)
-            .cloned()
-            .map(|item_base| (loot_entry.item_id.clone(), item_base))
-    })
+    chances
+}
+
+/// Compute the chance of each base item to be picked for a dropped item, in percent
+pub fn base_items_drop_chances(
+    loot_table: &LootTable,
+    items_store: &ItemsStore,
+    loot_template: &GenerateLootTemplate,
+    area_level: AreaLevel,
+    is_boss_level: bool,
+    loot_rarity: f64,
+) -> IndexMap<String, f64> {
+    let mut drop_chances = IndexMap::new();
+
+    for (rarity, rarity_chance) in rarity_chances(loot_template, loot_rarity) {
+        let items_available = base_items_pool(
+            loot_table,
+            items_store,
+            loot_template,
+            area_level,
+            is_boss_level,
+            rarity == ItemRarity::Unique,
+        );
+
+        let total_weight: u64 = items_available.iter().map(|entry| entry.weight).sum();
+        if total_weight == 0 {
+            continue;
+        }
+
+        for entry in items_available {
+            *drop_chances.entry(entry.item_id.clone()).or_default() +=
+                rarity_chance * entry.weight as f64 / total_weight as f64;
+        }
+    }
+
+    drop_chances
+}
+
+/// Compute the chance of each affix to be picked when adding an affix of its type
+/// to an item without affixes, in percent
+pub fn affixes_roll_chances<'a>(
+    base: &ItemBase,
+    item_level: AreaLevel,
+    affixes_table: &'a ItemAffixesTable,
+) -> Vec<(&'a ItemAffixBlueprint, f64)> {
+    if base.rarity == ItemRarity::Unique {
+        return vec![];
+    }
+
+    [AffixType::Prefix, AffixType::Suffix]
+        .into_iter()
+        .flat_map(|affix_type| {
+            let available_affixes: Vec<_> = affixes_table
+                .iter()
+                .filter(|a| is_affix_available(base, item_level, a) && a.affix_type == affix_type)
+                .map(|affix_blueprint| {
+                    (
+                        affix_blueprint,
+                        tweak_affix_weight(
+                            affix_blueprint.weight,
+                            affix_blueprint.item_level,
+                            item_level,
+                        ),
+                    )
+                })
+                .collect();
+
+            let total_weight: u64 = available_affixes.iter().map(|(_, weight)| weight).sum();
+
+            available_affixes
+                .into_iter()
+                .filter(move |_| total_weight > 0)
+                .map(move |(affix_blueprint, weight)| {
+                    (affix_blueprint, weight as f64 * 100.0 / total_weight as f64)
+                })
+        })
+        .collect()
 }
 
 fn roll_unique_affixes(base_item: &ItemBase, quality: f32) -> Vec<ItemAffix> {
@@ -349,6 +597,7 @@ fn roll_unique_affixes(base_item: &ItemBase, quality: f32) -> Vec<ItemAffix> {
                 tier: 1,
                 item_level: base_item.min_area_level,
                 effects: vec![effect],
+                triggers: Vec::new(),
             }
         })
         .collect()
@@ -366,39 +615,8 @@ pub fn add_affix(
         return false;
     }
 
-    let prefixes_amount = modifiers.count_affixes(AffixType::Prefix);
-    let suffixes_amount = modifiers.count_affixes(AffixType::Suffix);
-
-    if prefixes_amount + suffixes_amount >= MAX_AFFIXES {
+    let Some(affix_type) = pick_affix_type(modifiers, affix_type) else {
         return false;
-    }
-
-    let affix_type = match affix_type {
-        Some(AffixType::Prefix) => {
-            if prefixes_amount <= suffixes_amount {
-                AffixType::Prefix
-            } else {
-                return false;
-            }
-        }
-        Some(AffixType::Suffix) => {
-            if suffixes_amount <= prefixes_amount {
-                AffixType::Suffix
-            } else {
-                return false;
-            }
-        }
-        _ => {
-            if prefixes_amount < suffixes_amount {
-                AffixType::Prefix
-            } else if suffixes_amount < prefixes_amount {
-                AffixType::Suffix
-            } else if rng::flip_coin() {
-                AffixType::Prefix
-            } else {
-                AffixType::Suffix
-            }
-        }
     };
 
     if let Some(affix) = roll_affix(
@@ -413,7 +631,7 @@ pub fn add_affix(
         return false;
     }
 
-    let affixes_amount = prefixes_amount + suffixes_amount + 1;
+    let affixes_amount = modifiers.count_nonunique_affixes();
     update_rarity(
         base,
         modifiers,
@@ -425,6 +643,157 @@ pub fn add_affix(
     true
 }
 
+pub fn add_affix_family(
+    base: &ItemBase,
+    modifiers: &mut ItemModifiers,
+    family: &str,
+    affixes_table: &ItemAffixesTable,
+    adjectives_table: &ItemAdjectivesTable,
+    nouns_table: &ItemNounsTable,
+) -> bool {
+    if base.rarity == ItemRarity::Unique || modifiers.get_families().contains(family) {
+        return false;
+    }
+
+    let available_affixes: Vec<_> = affixes_table
+        .iter()
+        .filter(|a| a.family == family && is_affix_available(base, modifiers.level, a))
+        .map(|affix_blueprint| TweakedItemAffixBlueprint {
+            weight: tweak_affix_weight(
+                affix_blueprint.weight,
+                affix_blueprint.item_level,
+                modifiers.level,
+            ),
+            affix_blueprint,
+        })
+        .collect();
+
+    let Some(affix_type) = available_affixes
+        .first()
+        .map(|a| a.affix_blueprint.affix_type)
+    else {
+        return false;
+    };
+
+    if pick_affix_type(modifiers, Some(affix_type)).is_none() {
+        return false;
+    }
+
+    match rng::random_weighted_pick(&available_affixes) {
+        Some(a) => modifiers
+            .affixes
+            .push(affix_from_blueprint(a.affix_blueprint)),
+        None => return false,
+    }
+
+    let affixes_amount = modifiers.count_nonunique_affixes();
+    update_rarity(
+        base,
+        modifiers,
+        affixes_amount,
+        adjectives_table,
+        nouns_table,
+    );
+
+    true
+}
+
+/// List the families that could be added to the item, with the name of their best available tier
+pub fn available_affix_families<'a>(
+    base: &ItemBase,
+    modifiers: &ItemModifiers,
+    affixes_table: &'a ItemAffixesTable,
+) -> Vec<&'a ItemAffixBlueprint> {
+    if base.rarity == ItemRarity::Unique {
+        return vec![];
+    }
+
+    let families_in_use = modifiers.get_families();
+    let mut families: IndexMap<&str, &ItemAffixBlueprint> = IndexMap::new();
+    for affix_blueprint in affixes_table.iter().filter(|a| {
+        is_affix_available(base, modifiers.level, a)
+            && !families_in_use.contains(&a.family)
+            && pick_affix_type(modifiers, Some(a.affix_type)).is_some()
+    }) {
+        families
+            .entry(&affix_blueprint.family)
+            .and_modify(|best| {
+                if affix_blueprint.tier > best.tier {
+                    *best = affix_blueprint
+                }
+            })
+            .or_insert(affix_blueprint);
+    }
+
+    families.into_values().collect()
+}
+
+/// Reroll the values of all prefixes and suffixes within their current tier,
+/// except for the locked affix
+pub fn reroll_affixes(
+    base: &ItemBase,
+    modifiers: &mut ItemModifiers,
+    locked_affix: Option<usize>,
+    affixes_table: &ItemAffixesTable,
+) -> bool {
+    if base.rarity == ItemRarity::Unique {
+        return false;
+    }
+
+    let mut rerolled = false;
+    for (index, affix) in modifiers.affixes.iter_mut().enumerate() {
+        if Some(index) == locked_affix
+            || !matches!(affix.affix_type, AffixType::Prefix | AffixType::Suffix)
+        {
+            continue;
+        }
+
+        if let Some(affix_blueprint) =
+            find_affix_blueprint(base, &affix.family, affix.tier, affixes_table)
+        {
+            affix.effects = affix_blueprint
+                .effects
+                .iter()
+                .map(roll_affix_effect)
+                .collect();
+            rerolled = true;
+        }
+    }
+
+    rerolled
+}
+
+/// Upgrade the affix at given index to the next tier of its family, if allowed by the item level
+pub fn upgrade_affix_tier(
+    base: &ItemBase,
+    modifiers: &mut ItemModifiers,
+    affix_index: usize,
+    affixes_table: &ItemAffixesTable,
+) -> bool {
+    if base.rarity == ItemRarity::Unique {
+        return false;
+    }
+
+    let item_level = modifiers.level;
+    let Some(affix) = modifiers
+        .affixes
+        .get_mut(affix_index)
+        .filter(|affix| matches!(affix.affix_type, AffixType::Prefix | AffixType::Suffix))
+    else {
+        return false;
+    };
+
+    match find_affix_blueprint(base, &affix.family, affix.tier + 1, affixes_table)
+        .filter(|a| item_level >= a.item_level)
+    {
+        Some(affix_blueprint) => {
+            *affix = affix_from_blueprint(affix_blueprint);
+            true
+        }
+        None => false,
+    }
+}
+
 pub fn remove_affix(
     base: &ItemBase,
     modifiers: &mut ItemModifiers,
@@ -455,7 +824,7 @@ pub fn remove_affix(
     let affixes_amount = match affix_type {
         AffixType::Prefix => prefixes_amount,
         AffixType::Suffix => suffixes_amount,
-        AffixType::Unique | AffixType::Upgrade => 0,
+        AffixType::Unique | AffixType::Upgrade | AffixType::Corruption => 0,
     };
 
     let affix_subindex = rng::random_range(0..affixes_amount).unwrap_or_default();
@@ -483,6 +852,157 @@ pub fn remove_affix(
     true
 }
 
+/// Corrupt the item, rolling an outcome from the corruption table among the ones that
+/// can apply to the item. The resulting item cannot be modified anymore.
+pub fn corrupt_item(
+    item_specs: &ItemSpecs,
+    corruption_table: &CorruptionTable,
+    items_store: &ItemsStore,
+    affixes_table: &ItemAffixesTable,
+    adjectives_table: &ItemAdjectivesTable,
+    nouns_table: &ItemNounsTable,
+) -> Option<(CorruptionOutcome, ItemSpecs)> {
+    if item_specs.modifiers.corrupted {
+        return None;
+    }
+
+    let base = &item_specs.base;
+    let level = item_specs.modifiers.level;
+    let families_in_use = item_specs.modifiers.get_families();
+
+    let implicit_affixes: Vec<_> = corruption_table
+        .implicit_affixes
+        .iter()
+        .filter(|a| is_affix_available(base, level, a) && !families_in_use.contains(&a.family))
+        .map(|affix_blueprint| TweakedItemAffixBlueprint {
+            weight: affix_blueprint.weight,
+            affix_blueprint,
+        })
+        .collect();
+
+    let upgradable_affixes: Vec<_> = item_specs
+        .modifiers
+        .affixes
+        .iter()
+        .enumerate()
+        .filter(|(_, affix)| {
+            matches!(affix.affix_type, AffixType::Prefix | AffixType::Suffix)
+                && find_affix_blueprint(base, &affix.family, affix.tier + 1, affixes_table)
+                    .is_some_and(|a| level >= a.item_level)
+        })
+        .map(|(affix_index, _)| affix_index)
+        .collect();
+
+    let uniques: Vec<_> = items_store
+        .content
+        .iter()
+        .filter(|(item_id, unique_base)| {
+            unique_base.rarity == ItemRarity::Unique
+                && unique_base.categories == base.categories
+                && unique_base.min_area_level <= level
+                && **item_id != item_specs.modifiers.base_item_id
+        })
+        .collect();
+
+    let outcomes: Vec<_> = corruption_table
+        .outcomes
+        .iter()
+        .filter(|entry| match entry.outcome {
+            CorruptionOutcome::ImplicitAffix => !implicit_affixes.is_empty(),
+            CorruptionOutcome::UpgradeAffixTier => !upgradable_affixes.is_empty(),
+            CorruptionOutcome::UniqueReroll => !uniques.is_empty(),
+            CorruptionOutcome::Brick => true,
+        })
+        .cloned()
+        .collect();
+
+    let outcome = rng::random_weighted_pick(&outcomes)?.outcome;
+
+    let mut modifiers = item_specs.modifiers.clone();
+    let mut item_specs = match outcome {
+        CorruptionOutcome::ImplicitAffix => {
+            modifiers.affixes.push(affix_from_blueprint(
+                rng::random_weighted_pick(&implicit_affixes)?.affix_blueprint,
+            ));
+            items_controller::create_item_specs(
+                base.clone(),
+                modifiers,
+                item_specs.gold_price,
+                &items_store.signature_key,
+            )
+        }
+        CorruptionOutcome::UpgradeAffixTier => {
+            let affix_index = upgradable_affixes
+                [rng::random_range(0..upgradable_affixes.len()).unwrap_or_default()];
+            upgrade_affix_tier(base, &mut modifiers, affix_index, affixes_table);
+            items_controller::create_item_specs(
+                base.clone(),
+                modifiers,
+                item_specs.gold_price,
+                &items_store.signature_key,
+            )
+        }
+        CorruptionOutcome::UniqueReroll => {
+            let (item_id, unique_base) =
+                uniques[rng::random_range(0..uniques.len()).unwrap_or_default()];
+            roll_item_stats(
+                item_id.clone(),
+                unique_base.clone(),
+                ItemRarity::Unique,
+                level,
+                0,
+                affixes_table,
+                adjectives_table,
+                nouns_table,
+                false,
+                0.0,
+                &items_store.signature_key,
+            )
+        }
+        CorruptionOutcome::Brick => {
+            modifiers.affixes.retain(|affix| {
+                !matches!(
+                    affix.affix_type,
+                    AffixType::Prefix | AffixType::Suffix | AffixType::Upgrade
+                )
+            });
+            modifiers.upgrade_level = 0;
+            if modifiers.rarity != ItemRarity::Unique {
+                modifiers.rarity = ItemRarity::Normal;
+                modifiers.name = base.name.clone();
+            }
+            items_controller::create_item_specs(
+                base.clone(),
+                modifiers,
+                item_specs.gold_price,
+                &items_store.signature_key,
+            )
+        }
+    };
+
+    item_specs.modifiers.corrupted = true;
+    items_controller::sign_item_specs(&mut item_specs, &items_store.signature_key);
+
+    Some((outcome, item_specs))
+}
+
+/// Reroll the Unique affixes of the item against the current definition of its base
+pub fn divine_unique(base: &ItemBase, modifiers: &mut ItemModifiers) -> bool {
+    if modifiers.rarity != ItemRarity::Unique {
+        return false;
+    }
+
+    modifiers
+        .affixes
+        .retain(|affix| !matches!(affix.affix_type, AffixType::Unique));
+    modifiers
+        .affixes
+        .splice(0..0, roll_unique_affixes(base, modifiers.quality));
+    modifiers.base_version = base.version;
+
+    true
+}
+
 fn update_rarity(
     base: &ItemBase,
     modifiers: &mut ItemModifiers,
@@ -514,6 +1034,84 @@ fn update_rarity(
     modifiers.rarity = new_rarity;
 }
 
+fn pick_affix_type(modifiers: &ItemModifiers, affix_type: Option<AffixType>) -> Option<AffixType> {
+    let prefixes_amount = modifiers.count_affixes(AffixType::Prefix);
+    let suffixes_amount = modifiers.count_affixes(AffixType::Suffix);
+
+    if prefixes_amount + suffixes_amount >= MAX_AFFIXES {
+        return None;
+    }
+
+    match affix_type {
+        Some(AffixType::Prefix) => {
+            (prefixes_amount <= suffixes_amount).then_some(AffixType::Prefix)
+        }
+        Some(AffixType::Suffix) => {
+            (suffixes_amount <= prefixes_amount).then_some(AffixType::Suffix)
+        }
+        _ => Some(if prefixes_amount < suffixes_amount {
+            AffixType::Prefix
+        } else if suffixes_amount < prefixes_amount {
+            AffixType::Suffix
+        } else if rng::flip_coin() {
+            AffixType::Prefix
+        } else {
+            AffixType::Suffix
+        }),
+    }
+}
+
+fn is_affix_available(
+    base_item: &ItemBase,
+    area_level: AreaLevel,
+    affix_blueprint: &ItemAffixBlueprint,
+) -> bool {
+    is_affix_allowed(base_item, affix_blueprint) && area_level >= affix_blueprint.item_level
+}
+
+fn is_affix_allowed(base_item: &ItemBase, affix_blueprint: &ItemAffixBlueprint) -> bool {
+    affix_blueprint
+        .restrictions
+        .as_ref()
+        .map(|r| !r.is_disjoint(&base_item.categories))
+        .unwrap_or(true)
+}
+
+pub fn find_affix_blueprint<'a>(
+    base_item: &ItemBase,
+    family: &str,
+    tier: u8,
+    affixes_table: &'a ItemAffixesTable,
+) -> Option<&'a ItemAffixBlueprint> {
+    affixes_table
+        .iter()
+        .find(|a| a.family == family && a.tier == tier && is_affix_allowed(base_item, a))
+}
+
+fn affix_from_blueprint(affix_blueprint: &ItemAffixBlueprint) -> ItemAffix {
+    affix_from_blueprint_with_seed(affix_blueprint, &mut rng::roll_seed())
+}
+
+fn affix_from_blueprint_with_seed(
+    affix_blueprint: &ItemAffixBlueprint,
+    seed: &mut RngSeed,
+) -> ItemAffix {
+    ItemAffix {
+        name: affix_blueprint.name.clone(),
+        family: affix_blueprint.family.clone(),
+        tags: affix_blueprint.tags.clone(),
+        affix_type: affix_blueprint.affix_type,
+        tier: affix_blueprint.tier,
+        item_level: affix_blueprint.item_level,
+        effects: affix_blueprint
+            .effects
+            .iter()
+            .map(|effect_blueprint| roll_affix_effect_with_seed(effect_blueprint, seed))
+            .collect(),
+        triggers: affix_blueprint.triggers.clone(),
+    }
+}
+
 struct TweakedItemAffixBlueprint<'a> {
     affix_blueprint: &'a ItemAffixBlueprint,
     weight: u64,
@@ -535,11 +1133,7 @@ fn roll_affix(
     let available_affixes: Vec<_> = affixes_table
         .iter()
         .filter(|a| {
-            a.restrictions
-                .as_ref()
-                .map(|r| !r.is_disjoint(&base_item.categories))
-                .unwrap_or(true)
-                && area_level >= a.item_level
+            is_affix_available(base_item, area_level, a)
                 && a.affix_type == affix_type
                 && !families_in_use.contains(&a.family)
         })
@@ -555,20 +1149,7 @@ fn roll_affix(
 
     rng::random_weighted_pick(&available_affixes).map(|a| {
         families_in_use.insert(a.affix_blueprint.family.clone());
-        ItemAffix {
-            name: a.affix_blueprint.name.clone(),
-            family: a.affix_blueprint.family.clone(),
-            tags: a.affix_blueprint.tags.clone(),
-            affix_type,
-            tier: a.affix_blueprint.tier,
-            item_level: a.affix_blueprint.item_level,
-            effects: a
-                .affix_blueprint
-                .effects
-                .iter()
-                .map(roll_affix_effect)
-                .collect(),
-        }
+        affix_from_blueprint(a.affix_blueprint)
     })
 }
 
@@ -579,11 +1160,18 @@ fn tweak_affix_weight(base_weight: u64, tier_level: AreaLevel, area_level: AreaL
 }
 
 fn roll_affix_effect(effect_blueprint: &AffixEffectBlueprint) -> AffixEffect {
+    roll_affix_effect_with_seed(effect_blueprint, &mut rng::roll_seed())
+}
+
+fn roll_affix_effect_with_seed(
+    effect_blueprint: &AffixEffectBlueprint,
+    seed: &mut RngSeed,
+) -> AffixEffect {
     AffixEffect {
         stat_effect: StatEffect {
             stat: effect_blueprint.stat.clone(),
             modifier: effect_blueprint.modifier,
-            value: effect_blueprint.value.roll().round(),
+            value: effect_blueprint.value.roll_with_seed(seed).round(),
             bypass_ignore: effect_blueprint.bypass_ignore,
             // ignore_quality: effect_blueprint.ignore_quality,
         },
@@ -688,3 +1276,279 @@ impl RandomWeighted for &LootTableEntry {
         self.weight
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use shared::{data::forge::affix_operation_price, http::client::ForgeAffixOperation};
+
+    use shared::data::chance::Chance;
+
+    use crate::game::data::{
+        area::BossUniqueDrop, corruption_table::CorruptionOutcomeEntry, loot_table::LootTableEntry,
+    };
+
+    use super::*;
+
+    fn items_store() -> ItemsStore {
+        ItemsStore {
+            content: Default::default(),
+            signature_key: [0; 32],
+        }
+    }
+
+    fn item_specs(affix_types: &[AffixType]) -> ItemSpecs {
+        let base = ItemBase {
+            name: "Sword".into(),
+            ..Default::default()
+        };
+        let modifiers = ItemModifiers {
+            base_item_id: "sword".into(),
+            name: "Sharp Sword".into(),
+            rarity: ItemRarity::Rare,
+            level: 10,
+            affixes: affix_types
+                .iter()
+                .enumerate()
+                .map(|(index, affix_type)| ItemAffix {
+                    name: format!("affix {index}"),
+                    family: format!("family_{index}"),
+                    tags: Default::default(),
+                    affix_type: *affix_type,
+                    tier: 1,
+                    effects: Vec::new(),
+                    item_level: 1,
+                    triggers: Vec::new(),
+                })
+                .collect(),
+            quality: 0.0,
+            upgrade_level: 0,
+            corrupted: false,
+            socketed_runes: Vec::new(),
+            base_version: 0,
+        };
+        items_controller::create_item_specs(base, modifiers, 0.0, &[0; 32])
+    }
+
+    #[test]
+    fn test_corrupted_item_is_locked() {
+        let corruption_table = CorruptionTable {
+            outcomes: vec![CorruptionOutcomeEntry {
+                outcome: CorruptionOutcome::Brick,
+                weight: 1,
+            }],
+            implicit_affixes: Vec::new(),
+        };
+        let items_store = items_store();
+        let item_specs = item_specs(&[AffixType::Prefix, AffixType::Suffix, AffixType::Unique]);
+
+        let (outcome, corrupted) = corrupt_item(
+            &item_specs,
+            &corruption_table,
+            &items_store,
+            &Vec::new(),
+            &Vec::new(),
+            &Vec::new(),
+        )
+        .unwrap();
+
+        assert_eq!(outcome, CorruptionOutcome::Brick);
+        assert!(corrupted.modifiers.corrupted);
+        assert_eq!(corrupted.modifiers.rarity, ItemRarity::Normal);
+        assert_eq!(corrupted.modifiers.name, "Sword");
+        assert!(
+            corrupted
+                .modifiers
+                .affixes
+                .iter()
+                .all(|affix| affix.affix_type == AffixType::Unique)
+        );
+
+        assert!(
+            corrupt_item(
+                &corrupted,
+                &corruption_table,
+                &items_store,
+                &Vec::new(),
+                &Vec::new(),
+                &Vec::new(),
+            )
+            .is_none()
+        );
+        assert!(
+            affix_operation_price(
+                &ForgeAffixOperation::Add(None),
+                &corrupted.modifiers,
+                &corrupted.base,
+            )
+            .is_none()
+        );
+    }
+
+    fn boss_drops(boss_loot: &BossLootBlueprint, first_kill: bool) -> Vec<String> {
+        let mut items_store = items_store();
+        for (item_id, rarity) in [
+            ("guaranteed", ItemRarity::Normal),
+            ("unique", ItemRarity::Unique),
+            ("first_kill", ItemRarity::Unique),
+        ] {
+            items_store.content.insert(
+                item_id.into(),
+                ItemBase {
+                    name: item_id.into(),
+                    rarity,
+                    ..Default::default()
+                },
+            );
+        }
+
+        let mut drops: Vec<_> = generate_boss_drops(
+            boss_loot,
+            first_kill,
+            &items_store,
+            &Vec::new(),
+            &Vec::new(),
+            &Vec::new(),
+            10,
+            0,
+            100.0,
+            0.0,
+        )
+        .into_iter()
+        .map(|item_specs| item_specs.modifiers.base_item_id)
+        .collect();
+        drops.sort();
+        drops
+    }
+
+    #[test]
+    fn test_boss_drops() {
+        let boss_loot = BossLootBlueprint {
+            guaranteed_drops: vec!["guaranteed".into(), "missing".into()],
+            unique_drops: vec![BossUniqueDrop {
+                item_id: "unique".into(),
+                chance: Chance::new_sure(),
+            }],
+            first_kill_drops: vec!["first_kill".into()],
+            ..Default::default()
+        };
+
+        assert_eq!(boss_drops(&boss_loot, false), ["guaranteed", "unique"]);
+        assert_eq!(
+            boss_drops(&boss_loot, true),
+            ["first_kill", "guaranteed", "unique"]
+        );
+    }
+
+    #[test]
+    fn test_rarity_chances_match_roll_thresholds() {
+        let weights = RarityWeights::default();
+        let total = rarity_total_weight(&weights);
+        let samples = 100_000;
+
+        for loot_template in [&DEFAULT_LOOT_TEMPLATE, &REPEATED_LOOT_TEMPLATE] {
+            for loot_rarity in [0.0, 100.0, 350.0, 100_000.0] {
+                let chances = rarity_chances(loot_template, loot_rarity);
+                let sum: f64 = chances.iter().map(|(_, chance)| chance).sum();
+                assert!((sum - 100.0).abs() < 1e-9);
+
+                // Walk the whole roll range to count how often each rarity is picked
+                let mut picked: HashMap<ItemRarity, usize> = HashMap::new();
+                for i in 0..samples {
+                    let roll = (i as f64 + 0.5) * total / samples as f64;
+                    let mut rarity = pick_rarity(&weights, loot_rarity, roll);
+                    if !loot_template.allow_unique {
+                        rarity = rarity.min(ItemRarity::Rare);
+                    }
+                    *picked.entry(rarity).or_default() += 1;
+                }
+
+                for (rarity, chance) in chances {
+                    let picked_chance = picked.get(&rarity).copied().unwrap_or_default() as f64
+                        * 100.0
+                        / samples as f64;
+                    assert!(
+                        (picked_chance - chance).abs() < 0.01,
+                        "{rarity:?} at {loot_rarity}: {picked_chance} != {chance}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_items_drop_chances_sum_to_hundred() {
+        let mut items_store = items_store();
+        let mut loot_table = LootTable::default();
+        for (item_id, rarity, weight) in [
+            ("sword", ItemRarity::Normal, 10),
+            ("axe", ItemRarity::Normal, 30),
+            ("unique_sword", ItemRarity::Unique, 1),
+        ] {
+            items_store.content.insert(
+                item_id.into(),
+                ItemBase {
+                    name: item_id.into(),
+                    rarity,
+                    ..Default::default()
+                },
+            );
+            loot_table.entries.push(LootTableEntry {
+                item_id: item_id.into(),
+                weight,
+                min_area_level: None,
+                max_area_level: None,
+                boss_only: false,
+            });
+        }
+
+        for loot_rarity in [100.0, 1_000.0] {
+            let drop_chances = base_items_drop_chances(
+                &loot_table,
+                &items_store,
+                &DEFAULT_LOOT_TEMPLATE,
+                10,
+                false,
+                loot_rarity,
+            );
+            let sum: f64 = drop_chances.values().sum();
+            assert!((sum - 100.0).abs() < 1e-9);
+            assert!(drop_chances["axe"] > drop_chances["sword"]);
+        }
+    }
+
+    #[test]
+    fn test_affixes_roll_chances_sum_to_hundred_per_type() {
+        let affix = |family: &str, affix_type, weight, item_level| ItemAffixBlueprint {
+            name: family.into(),
+            family: family.into(),
+            tags: Default::default(),
+            affix_type,
+            tier: 1,
+            weight,
+            restrictions: None,
+            item_level,
+            effects: Vec::new(),
+            triggers: Vec::new(),
+        };
+        let affixes_table = vec![
+            affix("damage", AffixType::Prefix, 100, 1),
+            affix("armor", AffixType::Prefix, 50, 1),
+            affix("life", AffixType::Suffix, 100, 1),
+            affix("mana", AffixType::Suffix, 100, 50),
+        ];
+
+        let chances = affixes_roll_chances(&ItemBase::default(), 10, &affixes_table);
+
+        assert_eq!(chances.len(), 3);
+        for affix_type in [AffixType::Prefix, AffixType::Suffix] {
+            let sum: f64 = chances
+                .iter()
+                .filter(|(affix, _)| affix.affix_type == affix_type)
+                .map(|(_, chance)| chance)
+                .sum();
+            assert!((sum - 100.0).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use shared::data::{
+    area::AreaLevel,
+    character::CharacterId,
+    minion::{MinionSpecs, MinionState},
+    monster::{MonsterSpecs, MonsterState},
+    player::{PlayerSpecs, PlayerState},
+    skill::SkillType,
+};
+
+use crate::game::data::{
+    DataInit,
+    event::{EventsQueue, SummonEvent},
+    master_store::{MonstersSpecsStore, StatusesStore},
+};
+
+use super::{
+    characters_controller::Target, characters_updater, monsters_wave, skills_controller,
+    skills_updater,
+};
+
+/// Spawn a new minion from the summon event.
+/// If the summoning skill already reached its maximum amount of minions, the oldest one is dismissed.
+pub fn summon_minion(
+    monsters_specs_store: &MonstersSpecsStore,
+    statuses_store: &StatusesStore,
+    minion_base_specs: &mut Vec<MinionSpecs>,
+    minion_specs: &mut Vec<MinionSpecs>,
+    minion_states: &mut Vec<MinionState>,
+    summon_event: &SummonEvent,
+    minion_level: AreaLevel,
+) {
+    if summon_event.max_minions == 0 {
+        return;
+    }
+
+    let Some(base_monster_specs) = monsters_specs_store.get(&summon_event.minion_id) else {
+        tracing::error!("missing minion specs '{:?}'", summon_event.minion_id);
+        return;
+    };
+
+    let mut same_skill_minions: Vec<_> = minion_specs
+        .iter()
+        .zip(minion_states.iter_mut())
+        .filter(|(specs, state)| {
+            specs.skill_id == summon_event.skill_id && state.character_state.is_alive
+        })
+        .map(|(_, state)| state)
+        .collect();
+
+    if same_skill_minions.len() >= summon_event.max_minions as usize {
+        // Keep the most recent minions
+        same_skill_minions.sort_by(|a, b| {
+            a.elapsed_lifetime
+                .get()
+                .total_cmp(&b.elapsed_lifetime.get())
+        });
+        for minion_state in same_skill_minions
+            .into_iter()
+            .skip(summon_event.max_minions as usize - 1)
+        {
+            dismiss_minion(minion_state);
+        }
+    }
+
+    let mut character_specs = MonsterSpecs::init(base_monster_specs).character_specs;
+    monsters_wave::scale_character_specs(
+        statuses_store,
+        base_monster_specs,
+        &mut character_specs,
+        minion_level,
+    );
+
+    character_specs = characters_updater::update_character_specs(
+        statuses_store,
+        &Default::default(),
+        &character_specs,
+        &Default::default(),
+        None,
+        summon_event.minion_effects.clone(),
+    );
+
+    let effects = &character_specs.effects;
+    for skill_specs in character_specs.skills_specs.iter_mut() {
+        skills_updater::apply_effects_to_skill_specs(statuses_store, skill_specs, effects.iter());
+    }
+
+    // Reuse slots of dead minions to keep character ids stable
+    let index = minion_states
+        .iter()
+        .position(|minion_state| !minion_state.character_state.is_alive)
+        .unwrap_or(minion_states.len());
+
+    // Minions stand on the player side of the battlefield
+    character_specs.character_static.position_x = 0;
+    character_specs.character_static.position_y = (index % 2 + 1) as u8;
+
+    let specs = MinionSpecs {
+        character_specs,
+        minion_id: summon_event.minion_id.clone(),
+        skill_id: summon_event.skill_id.clone(),
+        lifetime: summon_event.lifetime,
+    };
+    let state = MinionState::init(&specs);
+
+    if index < minion_states.len() {
+        minion_base_specs[index] = specs.clone();
+        minion_specs[index] = specs;
+        minion_states[index] = state;
+    } else {
+        minion_base_specs.push(specs.clone());
+        minion_specs.push(specs);
+        minion_states.push(state);
+    }
+}
+
+pub fn dismiss_minion(minion_state: &mut MinionState) {
+    minion_state.character_state.is_alive = false;
+    minion_state.character_state.life = 0.0.into();
+    minion_state.character_state.mana = 0.0.into();
+    minion_state.character_state.ward = 0.0.into();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn control_minions(
+    statuses_store: &StatusesStore,
+    events_queue: &mut EventsQueue,
+    minion_specs: &[MinionSpecs],
+    minion_states: &mut [MinionState],
+    player_specs: &PlayerSpecs,
+    player_state: &mut PlayerState,
+    monster_specs: &[MonsterSpecs],
+    monster_states: &mut [MonsterState],
+    prevent_attack: bool,
+) {
+    if !player_state.character_state.is_alive {
+        return;
+    }
+
+    for (minion_id, this_minion_specs) in minion_specs.iter().enumerate() {
+        let (left, rest) = minion_states.split_at_mut(minion_id);
+        let (this_minion_state, right) = match rest.split_first_mut() {
+            Some(x) => x,
+            None => continue,
+        };
+
+        if !this_minion_state.character_state.is_alive
+            || this_minion_state.character_state.is_stunned()
+        {
+            continue;
+        }
+
+        let skills_ready: Vec<_> = this_minion_state
+            .character_state
+            .skills_states
+            .iter()
+            .zip(this_minion_specs.character_specs.skills_specs.iter())
+            .enumerate()
+            .filter_map(|(skill_index, (skill_state, skill_specs))| {
+                let prevented = prevent_attack
+                    && matches!(skill_specs.skill_type, SkillType::Attack | SkillType::Spell);
+                (skill_state.cast_state.is_active() || (skill_state.is_ready && !prevented))
+                    .then_some(skill_index)
+            })
+            .collect();
+
+        let mut me = (
+            CharacterId::Minion(minion_id),
+            (
+                &this_minion_specs.character_specs,
+                &mut this_minion_state.character_state,
+            ),
+        );
+
+        let mut friends: Vec<_> = std::iter::once((
+            CharacterId::Player,
+            (
+                &player_specs.character_specs,
+                &mut player_state.character_state,
+            ),
+        ))
+        .chain(
+            left.iter_mut()
+                .enumerate()
+                .chain(
+                    right
+                        .iter_mut()
+                        .enumerate()
+                        .map(|(i, s)| (i + 1 + minion_id, s)),
+                )
+                .filter_map(|(i, s)| {
+                    minion_specs.get(i).map(|specs| {
+                        (
+                            CharacterId::Minion(i),
+                            (&specs.character_specs, &mut s.character_state),
+                        )
+                    })
+                }),
+        )
+        .collect();
+
+        let mut monsters = monster_targets(monster_specs, monster_states);
+
+        skills_controller::repeat_skills(
+            statuses_store,
+            events_queue,
+            &mut me,
+            &mut friends,
+            &mut monsters,
+        );
+
+        for skill_index in skills_ready {
+            skills_controller::use_skill(
+                statuses_store,
+                events_queue,
+                skill_index,
+                &mut me,
+                &mut friends,
+                &mut monsters,
+            );
+        }
+    }
+}
+
+fn monster_targets<'a>(
+    monster_specs: &'a [MonsterSpecs],
+    monster_states: &'a mut [MonsterState],
+) -> Vec<Target<'a>> {
+    monster_specs
+        .iter()
+        .zip(monster_states.iter_mut())
+        .enumerate()
+        .filter(|(_, (_, m))| m.character_state.is_alive)
+        .map(|(i, (x, y))| {
+            (
+                CharacterId::Monster(i),
+                (&x.character_specs, &mut y.character_state),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::data::values::NonNegative;
+
+    use crate::game::data::monster::BaseMonsterSpecs;
+
+    use super::*;
+
+    fn summon_event(skill_id: &str, max_minions: u8) -> SummonEvent {
+        SummonEvent {
+            source: CharacterId::Player,
+            skill_id: skill_id.into(),
+            minion_id: "wolf".into(),
+            lifetime: NonNegative::new(10.0),
+            max_minions,
+            minion_effects: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_minion_cap_dismisses_oldest() {
+        let monsters_specs_store = MonstersSpecsStore::from([(
+            "wolf".to_string(),
+            BaseMonsterSpecs {
+                character_specs: Default::default(),
+                skills: Vec::new(),
+                rarity: Default::default(),
+                status_resistances: Vec::new(),
+            },
+        )]);
+        let statuses_store = StatusesStore::default();
+        let (mut minion_base_specs, mut minion_specs, mut minion_states) =
+            (Vec::new(), Vec::new(), Vec::new());
+
+        let mut summon = |summon_event: &SummonEvent, minion_states: &mut Vec<MinionState>| {
+            // Age the existing minions so they can be told apart
+            for minion_state in minion_states.iter_mut() {
+                minion_state.elapsed_lifetime =
+                    NonNegative::new(minion_state.elapsed_lifetime.get() + 1.0);
+            }
+            summon_minion(
+                &monsters_specs_store,
+                &statuses_store,
+                &mut minion_base_specs,
+                &mut minion_specs,
+                minion_states,
+                summon_event,
+                1,
+            );
+        };
+
+        summon(&summon_event("wolves", 2), &mut minion_states);
+        summon(&summon_event("wolves", 2), &mut minion_states);
+        summon(&summon_event("other", 1), &mut minion_states);
+        summon(&summon_event("wolves", 2), &mut minion_states);
+
+        let alive_lifetimes: Vec<_> = minion_states
+            .iter()
+            .filter(|minion_state| minion_state.character_state.is_alive)
+            .map(|minion_state| minion_state.elapsed_lifetime.get())
+            .collect();
+        // The oldest wolf was dismissed and its slot reused by the new one
+        assert_eq!(minion_states.len(), 3);
+        assert_eq!(alive_lifetimes, vec![0.0, 2.0, 1.0]);
+        assert_eq!(minion_specs[0].skill_id, "wolves");
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use shared::data::{
+    area::AreaThreat,
+    character::CharacterId,
+    minion::{MinionSpecs, MinionState},
+};
+
+use crate::game::{
+    data::{event::EventsQueue, master_store::StatusesStore},
+    systems::triggers_updater,
+};
+
+use super::{characters_updater, minions_controller, skills_updater};
+
+pub fn update_minion_states(
+    statuses_store: &StatusesStore,
+    events_queue: &mut EventsQueue,
+    elapsed_time: Duration,
+    minion_specs: &[MinionSpecs],
+    minion_states: &mut [MinionState],
+    area_threat: &AreaThreat,
+) {
+    for (minion_id, (minion_state, minion_specs)) in minion_states
+        .iter_mut()
+        .zip(minion_specs.iter())
+        .enumerate()
+        .filter(|(_, (s, _))| s.character_state.is_alive)
+    {
+        characters_updater::update_character_state(
+            statuses_store,
+            events_queue,
+            elapsed_time,
+            CharacterId::Minion(minion_id),
+            &minion_specs.character_specs,
+            &mut minion_state.character_state,
+            None,
+            area_threat,
+        );
+
+        // No lifetime means the minion stays until killed
+        minion_state.elapsed_lifetime += elapsed_time.as_secs_f64().into();
+        if minion_specs.lifetime.get() > 0.0
+            && minion_state.elapsed_lifetime.get() >= minion_specs.lifetime.get()
+        {
+            minions_controller::dismiss_minion(minion_state);
+        }
+    }
+}
+
+pub fn reset_minions(minion_states: &mut [MinionState]) {
+    for minion_state in minion_states.iter_mut() {
+        characters_updater::reset_character(&mut minion_state.character_state);
+    }
+}
+
+pub fn update_minion_specs(
+    statuses_store: &StatusesStore,
+    character_id: CharacterId,
+    base_specs: &MinionSpecs,
+    minion_specs: &mut MinionSpecs,
+    minion_state: &MinionState,
+    area_threat: &AreaThreat,
+) {
+    minion_specs.character_specs = characters_updater::update_character_specs(
+        statuses_store,
+        area_threat,
+        &base_specs.character_specs,
+        &minion_state.character_state,
+        None,
+        Vec::new(),
+    );
+
+    let effects = &minion_specs.character_specs.effects;
+
+    for skill_specs in minion_specs.character_specs.skills_specs.iter_mut() {
+        skills_updater::apply_effects_to_skill_specs(statuses_store, skill_specs, effects.iter());
+    }
+
+    for trigger_effect in minion_specs.character_specs.triggers.effects_iter_mut() {
+        triggers_updater::compute_trigger_specs_effects(
+            statuses_store,
+            trigger_effect,
+            effects.iter(),
+        );
+    }
+
+    characters_updater::extend_triggers_from_skills_and_statuses(
+        statuses_store,
+        character_id,
+        &mut minion_specs.character_specs,
+        &minion_state.character_state,
+    );
+}
@@ -1,12 +1,18 @@
 pub mod area_controller;
+pub mod atlas_controller;
 pub mod benedictions_controller;
+pub mod challenges_controller;
 pub mod characters_controller;
 pub mod characters_updater;
 pub mod events_resolver;
 pub mod inventory_controller;
 pub mod items_controller;
+pub mod legacy_items_controller;
 pub mod loot_controller;
+pub mod loot_explainer;
 pub mod loot_generator;
+pub mod minions_controller;
+pub mod minions_updater;
 pub mod monsters_controller;
 pub mod monsters_updater;
 pub mod monsters_wave;
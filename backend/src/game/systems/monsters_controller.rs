@@ -1,5 +1,6 @@
 use shared::data::{
     character::CharacterId,
+    minion::{MinionSpecs, MinionState},
     monster::{MonsterSpecs, MonsterState},
     player::{PlayerSpecs, PlayerState},
 };
@@ -8,6 +9,7 @@ use crate::game::data::{event::EventsQueue, master_store::StatusesStore};
 
 use super::skills_controller;
 
+#[allow(clippy::too_many_arguments)]
 pub fn control_monsters(
     statuses_store: &StatusesStore,
     events_queue: &mut EventsQueue,
@@ -15,6 +17,8 @@ pub fn control_monsters(
     monster_states: &mut [MonsterState],
     player_specs: &PlayerSpecs,
     player_state: &mut PlayerState,
+    minion_specs: &[MinionSpecs],
+    minion_states: &mut [MinionState],
 ) {
     if !player_state.character_state.is_alive {
         return;
@@ -41,7 +45,7 @@ pub fn control_monsters(
             .character_state
             .skills_states
             .iter()
-            .any(|skill_state| skill_state.is_ready)
+            .any(|skill_state| skill_state.is_ready || skill_state.cast_state.is_active())
             && !this_monster_state
                 .character_state
                 .repeated_skills
@@ -57,7 +61,7 @@ pub fn control_monsters(
             .iter()
             .enumerate()
             .filter_map(|(skill_index, skill_state)| {
-                if skill_state.is_ready {
+                if skill_state.is_ready || skill_state.cast_state.is_active() {
                     Some(skill_index)
                 } else {
                     None
@@ -92,20 +96,34 @@ pub fn control_monsters(
             })
             .collect();
 
-        let mut player = [(
+        let mut enemies: Vec<_> = std::iter::once((
             CharacterId::Player,
             (
                 &player_specs.character_specs,
                 &mut player_state.character_state,
             ),
-        )];
+        ))
+        .chain(
+            minion_specs
+                .iter()
+                .zip(minion_states.iter_mut())
+                .enumerate()
+                .filter(|(_, (_, m))| m.character_state.is_alive)
+                .map(|(i, (x, y))| {
+                    (
+                        CharacterId::Minion(i),
+                        (&x.character_specs, &mut y.character_state),
+                    )
+                }),
+        )
+        .collect();
 
         skills_controller::repeat_skills(
             statuses_store,
             events_queue,
             &mut me,
             &mut friends,
-            &mut player,
+            &mut enemies,
         );
 
         for skill_index in skills_ready {
@@ -115,7 +133,7 @@ pub fn control_monsters(
                 skill_index,
                 &mut me,
                 &mut friends,
-                &mut player,
+                &mut enemies,
             );
         }
     }
@@ -10,6 +10,7 @@ use shared::{
         area::{AreaLevel, AreaSpecs, AreaState},
         modifier::Modifier,
         monster::{MonsterRarity, MonsterSpecs, MonsterState},
+        player::CharacterSpecs,
         stat_effect::{StatEffect, StatType},
     },
 };
@@ -22,7 +23,7 @@ use crate::game::{
         monster::BaseMonsterSpecs,
     },
     systems::characters_updater,
-    utils::rng::{self, RandomWeighted, Rollable},
+    utils::rng::{self, RandomWeighted, RngSeed, Rollable},
 };
 
 use super::skills_updater;
@@ -39,7 +40,8 @@ impl RandomWeighted for &BossBlueprint {
     }
 }
 
-/// Return generated monsters
+/// Return generated monsters, with the boss blueprint if it is a boss wave.
+/// If a wave seed is given, the generation only depends on it and the area progress.
 pub fn generate_monsters_wave(
     monsters_specs_store: &MonstersSpecsStore,
     statuses_store: &StatusesStore,
@@ -47,41 +49,47 @@ pub fn generate_monsters_wave(
     bosses: &[BossBlueprint],
     area_specs: &AreaSpecs,
     area_state: &mut AreaState,
-) -> Result<(Vec<MonsterSpecs>, Vec<MonsterState>)> {
-    let (monster_specs, is_boss) = generate_monsters_wave_specs(
+    wave_seed: Option<u64>,
+) -> Result<(Vec<MonsterSpecs>, Vec<MonsterState>, Option<BossBlueprint>)> {
+    let mut seed = match wave_seed {
+        Some(wave_seed) => rng::fixed_seed(
+            wave_seed ^ ((area_state.area_level as u64) << 8) ^ area_state.waves_done as u64,
+        ),
+        None => rng::roll_seed(),
+    };
+
+    let (monster_specs, boss) = generate_monsters_wave_specs(
         monsters_specs_store,
         statuses_store,
         waves,
         bosses,
         area_specs,
         area_state,
+        &mut seed,
     )?;
     let monster_states = monster_specs.iter().map(MonsterState::init).collect();
-    area_state.is_boss = is_boss;
-    Ok((monster_specs, monster_states))
+    area_state.is_boss = boss.is_some();
+    Ok((monster_specs, monster_states, boss.cloned()))
 }
 
-/// Return generated monsters + if it is boss
-fn generate_monsters_wave_specs(
+/// Return generated monsters + boss blueprint if it is boss
+fn generate_monsters_wave_specs<'a>(
     monsters_specs_store: &MonstersSpecsStore,
     statuses_store: &StatusesStore,
     waves: &[MonsterWaveBlueprint],
-    bosses: &[BossBlueprint],
+    bosses: &'a [BossBlueprint],
     area_specs: &AreaSpecs,
     area_state: &mut AreaState,
-) -> Result<(Vec<MonsterSpecs>, bool)> {
+    seed: &mut RngSeed,
+) -> Result<(Vec<MonsterSpecs>, Option<&'a BossBlueprint>)> {
     // Can only fight boss once per level
     if area_state.max_area_level < area_state.area_level || area_specs.training {
         let available_bosses: Vec<_> = bosses
             .iter()
-            .filter(|b| {
-                area_state.area_level >= b.level
-                    && (area_state.area_level - b.level)
-                        .is_multiple_of(b.interval.unwrap_or(AreaLevel::MAX))
-            })
+            .filter(|b| b.is_available(area_state.area_level))
             .collect();
 
-        if let Some(boss) = rng::random_weighted_pick(&available_bosses) {
+        if let Some(boss) = rng::random_weighted_pick_with_seed(&available_bosses, seed) {
             return Ok((
                 generate_all_monsters_specs(
                     monsters_specs_store,
@@ -89,8 +97,9 @@ fn generate_monsters_wave_specs(
                     area_specs,
                     area_state,
                     &boss.spawns,
+                    seed,
                 ),
-                true,
+                Some(*boss),
             ));
         }
     }
@@ -103,7 +112,7 @@ fn generate_monsters_wave_specs(
         })
         .collect();
 
-    if let Some(wave) = rng::random_weighted_pick(&available_waves) {
+    if let Some(wave) = rng::random_weighted_pick_with_seed(&available_waves, seed) {
         return Ok((
             generate_all_monsters_specs(
                 monsters_specs_store,
@@ -111,8 +120,9 @@ fn generate_monsters_wave_specs(
                 area_specs,
                 area_state,
                 &wave.spawns,
+                seed,
             ),
-            false,
+            None,
         ));
     }
 
@@ -125,6 +135,7 @@ fn generate_all_monsters_specs(
     area_specs: &AreaSpecs,
     area_state: &mut AreaState,
     spawns: &[MonsterWaveSpawnBlueprint],
+    seed: &mut RngSeed,
 ) -> Vec<MonsterSpecs> {
     let mut grid = [[true; 3]; 2];
     let mut monsters = Vec::with_capacity(6);
@@ -135,7 +146,7 @@ fn generate_all_monsters_specs(
             continue;
         };
 
-        for _ in 0..spawn.quantity.roll() {
+        for _ in 0..spawn.quantity.roll_with_seed(seed) {
             if let Some((x, y)) = find_free_slot(
                 &grid,
                 base_monster_specs
@@ -159,6 +170,7 @@ fn generate_all_monsters_specs(
                     area_specs,
                     area_state,
                     base_monster_specs,
+                    seed,
                 );
                 specs.character_specs.character_static.position_x = (x + 1) as u8;
                 specs.character_specs.character_static.position_y = (y + 1) as u8;
@@ -206,6 +218,7 @@ fn generate_monster_specs(
     area_specs: &AreaSpecs,
     area_state: &mut AreaState,
     base_monster_specs: &BaseMonsterSpecs,
+    seed: &mut RngSeed,
 ) -> MonsterSpecs {
     let mut monster_specs = MonsterSpecs::init(base_monster_specs);
     let mut monster_level = area_state.area_level + *area_specs.power_level;
@@ -214,7 +227,8 @@ fn generate_monster_specs(
     monster_specs.character_specs.triggers = area_specs.triggers.clone();
 
     if monster_specs.rarity == MonsterRarity::Normal
-        && rng::random_range(0.0..=1.0).unwrap_or(1.0) < computations::gem_chance(area_state)
+        && rng::random_range_with_seed(0.0..=1.0, seed).unwrap_or(1.0)
+            < computations::gem_chance(area_state) * *area_specs.champion_chance * 0.01
     {
         // area_state.last_champion_spawn = area_state.area_level;
         monster_specs.rarity = MonsterRarity::Champion;
@@ -232,14 +246,47 @@ fn generate_monster_specs(
         area_state.area_level + *area_specs.power_level + *area_specs.item_level_modifier,
         SKILL_REWARD_INCREASE_FACTOR,
     );
+    scale_character_specs(
+        statuses_store,
+        base_monster_specs,
+        &mut monster_specs.character_specs,
+        monster_level,
+    );
+
+    monster_specs.character_specs = characters_updater::update_character_specs(
+        statuses_store,
+        &Default::default(),
+        &monster_specs.character_specs,
+        &Default::default(),
+        None,
+        (&area_specs.effects).into(),
+    );
+
+    let effects = &monster_specs.character_specs.effects;
+    for skill_specs in monster_specs.character_specs.skills_specs.iter_mut() {
+        skills_updater::apply_effects_to_skill_specs(statuses_store, skill_specs, effects.iter());
+    }
+
     monster_specs
-        .character_specs
+}
+
+/// Scale life, ward and skills of a character spawned from monster specs to the given level
+pub fn scale_character_specs(
+    statuses_store: &StatusesStore,
+    base_monster_specs: &BaseMonsterSpecs,
+    character_specs: &mut CharacterSpecs,
+    monster_level: AreaLevel,
+) {
+    let life_increase =
+        (computations::exponential(monster_level, MONSTER_LIFE_INCREASE_FACTOR) - 1.0) * 100.0;
+    character_specs
         .character_attrs
         .max_life
-        .apply_modifier(
-            (computations::exponential(monster_level, MONSTER_LIFE_INCREASE_FACTOR) - 1.0) * 100.0,
-            Modifier::More,
-        );
+        .apply_modifier(life_increase, Modifier::More);
+    character_specs
+        .character_attrs
+        .max_ward
+        .apply_modifier(life_increase, Modifier::More);
 
     // Apply upgrade effects
     let upgrade_effects = [StatEffect {
@@ -254,7 +301,7 @@ fn generate_monster_specs(
         bypass_ignore: true,
     }];
 
-    monster_specs.character_specs.skills_specs = base_monster_specs
+    character_specs.skills_specs = base_monster_specs
         .skills
         .iter()
         .map(|base_skill_specs| {
@@ -265,7 +312,7 @@ fn generate_monster_specs(
                     base_skill_specs,
                     1,
                     &upgrade_effects,
-                    &monster_specs.character_specs.character_attrs,
+                    &character_specs.character_attrs,
                     None,
                     None,
                 )
@@ -281,27 +328,11 @@ fn generate_monster_specs(
                     base_skill_specs,
                     1,
                     &effects,
-                    &monster_specs.character_specs.character_attrs,
+                    &character_specs.character_attrs,
                     None,
                     None,
                 )
             }
         })
         .collect();
-
-    monster_specs.character_specs = characters_updater::update_character_specs(
-        statuses_store,
-        &Default::default(),
-        &monster_specs.character_specs,
-        &Default::default(),
-        None,
-        (&area_specs.effects).into(),
-    );
-
-    let effects = &monster_specs.character_specs.effects;
-    for skill_specs in monster_specs.character_specs.skills_specs.iter_mut() {
-        skills_updater::apply_effects_to_skill_specs(statuses_store, skill_specs, effects.iter());
-    }
-
-    monster_specs
 }
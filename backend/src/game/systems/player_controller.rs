@@ -62,6 +62,7 @@ impl PlayerController {
         player_specs: &'a PlayerSpecs,
         player_inventory: &PlayerInventory,
         player_state: &'a mut PlayerState,
+        minions: &mut [Target<'a>],
         monsters: &mut [Target<'a>],
         prevent_attack: bool,
     ) {
@@ -101,13 +102,11 @@ impl PlayerController {
             ),
         );
 
-        let mut friends = vec![];
-
         skills_controller::repeat_skills(
             statuses_store,
             events_queue,
             &mut player,
-            &mut friends,
+            minions,
             monsters,
         );
 
@@ -150,11 +149,20 @@ impl PlayerController {
             .take(player_base_specs.max_skills as usize)
             .enumerate()
         {
+            // Skills being cast or channelled must keep going until they resolve
+            let is_casting = player
+                .1
+                .1
+                .skills_states
+                .get(i)
+                .is_some_and(|skill_state| skill_state.cast_state.is_active());
+
             // Always keep enough mana for a manual trigger, could be optional
-            if (!self.auto_skills.read().get(i).unwrap_or(&false)
-                || no_auto_use
-                || (skill_specs.mana_cost.get() > 0.0
-                    && mana_available.get() < min_mana_needed + skill_specs.mana_cost.get()))
+            if !is_casting
+                && (!self.auto_skills.read().get(i).unwrap_or(&false)
+                    || no_auto_use
+                    || (skill_specs.mana_cost.get() > 0.0
+                        && mana_available.get() < min_mana_needed + skill_specs.mana_cost.get()))
                 && !self.use_skills.contains(&i)
             {
                 continue;
@@ -165,7 +173,7 @@ impl PlayerController {
                 events_queue,
                 i,
                 &mut player,
-                &mut friends,
+                minions,
                 monsters,
             );
         }
@@ -26,7 +26,7 @@ use crate::game::{
     data::{
         DataInit,
         event::EventsQueue,
-        master_store::{SkillMasteriesStore, StatusesStore},
+        master_store::{ItemSetsStore, SkillMasteriesStore, StatusesStore},
     },
     systems::{skill_masteries_controller, triggers_updater},
 };
@@ -40,6 +40,8 @@ pub fn base_player_character_attrs(level: u8) -> CharacterAttrs {
         life_regen: 10.0.into(),
         max_mana: NonNegative::new(100.0).into(),
         mana_regen: 10.0.into(),
+        ward_recharge: 20.0.into(),
+        ward_recharge_delay: NonNegative::new(2.0).into(),
         ..Default::default()
     }
 }
@@ -111,6 +113,7 @@ pub fn reset_player(player_state: &mut PlayerState) {
 pub fn update_player_specs(
     skill_masteries_store: &SkillMasteriesStore,
     statuses_store: &StatusesStore,
+    item_sets_store: &ItemSetsStore,
     player_base_specs: &PlayerBaseSpecs,
     // player_specs: &PlayerSpecs,
     player_state: &PlayerState,
@@ -120,6 +123,17 @@ pub fn update_player_specs(
     area_specs: &AreaSpecs,
     area_threat: &AreaThreat,
 ) -> PlayerSpecs {
+    let item_set_bonuses: Vec<_> = player_inventory
+        .equipped_item_sets()
+        .into_iter()
+        .filter_map(|(item_set_id, pieces)| {
+            item_sets_store
+                .get(&item_set_id)
+                .map(|item_set| (item_set, pieces))
+        })
+        .flat_map(|(item_set, pieces)| item_set.active_bonuses(pieces))
+        .collect();
+
     let effects: Vec<_> = [
         player_base_specs.effects.clone(),
         player_inventory
@@ -130,6 +144,11 @@ pub fn update_player_specs(
                     .into_iter()
             })
             .collect(),
+        item_set_bonuses
+            .iter()
+            .flat_map(|bonus| bonus.effects.iter().cloned())
+            .collect(),
+        (&area_specs.player_effects).into(),
         passive::generate_effects_fom_passives(
             passives_tree_specs,
             &passives_tree_state.ascension,
@@ -162,7 +181,20 @@ pub fn update_player_specs(
         .chain(
             player_inventory
                 .equipped_items()
-                .flat_map(|(_, item_specs)| item_specs.base.triggers.iter()),
+                .flat_map(|(_, item_specs)| {
+                    item_specs.base.triggers.iter().chain(
+                        item_specs
+                            .modifiers
+                            .affixes
+                            .iter()
+                            .flat_map(|affix| affix.triggers.iter()),
+                    )
+                }),
+        )
+        .chain(
+            item_set_bonuses
+                .iter()
+                .flat_map(|bonus| bonus.triggers.iter()),
         )
     {
         player_specs.character_specs.triggers.push(
@@ -364,6 +396,9 @@ fn modify_player_specs(
             | StatType::LifeRegen
             | StatType::Mana
             | StatType::ManaRegen
+            | StatType::Ward
+            | StatType::WardRecharge
+            | StatType::WardRechargeDelay
             | StatType::Armor(_)
             | StatType::TakeFromManaBeforeLife
             | StatType::TakeFromLifeBeforeMana
@@ -405,6 +440,10 @@ fn modify_player_specs(
             | StatType::SkillTargetModifier { .. }
             | StatType::SkillRepeat { .. }
             | StatType::SkillEffectModifier { .. }
+            | StatType::DamageConversion { .. }
+            | StatType::MinionLifetime(_)
+            | StatType::MaxMinions(_)
+            | StatType::MinionModifier { .. }
             | StatType::SkillConditionalModifier { .. }
             | StatType::TriggerEffectModifier { .. } => {}
             // Other
@@ -412,6 +451,7 @@ fn modify_player_specs(
             | StatType::ItemAreaChance
             | StatType::ItemLevel
             | StatType::GemsFind
+            | StatType::ChampionChance
             | StatType::PowerLevel
             | StatType::Description(_)
             | StatType::Description2(_) => {}
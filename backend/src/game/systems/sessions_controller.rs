@@ -20,6 +20,7 @@ use shared::{
 use crate::{
     db::{self, characters::CharacterEntry},
     game::{
+        challenges::ChallengesStore,
         data::{
             DataInit, inventory_data::inventory_data_to_player_inventory,
             master_store::MasterStore, passives::ascension_data_to_passives_tree_ascension,
@@ -27,8 +28,8 @@ use crate::{
         game_data::GameInstanceData,
         sessions::{Session, SessionsStore},
         systems::{
-            benedictions_controller, inventory_controller, player_controller::PlayerController,
-            player_updater,
+            benedictions_controller, challenges_controller, inventory_controller,
+            player_controller::PlayerController, player_updater,
         },
     },
     rest::AppError,
@@ -39,6 +40,7 @@ use super::{loot_generator, player_controller};
 pub async fn create_session(
     db_pool: &db::DbPool,
     sessions_store: &SessionsStore,
+    challenges_store: &ChallengesStore,
     master_store: &MasterStore,
     character: CharacterEntry,
     allow_parallel_characters: bool,
@@ -79,7 +81,15 @@ pub async fn create_session(
     {
         saved_instance
     } else {
-        match new_game_instance(db_pool, master_store, character, area_config).await {
+        match new_game_instance(
+            db_pool,
+            challenges_store,
+            master_store,
+            character,
+            area_config,
+        )
+        .await
+        {
             Ok(instance) => instance,
             Err(err) => {
                 db::game_sessions::end_session(db_pool, &character_id).await?;
@@ -126,14 +136,36 @@ async fn load_game_instance(
 
 async fn new_game_instance(
     db_pool: &db::DbPool,
+    challenges_store: &ChallengesStore,
     master_store: &MasterStore,
     character: CharacterEntry,
     area_config: Option<StartAreaConfig>,
 ) -> Result<GameInstanceData> {
-    let area_config = area_config.ok_or(anyhow::anyhow!("missing area id"))?;
+    let mut area_config = area_config.ok_or(anyhow::anyhow!("missing area id"))?;
+
+    let (challenge, challenge_map_item) = match &area_config.challenge_id {
+        Some(schedule_id) => {
+            let challenge = challenges_store
+                .get(schedule_id)
+                .ok_or(anyhow::anyhow!("challenge not found"))?;
+            let challenge_map_item =
+                challenges_controller::generate_challenge_map(master_store, &challenge)
+                    .ok_or(anyhow::anyhow!("missing challenge map item"))?;
+            area_config.area_id = challenge.specs.area_id.clone();
+            area_config.map_item_index = None;
+            (Some(challenge), Some(challenge_map_item))
+        }
+        None => (None, None),
+    };
 
     let character_data =
         db::characters_data::load_character_data(db_pool, &character.character_id).await?;
+    // Challenges are the same for every player, so atlas modifiers don't apply
+    let atlas_state = if challenge.is_some() {
+        Default::default()
+    } else {
+        db::characters_data::load_character_atlas(db_pool, &character.character_id).await?
+    };
 
     let (max_area_level_ever, max_power_shard_level_ever) =
         db::characters::read_character_area_completed(
@@ -188,7 +220,8 @@ async fn new_game_instance(
                             &master_store.item_adjectives_table,
                             &master_store.item_nouns_table,
                             false,
-                            0.0, // &master_store.items_store.signature_key,
+                            0.0,
+                            &master_store.items_store.signature_key,
                         ),
                     );
                 }
@@ -258,7 +291,7 @@ async fn new_game_instance(
                 return Err(anyhow::anyhow!("missing map item"));
             }
         }
-        None => None,
+        None => challenge_map_item,
     };
 
     let area_id = map_item
@@ -277,6 +310,7 @@ async fn new_game_instance(
         max_power_shard_level_ever as AreaLevel,
         "default",
         passives_tree_state,
+        atlas_state,
         player_resources,
         player_base_specs,
         player_inventory,
@@ -284,6 +318,8 @@ async fn new_game_instance(
         player_controller,
     )?;
 
+    game_data.challenge = challenge;
+
     if game_data.area_specs.coming_soon {
         return Err(anyhow!("forbidden area"));
     }
@@ -314,6 +350,18 @@ async fn new_game_instance(
     Ok(game_data)
 }
 
+/// Wait for all game instances to exit, return false on timeout
+pub async fn wait_active_sessions(sessions_store: &SessionsStore, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while !sessions_store.active_sessions.is_empty() {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    true
+}
+
 pub async fn save_all_sessions(db_pool: &db::DbPool, sessions_store: &SessionsStore) -> Result<()> {
     let sessions = sessions_store
         .sessions
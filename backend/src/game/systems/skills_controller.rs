@@ -10,15 +10,18 @@ use shared::{
         item::{SkillRange, SkillShape},
         player::{PlayerBaseSkill, PlayerResources},
         skill::{
-            RepeatedSkillEffect, RestoreType, SkillEffect, SkillEffectType, SkillRepeatTarget,
-            SkillTargetsGroup, SkillType, TargetType,
+            RepeatedSkillEffect, RestoreType, SkillCastState, SkillEffect, SkillEffectType,
+            SkillRepeatTarget, SkillTargetsGroup, SkillType, TargetType,
         },
         values::NonNegative,
     },
 };
 
 use crate::game::{
-    data::{event::EventsQueue, master_store::StatusesStore},
+    data::{
+        event::{EventsQueue, GameEvent, SummonEvent},
+        master_store::StatusesStore,
+    },
     systems::{skills_updater, stats_updater},
     utils::rng::{self, RngSeed, Rollable, flip_coin},
 };
@@ -40,13 +43,64 @@ pub fn use_skill<'a>(
 
     let skill_specs = me.1.0.skills_specs.get(skill_index).unwrap();
 
+    // Only one skill can be cast or channelled at a time
+    if me
+        .1
+        .1
+        .skills_states
+        .iter()
+        .enumerate()
+        .any(|(index, skill_state)| index != skill_index && skill_state.cast_state.is_active())
     {
-        let skill_state = me.1.1.skills_states.get(skill_index).unwrap();
-        if !skill_state.is_ready || me.1.1.mana.get() < skill_specs.mana_cost.get() {
-            return me.1.1.mana;
-        }
+        return me.1.1.mana;
     }
 
+    let cast_state = {
+        let skill_state = me.1.1.skills_states.get_mut(skill_index).unwrap();
+        let enough_mana = me.1.1.mana.get() >= skill_specs.mana_cost.get();
+
+        match skill_state.cast_state {
+            SkillCastState::Idle => {
+                if !skill_state.is_ready || !enough_mana {
+                    return me.1.1.mana;
+                }
+                if skill_specs.cast_time.get() > 0.0 {
+                    skill_state.cast_state = SkillCastState::Casting {
+                        elapsed: Default::default(),
+                    };
+                    return me.1.1.mana;
+                }
+            }
+            SkillCastState::Casting { elapsed } => {
+                if !enough_mana {
+                    skill_state.cast_state = SkillCastState::Idle;
+                    return me.1.1.mana;
+                }
+                if elapsed.get() < skill_specs.cast_time.get() {
+                    return me.1.1.mana;
+                }
+            }
+            SkillCastState::Channelling {
+                elapsed,
+                elapsed_tick,
+            } => {
+                let Some(channel) = skill_specs.channel else {
+                    skill_state.cast_state = SkillCastState::Idle;
+                    return me.1.1.mana;
+                };
+                if !enough_mana || elapsed.get() >= channel.duration.get() {
+                    skill_state.cast_state = SkillCastState::Idle;
+                    return me.1.1.mana;
+                }
+                if elapsed_tick.get() < channel.tick_rate.get() {
+                    return me.1.1.mana;
+                }
+            }
+        }
+
+        skill_state.cast_state
+    };
+
     let mut applied = false;
 
     // Same seed for same amount of repeats
@@ -66,13 +120,32 @@ pub fn use_skill<'a>(
         );
     }
 
-    if applied {
-        characters_controller::spend_mana(&me.1.0.character_attrs, me.1.1, *skill_specs.mana_cost);
+    let skill_state = me.1.1.skills_states.get_mut(skill_index).unwrap();
+    match cast_state {
+        SkillCastState::Channelling { elapsed, .. } => {
+            skill_state.cast_state = SkillCastState::Channelling {
+                elapsed,
+                elapsed_tick: Default::default(),
+            };
+        }
+        _ if applied => {
+            skill_state.is_ready = false;
+            skill_state.elapsed_cooldown = Default::default();
+            skill_state.cast_state = match skill_specs.channel {
+                Some(_) => SkillCastState::Channelling {
+                    elapsed: Default::default(),
+                    elapsed_tick: Default::default(),
+                },
+                None => SkillCastState::Idle,
+            };
+        }
+        // No valid target at the end of the cast, keep the skill ready for later
+        _ => skill_state.cast_state = SkillCastState::Idle,
+    }
 
-        let skill_state = me.1.1.skills_states.get_mut(skill_index).unwrap();
+    if applied {
         skill_state.just_triggered = true;
-        skill_state.is_ready = false;
-        skill_state.elapsed_cooldown = Default::default();
+        characters_controller::spend_mana(&me.1.0.character_attrs, me.1.1, *skill_specs.mana_cost);
     }
 
     characters_controller::mana_available(&me.1.0.character_attrs, me.1.1)
@@ -465,6 +538,9 @@ fn is_skill_effect_applicable_on_target(skill_effect: &SkillEffect, target: &Tar
         SkillEffectType::RefreshStatus { .. } => {
             target.1.1.is_alive // TODO: Actually verify skills
         }
+        SkillEffectType::Cleanse { .. } => {
+            target.1.1.is_alive && !target.1.1.statuses.is_empty() // TODO: Actually verify statuses
+        }
         SkillEffectType::Restore { restore_type, .. } => {
             target.1.1.is_alive
                 && match restore_type {
@@ -481,6 +557,7 @@ fn is_skill_effect_applicable_on_target(skill_effect: &SkillEffect, target: &Tar
         SkillEffectType::RefreshCooldown { .. } => {
             target.1.1.is_alive // TODO: Actually verify skills
         }
+        SkillEffectType::Summon { .. } => target.1.1.is_alive,
     }
 }
 
@@ -588,6 +665,16 @@ fn apply_skill_effect_on_target(
             value.roll_with_seed(seed),
             modifier,
         ),
+        SkillEffectType::Cleanse { status_filter } => characters_controller::cleanse_statuses(
+            statuses_store,
+            events_queue,
+            target,
+            attacker,
+            status_filter,
+            skill_type,
+            skill_id,
+            trigger_depth,
+        ),
         SkillEffectType::Restore {
             restore_type,
             value,
@@ -615,6 +702,23 @@ fn apply_skill_effect_on_target(
             value.roll_with_seed(seed),
             modifier,
         ),
+        SkillEffectType::Summon {
+            minion_id,
+            minion_name: _,
+            lifetime,
+            max_minions,
+            minion_effects,
+        } => {
+            events_queue.register_event(GameEvent::Summoned(SummonEvent {
+                source: attacker,
+                skill_id: skill_id.to_string(),
+                minion_id: minion_id.clone(),
+                lifetime: **lifetime,
+                max_minions: **max_minions,
+                minion_effects: minion_effects.clone(),
+            }));
+            true
+        }
     }
 }
 
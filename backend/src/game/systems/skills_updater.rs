@@ -1,4 +1,7 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 use strum::IntoEnumIterator;
 
 use shared::data::{
@@ -12,7 +15,7 @@ use shared::data::{
     player::PlayerInventory,
     skill::{
         BaseSkillSpecs, DamageType, ItemStatsSource, ModifierEffect, ModifierEffectSource,
-        RepeatedSkillEffect, SkillEffect, SkillEffectType, SkillSpecs, SkillState,
+        RepeatedSkillEffect, SkillCastState, SkillEffect, SkillEffectType, SkillSpecs, SkillState,
         SkillTargetsGroup, SkillType,
     },
     skill_mastery::{
@@ -41,6 +44,26 @@ pub fn update_skills_states(
                 (elapsed_time.as_secs_f64() / skill_specs.cooldown.get()).into();
             skill_state.is_ready = skill_state.elapsed_cooldown.get() >= 1.0;
         }
+
+        match &mut skill_state.cast_state {
+            SkillCastState::Idle => {}
+            SkillCastState::Casting { elapsed } => {
+                *elapsed += elapsed_time.as_secs_f64().into();
+            }
+            SkillCastState::Channelling {
+                elapsed,
+                elapsed_tick,
+            } => {
+                *elapsed += elapsed_time.as_secs_f64().into();
+                *elapsed_tick += elapsed_time.as_secs_f64().into();
+            }
+        }
+    }
+}
+
+pub fn interrupt_skills(skills_states: &mut [SkillState]) {
+    for skill_state in skills_states.iter_mut() {
+        skill_state.cast_state = SkillCastState::Idle;
     }
 }
 
@@ -106,6 +129,8 @@ pub fn update_skill_specs(
         ignore_stat_effects: base_skill_specs.ignore_stat_effects.clone(),
         cooldown: base_skill_specs.cooldown.into(),
         mana_cost: base_skill_specs.mana_cost.into(),
+        cast_time: base_skill_specs.cast_time.into(),
+        channel: base_skill_specs.channel,
         targets: apply_weapon_effects(base_skill_specs.targets.clone(), inventory),
         triggers: base_skill_specs.triggers.clone(),
         level_modifier,
@@ -374,6 +399,10 @@ pub fn apply_effects_to_skill_specs<'a>(
             && skill_filter.is_match_with_skill(skill_specs.skill_type, &skill_specs.skill_id)
         {
             skill_specs.cooldown.apply_negative_effect(effect);
+            skill_specs.cast_time.apply_negative_effect(effect);
+            if let Some(channel) = skill_specs.channel.as_mut() {
+                channel.tick_rate.apply_negative_effect(effect);
+            }
         }
 
         if let StatType::ManaCost { skill_filter } = &effect.stat
@@ -938,6 +967,14 @@ pub fn compute_skill_specs_effect_with_extra<'a, 'b>(
         );
     }
 
+    apply_damage_conversions(
+        skill_id,
+        skill_type,
+        skill_effect,
+        effects.clone(),
+        extra_effects.clone(),
+    );
+
     compute_status_triggers_for_skill_effect(
         statuses_store,
         skill_effect,
@@ -948,6 +985,78 @@ pub fn compute_skill_specs_effect_with_extra<'a, 'b>(
     stats_converted
 }
 
+/// Convert hit damage from one type to another, once all other damage effects were applied.
+/// Damage can only be converted toward a later damage type (Physical -> Fire -> Storm),
+/// so that converted damage can be converted again but never loop back.
+/// Converted damage is taken from the base value and scaled by the modifiers of its new type.
+fn apply_damage_conversions<'a, 'b>(
+    skill_id: &String,
+    skill_type: SkillType,
+    skill_effect: &mut SkillEffect,
+    effects: impl Iterator<Item = &'a StatEffect>,
+    extra_effects: impl Iterator<Item = &'b StatEffect>,
+) {
+    let mut conversions: BTreeMap<(DamageType, bool, DamageType), f64> = Default::default();
+    let mut add_conversion = |effect: &StatEffect| {
+        if let StatType::DamageConversion {
+            skill_filter,
+            from,
+            to,
+            is_extra,
+        } = &effect.stat
+            && effect.modifier == Modifier::Flat
+            && from < to
+            && *to != DamageType::Poison
+            && skill_filter.is_match_with_skill(skill_type, skill_id)
+            && (effect.bypass_ignore
+                || skill_effect
+                    .ignore_stat_effects
+                    .iter()
+                    .all(|ignore| !effect.stat.is_match(ignore)))
+        {
+            *conversions.entry((*from, *is_extra, *to)).or_default() += effect.value;
+        }
+    };
+    effects.for_each(&mut add_conversion);
+    extra_effects.for_each(&mut add_conversion);
+
+    if conversions.is_empty() {
+        return;
+    }
+
+    let SkillEffectType::FlatDamage {
+        damage,
+        damage_factors,
+        ..
+    } = &mut skill_effect.effect_type
+    else {
+        return;
+    };
+
+    // Sorted by source damage type, so damage converted to a type can be converted further
+    for ((from, is_extra, to), percent) in conversions {
+        let Some(source) = damage.get_mut(&from) else {
+            continue;
+        };
+
+        let converted_min = source.min.convert_value(percent, is_extra, true);
+        let converted_max = source.max.convert_value(percent, is_extra, true);
+
+        let target = damage.entry(to).or_default();
+        target
+            .min
+            .apply_modifier(converted_min.get(), Modifier::Flat);
+        target
+            .max
+            .apply_modifier(converted_max.get(), Modifier::Flat);
+    }
+
+    *damage_factors = damage
+        .iter()
+        .map(|(damage_type, value)| (*damage_type, value.max.factor()))
+        .collect();
+}
+
 fn compute_status_triggers_for_skill_effect<'a, 'b>(
     statuses_store: &StatusesStore,
     skill_effect: &mut SkillEffect,
@@ -967,7 +1076,7 @@ fn compute_status_triggers_for_skill_effect<'a, 'b>(
         return;
     };
 
-    let mut result: HashMap<String, TriggerEffect> = Default::default();
+    let mut result: BTreeMap<String, TriggerEffect> = Default::default();
 
     for status_effect in status_specs.effects.iter() {
         if let StatusEffectType::Trigger {
@@ -1324,7 +1433,7 @@ pub fn apply_stat_effect_on_skill_effect(
 
             *value_factor = value.max.factor();
         }
-        SkillEffectType::RefreshStatus { .. } => {}
+        SkillEffectType::RefreshStatus { .. } | SkillEffectType::Cleanse { .. } => {}
         SkillEffectType::Restore {
             restore_type,
             value,
@@ -1349,6 +1458,35 @@ pub fn apply_stat_effect_on_skill_effect(
             value: _,
             modifier: _,
         } => {}
+        SkillEffectType::Summon {
+            lifetime,
+            max_minions,
+            minion_effects,
+            ..
+        } => {
+            if let StatType::MinionLifetime(skill_filter) = &effect.stat
+                && skill_filter.is_match_with_skill(skill_type, skill_id)
+            {
+                lifetime.apply_effect(effect);
+            }
+
+            if let StatType::MaxMinions(skill_filter) = &effect.stat
+                && skill_filter.is_match_with_skill(skill_type, skill_id)
+            {
+                max_minions.apply_effect(effect);
+            }
+
+            if let StatType::MinionModifier { stat, skill_filter } = &effect.stat
+                && skill_filter.is_match_with_skill(skill_type, skill_id)
+            {
+                minion_effects.push(StatEffect {
+                    stat: *(stat.clone()),
+                    modifier: effect.modifier,
+                    value: effect.value,
+                    bypass_ignore: effect.bypass_ignore,
+                });
+            }
+        }
     }
 
     None
@@ -1369,11 +1507,190 @@ fn into_stat_skill_effect_type(
             })
         }
         SkillEffectType::RefreshStatus { .. } => Some(StatSkillEffectType::RefreshStatus),
+        SkillEffectType::Cleanse { .. } => Some(StatSkillEffectType::Cleanse),
         SkillEffectType::Restore { restore_type, .. } => Some(StatSkillEffectType::Restore {
             restore_type: Some(*restore_type),
         }),
         SkillEffectType::Resurrect => Some(StatSkillEffectType::Resurrect),
         SkillEffectType::Kill => Some(StatSkillEffectType::Kill),
         SkillEffectType::RefreshCooldown { .. } => Some(StatSkillEffectType::RefreshCooldown),
+        SkillEffectType::Summon { .. } => Some(StatSkillEffectType::Summon),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::data::{stat_effect::StatSkillFilter, values::NonNegative};
+
+    use crate::game::systems::characters_controller;
+
+    use super::*;
+
+    fn flat_damage_effect(damage: &[(DamageType, f64)]) -> SkillEffect {
+        SkillEffect {
+            success_chance: Chance::new_sure(),
+            effect_type: SkillEffectType::FlatDamage {
+                damage: damage
+                    .iter()
+                    .map(|(damage_type, value)| {
+                        let value: ModifiableValue<NonNegative> = NonNegative::new(*value).into();
+                        (
+                            *damage_type,
+                            ChanceRange {
+                                min: value,
+                                max: value,
+                                lucky_chance: Default::default(),
+                            },
+                        )
+                    })
+                    .collect(),
+                damage_factors: Default::default(),
+                crit_chance: Default::default(),
+                crit_damage: Default::default(),
+                unblockable: false,
+                armor_penetration: Default::default(),
+            },
+            ignore_stat_effects: Default::default(),
+            conditional_modifiers: Default::default(),
+            independent_application: false,
+            optional_application: false,
+            description: None,
+        }
+    }
+
+    fn conversion(from: DamageType, to: DamageType, is_extra: bool, value: f64) -> StatEffect {
+        StatEffect {
+            stat: StatType::DamageConversion {
+                skill_filter: StatSkillFilter::default(),
+                from,
+                to,
+                is_extra,
+            },
+            modifier: Modifier::Flat,
+            value,
+            bypass_ignore: false,
+        }
+    }
+
+    fn converted_damage(skill_effect: &SkillEffect) -> HashMap<DamageType, NonNegative> {
+        match &skill_effect.effect_type {
+            SkillEffectType::FlatDamage { damage, .. } => damage
+                .iter()
+                .map(|(damage_type, value)| (*damage_type, *value.min))
+                .collect(),
+            _ => Default::default(),
+        }
+    }
+
+    fn apply_conversions(skill_effect: &mut SkillEffect, effects: &[StatEffect]) {
+        apply_damage_conversions(
+            &"skill".to_string(),
+            SkillType::Attack,
+            skill_effect,
+            effects.iter(),
+            std::iter::empty(),
+        );
+    }
+
+    #[test]
+    fn test_converted_damage_is_converted_again() {
+        let mut skill_effect = flat_damage_effect(&[(DamageType::Physical, 100.0)]);
+
+        // Given in reverse order to check conversions are applied by source damage type
+        apply_conversions(
+            &mut skill_effect,
+            &[
+                conversion(DamageType::Fire, DamageType::Storm, false, 50.0),
+                conversion(DamageType::Physical, DamageType::Fire, false, 50.0),
+            ],
+        );
+
+        let damage = converted_damage(&skill_effect);
+        assert_eq!(damage[&DamageType::Physical].get(), 50.0);
+        assert_eq!(damage[&DamageType::Fire].get(), 25.0);
+        assert_eq!(damage[&DamageType::Storm].get(), 25.0);
+    }
+
+    #[test]
+    fn test_conversion_cannot_go_backward_or_exceed_total() {
+        let mut skill_effect = flat_damage_effect(&[(DamageType::Fire, 100.0)]);
+
+        apply_conversions(
+            &mut skill_effect,
+            &[
+                conversion(DamageType::Fire, DamageType::Physical, false, 50.0),
+                conversion(DamageType::Fire, DamageType::Storm, false, 150.0),
+                conversion(DamageType::Fire, DamageType::Poison, false, 50.0),
+            ],
+        );
+
+        let damage = converted_damage(&skill_effect);
+        assert!(!damage.contains_key(&DamageType::Physical));
+        assert!(!damage.contains_key(&DamageType::Poison));
+        assert_eq!(damage[&DamageType::Fire].get(), 0.0);
+        assert_eq!(damage[&DamageType::Storm].get(), 100.0);
+    }
+
+    #[test]
+    fn test_damage_as_extra_keeps_source_damage() {
+        let mut skill_effect = flat_damage_effect(&[(DamageType::Physical, 100.0)]);
+
+        apply_conversions(
+            &mut skill_effect,
+            &[conversion(
+                DamageType::Physical,
+                DamageType::Fire,
+                true,
+                20.0,
+            )],
+        );
+
+        let damage = converted_damage(&skill_effect);
+        assert_eq!(damage[&DamageType::Physical].get(), 100.0);
+        assert_eq!(damage[&DamageType::Fire].get(), 20.0);
+    }
+
+    #[test]
+    fn test_converted_damage_bypasses_physical_armor() {
+        let mut character_attrs = CharacterAttrs::default();
+        character_attrs
+            .armor
+            .insert(DamageType::Physical, 1_000_000.0.into());
+
+        let take_hit = |damage: &HashMap<DamageType, NonNegative>| {
+            let (mut life, mut mana, mut ward) = (
+                NonNegative::new(1000.0),
+                NonNegative::default(),
+                NonNegative::default(),
+            );
+            characters_controller::damage_character(
+                &character_attrs,
+                &mut life,
+                &mut mana,
+                &mut ward,
+                &mut 0.0,
+                damage,
+                SkillType::Attack,
+                false,
+                Default::default(),
+            )
+        };
+
+        let mut skill_effect = flat_damage_effect(&[(DamageType::Physical, 100.0)]);
+        let unconverted_damage = take_hit(&converted_damage(&skill_effect));
+
+        apply_conversions(
+            &mut skill_effect,
+            &[conversion(
+                DamageType::Physical,
+                DamageType::Fire,
+                false,
+                40.0,
+            )],
+        );
+        let converted_damage = take_hit(&converted_damage(&skill_effect));
+
+        assert!(unconverted_damage < 1.0);
+        assert!((converted_damage - 40.0).abs() < 1.0);
     }
 }
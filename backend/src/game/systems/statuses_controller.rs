@@ -21,6 +21,13 @@ pub fn update_character_statuses(
 ) {
     let elapsed_time_f64 = elapsed_time.as_secs_f64().into();
 
+    character_state
+        .status_immunities
+        .retain(|_, remaining_duration| {
+            *remaining_duration -= elapsed_time_f64;
+            remaining_duration.get() > 0.0
+        });
+
     character_state.statuses.retain(|status_id, status_stacks| {
         let Some(status_specs) = statuses_store.get(status_id) else {
             return false;
@@ -31,20 +38,34 @@ pub fn update_character_statuses(
                 character_attrs,
                 &mut character_state.life,
                 &mut character_state.mana,
+                &mut character_state.ward,
+                &mut character_state.ward_recharge_elapsed,
                 &mut character_state.dirty_specs,
                 status_specs,
                 status_state,
                 elapsed_time_f64,
             )
         });
-        !status_stacks.is_empty()
+
+        if status_stacks.is_empty() {
+            if status_specs.immunity_duration.get() > 0.0 {
+                character_state
+                    .status_immunities
+                    .insert(status_id.clone(), status_specs.immunity_duration);
+            }
+            return false;
+        }
+        true
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn update_status(
     character_attrs: &CharacterAttrs,
     character_life: &mut NonNegative,
     character_mana: &mut NonNegative,
+    character_ward: &mut NonNegative,
+    character_ward_recharge_elapsed: &mut f64,
     character_buff_status_change: &mut bool,
     status_specs: &StatusSpecs,
     status_state: &mut StatusState,
@@ -73,6 +94,8 @@ fn update_status(
                 character_attrs,
                 character_life,
                 character_mana,
+                character_ward,
+                character_ward_recharge_elapsed,
                 &HashMap::from([(
                     *damage_type,
                     (status_effect.computed_value(status_state.value)
@@ -206,3 +229,72 @@ pub fn initialize_status_state(
         escalation,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_immune(
+        statuses_store: &StatusesStore,
+        character_state: &CharacterState,
+        status_id: &str,
+    ) -> bool {
+        character_state
+            .status_immunities
+            .contains_key(&statuses_store.id_with_key(status_id.into()))
+    }
+
+    #[test]
+    fn test_expired_status_grants_immunity_until_it_expires() {
+        let statuses_store: StatusesStore = serde_json::from_value(serde_json::json!({
+            "frozen": {
+                "name": "Frozen",
+                "icon": "statuses/frozen.svg",
+                "debuff": true,
+                "duration": 1.0,
+                "max_stacks": 1,
+                "effects": [],
+                "immunity_duration": 2.0
+            }
+        }))
+        .unwrap();
+        let character_attrs = CharacterAttrs::default();
+        let mut character_state = CharacterState {
+            is_alive: true,
+            ..Default::default()
+        };
+        character_state.statuses.insert(
+            statuses_store.id_with_key("frozen".into()),
+            vec![initialize_status_state(
+                CharacterId::Player,
+                SkillType::Spell,
+                NonNegative::new(1.0),
+                NonNegative::new(1.0),
+                NonNegative::default(),
+            )],
+        );
+
+        let update = |character_state: &mut CharacterState, elapsed_secs: f64| {
+            update_character_statuses(
+                &statuses_store,
+                &character_attrs,
+                character_state,
+                Duration::from_secs_f64(elapsed_secs),
+            )
+        };
+
+        update(&mut character_state, 0.5);
+        assert!(!character_state.statuses.is_empty());
+        assert!(!is_immune(&statuses_store, &character_state, "frozen"));
+
+        update(&mut character_state, 0.5);
+        assert!(character_state.statuses.is_empty());
+        assert!(is_immune(&statuses_store, &character_state, "frozen"));
+
+        update(&mut character_state, 1.5);
+        assert!(is_immune(&statuses_store, &character_state, "frozen"));
+
+        update(&mut character_state, 0.5);
+        assert!(!is_immune(&statuses_store, &character_state, "frozen"));
+    }
+}
@@ -77,7 +77,7 @@ pub fn apply_trigger_effects(
             TriggerTarget::Me => (owner_id, owner_id),
             TriggerTarget::Enemy => (
                 match owner_id {
-                    CharacterId::Player => CharacterId::Monster(
+                    CharacterId::Player | CharacterId::Minion(_) => CharacterId::Monster(
                         game_data
                             .monster_states
                             .iter()
@@ -282,10 +282,28 @@ pub fn apply_trigger_effects(
             })
             .collect();
 
+        let mut minions_still_alive: Vec<_> = game_data
+            .minion_specs
+            .iter()
+            .zip(game_data.minion_states.iter_mut())
+            .enumerate()
+            .filter(|(_, (_, m))| m.character_state.is_alive)
+            .map(|(i, (x, y))| {
+                (
+                    CharacterId::Minion(i),
+                    (&x.character_specs, &mut y.character_state),
+                )
+            })
+            .collect();
+
         let mut targets = match target_id {
             CharacterId::Player => {
                 vec![&mut player_target]
             }
+            CharacterId::Minion(_) => minions_still_alive
+                .iter_mut()
+                .filter(|(minion_id, _)| *minion_id == target_id)
+                .collect(),
             CharacterId::Monster(i) => {
                 let (target_position, target_size) = game_data
                     .monster_specs
@@ -329,6 +347,10 @@ pub fn apply_trigger_effects(
         {
             let owner = match owner_id {
                 CharacterId::Player => &mut game_data.player_state.character_state,
+                CharacterId::Minion(i) => match game_data.minion_states.get_mut(i) {
+                    Some(minion_state) => &mut minion_state.character_state,
+                    None => continue,
+                },
                 CharacterId::Monster(_) => todo!(),
             };
             owner.repeated_skills.push(RepeatedSkillEffect {
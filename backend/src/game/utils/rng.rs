@@ -16,6 +16,10 @@ pub fn roll_seed() -> RngSeed {
     RngSeed::seed_from_u64(rand::rng().random())
 }
 
+pub fn fixed_seed(seed: u64) -> RngSeed {
+    RngSeed::seed_from_u64(seed)
+}
+
 pub fn flip_coin() -> bool {
     let mut rng = rand::rng();
     rng.random_bool(0.5)
@@ -49,16 +53,25 @@ pub fn random_weighted_pick<I>(items: &[I]) -> Option<&I>
 where
     I: RandomWeighted,
 {
-    random_range(0..items.iter().map(|item| item.random_weight()).sum()).and_then(|p| {
-        items
-            .iter()
-            .scan(0, |cumul_prob, item| {
-                *cumul_prob += item.random_weight();
-                Some((*cumul_prob, item))
-            })
-            .find(|(max_prob, item)| p >= *max_prob - item.random_weight() && p < *max_prob)
-            .map(|(_, item)| item)
-    })
+    random_weighted_pick_with_seed(items, &mut roll_seed())
+}
+
+pub fn random_weighted_pick_with_seed<'a, I>(items: &'a [I], seed: &mut RngSeed) -> Option<&'a I>
+where
+    I: RandomWeighted,
+{
+    random_range_with_seed(0..items.iter().map(|item| item.random_weight()).sum(), seed).and_then(
+        |p| {
+            items
+                .iter()
+                .scan(0, |cumul_prob, item| {
+                    *cumul_prob += item.random_weight();
+                    Some((*cumul_prob, item))
+                })
+                .find(|(max_prob, item)| p >= *max_prob - item.random_weight() && p < *max_prob)
+                .map(|(_, item)| item)
+        },
+    )
 }
 
 pub trait Rollable<T> {
@@ -1,11 +1,21 @@
 use std::{env, sync::Arc};
 
-use shared::data::item::ItemSpecs;
+use chrono::{DateTime, Utc};
+use shared::{
+    data::item::{ItemSignature, ItemSpecs},
+    http::server::ErrorResponse,
+};
 use shared_chat::{
     messages::client::ClientPostMessage,
-    types::{ChatChannel, ChatContent, LinkedItemBytes, UserId},
+    moderation::{
+        ChatDeleteMessagesRequest, ChatIgnoreListResponse, ChatSanction, ChatSanctionType,
+        ChatSanctionsResponse, ChatUserMessagesResponse,
+    },
+    types::{ChatChannel, ChatContent, ChatMessage, LinkedItemBytes, UserId},
 };
 
+use crate::game::systems::items_controller;
+
 #[derive(Clone)]
 pub struct ChatIntegration {
     pub chat_url: Arc<String>,
@@ -68,10 +78,179 @@ impl ChatIntegration {
 
         Ok(())
     }
+
+    pub async fn read_sanctions(&self) -> anyhow::Result<Vec<(UserId, ChatSanction)>> {
+        let res = reqwest::Client::new()
+            .get(format!("{}/moderation/sanctions", self.chat_url))
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(res.json::<ChatSanctionsResponse>().await?.sanctions)
+    }
+
+    pub async fn apply_sanction(
+        &self,
+        user_id: UserId,
+        sanction: &ChatSanction,
+    ) -> anyhow::Result<()> {
+        let res = reqwest::Client::new()
+            .post(format!(
+                "{}/moderation/sanctions/{}",
+                self.chat_url, user_id
+            ))
+            .header("Content-Type", "application/json")
+            .json(sanction)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(())
+    }
+
+    /// Return whether there was an active sanction to revoke
+    pub async fn revoke_sanction(
+        &self,
+        user_id: UserId,
+        sanction_type: ChatSanctionType,
+    ) -> anyhow::Result<bool> {
+        let res = reqwest::Client::new()
+            .delete(format!(
+                "{}/moderation/sanctions/{}/{}",
+                self.chat_url,
+                user_id,
+                serde_plain::to_string(&sanction_type)?
+            ))
+            .send()
+            .await?;
+
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(true)
+    }
+
+    /// Recent messages of the user in public channels
+    pub async fn read_user_messages(&self, user_id: UserId) -> anyhow::Result<Vec<ChatMessage>> {
+        let res = reqwest::Client::new()
+            .get(format!("{}/moderation/messages/{}", self.chat_url, user_id))
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(res.json::<ChatUserMessagesResponse>().await?.messages)
+    }
+
+    /// Delete a message of the user, or all of them if no time is given
+    pub async fn delete_messages(
+        &self,
+        user_id: UserId,
+        sent_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let res = reqwest::Client::new()
+            .post(format!("{}/moderation/messages/delete", self.chat_url))
+            .header("Content-Type", "application/json")
+            .json(&ChatDeleteMessagesRequest { user_id, sent_at })
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(())
+    }
+
+    pub async fn read_ignore_list(&self, user_id: UserId) -> anyhow::Result<Vec<UserId>> {
+        let res = reqwest::Client::new()
+            .get(format!("{}/ignore-lists/{}", self.chat_url, user_id))
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(res.json::<ChatIgnoreListResponse>().await?.ignored_user_ids)
+    }
+
+    /// Return an error message if the user cannot be ignored
+    pub async fn ignore_user(
+        &self,
+        user_id: UserId,
+        ignored_user_id: UserId,
+    ) -> anyhow::Result<Result<(), String>> {
+        let res = reqwest::Client::new()
+            .post(format!(
+                "{}/ignore-lists/{}/{}",
+                self.chat_url, user_id, ignored_user_id
+            ))
+            .send()
+            .await?;
+
+        if res.status() == reqwest::StatusCode::CONFLICT {
+            return Ok(Err(res.json::<ErrorResponse>().await?.error));
+        }
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Return whether the user was ignored
+    pub async fn unignore_user(
+        &self,
+        user_id: UserId,
+        ignored_user_id: UserId,
+    ) -> anyhow::Result<bool> {
+        let res = reqwest::Client::new()
+            .delete(format!(
+                "{}/ignore-lists/{}/{}",
+                self.chat_url, user_id, ignored_user_id
+            ))
+            .send()
+            .await?;
+
+        if res.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("Chat API error: {}", err);
+        }
+
+        Ok(true)
+    }
 }
 
-fn to_linked_item_bytes(item_specs: &ItemSpecs) -> Option<LinkedItemBytes> {
-    // let mut cloned_item_specs = item_specs.clone();
-    // cloned_item_specs.signature = Default::default();
-    LinkedItemBytes::try_new(rmp_serde::to_vec(item_specs).ok()?).ok()
+fn to_linked_item_bytes(item_specs: &ItemSpecs) -> Option<(LinkedItemBytes, ItemSignature)> {
+    Some((
+        LinkedItemBytes::try_new(items_controller::serialize_unsigned_item_specs(item_specs).ok()?)
+            .ok()?,
+        item_specs.signature,
+    ))
 }
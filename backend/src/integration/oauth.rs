@@ -0,0 +1,485 @@
+use std::{env, sync::Arc};
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use indexmap::IndexMap;
+use reqwest::Url;
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+/// Identity providers allowed to sign in users, by provider id.
+///
+/// Discord is enabled with `DISCORD_OAUTH_CLIENT_ID` and `DISCORD_OAUTH_CLIENT_SECRET`.
+/// Other OpenID Connect providers are listed in `OIDC_PROVIDERS` (e.g. `google,local`), each with
+/// `OIDC_<ID>_ISSUER`, `OIDC_<ID>_CLIENT_ID`, `OIDC_<ID>_CLIENT_SECRET` and an optional
+/// `OIDC_<ID>_NAME`. Pointing an issuer to a local stub provider is enough for testing.
+/// OpenID Connect providers must return an ID token matching the nonce of the flow.
+#[derive(Clone)]
+pub struct OAuthIntegration {
+    providers: Arc<IndexMap<String, OAuthProvider>>,
+}
+
+pub struct OAuthProvider {
+    pub name: String,
+    client_id: String,
+    client_secret: String,
+    scopes: String,
+    endpoints: OAuthEndpointsSource,
+    claims: OAuthClaimsMapping,
+}
+
+/// Identity returned by the provider
+pub struct OAuthIdentity {
+    /// Stable identifier of the user for the provider
+    pub subject: String,
+    pub username: Option<String>,
+}
+
+#[derive(Clone, Deserialize)]
+struct OAuthEndpoints {
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+}
+
+enum OAuthEndpointsSource {
+    Static(OAuthEndpoints),
+    /// Resolved once from the OpenID discovery document of the issuer
+    Discovery {
+        issuer: String,
+        endpoints: OnceCell<OAuthEndpoints>,
+    },
+}
+
+/// Fields of the userinfo response
+struct OAuthClaimsMapping {
+    subject: &'static str,
+    username: &'static str,
+}
+
+#[derive(Deserialize)]
+struct OAuthTokenResponse {
+    access_token: String,
+    id_token: Option<String>,
+}
+
+/// Signature is not checked, as the token comes straight from the token endpoint over TLS
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: IdTokenAudience,
+    exp: i64,
+    nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum IdTokenAudience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OAuthIntegration {
+    pub fn from_env() -> Self {
+        let mut providers = IndexMap::new();
+
+        // Empty values are treated as unset, as docker compose defaults them
+        if let (Ok(client_id), Ok(client_secret)) = (
+            env::var("DISCORD_OAUTH_CLIENT_ID"),
+            env::var("DISCORD_OAUTH_CLIENT_SECRET"),
+        ) && !client_id.is_empty()
+            && !client_secret.is_empty()
+        {
+            providers.insert(
+                "discord".to_string(),
+                OAuthProvider::discord(client_id, client_secret),
+            );
+        }
+
+        for provider_id in env::var("OIDC_PROVIDERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|provider_id| provider_id.trim().to_lowercase())
+            .filter(|provider_id| !provider_id.is_empty())
+        {
+            let setting = |name: &str| {
+                let setting_name = format!("OIDC_{}_{name}", provider_id.to_uppercase());
+                env::var(&setting_name).unwrap_or_else(|_| panic!("{setting_name} must be set"))
+            };
+
+            providers.insert(
+                provider_id.clone(),
+                OAuthProvider::oidc(
+                    env::var(format!("OIDC_{}_NAME", provider_id.to_uppercase()))
+                        .unwrap_or_else(|_| provider_id.clone()),
+                    &setting("ISSUER"),
+                    setting("CLIENT_ID"),
+                    setting("CLIENT_SECRET"),
+                ),
+            );
+        }
+
+        Self {
+            providers: Arc::new(providers),
+        }
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = (&String, &OAuthProvider)> {
+        self.providers.iter()
+    }
+
+    pub fn provider(&self, provider_id: &str) -> Option<&OAuthProvider> {
+        self.providers.get(provider_id)
+    }
+}
+
+impl OAuthProvider {
+    fn discord(client_id: String, client_secret: String) -> Self {
+        Self {
+            name: "Discord".to_string(),
+            client_id,
+            client_secret,
+            scopes: "identify".to_string(),
+            endpoints: OAuthEndpointsSource::Static(OAuthEndpoints {
+                authorization_endpoint: "https://discord.com/oauth2/authorize".into(),
+                token_endpoint: "https://discord.com/api/oauth2/token".into(),
+                userinfo_endpoint: "https://discord.com/api/users/@me".into(),
+            }),
+            claims: OAuthClaimsMapping {
+                subject: "id",
+                username: "username",
+            },
+        }
+    }
+
+    fn oidc(name: String, issuer: &str, client_id: String, client_secret: String) -> Self {
+        Self {
+            name,
+            client_id,
+            client_secret,
+            scopes: "openid profile".to_string(),
+            endpoints: OAuthEndpointsSource::Discovery {
+                issuer: issuer.trim_end_matches('/').to_string(),
+                endpoints: OnceCell::new(),
+            },
+            claims: OAuthClaimsMapping {
+                subject: "sub",
+                username: "preferred_username",
+            },
+        }
+    }
+
+    pub async fn authorize_url(
+        &self,
+        redirect_uri: &str,
+        state: &str,
+        nonce: &str,
+    ) -> anyhow::Result<String> {
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("scope", self.scopes.as_str()),
+            ("state", state),
+        ];
+        if self.issuer().is_some() {
+            params.push(("nonce", nonce));
+        }
+
+        Ok(
+            Url::parse_with_params(&self.endpoints().await?.authorization_endpoint, &params)?
+                .into(),
+        )
+    }
+
+    /// Exchange the authorization code and read the identity of the user
+    pub async fn fetch_identity(
+        &self,
+        redirect_uri: &str,
+        code: &str,
+        nonce: &str,
+    ) -> anyhow::Result<OAuthIdentity> {
+        let endpoints = self.endpoints().await?;
+        let client = reqwest::Client::new();
+
+        let res = client
+            .post(&endpoints.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+            ])
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("OAuth token error: {}", err);
+        }
+
+        let token: OAuthTokenResponse = res.json().await?;
+
+        let id_token_subject = match self.issuer() {
+            Some(issuer) => Some(self.verify_id_token(issuer, token.id_token.as_deref(), nonce)?),
+            None => None,
+        };
+
+        let res = client
+            .get(&endpoints.userinfo_endpoint)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await?;
+
+        if !res.status().is_success() {
+            let err = res.text().await?;
+            anyhow::bail!("OAuth userinfo error: {}", err);
+        }
+
+        let userinfo: serde_json::Value = res.json().await?;
+
+        let subject = match userinfo.get(self.claims.subject) {
+            Some(serde_json::Value::String(subject)) => subject.clone(),
+            Some(serde_json::Value::Number(subject)) => subject.to_string(),
+            _ => anyhow::bail!("OAuth userinfo is missing '{}'", self.claims.subject),
+        };
+
+        if id_token_subject.is_some_and(|id_token_subject| id_token_subject != subject) {
+            anyhow::bail!("OAuth userinfo doesn't match the ID token");
+        }
+
+        Ok(OAuthIdentity {
+            subject,
+            username: userinfo
+                .get(self.claims.username)
+                .and_then(|username| username.as_str())
+                .map(Into::into),
+        })
+    }
+
+    /// Return the subject of the ID token, once checked it was issued for this flow
+    fn verify_id_token(
+        &self,
+        issuer: &str,
+        id_token: Option<&str>,
+        nonce: &str,
+    ) -> anyhow::Result<String> {
+        let payload = id_token
+            .and_then(|id_token| id_token.split('.').nth(1))
+            .ok_or_else(|| anyhow::anyhow!("OpenID token response is missing the ID token"))?;
+        let claims: IdTokenClaims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload)?)?;
+
+        let audience_valid = match &claims.aud {
+            IdTokenAudience::One(audience) => *audience == self.client_id,
+            IdTokenAudience::Many(audiences) => audiences.contains(&self.client_id),
+        };
+
+        if claims.iss.trim_end_matches('/') != issuer
+            || !audience_valid
+            || claims.exp < Utc::now().timestamp()
+        {
+            anyhow::bail!("OpenID ID token was not issued for this client");
+        }
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            anyhow::bail!("OpenID ID token nonce doesn't match");
+        }
+
+        Ok(claims.sub)
+    }
+
+    fn issuer(&self) -> Option<&str> {
+        match &self.endpoints {
+            OAuthEndpointsSource::Static(_) => None,
+            OAuthEndpointsSource::Discovery { issuer, .. } => Some(issuer),
+        }
+    }
+
+    async fn endpoints(&self) -> anyhow::Result<&OAuthEndpoints> {
+        match &self.endpoints {
+            OAuthEndpointsSource::Static(endpoints) => Ok(endpoints),
+            OAuthEndpointsSource::Discovery { issuer, endpoints } => {
+                endpoints
+                    .get_or_try_init(|| discover_endpoints(issuer))
+                    .await
+            }
+        }
+    }
+}
+
+async fn discover_endpoints(issuer: &str) -> anyhow::Result<OAuthEndpoints> {
+    let res = reqwest::Client::new()
+        .get(format!("{issuer}/.well-known/openid-configuration"))
+        .send()
+        .await?;
+
+    if !res.status().is_success() {
+        let err = res.text().await?;
+        anyhow::bail!("OpenID discovery error: {}", err);
+    }
+
+    Ok(res.json().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Form, Json, Router,
+        extract::State,
+        http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+        routing::{get, post},
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    use super::*;
+
+    const CLIENT_ID: &str = "stub_client";
+    const AUTHORIZATION_CODE: &str = "stub_code";
+    const ACCESS_TOKEN: &str = "stub_access_token";
+
+    #[derive(Clone)]
+    struct StubIdp {
+        issuer: String,
+        id_token_nonce: String,
+    }
+
+    /// Minimal OpenID Connect provider, returning the given nonce in its ID tokens
+    async fn spawn_stub_idp(id_token_nonce: &str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/token", post(token))
+            .route("/userinfo", get(userinfo))
+            .with_state(StubIdp {
+                issuer: issuer.clone(),
+                id_token_nonce: id_token_nonce.to_string(),
+            });
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        issuer
+    }
+
+    async fn discovery(State(stub): State<StubIdp>) -> Json<serde_json::Value> {
+        Json(json!({
+            "issuer": stub.issuer,
+            "authorization_endpoint": format!("{}/authorize", stub.issuer),
+            "token_endpoint": format!("{}/token", stub.issuer),
+            "userinfo_endpoint": format!("{}/userinfo", stub.issuer),
+        }))
+    }
+
+    async fn token(
+        State(stub): State<StubIdp>,
+        Form(form): Form<HashMap<String, String>>,
+    ) -> Result<Json<serde_json::Value>, StatusCode> {
+        if form.get("code").map(String::as_str) != Some(AUTHORIZATION_CODE) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+
+        let claims = json!({
+            "iss": stub.issuer,
+            "sub": "stub_user",
+            "aud": CLIENT_ID,
+            "exp": Utc::now().timestamp() + 60,
+            "nonce": stub.id_token_nonce,
+        });
+        let id_token = format!(
+            "{}.{}.",
+            URL_SAFE_NO_PAD.encode(br#"{"alg":"none"}"#),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+
+        Ok(Json(json!({
+            "access_token": ACCESS_TOKEN,
+            "token_type": "Bearer",
+            "id_token": id_token,
+        })))
+    }
+
+    async fn userinfo(headers: HeaderMap) -> Result<Json<serde_json::Value>, StatusCode> {
+        if headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            != Some(&format!("Bearer {ACCESS_TOKEN}"))
+        {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+
+        Ok(Json(json!({
+            "sub": "stub_user",
+            "preferred_username": "stubby",
+        })))
+    }
+
+    fn stub_provider(issuer: &str) -> OAuthProvider {
+        OAuthProvider::oidc(
+            "Stub".into(),
+            issuer,
+            CLIENT_ID.into(),
+            "stub_secret".into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_authorize_url_sends_state_and_nonce() {
+        let issuer = spawn_stub_idp("nonce").await;
+        let authorize_url = stub_provider(&issuer)
+            .authorize_url("http://localhost/oauth-callback", "state", "nonce")
+            .await
+            .unwrap();
+
+        let authorize_url = Url::parse(&authorize_url).unwrap();
+        let params: HashMap<_, _> = authorize_url.query_pairs().collect();
+        assert_eq!(authorize_url.path(), "/authorize");
+        assert_eq!(params.get("state").map(|p| p.as_ref()), Some("state"));
+        assert_eq!(params.get("nonce").map(|p| p.as_ref()), Some("nonce"));
+        assert_eq!(params.get("client_id").map(|p| p.as_ref()), Some(CLIENT_ID));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_identity() {
+        let issuer = spawn_stub_idp("nonce").await;
+        let identity = stub_provider(&issuer)
+            .fetch_identity(
+                "http://localhost/oauth-callback",
+                AUTHORIZATION_CODE,
+                "nonce",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(identity.subject, "stub_user");
+        assert_eq!(identity.username.as_deref(), Some("stubby"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_identity_rejects_other_nonce() {
+        let issuer = spawn_stub_idp("attacker_nonce").await;
+        assert!(
+            stub_provider(&issuer)
+                .fetch_identity(
+                    "http://localhost/oauth-callback",
+                    AUTHORIZATION_CODE,
+                    "nonce"
+                )
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_identity_rejects_invalid_code() {
+        let issuer = spawn_stub_idp("nonce").await;
+        assert!(
+            stub_provider(&issuer)
+                .fetch_identity("http://localhost/oauth-callback", "invalid_code", "nonce")
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use std::{net::SocketAddr, sync::Arc};
+use chrono::Utc;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::{
     Router,
@@ -13,21 +14,30 @@ use tower_http::{
     cors::CorsLayer,
     trace::{DefaultMakeSpan, TraceLayer},
 };
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{Layer, layer::SubscriberExt, util::SubscriberInitExt};
 
-use backend_shared::{profanities_checker::ProfanitiesChecker, signature};
+use backend_shared::{
+    metrics::PrometheusHandle, profanities_checker::ProfanitiesChecker, signature,
+};
 
 use backend::{
     app_state::{AppSettings, AppState},
-    db::{self, pool},
+    db::{self, pool, query_metrics::QueryMetricsLayer},
     email::EmailService,
     game::{
-        data::master_store::MasterStore, sessions::SessionsStore, systems::sessions_controller,
+        challenges::ChallengesStore, data::master_store::MasterStore,
+        maintenance::MaintenanceStore, sessions::SessionsStore, systems::sessions_controller,
     },
-    integration::{chat::ChatIntegration, discord::DiscordIntegration},
+    integration::{chat::ChatIntegration, discord::DiscordIntegration, oauth::OAuthIntegration},
     rest, tasks, websocket,
 };
 
+/// Delay given to the clients before reconnecting, to let the next instance start
+const RESTART_RECONNECT_DELAY: chrono::Duration = chrono::Duration::seconds(15);
+/// Maximum time to wait for the game sessions to save and exit.
+/// Must stay below the container stop grace period (30s in docker-compose.yml).
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(20);
+
 #[tokio::main]
 async fn main() {
     let _ = dotenvy::dotenv();
@@ -68,12 +78,17 @@ async fn main() {
 
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| default_level.into()),
+            tracing_subscriber::fmt::layer().with_filter(
+                tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| default_level.into()),
+            ),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(QueryMetricsLayer.with_filter(QueryMetricsLayer::filter()))
         .init();
 
+    let metrics_handle =
+        backend_shared::metrics::install_recorder().expect("failed to install metrics recorder");
+
     let tracer_layer =
         TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default().include_headers(true));
 
@@ -96,14 +111,24 @@ async fn main() {
         sessions_store.clone(),
     ));
 
+    let challenges_store = ChallengesStore::new();
+
+    let rotate_challenges_handle = tokio::spawn(tasks::rotate_challenges(
+        master_store.clone(),
+        challenges_store.clone(),
+    ));
+
     let app_state = AppState {
         app_settings: AppSettings::from_env(),
         db_pool: db_pool.clone(),
         email_service: EmailService::from_env(),
         master_store,
         sessions_store: sessions_store.clone(),
+        challenges_store,
+        maintenance_store: MaintenanceStore::new(),
         discord_integration: DiscordIntegration::from_env(),
         chat_integration: ChatIntegration::from_env(),
+        oauth_integration: OAuthIntegration::from_env(),
         profanities_checker: Arc::new(
             ProfanitiesChecker::load_from_file(
                 "profanities/strong_profanities.txt",
@@ -113,8 +138,15 @@ async fn main() {
         ),
     };
 
+    let metrics_sessions_store = sessions_store.clone();
     let app = Router::new()
         .route("/", get(|| async { "OK" }))
+        .route(
+            "/metrics",
+            get(move || {
+                std::future::ready(render_metrics(&metrics_handle, &metrics_sessions_store))
+            }),
+        )
         .merge(rest::routes(app_state.clone()))
         .route("/ws", any(websocket::handler))
         .with_state(app_state.clone())
@@ -130,14 +162,26 @@ async fn main() {
 
     tokio::select! {
         _ = server => {},
-        _ = signal::ctrl_c() => {
+        _ = shutdown_signal() => {
             println!("Received shutdown signal");
         }
     }
 
+    // Live game sessions save their progress and tell their clients to reconnect soon
+    app_state
+        .maintenance_store
+        .drain(Utc::now() + RESTART_RECONNECT_DELAY);
+    if !sessions_controller::wait_active_sessions(&sessions_store, DRAIN_TIMEOUT).await {
+        tracing::warn!(
+            "{} game sessions did not drain in time",
+            sessions_store.active_sessions.len()
+        );
+    }
+
     purge_sessions_handle.abort();
+    rotate_challenges_handle.abort();
 
-    // Note that this only save the sessions that were not active but in the store...
+    // Drained sessions are back in the store with the disconnected ones
     if let Err(e) = sessions_controller::save_all_sessions(&db_pool, &sessions_store).await {
         tracing::error!("failed to save all sessions: {}", e);
     }
@@ -145,6 +189,36 @@ async fn main() {
     tracing::debug!("server has been shut down");
 }
 
+fn render_metrics(metrics_handle: &PrometheusHandle, sessions_store: &SessionsStore) -> String {
+    metrics::gauge!("game_sessions_active").set(sessions_store.active_sessions.len() as f64);
+    metrics::gauge!("game_sessions_parked").set(sessions_store.sessions.len() as f64);
+    metrics_handle.render()
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install terminate signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
 async fn migrate_data(db_pool: &db::DbPool, master_store: &MasterStore) -> anyhow::Result<()> {
     let _ = master_store;
     db::migrations::migration_0_2_00_to_0_2_01::migrate(db_pool).await?;
@@ -0,0 +1,518 @@
+use anyhow::Result;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    middleware,
+    routing::{get, post},
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+
+use shared::{
+    data::{item::ItemRarity, user::UserCharacterId},
+    http::{
+        client::{
+            AdminBroadcastRequest, AdminCharacterResourcesRequest,
+            AdminDeleteCharacterItemsRequest, AdminDrainRequest, AdminGrantItemRequest,
+            AdminMaintenanceRequest,
+        },
+        server::{
+            AdminCharacterResourcesResponse, AdminCharacterResponse,
+            AdminInventoriesLegacyItemsResponse, AdminInventoryResponse, AdminLegacyItem,
+            AdminMaintenanceResponse, AdminSession, AdminSessionsResponse,
+            AdminStashLegacyItemsResponse,
+        },
+    },
+    types::PaginationLimit,
+};
+
+use crate::{
+    app_state::{AppState, MasterStore, SessionsStore},
+    auth::{self, User},
+    db::{self, stash_items::StashItemId},
+    game::{
+        data::inventory_data::inventory_data_to_player_inventory,
+        maintenance::{Maintenance, MaintenanceStore},
+        systems::{
+            inventory_controller,
+            legacy_items_controller::{self, LegacyItemReport},
+            loot_generator,
+        },
+    },
+    integration::chat::ChatIntegration,
+    rest::utils::{verify_character_in_town, verify_character_not_deleted},
+};
+
+use super::{AppError, moderation_api};
+
+pub fn routes(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/admin/sessions", get(get_sessions))
+        .route(
+            "/admin/sessions/{character_id}/save",
+            post(post_save_session),
+        )
+        .route(
+            "/admin/sessions/{character_id}/kick",
+            post(post_kick_session),
+        )
+        .route("/admin/characters/{character_id}", get(get_character))
+        .route(
+            "/admin/characters/{character_id}/resources",
+            post(post_character_resources),
+        )
+        .route(
+            "/admin/characters/{character_id}/inventory/delete",
+            post(post_delete_character_items),
+        )
+        .route(
+            "/admin/characters/{character_id}/items/grant",
+            post(post_grant_item),
+        )
+        .route("/admin/broadcast", post(post_broadcast))
+        .route(
+            "/admin/maintenance",
+            get(get_maintenance)
+                .post(post_maintenance)
+                .delete(delete_maintenance),
+        )
+        .route("/admin/drain", post(post_drain))
+        .route(
+            "/admin/legacy_items/inventories",
+            get(get_inventories_legacy_items),
+        )
+        .route("/admin/legacy_items/stash", get(get_stash_legacy_items))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            auth::admin_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            auth::authorization_middleware,
+        ))
+}
+
+async fn get_sessions(
+    State(sessions_store): State<SessionsStore>,
+) -> Result<Json<AdminSessionsResponse>, AppError> {
+    let last_active = |last_active: std::time::Instant| {
+        Utc::now() - Duration::from_std(last_active.elapsed()).unwrap_or_default()
+    };
+
+    let sessions = sessions_store
+        .active_sessions
+        .iter()
+        .map(|entry| AdminSession {
+            character_id: *entry.key(),
+            area_id: entry.area_id.clone(),
+            area_level: entry.area_level,
+            last_active: last_active(entry.last_active),
+            connected: true,
+        })
+        .chain(sessions_store.sessions.iter().map(|entry| AdminSession {
+            character_id: *entry.key(),
+            area_id: entry.game_data.area_id.clone(),
+            area_level: entry.game_data.area_state.read().area_level,
+            last_active: last_active(entry.last_active),
+            connected: false,
+        }))
+        .collect();
+
+    Ok(Json(AdminSessionsResponse { sessions }))
+}
+
+async fn post_save_session(
+    State(db_pool): State<db::DbPool>,
+    State(sessions_store): State<SessionsStore>,
+    Extension(user): Extension<User>,
+    Path(character_id): Path<UserCharacterId>,
+) -> Result<Json<()>, AppError> {
+    tracing::info!("admin: '{}' saves session {character_id}", user.username);
+
+    if sessions_store.active_sessions.contains_key(&character_id) {
+        sessions_store.sessions_saving.insert(character_id);
+        return Ok(Json(()));
+    }
+
+    let game_data = sessions_store
+        .sessions
+        .get(&character_id)
+        .map(|session| session.game_data.clone())
+        .ok_or(AppError::NotFound)?;
+
+    db::game_instances::save_game_instance_data(&db_pool, &character_id, *game_data).await?;
+
+    Ok(Json(()))
+}
+
+async fn post_kick_session(
+    State(db_pool): State<db::DbPool>,
+    State(sessions_store): State<SessionsStore>,
+    Extension(user): Extension<User>,
+    Path(character_id): Path<UserCharacterId>,
+) -> Result<Json<()>, AppError> {
+    tracing::info!("admin: '{}' kicks session {character_id}", user.username);
+
+    if sessions_store.active_sessions.contains_key(&character_id) {
+        sessions_store.sessions_stealing.insert(character_id);
+        return Ok(Json(()));
+    }
+
+    // Disconnected sessions are saved and dropped from memory
+    let (_, session) = sessions_store
+        .sessions
+        .remove(&character_id)
+        .ok_or(AppError::NotFound)?;
+
+    db::game_instances::save_game_instance_data(&db_pool, &character_id, *session.game_data)
+        .await?;
+
+    Ok(Json(()))
+}
+
+async fn get_character(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Path(character_id): Path<UserCharacterId>,
+) -> Result<Json<AdminCharacterResponse>, AppError> {
+    let character = db::characters::read_character(&db_pool, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    verify_character_not_deleted(&character)?;
+
+    let inventory = db::characters_data::load_character_data(&db_pool, &character_id)
+        .await?
+        .map(|(inventory_data, ..)| {
+            inventory_data_to_player_inventory(&master_store.items_store, inventory_data)
+        })
+        .unwrap_or_default();
+
+    Ok(Json(AdminCharacterResponse {
+        character_id,
+        user_id: character.user_id,
+        character_name: character.character_name,
+        area_id: character.area_id,
+        resource_gems: character.resource_gems,
+        resource_shards: character.resource_shards,
+        resource_gold: character.resource_gold,
+        inventory,
+    }))
+}
+
+async fn post_character_resources(
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Path(character_id): Path<UserCharacterId>,
+    Json(payload): Json<AdminCharacterResourcesRequest>,
+) -> Result<Json<AdminCharacterResourcesResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    verify_character_not_deleted(&character)?;
+
+    // Resources gained in a running game are added on top when it ends
+    let character_resources = db::characters::update_character_resources(
+        &mut *tx,
+        &character_id,
+        payload
+            .resource_gems
+            .map_or(0.0, |gems| gems - character.resource_gems),
+        payload
+            .resource_shards
+            .map_or(0.0, |shards| shards - character.resource_shards),
+        payload
+            .resource_gold
+            .map_or(0.0, |gold| gold - character.resource_gold),
+        0.0,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    tracing::info!(
+        "admin: '{}' sets resources of {character_id}: {payload:?}",
+        user.username
+    );
+
+    Ok(Json(AdminCharacterResourcesResponse {
+        resource_gems: character_resources.resource_gems,
+        resource_shards: character_resources.resource_shards,
+        resource_gold: character_resources.resource_gold,
+    }))
+}
+
+async fn post_delete_character_items(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Path(character_id): Path<UserCharacterId>,
+    Json(payload): Json<AdminDeleteCharacterItemsRequest>,
+) -> Result<Json<AdminInventoryResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    // The inventory of a running game would overwrite the changes
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, ..) = db::characters_data::load_character_data(&mut *tx, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    let mut item_indexes = payload.item_indexes;
+    item_indexes.sort_unstable();
+    item_indexes.dedup();
+
+    for &item_index in item_indexes.iter().rev() {
+        let item_specs = inventory_controller::remove_item_from_bag(&mut inventory, item_index)?;
+        tracing::info!(
+            "admin: '{}' deletes item '{}' of {character_id}",
+            user.username,
+            item_specs.modifiers.name
+        );
+    }
+
+    db::characters_data::save_character_inventory(&mut *tx, &character_id, &inventory).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(AdminInventoryResponse { inventory }))
+}
+
+async fn post_grant_item(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Path(character_id): Path<UserCharacterId>,
+    Json(payload): Json<AdminGrantItemRequest>,
+) -> Result<Json<AdminInventoryResponse>, AppError> {
+    let base = master_store
+        .items_store
+        .content
+        .get(&payload.base_item_id)
+        .ok_or_else(|| AppError::UserError(format!("unknown item '{}'", payload.base_item_id)))?
+        .clone();
+
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, ..) = db::characters_data::load_character_data(&mut *tx, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    let rarity = if base.rarity == ItemRarity::Unique {
+        ItemRarity::Unique
+    } else {
+        payload.rarity.clamp(base.rarity, ItemRarity::Rare)
+    };
+
+    let item_specs = loot_generator::roll_item_stats(
+        payload.base_item_id,
+        base,
+        rarity,
+        payload.level,
+        0,
+        &master_store.item_affixes_table,
+        &master_store.item_adjectives_table,
+        &master_store.item_nouns_table,
+        false,
+        0.0,
+        &master_store.items_store.signature_key,
+    );
+
+    tracing::info!(
+        "admin: '{}' grants item '{}' to {character_id}",
+        user.username,
+        item_specs.modifiers.name
+    );
+
+    inventory_controller::store_item_to_bag(&mut inventory, item_specs)?;
+
+    db::characters_data::save_character_inventory(&mut *tx, &character_id, &inventory).await?;
+
+    tx.commit().await?;
+
+    Ok(Json(AdminInventoryResponse { inventory }))
+}
+
+async fn post_broadcast(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminBroadcastRequest>,
+) -> Result<Json<()>, AppError> {
+    moderation_api::broadcast(&db_pool, &chat_integration, &user, payload.message).await?;
+
+    Ok(Json(()))
+}
+
+async fn get_maintenance(
+    State(maintenance_store): State<MaintenanceStore>,
+) -> Result<Json<AdminMaintenanceResponse>, AppError> {
+    let maintenance = maintenance_store.get();
+
+    Ok(Json(AdminMaintenanceResponse {
+        starts_at: maintenance.map(|maintenance| maintenance.starts_at),
+        ends_at: maintenance.map(|maintenance| maintenance.ends_at),
+    }))
+}
+
+async fn post_maintenance(
+    State(maintenance_store): State<MaintenanceStore>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminMaintenanceRequest>,
+) -> Result<Json<AdminMaintenanceResponse>, AppError> {
+    if payload.countdown_secs < 0 || payload.duration_secs <= 0 {
+        return Err(AppError::UserError(
+            "countdown must be positive and duration strictly positive".into(),
+        ));
+    }
+
+    let starts_at = Utc::now() + Duration::seconds(payload.countdown_secs);
+    let ends_at = starts_at + Duration::seconds(payload.duration_secs);
+
+    maintenance_store.schedule(Maintenance { starts_at, ends_at });
+
+    tracing::info!(
+        "admin: '{}' schedules maintenance from {starts_at} to {ends_at}",
+        user.username
+    );
+
+    // Players are warned in the chat, running games also get the schedule on their next tick
+    if let Err(err) = chat_integration
+        .broadcast_message(
+            format!(
+                "The server will go down for maintenance in {} minutes, for about {} minutes.",
+                (payload.countdown_secs + 59) / 60,
+                (payload.duration_secs + 59) / 60,
+            ),
+            None,
+        )
+        .await
+    {
+        tracing::error!("failed to announce maintenance: {err}");
+    }
+
+    Ok(Json(AdminMaintenanceResponse {
+        starts_at: Some(starts_at),
+        ends_at: Some(ends_at),
+    }))
+}
+
+async fn delete_maintenance(
+    State(maintenance_store): State<MaintenanceStore>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+) -> Result<Json<()>, AppError> {
+    maintenance_store.cancel().ok_or(AppError::NotFound)?;
+
+    tracing::info!("admin: '{}' cancels maintenance", user.username);
+
+    if let Err(err) = chat_integration
+        .broadcast_message("The scheduled maintenance has been cancelled.".into(), None)
+        .await
+    {
+        tracing::error!("failed to announce maintenance cancellation: {err}");
+    }
+
+    Ok(Json(()))
+}
+
+/// Close all game sessions after saving them, clients reconnect after the delay
+async fn post_drain(
+    State(maintenance_store): State<MaintenanceStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminDrainRequest>,
+) -> Result<Json<()>, AppError> {
+    if payload.reconnect_delay_secs <= 0 {
+        return Err(AppError::UserError(
+            "reconnect delay must be strictly positive".into(),
+        ));
+    }
+
+    tracing::info!("admin: '{}' drains game sessions", user.username);
+
+    maintenance_store.drain(Utc::now() + Duration::seconds(payload.reconnect_delay_secs));
+
+    Ok(Json(()))
+}
+
+#[derive(Deserialize)]
+struct InventoriesLegacyItemsQuery {
+    after: Option<UserCharacterId>,
+    #[serde(default)]
+    limit: PaginationLimit,
+}
+
+#[derive(Deserialize)]
+struct StashLegacyItemsQuery {
+    after: Option<usize>,
+    #[serde(default)]
+    limit: PaginationLimit,
+}
+
+/// Scan a page of inventories for items that don't match the current definitions
+async fn get_inventories_legacy_items(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Query(query): Query<InventoriesLegacyItemsQuery>,
+) -> Result<Json<AdminInventoriesLegacyItemsResponse>, AppError> {
+    let (reports, next_character_id) = legacy_items_controller::report_inventories_legacy_items(
+        &db_pool,
+        &master_store,
+        query.after,
+        *query.limit,
+    )
+    .await?;
+
+    Ok(Json(AdminInventoriesLegacyItemsResponse {
+        items: reports.into_iter().map(into_admin_legacy_item).collect(),
+        next_character_id,
+    }))
+}
+
+/// Scan a page of stashed items for items that don't match the current definitions
+async fn get_stash_legacy_items(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Query(query): Query<StashLegacyItemsQuery>,
+) -> Result<Json<AdminStashLegacyItemsResponse>, AppError> {
+    let (reports, next_stash_item_id) = legacy_items_controller::report_stash_legacy_items(
+        &db_pool,
+        &master_store,
+        query
+            .after
+            .map(|stash_item_id| stash_item_id as StashItemId),
+        *query.limit,
+    )
+    .await?;
+
+    Ok(Json(AdminStashLegacyItemsResponse {
+        items: reports.into_iter().map(into_admin_legacy_item).collect(),
+        next_stash_item_id: next_stash_item_id.map(|stash_item_id| stash_item_id as usize),
+    }))
+}
+
+fn into_admin_legacy_item(report: LegacyItemReport) -> AdminLegacyItem {
+    AdminLegacyItem {
+        location: report.location.to_string(),
+        base_item_id: report.base_item_id,
+        name: report.name,
+        issues: report.issues.iter().map(ToString::to_string).collect(),
+    }
+}
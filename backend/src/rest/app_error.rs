@@ -15,6 +15,8 @@ pub enum AppError {
     Unauthorized(String),
     Forbidden,
     NotFound,
+    /// Action must be confirmed with a second factor
+    TotpRequired,
 }
 
 impl fmt::Display for AppError {
@@ -26,6 +28,7 @@ impl fmt::Display for AppError {
             AppError::Unauthorized(err) => write!(f, "{err}"),
             AppError::Forbidden => write!(f, "Forbidden"),
             AppError::NotFound => write!(f, "Not found"),
+            AppError::TotpRequired => write!(f, "two-factor code required"),
         }
     }
 }
@@ -38,6 +41,7 @@ impl Error for AppError {
             AppError::NotFound
             | AppError::Unauthorized(_)
             | AppError::Forbidden
+            | AppError::TotpRequired
             | AppError::UserError(_) => None,
         }
     }
@@ -61,6 +65,7 @@ impl IntoResponse for AppError {
             AppError::NotFound => StatusCode::NOT_FOUND,
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             AppError::Forbidden => StatusCode::FORBIDDEN,
+            AppError::TotpRequired => StatusCode::PRECONDITION_REQUIRED,
             AppError::UserError(_) => StatusCode::CONFLICT,
             AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Anyhow(_) => StatusCode::INTERNAL_SERVER_ERROR,
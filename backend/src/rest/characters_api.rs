@@ -150,6 +150,7 @@ async fn read_character_details(
     let (
         areas_completed,
         character_data,
+        atlas,
         passives_build,
         // last_grind_data,
         character_stash,
@@ -158,6 +159,7 @@ async fn read_character_details(
     ) = tokio::join!(
         db::characters::read_character_areas_completed(&db_pool, &character_id),
         db::characters_data::load_character_data(&db_pool, &character_id),
+        db::characters_data::load_character_atlas(&db_pool, &character_id),
         db::characters_builds::load_character_build(&db_pool, &character_id),
         // db::game_stats::load_last_game_stats(&db_pool, &character_id),
         db::stashes::get_character_stash_by_type(&db_pool, &character, StashType::Character),
@@ -166,6 +168,7 @@ async fn read_character_details(
     );
 
     let areas_completed = areas_completed?;
+    let atlas = atlas?;
     let (inventory_data, ascension_data, benedictions, mut skill_masteries) =
         character_data?.unwrap_or_default();
     // let last_grind_data = last_grind_data?;
@@ -219,6 +222,7 @@ async fn read_character_details(
         ascension,
         passives_build,
         benedictions,
+        atlas,
         // last_grind,
         character_stash,
         user_stash,
@@ -0,0 +1,97 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::get,
+};
+use strum::IntoEnumIterator;
+
+use backend_shared::http::chat::{GetChatLadderRankResponse, GetChatLinkedItemResponse};
+use shared::data::{area::AreaLevel, item::ItemSlot, user::UserCharacterId};
+
+use crate::{
+    app_state::{AppState, MasterStore},
+    db,
+    game::{data::inventory_data::inventory_data_to_player_inventory, systems::items_controller},
+    rest::utils::verify_character_not_deleted,
+};
+
+use super::AppError;
+
+/// Public data used by the chat commands
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/chat/characters/{character_id}/equipped/{item_slot}",
+            get(get_linked_item),
+        )
+        .route(
+            "/chat/characters/{character_id}/ladder/{area}",
+            get(get_ladder_rank),
+        )
+}
+
+async fn get_linked_item(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Path((character_id, item_slot)): Path<(UserCharacterId, String)>,
+) -> Result<Json<GetChatLinkedItemResponse>, AppError> {
+    let item_slot = ItemSlot::iter()
+        .find(|slot| {
+            serde_plain::to_string(slot).is_ok_and(|slot| slot.eq_ignore_ascii_case(&item_slot))
+        })
+        .ok_or_else(|| AppError::UserError(format!("unknown item slot '{item_slot}'")))?;
+
+    let character = db::characters::read_character(&db_pool, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    verify_character_not_deleted(&character)?;
+
+    let (inventory_data, ..) = db::characters_data::load_character_data(&db_pool, &character_id)
+        .await?
+        .unwrap_or_default();
+    let inventory = inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    Ok(Json(GetChatLinkedItemResponse {
+        linked_item: inventory
+            .get_equipped_item(item_slot)
+            .map(|item_specs| {
+                items_controller::serialize_unsigned_item_specs(item_specs)
+                    .map(|serialized_item_specs| (serialized_item_specs, item_specs.signature))
+            })
+            .transpose()
+            .map_err(anyhow::Error::from)?,
+    }))
+}
+
+async fn get_ladder_rank(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Path((character_id, area)): Path<(UserCharacterId, String)>,
+) -> Result<Json<GetChatLadderRankResponse>, AppError> {
+    let (area_id, area_blueprint) = master_store
+        .area_blueprints_store
+        .iter()
+        .find(|(area_id, area_blueprint)| {
+            area_id.eq_ignore_ascii_case(&area)
+                || area_blueprint.specs.name.eq_ignore_ascii_case(&area)
+        })
+        .ok_or_else(|| AppError::UserError(format!("unknown area '{area}'")))?;
+
+    let character = db::characters::read_character(&db_pool, &character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+    verify_character_not_deleted(&character)?;
+
+    let rank = db::leaderboard::read_character_area_rank(
+        &db_pool,
+        &character_id,
+        &character.realm_id,
+        area_id,
+    )
+    .await?;
+
+    Ok(Json(GetChatLadderRankResponse {
+        area_name: area_blueprint.specs.name.clone(),
+        rank: rank.map(|(rank, area_level)| (rank, area_level as AreaLevel)),
+    }))
+}
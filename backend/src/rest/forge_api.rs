@@ -4,10 +4,21 @@ use axum::{Extension, Json, Router, extract::State, middleware, routing::post};
 
 use shared::{
     computations, constants,
-    data::forge::affix_operation_price,
+    data::{
+        forge::{self, affix_operation_price},
+        item::ItemSlot,
+    },
     http::{
-        client::{ForgeAffixOperation, ForgeAffixRequest, ForgeUpgradeRequest, GambleItemRequest},
-        server::{ForgeAffixResponse, ForgeUpgradeResponse, GambleItemResponse},
+        client::{
+            ForgeAffixFamiliesRequest, ForgeAffixOperation, ForgeAffixRequest, ForgeCorruptRequest,
+            ForgeDivineRequest, ForgeSocketRuneRequest, ForgeUnsocketRuneRequest,
+            ForgeUpgradeRequest, GambleItemRequest,
+        },
+        server::{
+            ForgeAffixFamiliesResponse, ForgeAffixFamily, ForgeAffixResponse, ForgeCorruptResponse,
+            ForgeDivineResponse, ForgeSocketRuneResponse, ForgeUnsocketRuneResponse,
+            ForgeUpgradeResponse, GambleItemResponse,
+        },
     },
 };
 
@@ -21,6 +32,7 @@ use crate::{
             inventory_controller, items_controller,
             loot_generator::{self, GenerateLootTemplate},
         },
+        utils::rng,
     },
     rest::utils::{verify_character_in_town, verify_character_user},
 };
@@ -30,8 +42,13 @@ use super::AppError;
 pub fn routes(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/forge/affix", post(post_affix))
+        .route("/forge/families", post(post_families))
         .route("/forge/upgrade", post(post_upgrade))
+        .route("/forge/corrupt", post(post_corrupt))
+        .route("/forge/divine", post(post_divine))
         .route("/forge/gamble", post(post_gamble))
+        .route("/forge/socket", post(post_socket_rune))
+        .route("/forge/unsocket", post(post_unsocket_rune))
         .layer(middleware::from_fn_with_state(
             app_state,
             auth::authorization_middleware,
@@ -65,12 +82,14 @@ pub async fn post_affix(
         .nth_mut(payload.item_index as usize)
         .ok_or(AppError::NotFound)?;
 
-    let price = affix_operation_price(
-        payload.operation,
-        item.modifiers.count_nonunique_affixes(),
-        &item.base,
-    )
-    .ok_or(AppError::UserError("forge operation unavailable".into()))?;
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError(
+            "corrupted items cannot be modified".into(),
+        ));
+    }
+
+    let price = affix_operation_price(&payload.operation, &item.modifiers, &item.base)
+        .ok_or(AppError::UserError("forge operation unavailable".into()))?;
 
     let character_resources = db::characters::update_character_resources(
         &mut *tx,
@@ -95,17 +114,42 @@ pub async fn post_affix(
             &master_store.item_adjectives_table,
             &master_store.item_nouns_table,
         ),
+        ForgeAffixOperation::AddFamily(ref family) => loot_generator::add_affix_family(
+            &item.base,
+            &mut item.modifiers,
+            family,
+            &master_store.item_affixes_table,
+            &master_store.item_adjectives_table,
+            &master_store.item_nouns_table,
+        ),
         ForgeAffixOperation::Remove => loot_generator::remove_affix(
             &item.base,
             &mut item.modifiers,
             &master_store.item_adjectives_table,
             &master_store.item_nouns_table,
         ),
+        ForgeAffixOperation::Reroll { locked_affix } => loot_generator::reroll_affixes(
+            &item.base,
+            &mut item.modifiers,
+            locked_affix,
+            &master_store.item_affixes_table,
+        ),
+        ForgeAffixOperation::UpgradeTier(affix_index) => loot_generator::upgrade_affix_tier(
+            &item.base,
+            &mut item.modifiers,
+            affix_index,
+            &master_store.item_affixes_table,
+        ),
     } {
         return Err(AppError::UserError("forge operation failed".into()));
     }
 
-    *item = items_controller::create_item_specs(item.base.clone(), item.modifiers.clone(), 0.0);
+    *item = items_controller::create_item_specs(
+        item.base.clone(),
+        item.modifiers.clone(),
+        0.0,
+        &master_store.items_store.signature_key,
+    );
 
     if payload.item_index < 9 && item.required_level as i32 > character.max_area_level {
         return Err(AppError::UserError(
@@ -124,6 +168,45 @@ pub async fn post_affix(
     }))
 }
 
+pub async fn post_families(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ForgeAffixFamiliesRequest>,
+) -> Result<Json<ForgeAffixFamiliesResponse>, AppError> {
+    let character = db::characters::read_character(&db_pool, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+
+    let (inventory_data, _, _, _) =
+        db::characters_data::load_character_data(&db_pool, &payload.character_id)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+    let inventory = inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    let item = inventory
+        .nth(payload.item_index as usize)
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(ForgeAffixFamiliesResponse {
+        families: loot_generator::available_affix_families(
+            &item.base,
+            &item.modifiers,
+            &master_store.item_affixes_table,
+        )
+        .into_iter()
+        .map(|affix_blueprint| ForgeAffixFamily {
+            family: affix_blueprint.family.clone(),
+            name: affix_blueprint.name.clone(),
+            affix_type: affix_blueprint.affix_type,
+        })
+        .collect(),
+    }))
+}
+
 pub async fn post_upgrade(
     State(db_pool): State<db::DbPool>,
     State(master_store): State<MasterStore>,
@@ -151,6 +234,12 @@ pub async fn post_upgrade(
         .nth_mut(payload.item_index as usize)
         .ok_or(AppError::NotFound)?;
 
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError(
+            "corrupted items cannot be modified".into(),
+        ));
+    }
+
     let price = computations::upgrade_item_price(item).ok_or(AppError::UserError(
         "maximum upgrade level reached for that item".into(),
     ))?;
@@ -169,7 +258,7 @@ pub async fn post_upgrade(
         return Err(AppError::UserError("not enough gems".into()));
     }
 
-    *item = items_controller::upgrade_item(item)?;
+    *item = items_controller::upgrade_item(item, &master_store.items_store.signature_key)?;
 
     if payload.item_index < 9 && item.required_level as i32 > character.max_area_level {
         return Err(AppError::UserError(
@@ -188,6 +277,165 @@ pub async fn post_upgrade(
     }))
 }
 
+pub async fn post_corrupt(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ForgeCorruptRequest>,
+) -> Result<Json<ForgeCorruptResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, _, _, _) =
+        db::characters_data::load_character_data(&mut *tx, &payload.character_id)
+            .await?
+            .ok_or(AppError::UserError("newbies can't forge items".into()))?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    // An equipped item whose power level becomes too high is moved to the bag,
+    // so there must be room for it before rolling the outcome
+    let equipped_slot = ItemSlot::try_from(payload.item_index as usize).ok();
+    if equipped_slot.is_some() && inventory.bag.len() >= inventory.max_bag_size as usize {
+        return Err(AppError::UserError(
+            "not enough space to corrupt an equipped item".into(),
+        ));
+    }
+
+    let item = inventory
+        .nth_mut(payload.item_index as usize)
+        .ok_or(AppError::NotFound)?;
+
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError("item is already corrupted".into()));
+    }
+
+    let price = forge::corrupt_price(item.modifiers.level);
+
+    let character_resources = db::characters::update_character_resources(
+        &mut *tx,
+        &payload.character_id,
+        -price,
+        0.0,
+        0.0,
+        0.0,
+    )
+    .await?;
+
+    if character_resources.resource_gems < 0.0 {
+        return Err(AppError::UserError("not enough gems".into()));
+    }
+
+    let (outcome, corrupted_item) = loot_generator::corrupt_item(
+        item,
+        &master_store.corruption_table,
+        &master_store.items_store,
+        &master_store.item_affixes_table,
+        &master_store.item_adjectives_table,
+        &master_store.item_nouns_table,
+    )
+    .ok_or(AppError::UserError("forge operation failed".into()))?;
+
+    *item = corrupted_item;
+
+    if let Some(item_slot) = equipped_slot
+        && item.required_level as i32 > character.max_area_level
+    {
+        inventory_controller::unequip_item_to_bag(&mut inventory, item_slot)?;
+    }
+
+    db::characters_data::save_character_inventory(&mut *tx, &payload.character_id, &inventory)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ForgeCorruptResponse {
+        resource_gems: character_resources.resource_gems,
+        inventory,
+        outcome,
+    }))
+}
+
+pub async fn post_divine(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ForgeDivineRequest>,
+) -> Result<Json<ForgeDivineResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, _, _, _) =
+        db::characters_data::load_character_data(&mut *tx, &payload.character_id)
+            .await?
+            .ok_or(AppError::UserError("newbies can't forge items".into()))?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    let item = inventory
+        .nth_mut(payload.item_index as usize)
+        .ok_or(AppError::NotFound)?;
+
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError(
+            "corrupted items cannot be modified".into(),
+        ));
+    }
+
+    let price = forge::divine_price(item.modifiers.level);
+
+    let character_resources = db::characters::update_character_resources(
+        &mut *tx,
+        &payload.character_id,
+        -price,
+        0.0,
+        0.0,
+        0.0,
+    )
+    .await?;
+
+    if character_resources.resource_gems < 0.0 {
+        return Err(AppError::UserError("not enough gems".into()));
+    }
+
+    if !loot_generator::divine_unique(&item.base, &mut item.modifiers) {
+        return Err(AppError::UserError(
+            "only Unique items can be divined".into(),
+        ));
+    }
+
+    *item = items_controller::create_item_specs(
+        item.base.clone(),
+        item.modifiers.clone(),
+        0.0,
+        &master_store.items_store.signature_key,
+    );
+
+    db::characters_data::save_character_inventory(&mut *tx, &payload.character_id, &inventory)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ForgeDivineResponse {
+        resource_gems: character_resources.resource_gems,
+        inventory,
+    }))
+}
+
 pub async fn post_gamble(
     State(db_pool): State<db::DbPool>,
     State(master_store): State<MasterStore>,
@@ -264,3 +512,141 @@ pub async fn post_gamble(
         inventory,
     }))
 }
+
+pub async fn post_socket_rune(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ForgeSocketRuneRequest>,
+) -> Result<Json<ForgeSocketRuneResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, _, _, _) =
+        db::characters_data::load_character_data(&mut *tx, &payload.character_id)
+            .await?
+            .ok_or(AppError::UserError("newbies can't forge items".into()))?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    // Removing the rune from the bag shifts the index of the following bag items
+    let item_index = match payload.item_index.checked_sub(9) {
+        Some(bag_index) if bag_index == payload.rune_index as u32 => {
+            return Err(AppError::UserError(
+                "cannot socket a rune into itself".into(),
+            ));
+        }
+        Some(bag_index) if bag_index > payload.rune_index as u32 => payload.item_index - 1,
+        _ => payload.item_index,
+    };
+
+    let rune = inventory_controller::remove_item_from_bag(&mut inventory, payload.rune_index)?;
+
+    let item = inventory
+        .nth_mut(item_index as usize)
+        .ok_or(AppError::NotFound)?;
+
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError(
+            "corrupted items cannot be modified".into(),
+        ));
+    }
+
+    *item = items_controller::socket_rune(item, &rune, &master_store.items_store.signature_key)?;
+
+    if item_index < 9 && item.required_level as i32 > character.max_area_level {
+        return Err(AppError::UserError(
+            "cannot socket equipped item to that power level".into(),
+        ));
+    }
+
+    db::characters_data::save_character_inventory(&mut *tx, &payload.character_id, &inventory)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ForgeSocketRuneResponse { inventory }))
+}
+
+pub async fn post_unsocket_rune(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ForgeUnsocketRuneRequest>,
+) -> Result<Json<ForgeUnsocketRuneResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+    verify_character_in_town(&character)?;
+
+    let (inventory_data, _, _, _) =
+        db::characters_data::load_character_data(&mut *tx, &payload.character_id)
+            .await?
+            .ok_or(AppError::UserError("newbies can't forge items".into()))?;
+
+    let mut inventory =
+        inventory_data_to_player_inventory(&master_store.items_store, inventory_data);
+
+    let item = inventory
+        .nth_mut(payload.item_index as usize)
+        .ok_or(AppError::NotFound)?;
+
+    if item.modifiers.corrupted {
+        return Err(AppError::UserError(
+            "corrupted items cannot be modified".into(),
+        ));
+    }
+
+    let (unsocketed_item, rune) = items_controller::unsocket_rune(
+        item,
+        payload.socket_index as usize,
+        &master_store.items_store.signature_key,
+    )?;
+    *item = unsocketed_item;
+
+    let price = forge::unsocket_rune_price(rune.level);
+
+    let character_resources = db::characters::update_character_resources(
+        &mut *tx,
+        &payload.character_id,
+        -price,
+        0.0,
+        0.0,
+        0.0,
+    )
+    .await?;
+
+    if character_resources.resource_gems < 0.0 {
+        return Err(AppError::UserError("not enough gems".into()));
+    }
+
+    let rune_destroyed = items_controller::roll_rune_destroyed(&mut rng::roll_seed());
+
+    if !rune_destroyed {
+        let rune = items_controller::init_item_specs_from_store(&master_store.items_store, rune)
+            .ok_or(AppError::UserError("unknown rune".into()))?;
+        inventory_controller::store_item_to_bag(&mut inventory, rune)?;
+    }
+
+    db::characters_data::save_character_inventory(&mut *tx, &payload.character_id, &inventory)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ForgeUnsocketRuneResponse {
+        resource_gems: character_resources.resource_gems,
+        inventory,
+        rune_destroyed,
+    }))
+}
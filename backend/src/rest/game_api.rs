@@ -2,21 +2,26 @@ use anyhow::Result;
 
 use axum::{
     Extension, Json, Router,
-    extract::State,
+    extract::{Path, Query, State},
     middleware,
     routing::{get, post},
 };
 
+use serde::Deserialize;
+
 use shared::{
     data::area::AreaLevel,
     http::{
         client::{
-            AscendPassivesRequest, BuyBenedictionsRequest, SaveFavoriteSkillsRequest,
-            SavePassivesRequest, SaveSkillMasteryUpgradesRequest, SocketPassiveRequest,
+            AscendPassivesRequest, BuyBenedictionsRequest, ClaimChallengeRewardRequest,
+            SaveAtlasModifiersRequest, SaveFavoriteSkillsRequest, SavePassivesRequest,
+            SaveSkillMasteryUpgradesRequest, SocketPassiveRequest,
         },
         server::{
-            AscendPassivesResponse, BuyBenedictionsResponse, GetAreasResponse,
-            GetBenedictionsResponse, GetPassivesResponse, GetSkillsResponse, GetStatusesResponse,
+            AscendPassivesResponse, BuyBenedictionsResponse, ClaimChallengeRewardResponse,
+            GetAreaLootResponse, GetAreasResponse, GetAtlasResponse, GetBenedictionsResponse,
+            GetChallengesResponse, GetItemLootResponse, GetItemSetsResponse, GetPassivesResponse,
+            GetSkillsResponse, GetStatusesResponse, SaveAtlasModifiersResponse,
             SaveFavoriteSkillsResponse, SavePassivesResponse, SaveSkillMasteryUpgradesResponse,
             SocketPassiveResponse,
         },
@@ -28,13 +33,14 @@ use crate::{
     auth::{self, User},
     db,
     game::{
+        challenges::ChallengesStore,
         data::{
             inventory_data::inventory_data_to_player_inventory,
             passives::ascension_data_to_passives_tree_ascension,
         },
         systems::{
-            benedictions_controller, inventory_controller, passives_controller,
-            skill_masteries_controller, skills_updater,
+            atlas_controller, benedictions_controller, challenges_controller, inventory_controller,
+            loot_explainer, passives_controller, skill_masteries_controller, skills_updater,
         },
     },
     rest::utils::{verify_character_in_town, verify_character_user},
@@ -48,6 +54,8 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
         .route("/game/passives/socket", post(post_socket_passive))
         .route("/game/passives/build", post(post_save_passives_build))
         .route("/game/benedictions", post(post_buy_benedictions))
+        .route("/game/atlas", post(post_save_atlas_modifiers))
+        .route("/game/challenges/claim", post(post_claim_challenge_reward))
         .route(
             "/game/skill-masteries/favorites",
             post(post_save_favorite_skills),
@@ -65,8 +73,13 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
         .route("/game/areas", get(get_areas))
         .route("/game/skills", get(get_skills))
         .route("/game/statuses", get(get_statuses))
+        .route("/game/item-sets", get(get_item_sets))
         .route("/game/passives", get(get_passives))
         .route("/game/benedictions", get(get_benedictions))
+        .route("/game/atlas", get(get_atlas))
+        .route("/game/challenges", get(get_challenges))
+        .route("/game/loot/areas/{area_id}", get(get_area_loot))
+        .route("/game/loot/items/{item_id}", get(get_item_loot))
         .merge(auth_routes)
 }
 
@@ -79,6 +92,19 @@ pub async fn get_areas(
             .iter()
             .map(|(k, v)| (k.clone(), v.specs.clone()))
             .collect(),
+        bosses_drops: master_store
+            .area_blueprints_store
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.clone(),
+                    v.bosses
+                        .iter()
+                        .filter_map(|boss| boss.possible_drops(&master_store.items_store))
+                        .collect(),
+                )
+            })
+            .collect(),
     }))
 }
 
@@ -99,6 +125,14 @@ pub async fn get_statuses(
     }))
 }
 
+pub async fn get_item_sets(
+    State(master_store): State<MasterStore>,
+) -> Result<Json<GetItemSetsResponse>, AppError> {
+    Ok(Json(GetItemSetsResponse {
+        item_sets: (*master_store.item_sets_store).clone(),
+    }))
+}
+
 pub async fn get_passives(
     State(master_store): State<MasterStore>,
 ) -> Result<Json<GetPassivesResponse>, AppError> {
@@ -119,6 +153,81 @@ pub async fn get_benedictions(
     }))
 }
 
+pub async fn get_atlas(
+    State(master_store): State<MasterStore>,
+) -> Result<Json<GetAtlasResponse>, AppError> {
+    Ok(Json(GetAtlasResponse {
+        atlas_specs: master_store
+            .atlas_store
+            .get("default")
+            .cloned()
+            .unwrap_or_default(),
+    }))
+}
+
+pub async fn get_challenges(
+    State(challenges_store): State<ChallengesStore>,
+) -> Result<Json<GetChallengesResponse>, AppError> {
+    Ok(Json(GetChallengesResponse {
+        challenges: challenges_store
+            .active_challenges
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect(),
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct AreaLootQuery {
+    area_level: AreaLevel,
+    #[serde(default)]
+    boss: bool,
+}
+
+pub async fn get_area_loot(
+    State(master_store): State<MasterStore>,
+    Path(area_id): Path<String>,
+    Query(query): Query<AreaLootQuery>,
+) -> Result<Json<GetAreaLootResponse>, AppError> {
+    Ok(Json(GetAreaLootResponse {
+        loot: loot_explainer::explain_area_loot(
+            &master_store,
+            &area_id,
+            query.area_level,
+            query.boss,
+        )
+        .ok_or(AppError::NotFound)?,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct ItemLootQuery {
+    item_level: Option<AreaLevel>,
+}
+
+pub async fn get_item_loot(
+    State(master_store): State<MasterStore>,
+    Path(item_id): Path<String>,
+    Query(query): Query<ItemLootQuery>,
+) -> Result<Json<GetItemLootResponse>, AppError> {
+    let item_level = query
+        .item_level
+        .or_else(|| {
+            master_store
+                .items_store
+                .content
+                .get(&item_id)
+                .map(|base| base.min_area_level)
+        })
+        .unwrap_or(1);
+
+    Ok(Json(GetItemLootResponse {
+        affixes: loot_explainer::explain_item_affixes(&master_store, &item_id, item_level)
+            .ok_or(AppError::NotFound)?,
+        drop_areas: loot_explainer::find_item_drop_areas(&master_store, &item_id),
+    }))
+}
+
 pub async fn post_ascend_passives(
     State(master_store): State<MasterStore>,
     State(db_pool): State<db::DbPool>,
@@ -291,6 +400,65 @@ pub async fn post_buy_benedictions(
     }))
 }
 
+pub async fn post_save_atlas_modifiers(
+    State(master_store): State<MasterStore>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<SaveAtlasModifiersRequest>,
+) -> Result<Json<SaveAtlasModifiersResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+    verify_character_in_town(&character)?;
+
+    let atlas = atlas_controller::update_atlas_modifiers(
+        &mut tx,
+        &master_store,
+        &payload.character_id,
+        &payload.purchased_modifiers,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(SaveAtlasModifiersResponse { atlas }))
+}
+
+pub async fn post_claim_challenge_reward(
+    State(challenges_store): State<ChallengesStore>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ClaimChallengeRewardRequest>,
+) -> Result<Json<ClaimChallengeRewardResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let character = db::characters::read_character(&mut *tx, &payload.character_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    verify_character_user(&character, &user)?;
+
+    let resources = challenges_controller::claim_challenge_reward(
+        &mut tx,
+        &challenges_store,
+        &payload.character_id,
+        &character.realm_id,
+        &payload.schedule_id,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(ClaimChallengeRewardResponse {
+        resource_gems: resources.resource_gems,
+        resource_shards: resources.resource_shards,
+    }))
+}
+
 pub async fn post_save_favorite_skills(
     State(db_pool): State<db::DbPool>,
     Extension(user): Extension<User>,
@@ -17,7 +17,7 @@ use shared::{
 };
 
 use crate::{
-    app_state::{AppState, MasterStore},
+    app_state::{AppSettings, AppState, MasterStore},
     auth::{self, User},
     db::{self, market::MarketEntry},
     game::{
@@ -30,6 +30,9 @@ use crate::{
 
 use super::AppError;
 
+/// Purchases from this price require a two-factor code when enabled
+const LARGE_PURCHASE_PRICE: f64 = 1e6;
+
 pub fn routes(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/market", post(post_browse_market))
@@ -73,12 +76,23 @@ pub async fn post_browse_market(
 }
 
 pub async fn post_buy_market_item(
+    State(app_settings): State<AppSettings>,
     State(db_pool): State<db::DbPool>,
     State(master_store): State<MasterStore>,
     State(chat_integration): State<ChatIntegration>,
     Extension(user): Extension<User>,
     Json(payload): Json<BuyMarketItemRequest>,
 ) -> Result<Json<BuyMarketItemResponse>, AppError> {
+    // Verified before the transaction, as failed attempts must be recorded
+    let totp_confirmed = match payload.totp_code.as_deref() {
+        Some(totp_code) => {
+            auth::verify_sensitive_action(&app_settings, &db_pool, &user.user_id, Some(totp_code))
+                .await?;
+            true
+        }
+        None => !db::user_totp::is_totp_enabled(&db_pool, &user.user_id).await?,
+    };
+
     let mut tx = db_pool.begin().await?;
 
     let character = db::characters::read_character(&mut *tx, &payload.character_id)
@@ -114,6 +128,11 @@ pub async fn post_buy_market_item(
             return Err(AppError::Forbidden);
         }
 
+        // Checked against the locked listing, so that the price cannot change meanwhile
+        if market_buy_entry.price >= LARGE_PURCHASE_PRICE && !totp_confirmed {
+            return Err(AppError::TotpRequired);
+        }
+
         market_buy_entry.price
     } else {
         0.0
@@ -162,6 +181,12 @@ pub async fn post_buy_market_item(
 
     tx.commit().await?;
 
+    if character.user_id != item_bought.user_id {
+        metrics::counter!("market_transactions_total", "kind" => "buy").increment(1);
+    } else {
+        metrics::counter!("market_transactions_total", "kind" => "remove").increment(1);
+    }
+
     Ok(Json(BuyMarketItemResponse {
         resource_gems: character_resources.resource_gems,
         inventory,
@@ -177,6 +202,8 @@ pub async fn post_reject_market_item(
         return Err(AppError::NotFound);
     }
 
+    metrics::counter!("market_transactions_total", "kind" => "reject").increment(1);
+
     Ok(Json(RejectMarketItemResponse {}))
 }
 
@@ -254,6 +281,8 @@ pub async fn post_sell_market_item(
 
     tx.commit().await?;
 
+    metrics::counter!("market_transactions_total", "kind" => "sell").increment(1);
+
     Ok(Json(SellMarketItemResponse {
         inventory,
         stash: stash.into(),
@@ -308,6 +337,8 @@ pub async fn post_edit_market_item(
 
     tx.commit().await?;
 
+    metrics::counter!("market_transactions_total", "kind" => "edit").increment(1);
+
     Ok(Json(EditMarketItemResponse {}))
 }
 
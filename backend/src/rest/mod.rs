@@ -2,14 +2,19 @@ use axum::Router;
 
 use crate::app_state::AppState;
 
+mod admin_api;
 mod app_error;
 mod characters_api;
+mod chat_api;
 mod forge_api;
 mod game_api;
 mod inventory_api;
 mod market_api;
+mod moderation_api;
+mod oauth_api;
 mod public_api;
 mod stashes_api;
+mod totp_api;
 mod users_api;
 pub mod utils;
 
@@ -17,7 +22,9 @@ pub use app_error::AppError;
 
 pub fn routes(app_state: AppState) -> Router<AppState> {
     Router::new()
+        .merge(admin_api::routes(app_state.clone()))
         .merge(characters_api::routes(app_state.clone()))
+        .merge(chat_api::routes())
         .merge(game_api::routes(app_state.clone()))
         .merge(public_api::routes())
         .merge(users_api::routes(app_state.clone()))
@@ -25,4 +32,7 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
         .merge(stashes_api::routes(app_state.clone()))
         .merge(forge_api::routes(app_state.clone()))
         .merge(inventory_api::routes(app_state.clone()))
+        .merge(moderation_api::routes(app_state.clone()))
+        .merge(oauth_api::routes(app_state.clone()))
+        .merge(totp_api::routes(app_state.clone()))
 }
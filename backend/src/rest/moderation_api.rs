@@ -0,0 +1,407 @@
+use anyhow::Result;
+
+use axum::{
+    Extension, Json, Router,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    middleware,
+    routing::{get, post},
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use backend_shared::{
+    http::chat::CHAT_SIGNATURE_HEADER,
+    signature::{self, HmacKey},
+};
+use shared::{
+    http::{
+        client::{
+            AdminChatSanctionRequest, AdminDeleteChatMessagesRequest,
+            AdminRevokeChatSanctionRequest, ReportChatUserRequest,
+        },
+        server::{
+            AdminChatModerationLog, AdminChatModerationLogsResponse, AdminChatReport,
+            AdminChatReportsResponse, AdminChatSanctionsResponse, ReportChatUserResponse,
+        },
+    },
+    types::PaginationLimit,
+};
+use shared_chat::{
+    moderation::{
+        ChatCommandBroadcastRequest, ChatCommandSanctionRequest, ChatReportId, ChatSanction,
+    },
+    types::ChatContent,
+};
+
+use crate::{
+    app_state::{AppState, MasterStore},
+    auth::{self, User, UserId},
+    db,
+    integration::chat::ChatIntegration,
+};
+
+use super::AppError;
+
+pub fn routes(app_state: AppState) -> Router<AppState> {
+    let admin_routes = Router::new()
+        .route(
+            "/admin/chat/sanctions",
+            get(get_sanctions).post(post_sanction),
+        )
+        .route("/admin/chat/sanctions/revoke", post(post_revoke_sanction))
+        .route("/admin/chat/messages/delete", post(post_delete_messages))
+        .route("/admin/chat/reports", get(get_reports))
+        .route(
+            "/admin/chat/reports/{report_id}/resolve",
+            post(post_resolve_report),
+        )
+        .route("/admin/chat/logs", get(get_moderation_logs))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            auth::admin_middleware,
+        ));
+
+    // Admin chat commands, relayed by the chat server
+    let chat_command_routes = Router::new()
+        .route(
+            "/chat/moderation/sanctions",
+            post(post_chat_command_sanction),
+        )
+        .route(
+            "/chat/moderation/broadcast",
+            post(post_chat_command_broadcast),
+        );
+
+    Router::new()
+        .route("/chat/report", post(post_report))
+        .merge(admin_routes)
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            auth::authorization_middleware,
+        ))
+        .merge(chat_command_routes)
+}
+
+#[derive(Deserialize)]
+struct ReportsQuery {
+    #[serde(default)]
+    include_resolved: bool,
+    #[serde(default)]
+    skip: i64,
+    #[serde(default)]
+    limit: PaginationLimit,
+}
+
+#[derive(Deserialize)]
+struct ModerationLogsQuery {
+    user_id: Option<UserId>,
+    #[serde(default)]
+    skip: i64,
+    #[serde(default)]
+    limit: PaginationLimit,
+}
+
+async fn post_report(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<ReportChatUserRequest>,
+) -> Result<Json<ReportChatUserResponse>, AppError> {
+    if payload.user_id == user.user_id {
+        return Err(AppError::UserError("cannot report yourself".to_string()));
+    }
+
+    // Context is taken from the chat history so it cannot be forged by the reporter
+    let context_messages = chat_integration.read_user_messages(payload.user_id).await?;
+
+    db::chat_moderation::create_report(
+        &db_pool,
+        &user.user_id,
+        &payload.user_id,
+        &payload.reason,
+        serde_json::to_value(&context_messages).map_err(anyhow::Error::from)?,
+    )
+    .await?;
+
+    Ok(Json(ReportChatUserResponse {}))
+}
+
+async fn get_sanctions(
+    State(chat_integration): State<ChatIntegration>,
+) -> Result<Json<AdminChatSanctionsResponse>, AppError> {
+    Ok(Json(AdminChatSanctionsResponse {
+        sanctions: chat_integration.read_sanctions().await?,
+    }))
+}
+
+async fn post_sanction(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminChatSanctionRequest>,
+) -> Result<Json<()>, AppError> {
+    let sanction = ChatSanction {
+        sanction_type: payload.sanction_type,
+        until: payload
+            .duration_secs
+            .map(|duration_secs| Utc::now() + Duration::seconds(duration_secs)),
+        reason: payload.reason,
+    };
+
+    apply_sanction(
+        &db_pool,
+        &chat_integration,
+        &user,
+        payload.user_id,
+        &sanction,
+    )
+    .await?;
+
+    Ok(Json(()))
+}
+
+async fn post_chat_command_sanction(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    State(chat_integration): State<ChatIntegration>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<()>, AppError> {
+    let payload: ChatCommandSanctionRequest =
+        verify_chat_command(&master_store.items_store.signature_key, &headers, &body)?;
+    let moderator = read_moderator(&db_pool, &payload.moderator_id).await?;
+
+    apply_sanction(
+        &db_pool,
+        &chat_integration,
+        &moderator,
+        payload.user_id,
+        &payload.sanction,
+    )
+    .await?;
+
+    Ok(Json(()))
+}
+
+async fn post_chat_command_broadcast(
+    State(db_pool): State<db::DbPool>,
+    State(master_store): State<MasterStore>,
+    State(chat_integration): State<ChatIntegration>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<()>, AppError> {
+    let payload: ChatCommandBroadcastRequest =
+        verify_chat_command(&master_store.items_store.signature_key, &headers, &body)?;
+    let moderator = read_moderator(&db_pool, &payload.moderator_id).await?;
+
+    broadcast(&db_pool, &chat_integration, &moderator, payload.message).await?;
+
+    Ok(Json(()))
+}
+
+async fn post_revoke_sanction(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminRevokeChatSanctionRequest>,
+) -> Result<Json<()>, AppError> {
+    if !chat_integration
+        .revoke_sanction(payload.user_id, payload.sanction_type)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+
+    log_action(
+        &db_pool,
+        &user,
+        Some(&payload.user_id),
+        "revoke_sanction",
+        &payload,
+    )
+    .await?;
+
+    Ok(Json(()))
+}
+
+async fn post_delete_messages(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<AdminDeleteChatMessagesRequest>,
+) -> Result<Json<()>, AppError> {
+    chat_integration
+        .delete_messages(payload.user_id, payload.sent_at)
+        .await?;
+
+    log_action(
+        &db_pool,
+        &user,
+        Some(&payload.user_id),
+        "delete_messages",
+        &payload,
+    )
+    .await?;
+
+    Ok(Json(()))
+}
+
+async fn get_reports(
+    State(db_pool): State<db::DbPool>,
+    Query(query): Query<ReportsQuery>,
+) -> Result<Json<AdminChatReportsResponse>, AppError> {
+    let reports = db::chat_moderation::read_reports(
+        &db_pool,
+        query.include_resolved,
+        query.skip,
+        *query.limit,
+    )
+    .await?
+    .into_iter()
+    .map(|entry| AdminChatReport {
+        report_id: entry.report_id,
+        reporter_id: entry.reporter_id,
+        reported_user_id: entry.reported_user_id,
+        reason: entry.reason,
+        context_messages: serde_json::from_value(entry.context_messages).unwrap_or_default(),
+        created_at: entry.created_at.into(),
+        resolved_at: entry.resolved_at.map(Into::into),
+        resolved_by: entry.resolved_by,
+    })
+    .collect();
+
+    Ok(Json(AdminChatReportsResponse { reports }))
+}
+
+async fn post_resolve_report(
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Path(report_id): Path<ChatReportId>,
+) -> Result<Json<()>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    let reported_user_id = db::chat_moderation::resolve_report(&mut *tx, report_id, &user.user_id)
+        .await?
+        .ok_or(AppError::NotFound)?;
+
+    log_action(
+        &mut *tx,
+        &user,
+        Some(&reported_user_id),
+        "resolve_report",
+        &report_id,
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(()))
+}
+
+async fn get_moderation_logs(
+    State(db_pool): State<db::DbPool>,
+    Query(query): Query<ModerationLogsQuery>,
+) -> Result<Json<AdminChatModerationLogsResponse>, AppError> {
+    let logs = db::chat_moderation::read_moderation_logs(
+        &db_pool,
+        query.user_id.as_ref(),
+        query.skip,
+        *query.limit,
+    )
+    .await?
+    .into_iter()
+    .map(|entry| AdminChatModerationLog {
+        moderator_id: entry.moderator_id,
+        target_user_id: entry.target_user_id,
+        action: entry.action,
+        details: entry.details,
+        created_at: entry.created_at.into(),
+    })
+    .collect();
+
+    Ok(Json(AdminChatModerationLogsResponse { logs }))
+}
+
+async fn apply_sanction(
+    db_pool: &db::DbPool,
+    chat_integration: &ChatIntegration,
+    moderator: &User,
+    user_id: UserId,
+    sanction: &ChatSanction,
+) -> Result<(), AppError> {
+    chat_integration.apply_sanction(user_id, sanction).await?;
+
+    log_action(
+        db_pool,
+        moderator,
+        Some(&user_id),
+        "apply_sanction",
+        sanction,
+    )
+    .await
+}
+
+/// Send a system message to everyone in the chat
+pub async fn broadcast(
+    db_pool: &db::DbPool,
+    chat_integration: &ChatIntegration,
+    moderator: &User,
+    message: ChatContent,
+) -> Result<(), AppError> {
+    chat_integration
+        .broadcast_message(message.clone().into_inner(), None)
+        .await?;
+
+    log_action(db_pool, moderator, None, "broadcast", &message).await
+}
+
+/// Decode a request signed by the chat server with the shared key
+fn verify_chat_command<T: DeserializeOwned>(
+    signature_key: &HmacKey,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<T, AppError> {
+    if !headers
+        .get(CHAT_SIGNATURE_HEADER)
+        .and_then(|header| header.to_str().ok())
+        .is_some_and(|header| signature::verify_hex_hmac(body, header, signature_key))
+    {
+        return Err(AppError::Unauthorized("invalid signature".to_string()));
+    }
+
+    serde_json::from_slice(body).map_err(|err| AppError::UserError(err.to_string()))
+}
+
+/// The chat only relays the commands, admin rights are checked again against the database
+async fn read_moderator(db_pool: &db::DbPool, user_id: &UserId) -> Result<User, AppError> {
+    match db::users::read_user(db_pool, user_id).await? {
+        Some(user) if user.is_admin => Ok(user.into()),
+        _ => Err(AppError::Forbidden),
+    }
+}
+
+async fn log_action<'c>(
+    executor: impl db::pool::DbExecutor<'c>,
+    moderator: &User,
+    target_user_id: Option<&UserId>,
+    action: &str,
+    details: &impl Serialize,
+) -> Result<(), AppError> {
+    tracing::info!(
+        "chat moderation: '{}' {action} on {target_user_id:?}",
+        moderator.username
+    );
+
+    db::chat_moderation::create_moderation_log(
+        executor,
+        &moderator.user_id,
+        target_user_id,
+        action,
+        serde_json::to_value(details).map_err(anyhow::Error::from)?,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,398 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, header::SET_COOKIE},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+use backend_shared::profanities_checker::ProfanitiesChecker;
+use shared::{
+    constants::DEFAULT_MAX_CHARACTERS,
+    data::user::{OAuthProviderInfo, UserIdentity},
+    http::{
+        client::{OAuthAuthorizeRequest, OAuthCallbackRequest},
+        server::{
+            GetOAuthProvidersResponse, GetUserIdentitiesResponse, LinkUserIdentityResponse,
+            OAuthAuthorizeResponse, UnlinkUserIdentityResponse,
+        },
+    },
+};
+
+use crate::{
+    app_state::{AppSettings, AppState},
+    auth::{self, OAuthIntent, OAuthState, User},
+    db,
+    integration::oauth::{OAuthIdentity, OAuthIntegration, OAuthProvider},
+};
+
+use super::{AppError, users_api::sign_in_response};
+
+/// Leave room for the random suffix added on conflicts
+const USERNAME_BASE_MAX_LENGTH: usize = 15;
+const USERNAME_ATTEMPTS: usize = 5;
+
+pub fn routes(app_state: AppState) -> Router<AppState> {
+    let auth_routes = Router::new()
+        .route("/account/identities", get(get_user_identities))
+        .route(
+            "/account/identities/{provider_id}",
+            post(post_link_identity).delete(delete_user_identity),
+        )
+        .route(
+            "/account/identities/{provider_id}/authorize",
+            post(post_link_authorize),
+        )
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            auth::authorization_middleware,
+        ));
+
+    Router::new()
+        .route("/account/oauth/providers", get(get_oauth_providers))
+        .route(
+            "/account/oauth/{provider_id}/authorize",
+            post(post_oauth_authorize),
+        )
+        .route(
+            "/account/oauth/{provider_id}/signin",
+            post(post_oauth_sign_in),
+        )
+        .merge(auth_routes)
+}
+
+async fn get_oauth_providers(
+    State(oauth_integration): State<OAuthIntegration>,
+) -> Result<Json<GetOAuthProvidersResponse>, AppError> {
+    Ok(Json(GetOAuthProvidersResponse {
+        providers: oauth_integration
+            .providers()
+            .map(|(provider_id, provider)| OAuthProviderInfo {
+                provider_id: provider_id.clone(),
+                name: provider.name.clone(),
+            })
+            .collect(),
+    }))
+}
+
+async fn post_oauth_authorize(
+    State(app_settings): State<AppSettings>,
+    State(oauth_integration): State<OAuthIntegration>,
+    Path(provider_id): Path<String>,
+    Json(payload): Json<OAuthAuthorizeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    authorize_response(
+        &app_settings,
+        &oauth_integration,
+        &provider_id,
+        OAuthIntent::SignIn {
+            accepted_terms: payload.accepted_terms,
+        },
+    )
+    .await
+}
+
+async fn post_oauth_sign_in(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    State(oauth_integration): State<OAuthIntegration>,
+    State(profanities_checker): State<Arc<ProfanitiesChecker>>,
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<OAuthCallbackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let provider = oauth_integration
+        .provider(&provider_id)
+        .ok_or(AppError::NotFound)?;
+
+    let Some(OAuthState {
+        intent: OAuthIntent::SignIn { accepted_terms },
+        nonce,
+    }) = auth::authorize_oauth_state(&app_settings, &payload.state, &provider_id, &headers)
+    else {
+        return Err(AppError::Unauthorized("invalid state".to_string()));
+    };
+
+    let identity = fetch_identity(&app_settings, provider, &payload.code, &nonce).await?;
+
+    let user =
+        match db::user_identities::read_identity_user(&db_pool, &provider_id, &identity.subject)
+            .await?
+        {
+            Some(user) => user,
+            None if accepted_terms => {
+                create_identity_user(&db_pool, &profanities_checker, &provider_id, &identity)
+                    .await?
+            }
+            None => {
+                return Err(AppError::UserError(format!(
+                    "no account is linked to this {} account, please create one first",
+                    provider.name
+                )));
+            }
+        };
+
+    let mut response = sign_in_response(auth::sign_in_user(&app_settings, &db_pool, user).await?)?;
+    response
+        .headers_mut()
+        .append(SET_COOKIE, expired_oauth_nonce_cookie()?);
+    Ok(response)
+}
+
+async fn get_user_identities(
+    State(db_pool): State<db::DbPool>,
+    State(oauth_integration): State<OAuthIntegration>,
+    Extension(user): Extension<User>,
+) -> Result<Json<GetUserIdentitiesResponse>, AppError> {
+    let identities = db::user_identities::read_user_identities(&db_pool, &user.user_id)
+        .await?
+        .into_iter()
+        .map(|identity| UserIdentity {
+            provider: OAuthProviderInfo {
+                name: oauth_integration
+                    .provider(&identity.provider)
+                    .map(|provider| provider.name.clone())
+                    .unwrap_or_else(|| identity.provider.clone()),
+                provider_id: identity.provider,
+            },
+            provider_username: identity.provider_username,
+            linked_at: DateTime::<Utc>::from(identity.created_at),
+        })
+        .collect();
+
+    Ok(Json(GetUserIdentitiesResponse {
+        identities,
+        has_password: db::users::has_password(&db_pool, &user.user_id).await?,
+    }))
+}
+
+async fn post_link_authorize(
+    State(app_settings): State<AppSettings>,
+    State(oauth_integration): State<OAuthIntegration>,
+    Extension(user): Extension<User>,
+    Path(provider_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    authorize_response(
+        &app_settings,
+        &oauth_integration,
+        &provider_id,
+        OAuthIntent::Link {
+            user_id: user.user_id,
+        },
+    )
+    .await
+}
+
+async fn post_link_identity(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    State(oauth_integration): State<OAuthIntegration>,
+    Extension(user): Extension<User>,
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<OAuthCallbackRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let provider = oauth_integration
+        .provider(&provider_id)
+        .ok_or(AppError::NotFound)?;
+
+    let nonce =
+        match auth::authorize_oauth_state(&app_settings, &payload.state, &provider_id, &headers) {
+            Some(OAuthState {
+                intent: OAuthIntent::Link { user_id },
+                nonce,
+            }) if user_id == user.user_id => nonce,
+            Some(_) => return Err(AppError::Forbidden),
+            None => return Err(AppError::Unauthorized("invalid state".to_string())),
+        };
+
+    let identity = fetch_identity(&app_settings, provider, &payload.code, &nonce).await?;
+
+    if !db::user_identities::create_user_identity(
+        &db_pool,
+        &user.user_id,
+        &provider_id,
+        &identity.subject,
+        identity.username.as_deref(),
+    )
+    .await?
+    {
+        return Err(AppError::UserError(
+            match db::user_identities::read_identity_user(&db_pool, &provider_id, &identity.subject)
+                .await?
+            {
+                Some(_) => format!("this {} account is already linked", provider.name),
+                None => format!("another {} account is already linked", provider.name),
+            },
+        ));
+    }
+
+    tracing::info!("user '{}' linked '{}' identity", user.user_id, provider_id);
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(SET_COOKIE, expired_oauth_nonce_cookie()?);
+
+    Ok((response_headers, Json(LinkUserIdentityResponse {})))
+}
+
+async fn delete_user_identity(
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Path(provider_id): Path<String>,
+) -> Result<Json<UnlinkUserIdentityResponse>, AppError> {
+    let mut tx = db_pool.begin().await?;
+
+    // Keep at least one way to sign in
+    if !db::users::has_password(&mut *tx, &user.user_id).await?
+        && db::user_identities::read_user_identities(&mut *tx, &user.user_id)
+            .await?
+            .len()
+            <= 1
+    {
+        return Err(AppError::UserError(
+            "set a password before unlinking your last sign-in method".into(),
+        ));
+    }
+
+    if !db::user_identities::delete_user_identity(&mut *tx, &user.user_id, &provider_id).await? {
+        return Err(AppError::NotFound);
+    }
+
+    tx.commit().await?;
+
+    Ok(Json(UnlinkUserIdentityResponse {}))
+}
+
+async fn authorize_response(
+    app_settings: &AppSettings,
+    oauth_integration: &OAuthIntegration,
+    provider_id: &str,
+    intent: OAuthIntent,
+) -> Result<Response, AppError> {
+    let provider = oauth_integration
+        .provider(provider_id)
+        .ok_or(AppError::NotFound)?;
+
+    let (state, nonce) = auth::oauth_state(app_settings, provider_id, intent)?;
+
+    // Binds the flow to this browser, the callback is rejected anywhere else
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        SET_COOKIE,
+        auth::oauth_nonce_cookie(&nonce)
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid oauth cookie"))?,
+    );
+
+    Ok((
+        response_headers,
+        Json(OAuthAuthorizeResponse {
+            authorize_url: provider
+                .authorize_url(&redirect_uri(app_settings), &state, &nonce)
+                .await?,
+            state,
+        }),
+    )
+        .into_response())
+}
+
+fn expired_oauth_nonce_cookie() -> Result<HeaderValue, AppError> {
+    Ok(auth::expired_oauth_nonce_cookie()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid oauth cookie"))?)
+}
+
+async fn fetch_identity(
+    app_settings: &AppSettings,
+    provider: &OAuthProvider,
+    code: &str,
+    nonce: &str,
+) -> Result<OAuthIdentity, AppError> {
+    provider
+        .fetch_identity(&redirect_uri(app_settings), code, nonce)
+        .await
+        .map_err(|e| {
+            tracing::warn!("failed to fetch '{}' identity: {e}", provider.name);
+            AppError::Unauthorized(format!("{} sign-in failed", provider.name))
+        })
+}
+
+fn redirect_uri(app_settings: &AppSettings) -> String {
+    format!("{}/oauth-callback", app_settings.frontend_url)
+}
+
+/// Derive a valid username from the provider one, users can rename themselves afterwards
+async fn create_identity_user(
+    db_pool: &db::DbPool,
+    profanities_checker: &ProfanitiesChecker,
+    provider_id: &str,
+    identity: &OAuthIdentity,
+) -> Result<User, AppError> {
+    let base_username: String = identity
+        .username
+        .as_deref()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .take(USERNAME_BASE_MAX_LENGTH)
+        .collect();
+    let base_username = if base_username.is_empty()
+        || profanities_checker.find_profanity(&base_username).is_some()
+    {
+        "player".to_string()
+    } else {
+        base_username
+    };
+
+    for attempt in 0..USERNAME_ATTEMPTS {
+        let username = if attempt == 0 {
+            base_username.clone()
+        } else {
+            format!("{base_username}_{}", rand::rng().random_range(1000..10000))
+        };
+
+        // Dropping the transaction on conflict rolls it back
+        let mut tx = db_pool.begin().await?;
+
+        let Some(user_id) = db::users::create_user(
+            &mut *tx,
+            &username,
+            None,
+            None,
+            None,
+            &Utc::now(),
+            DEFAULT_MAX_CHARACTERS as i16,
+        )
+        .await?
+        else {
+            continue;
+        };
+
+        if !db::user_identities::create_user_identity(
+            &mut *tx,
+            &user_id,
+            provider_id,
+            &identity.subject,
+            identity.username.as_deref(),
+        )
+        .await?
+        {
+            return Err(AppError::UserError("this account is already linked".into()));
+        }
+
+        tx.commit().await?;
+
+        tracing::info!("user '{}' created from '{}' identity", user_id, provider_id);
+
+        return Ok(User { user_id, username });
+    }
+
+    Err(AppError::UserError(
+        "couldn't find an available username".into(),
+    ))
+}
@@ -18,6 +18,7 @@ use shared::{
 use crate::{
     app_state::{AppState, DbPool},
     db,
+    game::challenges::ChallengesStore,
     integration::discord::DiscordIntegration,
 };
 
@@ -26,6 +27,7 @@ use super::AppError;
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/leaderboard", get(get_leaderboard))
+        .route("/leaderboard/challenges", get(get_challenges_leaderboard))
         .route("/players", get(get_players_count))
         .route("/news", get(get_news))
 }
@@ -35,6 +37,12 @@ struct LeaderboardQuery {
     realm: Option<Realm>,
 }
 
+#[derive(Deserialize)]
+struct ChallengesLeaderboardQuery {
+    realm: Option<Realm>,
+    schedule_id: String,
+}
+
 async fn get_news(
     State(discord): State<DiscordIntegration>,
 ) -> Result<Json<NewsResponse>, AppError> {
@@ -81,6 +89,30 @@ async fn get_leaderboard(
     }))
 }
 
+async fn get_challenges_leaderboard(
+    State(db_pool): State<DbPool>,
+    State(challenges_store): State<ChallengesStore>,
+    Query(query): Query<ChallengesLeaderboardQuery>,
+) -> Result<Json<LeaderboardResponse>, AppError> {
+    let realm_id = query.realm.unwrap_or_default().realm_id();
+    let challenge = challenges_store
+        .get(&query.schedule_id)
+        .ok_or(AppError::NotFound)?;
+
+    Ok(Json(LeaderboardResponse {
+        entries: db::leaderboard::get_area_leaderboard(
+            &db_pool,
+            10,
+            &realm_id,
+            &challenge.challenge_id(),
+        )
+        .await?
+        .into_iter()
+        .map(|entry| entry.into())
+        .collect(),
+    }))
+}
+
 impl From<db::leaderboard::LeaderboardEntry> for LeaderboardEntry {
     fn from(val: db::leaderboard::LeaderboardEntry) -> Self {
         LeaderboardEntry {
@@ -0,0 +1,150 @@
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+};
+
+use shared::http::{
+    client::{TotpCodeRequest, TotpSignInRequest},
+    server::{
+        DisableTotpResponse, EnableTotpResponse, EnrollTotpResponse, GetTotpStatusResponse,
+        RegenerateRecoveryCodesResponse,
+    },
+};
+
+use crate::{
+    app_state::{AppSettings, AppState},
+    auth::{self, User},
+    db,
+};
+
+use super::{AppError, users_api::sign_in_response};
+
+pub fn routes(app_state: AppState) -> Router<AppState> {
+    let auth_routes = Router::new()
+        .route("/account/totp", get(get_totp_status))
+        .route("/account/totp/enroll", post(post_enroll_totp))
+        .route("/account/totp/enable", post(post_enable_totp))
+        .route("/account/totp/disable", post(post_disable_totp))
+        .route(
+            "/account/totp/recovery-codes",
+            post(post_regenerate_recovery_codes),
+        )
+        .layer(middleware::from_fn_with_state(
+            app_state,
+            auth::authorization_middleware,
+        ));
+
+    Router::new()
+        .route("/account/signin/totp", post(post_totp_sign_in))
+        .merge(auth_routes)
+}
+
+async fn post_totp_sign_in(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    Json(payload): Json<TotpSignInRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let tokens =
+        auth::sign_in_totp(&app_settings, &db_pool, &payload.challenge, &payload.code).await?;
+
+    sign_in_response(auth::SignInOutcome::SignedIn(tokens))
+}
+
+async fn get_totp_status(
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+) -> Result<Json<GetTotpStatusResponse>, AppError> {
+    let enabled = db::user_totp::is_totp_enabled(&db_pool, &user.user_id).await?;
+
+    Ok(Json(GetTotpStatusResponse {
+        enabled,
+        recovery_codes_left: if enabled {
+            db::user_totp::count_recovery_codes(&db_pool, &user.user_id).await? as u32
+        } else {
+            0
+        },
+    }))
+}
+
+async fn post_enroll_totp(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+) -> Result<Json<EnrollTotpResponse>, AppError> {
+    let (secret_crypt, provisioning) = auth::generate_totp_secret(&app_settings, &user.username)?;
+
+    if !db::user_totp::upsert_pending_totp(&db_pool, &user.user_id, &secret_crypt).await? {
+        return Err(AppError::UserError(
+            "two-factor authentication is already enabled".into(),
+        ));
+    }
+
+    Ok(Json(EnrollTotpResponse {
+        provisioning_uri: provisioning.provisioning_uri,
+        qr_code: provisioning.qr_code,
+        secret: provisioning.secret,
+    }))
+}
+
+async fn post_enable_totp(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<EnableTotpResponse>, AppError> {
+    let totp_entry = db::user_totp::read_user_totp(&db_pool, &user.user_id)
+        .await?
+        .filter(|totp_entry| totp_entry.enabled_at.is_none())
+        .ok_or_else(|| AppError::UserError("no pending two-factor enrolment".into()))?;
+
+    // Confirm the authenticator app was set up correctly before enforcing it
+    auth::verify_totp_entry(&app_settings, &db_pool, &totp_entry, &payload.code).await?;
+
+    let (recovery_codes, code_hashes) = auth::generate_recovery_codes(&app_settings);
+
+    let mut tx = db_pool.begin().await?;
+    db::user_totp::enable_user_totp(&mut *tx, &user.user_id).await?;
+    db::user_totp::replace_recovery_codes(&mut tx, &user.user_id, &code_hashes).await?;
+    tx.commit().await?;
+
+    tracing::info!("user '{}' enabled two-factor authentication", user.user_id);
+
+    Ok(Json(EnableTotpResponse { recovery_codes }))
+}
+
+async fn post_disable_totp(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<DisableTotpResponse>, AppError> {
+    auth::verify_totp(&app_settings, &db_pool, &user.user_id, &payload.code).await?;
+
+    let mut tx = db_pool.begin().await?;
+    db::user_totp::delete_user_totp(&mut tx, &user.user_id).await?;
+    tx.commit().await?;
+
+    tracing::info!("user '{}' disabled two-factor authentication", user.user_id);
+
+    Ok(Json(DisableTotpResponse {}))
+}
+
+async fn post_regenerate_recovery_codes(
+    State(app_settings): State<AppSettings>,
+    State(db_pool): State<db::DbPool>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<TotpCodeRequest>,
+) -> Result<Json<RegenerateRecoveryCodesResponse>, AppError> {
+    auth::verify_totp(&app_settings, &db_pool, &user.user_id, &payload.code).await?;
+
+    let (recovery_codes, code_hashes) = auth::generate_recovery_codes(&app_settings);
+
+    let mut tx = db_pool.begin().await?;
+    db::user_totp::replace_recovery_codes(&mut tx, &user.user_id, &code_hashes).await?;
+    tx.commit().await?;
+
+    Ok(Json(RegenerateRecoveryCodesResponse { recovery_codes }))
+}
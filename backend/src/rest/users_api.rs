@@ -5,7 +5,7 @@ use axum::{
     extract::{Path, State},
     http::{HeaderMap, header::SET_COOKIE},
     middleware,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
 
@@ -21,12 +21,13 @@ use shared::{
     },
     http::{
         client::{
-            ForgotPasswordRequest, ResetPasswordRequest, SignInRequest, SignUpRequest,
-            UpdateAccountRequest,
+            DeleteAccountRequest, ForgotPasswordRequest, IgnoreUserRequest, ResetPasswordRequest,
+            SignInRequest, SignUpRequest, UpdateAccountRequest,
         },
         server::{
             DeleteAccountResponse, ForgotPasswordResponse, GetDiscordInviteResponse,
-            GetUserDetailsResponse, ResetPasswordResponse, SignInResponse, SignUpResponse,
+            GetIgnoredUsersResponse, GetUserDetailsResponse, IgnoreUserResponse,
+            ResetPasswordResponse, SignInResponse, SignUpResponse, UnignoreUserResponse,
             UpdateAccountResponse,
         },
     },
@@ -37,7 +38,7 @@ use crate::{
     auth::{self, User},
     db::{self, users::UserUpdate},
     email::EmailService,
-    integration::discord::DiscordIntegration,
+    integration::{chat::ChatIntegration, discord::DiscordIntegration},
 };
 
 use super::AppError;
@@ -47,6 +48,11 @@ pub fn routes(app_state: AppState) -> Router<AppState> {
         .route("/account/me", get(get_me))
         .route("/account/update", post(post_update_account))
         .route("/account/{user_id}", delete(delete_account))
+        .route(
+            "/account/ignores",
+            get(get_ignored_users).post(post_ignore_user),
+        )
+        .route("/account/ignores/{user_id}", delete(delete_ignored_user))
         .route("/discord", get(get_discord_invite))
         .layer(middleware::from_fn_with_state(
             app_state,
@@ -99,7 +105,7 @@ async fn post_sign_up(
         &payload.username,
         email_crypt.as_deref(),
         email_hash.as_deref(),
-        &auth::hash_password(&payload.password)?,
+        Some(&auth::hash_password(&payload.password)?),
         &Utc::now(),
         DEFAULT_MAX_CHARACTERS as i16,
     )
@@ -119,7 +125,7 @@ async fn post_sign_in(
 ) -> Result<impl IntoResponse, AppError> {
     auth::verify_captcha(&payload.captcha_token).await?;
 
-    let tokens = auth::sign_in(
+    let sign_in_outcome = auth::sign_in(
         &app_settings,
         &db_pool,
         &payload.username.into_inner(),
@@ -127,6 +133,22 @@ async fn post_sign_in(
     )
     .await?;
 
+    sign_in_response(sign_in_outcome)
+}
+
+/// Set the refresh cookie along the access token, or return the TOTP challenge
+pub(super) fn sign_in_response(sign_in_outcome: auth::SignInOutcome) -> Result<Response, AppError> {
+    let tokens = match sign_in_outcome {
+        auth::SignInOutcome::SignedIn(tokens) => tokens,
+        auth::SignInOutcome::TotpRequired(challenge) => {
+            return Ok(Json(SignInResponse {
+                jwt: String::new(),
+                totp_challenge: Some(challenge),
+            })
+            .into_response());
+        }
+    };
+
     let mut response_headers = HeaderMap::new();
     response_headers.insert(
         SET_COOKIE,
@@ -139,8 +161,10 @@ async fn post_sign_in(
         response_headers,
         Json(SignInResponse {
             jwt: tokens.access_token,
+            totp_challenge: None,
         }),
-    ))
+    )
+        .into_response())
 }
 
 async fn post_refresh(
@@ -155,6 +179,7 @@ async fn post_refresh(
 
     Ok(Json(SignInResponse {
         jwt: auth::access_token(&app_settings, user)?,
+        totp_challenge: None,
     }))
 }
 
@@ -167,10 +192,7 @@ async fn post_sign_out() -> Result<impl IntoResponse, AppError> {
             .map_err(|_| anyhow::anyhow!("invalid auth cookie"))?,
     );
 
-    Ok((
-        response_headers,
-        Json(SignInResponse { jwt: String::new() }),
-    ))
+    Ok((response_headers, Json(SignInResponse::default())))
 }
 
 async fn get_discord_invite(
@@ -204,6 +226,7 @@ async fn get_me(
         user_details: UserDetails {
             max_characters: user.max_characters as u8,
             chat_badge: user.chat_badge.clone(),
+            is_admin: user.is_admin,
             user: user.into(),
             email,
         },
@@ -353,6 +376,16 @@ async fn post_update_account(
     State(profanities_checker): State<Arc<ProfanitiesChecker>>,
     Json(payload): Json<UpdateAccountRequest>,
 ) -> Result<Json<UpdateAccountResponse>, AppError> {
+    if payload.email.is_some() {
+        auth::verify_sensitive_action(
+            &app_settings,
+            &db_pool,
+            &user.user_id,
+            payload.totp_code.as_deref(),
+        )
+        .await?;
+    }
+
     let (email_crypt, email_hash) = if let Some(email) = payload.email {
         match email.as_deref() {
             Some(email) => {
@@ -366,8 +399,9 @@ async fn post_update_account(
         (None, None)
     };
 
-    // Double check authentication when trying to reset password
-    if payload.password.is_some() {
+    // Double check authentication when trying to reset password,
+    // accounts created through an identity provider can set a first one
+    if payload.password.is_some() && db::users::has_password(&db_pool, &user.user_id).await? {
         auth::sign_in(
             &app_settings,
             &db_pool,
@@ -411,14 +445,74 @@ async fn post_update_account(
 }
 
 async fn delete_account(
+    State(app_settings): State<AppSettings>,
     State(db_pool): State<db::DbPool>,
     Path(user_id): Path<UserId>,
     Extension(user): Extension<User>,
+    Json(payload): Json<DeleteAccountRequest>,
 ) -> Result<Json<DeleteAccountResponse>, AppError> {
     if user.user_id != user_id {
         return Err(AppError::Forbidden);
     }
 
+    auth::verify_sensitive_action(
+        &app_settings,
+        &db_pool,
+        &user.user_id,
+        payload.totp_code.as_deref(),
+    )
+    .await?;
+
     db::users::delete_user(&db_pool, &user_id).await?;
     Ok(Json(DeleteAccountResponse {}))
 }
+
+async fn get_ignored_users(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+) -> Result<Json<GetIgnoredUsersResponse>, AppError> {
+    let ignored_user_ids = chat_integration.read_ignore_list(user.user_id).await?;
+    let mut ignored_users: Vec<_> = db::users::read_users(&db_pool, &ignored_user_ids)
+        .await?
+        .into_iter()
+        .map(User::from)
+        .collect();
+
+    ignored_users.sort_by_key(|ignored_user| ignored_user.username.to_lowercase());
+
+    Ok(Json(GetIgnoredUsersResponse { ignored_users }))
+}
+
+async fn post_ignore_user(
+    State(db_pool): State<db::DbPool>,
+    State(chat_integration): State<ChatIntegration>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<IgnoreUserRequest>,
+) -> Result<Json<IgnoreUserResponse>, AppError> {
+    let ignored_user_id = db::users::get_user_by_name(&db_pool, &payload.username)
+        .await?
+        .ok_or_else(|| AppError::UserError("user not found".to_string()))?;
+
+    chat_integration
+        .ignore_user(user.user_id, ignored_user_id)
+        .await?
+        .map_err(AppError::UserError)?;
+
+    Ok(Json(IgnoreUserResponse {}))
+}
+
+async fn delete_ignored_user(
+    State(chat_integration): State<ChatIntegration>,
+    Path(ignored_user_id): Path<UserId>,
+    Extension(user): Extension<User>,
+) -> Result<Json<UnignoreUserResponse>, AppError> {
+    if !chat_integration
+        .unignore_user(user.user_id, ignored_user_id)
+        .await?
+    {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(Json(UnignoreUserResponse {}))
+}
@@ -1,6 +1,29 @@
 use std::time::{Duration, Instant};
 
-use crate::{db, game::sessions::SessionsStore};
+use chrono::Utc;
+
+use crate::{
+    app_state::MasterStore,
+    db,
+    game::{challenges::ChallengesStore, sessions::SessionsStore, systems::challenges_controller},
+};
+
+pub async fn rotate_challenges(master_store: MasterStore, challenges_store: ChallengesStore) {
+    loop {
+        for challenge in
+            challenges_controller::rotate_challenges(&master_store, &challenges_store, Utc::now())
+        {
+            tracing::info!(
+                "new challenge '{}' for schedule '{}', ending at {}",
+                challenge.specs.name,
+                challenge.schedule_id,
+                challenge.ends_at
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    }
+}
 
 pub async fn purge_sessions(db_pool: db::DbPool, sessions_store: SessionsStore) {
     loop {
@@ -170,9 +170,13 @@ fn into_ws_msg(message: &ServerMessage, send_buffer: &mut Vec<u8>) -> Result<Mes
     send_buffer.clear();
     message.serialize(&mut rmp_serde::Serializer::new(&mut *send_buffer))?;
 
+    let raw_size = send_buffer.len();
     let bytes =
         compression::encode_payload_from_slice(send_buffer)?.unwrap_or(std::mem::take(send_buffer));
 
+    metrics::histogram!("websocket_message_raw_bytes").record(raw_size as f64);
+    metrics::histogram!("websocket_message_sent_bytes").record(bytes.len() as f64);
+
     Ok(Message::Binary(Bytes::from_owner(bytes)))
 }
 
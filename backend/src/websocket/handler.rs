@@ -32,7 +32,7 @@ use crate::{
     db::{self},
     game::{
         GameInstance,
-        sessions::{Session, SessionsStore},
+        sessions::{ActiveSession, Session, SessionsStore},
         systems::sessions_controller,
     },
     rest::{
@@ -66,7 +66,11 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, app_state: AppState)
     let mut conn = WebSocketConnection::establish(socket, addr, CLIENT_INACTIVITY_TIMEOUT);
     let mut pending_connect = None;
 
-    if let Some(start_at) = app_state.app_settings.game_start_at_utc
+    let start_at = app_state
+        .app_settings
+        .game_start_at_utc
+        .max(app_state.maintenance_store.down_until());
+    if let Some(start_at) = start_at
         && start_at > Utc::now()
     {
         tracing::info!(%addr, %start_at, "waiting for game server start time");
@@ -123,6 +127,17 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, app_state: AppState)
     };
     tracing::debug!("client connected");
 
+    let character_id = session.character_id;
+
+    app_state.sessions_store.active_sessions.insert(
+        character_id,
+        ActiveSession {
+            area_id: session.game_data.area_id.clone(),
+            area_level: session.game_data.area_state.read().area_level,
+            last_active: Instant::now(),
+        },
+    );
+
     let game = GameInstance::new(
         &mut conn,
         &session.character_id,
@@ -131,10 +146,9 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, app_state: AppState)
         app_state.chat_integration.clone(),
         app_state.master_store,
         app_state.sessions_store.clone(),
+        app_state.maintenance_store.clone(),
     );
 
-    let character_id = session.character_id;
-
     match game.run().await {
         Ok(()) => {
             if let Err(e) = handle_disconnect(&app_state.sessions_store, session).await {
@@ -148,6 +162,12 @@ async fn handle_socket(socket: WebSocket, addr: SocketAddr, app_state: AppState)
         .await
         .unwrap_or_else(|e| tracing::error!("error ending session for '{character_id}': {e}"));
 
+    // Only once the session is back in the store, so draining can wait for it
+    app_state
+        .sessions_store
+        .active_sessions
+        .remove(&character_id);
+
     // returning from the handler closes the websocket connection
     tracing::info!("websocket context '{addr}' destroyed");
 }
@@ -239,6 +259,7 @@ async fn handle_connect(
     let session = sessions_controller::create_session(
         &app_state.db_pool,
         &app_state.sessions_store,
+        &app_state.challenges_store,
         &app_state.master_store,
         user_character,
         realm.allow_parallel_characters(),
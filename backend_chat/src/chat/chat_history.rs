@@ -0,0 +1,581 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tokio::io::AsyncWriteExt;
+
+use shared_chat::{
+    ring_buffer::RingBuffer,
+    types::{ChatChannel, ChatMessage, UserId},
+};
+
+const CHANNEL_HISTORY_CAPACITY: usize = 100;
+const WHISPER_HISTORY_CAPACITY: usize = 30;
+
+/// Number of most recent daily log files replayed at startup
+const LOADED_LOG_FILES: usize = 7;
+/// Number of daily log files scrollback goes through before giving up
+const MAX_SCROLLBACK_LOG_FILES: usize = 31;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct HistoryMessage {
+    #[serde(flatten)]
+    message: ChatMessage,
+    /// Only shown to its author, e.g. messages of shadow muted users
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    hidden: bool,
+}
+
+impl HistoryMessage {
+    fn is_visible_to(&self, user_id: UserId) -> bool {
+        !self.hidden || self.message.user_id == Some(user_id)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+struct MessagesDeletion {
+    deleted_user_id: UserId,
+    deleted_sent_at: Option<DateTime<Utc>>,
+    deleted_at: DateTime<Utc>,
+}
+
+impl MessagesDeletion {
+    fn matches(&self, message: &ChatMessage) -> bool {
+        message.user_id == Some(self.deleted_user_id)
+            && match self.deleted_sent_at {
+                Some(sent_at) => message.sent_at == sent_at,
+                None => message.sent_at <= self.deleted_at,
+            }
+    }
+}
+
+/// Chat messages kept in memory per channel and appended to JSON lines log files.
+/// Messages are logged in one file per day of sending, next to the configured path
+/// (`chat_history.jsonl` becomes `chat_history.2025-01-31.jsonl`), and deletions are
+/// logged in their own file (`chat_history.deletions.jsonl`).
+/// Whispers are kept under `ChatChannel::Whisper(user_id)` of both the sender and the recipient.
+#[derive(Debug)]
+pub struct ChatHistory {
+    buffers: Mutex<HashMap<ChatChannel, RingBuffer<Arc<HistoryMessage>>>>,
+    deletions: Mutex<Vec<MessagesDeletion>>,
+    log_days: Mutex<BTreeSet<NaiveDate>>,
+    log_path: Option<PathBuf>,
+    log_file: tokio::sync::Mutex<Option<(NaiveDate, tokio::fs::File)>>,
+}
+
+impl ChatHistory {
+    /// Restore the most recent messages of each channel from the last log files, if any
+    pub fn load(log_path: Option<PathBuf>) -> Self {
+        let history = Self {
+            buffers: Default::default(),
+            deletions: Default::default(),
+            log_days: Default::default(),
+            log_path,
+            log_file: Default::default(),
+        };
+
+        if let Some(log_path) = history.log_path.as_ref() {
+            let deletions_path = deletions_log_path(log_path);
+            if deletions_path.exists() {
+                match read_log_entries::<MessagesDeletion>(&deletions_path) {
+                    Ok(deletions) => history.deletions.lock().unwrap().extend(deletions),
+                    Err(err) => tracing::error!("failed to load chat deletions: {err}"),
+                }
+            }
+
+            match list_log_days(log_path) {
+                Ok(log_days) => *history.log_days.lock().unwrap() = log_days,
+                Err(err) => tracing::error!("failed to list chat history files: {err}"),
+            }
+
+            let loaded_days: Vec<_> = history
+                .log_days
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .take(LOADED_LOG_FILES)
+                .rev()
+                .copied()
+                .collect();
+            let deletions = history.deletions.lock().unwrap().clone();
+            for day in loaded_days {
+                match read_log_day(log_path, day, &deletions) {
+                    Ok(messages) => {
+                        for message in messages {
+                            history.store(Arc::new(message));
+                        }
+                    }
+                    Err(err) => tracing::error!("failed to load chat history of {day}: {err}"),
+                }
+            }
+        }
+
+        history
+    }
+
+    pub async fn push(&self, message: ChatMessage) {
+        self.push_entry(HistoryMessage {
+            message,
+            hidden: false,
+        })
+        .await
+    }
+
+    /// Store a message that is only shown back to its author
+    pub async fn push_hidden(&self, message: ChatMessage) {
+        self.push_entry(HistoryMessage {
+            message,
+            hidden: true,
+        })
+        .await
+    }
+
+    async fn push_entry(&self, entry: HistoryMessage) {
+        if let Err(err) = self.append_message_to_log(&entry).await {
+            tracing::error!("failed to persist chat message: {err}");
+        }
+        self.store(Arc::new(entry));
+    }
+
+    /// Remove a message of the user, or all of them if no time is given
+    pub async fn delete(&self, user_id: UserId, sent_at: Option<DateTime<Utc>>) {
+        let deletion = MessagesDeletion {
+            deleted_user_id: user_id,
+            deleted_sent_at: sent_at,
+            deleted_at: Utc::now(),
+        };
+
+        for buffer in self.buffers.lock().unwrap().values_mut() {
+            buffer.retain(|entry| !deletion.matches(&entry.message));
+        }
+        self.deletions.lock().unwrap().push(deletion);
+
+        if let Err(err) = self.append_deletion_to_log(&deletion).await {
+            tracing::error!("failed to persist chat messages deletion: {err}");
+        }
+    }
+
+    /// Most recent messages sent by the user in public channels, hidden ones included,
+    /// newest first
+    pub fn user_messages(&self, user_id: UserId, limit: usize) -> Vec<ChatMessage> {
+        let buffers = self.buffers.lock().unwrap();
+        let mut messages: Vec<_> = [ChatChannel::Global, ChatChannel::Trade]
+            .iter()
+            .filter_map(|channel| buffers.get(channel))
+            .flat_map(|buffer| buffer.iter_rev())
+            .filter(|entry| entry.message.user_id == Some(user_id))
+            .map(|entry| entry.message.clone())
+            .collect();
+
+        messages.sort_by_key(|message| Reverse(message.sent_at));
+        messages.truncate(limit);
+        messages
+    }
+
+    /// Number of distinct users who posted in the channel since the given time
+    pub fn active_users(&self, channel: ChatChannel, since: DateTime<Utc>) -> usize {
+        let buffers = self.buffers.lock().unwrap();
+        buffers
+            .get(&channel)
+            .map(|buffer| {
+                buffer
+                    .iter_rev()
+                    .take_while(|entry| entry.message.sent_at >= since)
+                    .filter(|entry| !entry.hidden)
+                    .filter_map(|entry| entry.message.user_id)
+                    .collect::<HashSet<_>>()
+                    .len()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Most recent messages of all given channels visible to the user, newest first
+    pub fn recent(
+        &self,
+        channels: &[ChatChannel],
+        viewer_id: UserId,
+        limit: usize,
+    ) -> Vec<ChatMessage> {
+        let buffers = self.buffers.lock().unwrap();
+        let mut messages: Vec<_> = channels
+            .iter()
+            .filter_map(|channel| buffers.get(channel))
+            .flat_map(|buffer| {
+                buffer
+                    .iter_rev()
+                    .filter(|entry| entry.is_visible_to(viewer_id))
+                    .take(limit)
+            })
+            .map(|entry| entry.message.clone())
+            .collect();
+
+        messages.sort_by_key(|message| Reverse(message.sent_at));
+        messages.truncate(limit);
+        messages
+    }
+
+    /// Messages of the channel visible to the user sent before the given time, newest first.
+    /// Falls back to the daily log files when the in-memory buffer does not go far enough.
+    pub async fn scrollback(
+        &self,
+        channel: ChatChannel,
+        viewer_id: UserId,
+        before: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> (Vec<ChatMessage>, bool) {
+        let (mut messages, complete) = {
+            let buffers = self.buffers.lock().unwrap();
+            match buffers.get(&channel) {
+                Some(buffer) => (
+                    buffer
+                        .iter_rev()
+                        .filter(|entry| {
+                            entry.is_visible_to(viewer_id)
+                                && before.is_none_or(|before| entry.message.sent_at < before)
+                        })
+                        .take(limit + 1)
+                        .map(|entry| entry.message.clone())
+                        .collect::<Vec<_>>(),
+                    buffer.len() < buffer.capacity(),
+                ),
+                None => (Vec::new(), true),
+            }
+        };
+
+        if messages.len() <= limit
+            && !complete
+            && let Some(log_path) = self.log_path.clone()
+        {
+            let log_days: Vec<_> = self
+                .log_days
+                .lock()
+                .unwrap()
+                .range(..=before.unwrap_or_else(Utc::now).date_naive())
+                .rev()
+                .take(MAX_SCROLLBACK_LOG_FILES)
+                .copied()
+                .collect();
+            let deletions = self.deletions.lock().unwrap().clone();
+
+            match tokio::task::spawn_blocking(move || {
+                read_log_before(
+                    &log_path,
+                    &log_days,
+                    &deletions,
+                    channel,
+                    viewer_id,
+                    before,
+                    limit + 1,
+                )
+            })
+            .await
+            {
+                Ok(Ok(log_messages)) => messages = log_messages,
+                Ok(Err(err)) => tracing::error!("failed to read chat history: {err}"),
+                Err(err) => tracing::error!("failed to read chat history: {err}"),
+            }
+        }
+
+        let has_more = messages.len() > limit;
+        messages.truncate(limit);
+        (messages, has_more)
+    }
+
+    fn store(&self, entry: Arc<HistoryMessage>) {
+        let mut buffers = self.buffers.lock().unwrap();
+        for channel in history_channels(&entry.message) {
+            buffers
+                .entry(channel)
+                .or_insert_with(|| {
+                    RingBuffer::new(match channel {
+                        ChatChannel::Whisper(_) => WHISPER_HISTORY_CAPACITY,
+                        _ => CHANNEL_HISTORY_CAPACITY,
+                    })
+                })
+                .push(entry.clone());
+        }
+    }
+
+    async fn append_message_to_log(&self, entry: &HistoryMessage) -> Result<()> {
+        let Some(log_path) = self.log_path.as_ref() else {
+            return Ok(());
+        };
+
+        let line = to_log_line(entry)?;
+        let day = entry.message.sent_at.date_naive();
+
+        let mut log_file = self.log_file.lock().await;
+        let file = match log_file.as_mut() {
+            Some((file_day, file)) if *file_day == day => file,
+            _ => {
+                let file = open_log_file(&day_log_path(log_path, day)).await?;
+                self.log_days.lock().unwrap().insert(day);
+                &mut log_file.insert((day, file)).1
+            }
+        };
+
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn append_deletion_to_log(&self, deletion: &MessagesDeletion) -> Result<()> {
+        let Some(log_path) = self.log_path.as_ref() else {
+            return Ok(());
+        };
+
+        let line = to_log_line(deletion)?;
+        let mut file = open_log_file(&deletions_log_path(log_path)).await?;
+        file.write_all(&line).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+fn history_channels(message: &ChatMessage) -> Vec<ChatChannel> {
+    match (message.channel, message.user_id) {
+        (ChatChannel::Whisper(target), Some(sender)) if target != sender => {
+            vec![message.channel, ChatChannel::Whisper(sender)]
+        }
+        (channel, _) => vec![channel],
+    }
+}
+
+fn day_log_path(log_path: &Path, day: NaiveDate) -> PathBuf {
+    suffixed_log_path(log_path, &day.format("%Y-%m-%d").to_string())
+}
+
+fn deletions_log_path(log_path: &Path) -> PathBuf {
+    suffixed_log_path(log_path, "deletions")
+}
+
+fn suffixed_log_path(log_path: &Path, suffix: &str) -> PathBuf {
+    let stem = log_path.file_stem().unwrap_or_default().to_string_lossy();
+    match log_path.extension() {
+        Some(extension) => {
+            log_path.with_file_name(format!("{stem}.{suffix}.{}", extension.to_string_lossy()))
+        }
+        None => log_path.with_file_name(format!("{stem}.{suffix}")),
+    }
+}
+
+/// Days for which a log file exists next to the configured path
+fn list_log_days(log_path: &Path) -> Result<BTreeSet<NaiveDate>> {
+    let directory = match log_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if !directory.exists() {
+        return Ok(BTreeSet::new());
+    }
+
+    let template = day_log_path(log_path, NaiveDate::MIN)
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let date_marker = NaiveDate::MIN.format("%Y-%m-%d").to_string();
+    let (prefix, suffix) = template.split_once(&date_marker).unwrap_or((&template, ""));
+
+    Ok(fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let date = file_name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+        })
+        .collect())
+}
+
+async fn open_log_file(path: &Path) -> Result<tokio::fs::File> {
+    Ok(tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?)
+}
+
+fn to_log_line(entry: &impl Serialize) -> Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+fn read_log_entries<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<impl Iterator<Item = T>> {
+    Ok(BufReader::new(fs::File::open(path)?)
+        .lines()
+        .map_while(|line| line.ok())
+        // Skip lines that were only partially written
+        .filter_map(|line| serde_json::from_str(&line).ok()))
+}
+
+fn read_log_day(
+    log_path: &Path,
+    day: NaiveDate,
+    deletions: &[MessagesDeletion],
+) -> Result<Vec<HistoryMessage>> {
+    let path = day_log_path(log_path, day);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(read_log_entries::<HistoryMessage>(&path)?
+        .filter(|entry| {
+            !deletions
+                .iter()
+                .any(|deletion| deletion.matches(&entry.message))
+        })
+        .collect())
+}
+
+/// Read the given days, newest first, until enough messages were found
+fn read_log_before(
+    log_path: &Path,
+    log_days: &[NaiveDate],
+    deletions: &[MessagesDeletion],
+    channel: ChatChannel,
+    viewer_id: UserId,
+    before: Option<DateTime<Utc>>,
+    limit: usize,
+) -> Result<Vec<ChatMessage>> {
+    let mut messages = Vec::with_capacity(limit);
+    for day in log_days {
+        let mut day_messages = RingBuffer::new(limit - messages.len());
+        day_messages.extend(
+            read_log_day(log_path, *day, deletions)?
+                .into_iter()
+                .filter(|entry| {
+                    entry.is_visible_to(viewer_id)
+                        && before.is_none_or(|before| entry.message.sent_at < before)
+                        && history_channels(&entry.message).contains(&channel)
+                })
+                .map(|entry| entry.message),
+        );
+        messages.extend(day_messages.iter_rev().cloned());
+
+        if messages.len() >= limit {
+            break;
+        }
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn message(user_id: UserId, sent_at: DateTime<Utc>, content: &str) -> ChatMessage {
+        ChatMessage {
+            channel: ChatChannel::Global,
+            sent_at,
+            user_id: Some(user_id),
+            username: None,
+            character_id: None,
+            character_name: None,
+            chat_badge: None,
+            content: content.into(),
+            linked_item: None,
+            linked_item_verified: false,
+            item_signature: None,
+        }
+    }
+
+    fn contents(messages: &[ChatMessage]) -> Vec<&str> {
+        messages
+            .iter()
+            .map(|message| message.content.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_scrollback_reads_daily_logs_and_applies_deletions() {
+        let log_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&log_dir).unwrap();
+        let log_path = log_dir.join("chat_history.jsonl");
+
+        let (user_id, muted_user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+        let history = ChatHistory::load(Some(log_path.clone()));
+        for days in (0..3).rev() {
+            let sent_at = now - TimeDelta::days(days);
+            history
+                .push(message(user_id, sent_at, &format!("day {days}")))
+                .await;
+            history
+                .push(message(muted_user_id, sent_at, "deleted"))
+                .await;
+        }
+        history.delete(muted_user_id, None).await;
+
+        assert!(day_log_path(&log_path, now.date_naive()).exists());
+        assert!(deletions_log_path(&log_path).exists());
+
+        let history = ChatHistory::load(Some(log_path.clone()));
+        assert_eq!(
+            contents(&history.recent(&[ChatChannel::Global], user_id, 10)),
+            vec!["day 0", "day 1", "day 2"]
+        );
+
+        let messages = read_log_before(
+            &log_path,
+            &history
+                .log_days
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .copied()
+                .collect::<Vec<_>>(),
+            &history.deletions.lock().unwrap(),
+            ChatChannel::Global,
+            user_id,
+            Some(now),
+            2,
+        )
+        .unwrap();
+        assert_eq!(contents(&messages), vec!["day 1", "day 2"]);
+
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_hidden_messages_are_only_visible_to_their_author() {
+        let log_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&log_dir).unwrap();
+        let log_path = log_dir.join("chat_history.jsonl");
+
+        let (user_id, muted_user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let history = ChatHistory::load(Some(log_path.clone()));
+        history.push(message(user_id, Utc::now(), "visible")).await;
+        history
+            .push_hidden(message(muted_user_id, Utc::now(), "hidden"))
+            .await;
+
+        let history = ChatHistory::load(Some(log_path));
+        assert_eq!(
+            contents(&history.recent(&[ChatChannel::Global], user_id, 10)),
+            vec!["visible"]
+        );
+        assert_eq!(
+            contents(&history.recent(&[ChatChannel::Global], muted_user_id, 10)),
+            vec!["hidden", "visible"]
+        );
+        assert_eq!(
+            history.active_users(ChatChannel::Global, Utc::now() - TimeDelta::hours(1)),
+            1
+        );
+
+        fs::remove_dir_all(log_dir).unwrap();
+    }
+}
@@ -7,10 +7,12 @@ use uuid::Uuid;
 
 use shared_chat::{
     messages::{
-        client::{ClientChatMessage, ClientPostMessage},
-        server::{ErrorMessage, ErrorType, ServerChatMessage, ServerConnectMessage},
+        client::{ClientChatMessage, ClientHistoryRequest, ClientPostMessage},
+        server::{
+            ErrorMessage, ErrorType, ServerChatMessage, ServerConnectMessage, ServerHistoryMessage,
+        },
     },
-    types::{ChatMessage, UserId},
+    types::{ChatChannel, ChatMessage, UserId},
 };
 
 use crate::{
@@ -19,6 +21,8 @@ use crate::{
 };
 
 const MAX_OUTBOUND_HISTORY_MESSAGE_SIZE: usize = 7 * 1024;
+const CONNECT_HISTORY_LENGTH: usize = 30;
+const MAX_HISTORY_REQUEST_LIMIT: u16 = 50;
 
 pub struct ChatSession {
     session_id: Uuid,
@@ -48,15 +52,17 @@ impl ChatSession {
         mut ws_receiver: WebSocketReceiver,
     ) -> Result<()> {
         let mut broadcast_rx = self.chat_state.outbound_tx.subscribe();
-        let history: Vec<_> = self
-            .chat_state
-            .history
-            .lock()
-            .unwrap()
-            .iter_rev()
-            .take(30)
-            .map(|m| (**m).clone())
-            .collect();
+        let mut history = self.chat_state.history.recent(
+            &[
+                ChatChannel::System,
+                ChatChannel::Global,
+                ChatChannel::Trade,
+                ChatChannel::Whisper(self.user_details.user.user_id),
+            ],
+            self.user_details.user.user_id,
+            CONNECT_HISTORY_LENGTH,
+        );
+        self.filter_ignored_users(&mut history);
         let history_message =
             build_history_connect_message(self.user_details.user.user_id, history);
 
@@ -82,15 +88,28 @@ impl ChatSession {
                 self.user_details.user.user_id,
                 self.user_details.user.username.clone(),
             ));
+        if self.user_details.is_admin {
+            self.chat_state
+                .admins
+                .insert(self.user_details.user.user_id);
+        }
         ///////////////////////////////
 
+        let user_id = self.user_details.user.user_id;
+        let ignore_lists = self.chat_state.ignore_lists.clone();
+
         let write_task = tokio::spawn(async move {
             loop {
                 tokio::select! {
                     result = broadcast_rx.recv() => {
                         match result {
                             Ok(msg) => {
-                                if let Err(err) = ws_sender.send_raw(msg).await {
+                                let is_ignored = msg.author_id.is_some_and(|author_id| {
+                                    ignore_lists.is_ignoring(user_id, author_id)
+                                });
+                                if !is_ignored
+                                    && let Err(err) = ws_sender.send_raw(msg.bytes).await
+                                {
                                     tracing::warn!("failed to send message: {}", err);
                                     break;
                                 }
@@ -158,6 +177,9 @@ impl ChatSession {
             self.chat_state
                 .usernames_map
                 .remove(&self.user_details.user.username.to_ascii_lowercase());
+            self.chat_state
+                .admins
+                .remove(&self.user_details.user.user_id);
         }
         self.chat_state.reply_map.remove(&self.session_id);
 
@@ -182,15 +204,56 @@ impl ChatSession {
                     return Some(error_message);
                 }
             }
+            ClientChatMessage::RequestHistory(m) => self.handle_history_request(*m).await,
         }
         None
     }
 
+    async fn handle_history_request(&mut self, msg: ClientHistoryRequest) {
+        // Users can only read their own whispers
+        let channel = match msg.channel {
+            ChatChannel::Whisper(_) => ChatChannel::Whisper(self.user_details.user.user_id),
+            channel => channel,
+        };
+
+        let (mut messages, has_more) = self
+            .chat_state
+            .history
+            .scrollback(
+                channel,
+                self.user_details.user.user_id,
+                msg.before,
+                msg.limit.min(MAX_HISTORY_REQUEST_LIMIT) as usize,
+            )
+            .await;
+        self.filter_ignored_users(&mut messages);
+
+        if let Some(reply_queue) = self.chat_state.reply_map.get(&self.session_id) {
+            let _ = reply_queue
+                .send(build_history_message(channel, messages, has_more))
+                .await;
+        }
+    }
+
+    fn filter_ignored_users(&self, messages: &mut Vec<ChatMessage>) {
+        let ignore_list = self
+            .chat_state
+            .ignore_lists
+            .get(self.user_details.user.user_id);
+        if !ignore_list.is_empty() {
+            messages.retain(|message| {
+                message
+                    .user_id
+                    .is_none_or(|author_id| !ignore_list.contains(&author_id))
+            });
+        }
+    }
+
     async fn handle_chat_message(
         &mut self,
         msg: ClientPostMessage,
     ) -> std::result::Result<(), ErrorMessage> {
-        // let (linked_item, item_signature) = msg.linked_item.unzip();
+        let (linked_item, item_signature) = msg.linked_item.unzip();
         let character_name = self
             .character_resolver
             .resolve(msg.character_id)
@@ -213,8 +276,9 @@ impl ChatSession {
                     character_name,
                     chat_badge: self.user_details.chat_badge.clone(),
                     content: msg.content.into_inner(),
-                    linked_item: msg.linked_item,
-                    // item_signature,
+                    linked_item,
+                    linked_item_verified: false,
+                    item_signature,
                     sent_at: Utc::now(),
                 },
             ))
@@ -255,3 +319,33 @@ fn build_history_connect_message(user_id: UserId, history: Vec<ChatMessage>) ->
 
     message.into()
 }
+
+fn build_history_message(
+    channel: ChatChannel,
+    history: Vec<ChatMessage>,
+    has_more: bool,
+) -> ServerChatMessage {
+    let mut message = ServerHistoryMessage {
+        channel,
+        messages: Vec::new(),
+        has_more,
+    };
+
+    for history_message in history.into_iter() {
+        message.messages.push(history_message);
+
+        if rmp_serde::to_vec(&message)
+            .map(|bytes| bytes.len())
+            .unwrap_or(usize::MAX)
+            <= MAX_OUTBOUND_HISTORY_MESSAGE_SIZE
+        {
+            continue;
+        }
+
+        message.messages.pop();
+        message.has_more = true;
+        break;
+    }
+
+    message.into()
+}
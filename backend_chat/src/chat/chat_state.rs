@@ -1,29 +1,45 @@
 use axum::body::Bytes;
-use backend_shared::http::users::UserId;
-use dashmap::DashMap;
-use std::{
-    collections::HashSet,
-    sync::{Arc, Mutex},
-};
+use backend_shared::{http::users::UserId, signature::HmacKey};
+use dashmap::{DashMap, DashSet};
+use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 
-use shared_chat::{
-    messages::server::ServerChatMessage, ring_buffer::RingBuffer, types::ChatMessage,
+use shared_chat::{messages::server::ServerChatMessage, types::ChatMessage};
+
+use crate::chat::{
+    chat_history::ChatHistory, ignore_lists_store::IgnoreListsStore,
+    sanctions_store::SanctionsStore, user_moderation::UserModerationState,
 };
 
-use crate::chat::user_moderation::UserModerationState;
+/// Serialized once for all sessions
+#[derive(Debug, Clone)]
+pub struct OutboundMessage {
+    /// Used to filter out ignored users
+    pub author_id: Option<UserId>,
+    pub bytes: Arc<Bytes>,
+}
 
 #[derive(Debug, Clone)]
 pub struct ChatState {
     pub inbound_tx: mpsc::Sender<(Uuid, ChatMessage)>,
-    pub outbound_tx: broadcast::Sender<Arc<Bytes>>,
+    pub outbound_tx: broadcast::Sender<OutboundMessage>,
 
     pub reply_map: Arc<DashMap<Uuid, mpsc::Sender<ServerChatMessage>>>,
     pub users_map: Arc<DashMap<UserId, HashSet<Uuid>>>,
     pub usernames_map: Arc<DashMap<String, (UserId, String)>>,
+    pub admins: Arc<DashSet<UserId>>,
+
+    /// Last user who whispered to the user, with their username
+    pub reply_targets: Arc<DashMap<UserId, (UserId, String)>>,
+    pub ignore_lists: Arc<IgnoreListsStore>,
 
-    pub history: Arc<Mutex<RingBuffer<Arc<ChatMessage>>>>,
+    pub history: Arc<ChatHistory>,
 
     pub users_moderation: DashMap<UserId, UserModerationState>,
+    pub sanctions: Arc<SanctionsStore>,
+
+    /// Shared with the game server, which signs the items it serves.
+    /// Also signs the admin commands relayed to the backend.
+    pub item_signature_key: Arc<HmacKey>,
 }
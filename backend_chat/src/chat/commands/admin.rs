@@ -0,0 +1,163 @@
+use chrono::{Duration, Utc};
+use futures::{FutureExt, future::BoxFuture};
+
+use shared_chat::{
+    moderation::{
+        ChatCommandBroadcastRequest, ChatCommandSanctionRequest, ChatSanction, ChatSanctionType,
+    },
+    types::ChatContent,
+};
+
+use super::{ChatCommand, CommandContext, CommandOutcome, CommandResult, game::BackendClient};
+
+const DEFAULT_MUTE_MINUTES: i64 = 60;
+
+// Admin commands go through the backend, which checks the admin rights again and logs the action
+
+pub struct MuteCommand {
+    backend_client: BackendClient,
+}
+
+impl MuteCommand {
+    pub fn new(backend_client: BackendClient) -> Self {
+        Self { backend_client }
+    }
+}
+
+impl ChatCommand for MuteCommand {
+    fn name(&self) -> &'static str {
+        "mute"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/mute <name> [minutes] [reason]"
+    }
+
+    fn description(&self) -> &'static str {
+        "mute a connected player (60 minutes by default)"
+    }
+
+    fn admin_only(&self) -> bool {
+        true
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl MuteCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        let mut parts = args.splitn(3, ' ');
+
+        let username = parts.next().unwrap_or_default();
+        if username.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        let (target_user_id, username) = context
+            .chat_state
+            .usernames_map
+            .get(&username.to_ascii_lowercase())
+            .map(|entry| entry.value().clone())
+            .ok_or("unknown user or not connected")?;
+
+        let minutes = match parts.next() {
+            Some(minutes) => minutes
+                .parse::<i64>()
+                .ok()
+                .filter(|minutes| *minutes > 0)
+                .ok_or("minutes must be a positive number")?,
+            None => DEFAULT_MUTE_MINUTES,
+        };
+
+        let sanction = ChatSanction {
+            sanction_type: ChatSanctionType::Mute,
+            until: Some(Utc::now() + Duration::minutes(minutes)),
+            reason: parts
+                .next()
+                .map(str::trim)
+                .filter(|reason| !reason.is_empty())
+                .unwrap_or("muted by a moderator")
+                .to_string(),
+        };
+
+        self.backend_client
+            .post_signed::<_, ()>(
+                &["chat", "moderation", "sanctions"],
+                &ChatCommandSanctionRequest {
+                    moderator_id: context.user_id,
+                    user_id: target_user_id,
+                    sanction,
+                },
+                &context.chat_state.item_signature_key,
+            )
+            .await?;
+
+        Ok(CommandOutcome::Reply(format!(
+            "{username} has been muted for {minutes} minutes."
+        )))
+    }
+}
+
+pub struct AnnounceCommand {
+    backend_client: BackendClient,
+}
+
+impl AnnounceCommand {
+    pub fn new(backend_client: BackendClient) -> Self {
+        Self { backend_client }
+    }
+}
+
+impl ChatCommand for AnnounceCommand {
+    fn name(&self) -> &'static str {
+        "announce"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/announce <message>"
+    }
+
+    fn description(&self) -> &'static str {
+        "broadcast a system message to everyone"
+    }
+
+    fn admin_only(&self) -> bool {
+        true
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl AnnounceCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        if args.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        self.backend_client
+            .post_signed::<_, ()>(
+                &["chat", "moderation", "broadcast"],
+                &ChatCommandBroadcastRequest {
+                    moderator_id: context.user_id,
+                    message: ChatContent::try_new(args.to_string())
+                        .map_err(|_| "invalid message")?,
+                },
+                &context.chat_state.item_signature_key,
+            )
+            .await?;
+
+        Ok(CommandOutcome::Done)
+    }
+}
@@ -0,0 +1,235 @@
+use std::time::Duration;
+
+use futures::{FutureExt, future::BoxFuture};
+use serde::{Serialize, de::DeserializeOwned};
+
+use backend_shared::{
+    http::chat::{
+        CHAT_SIGNATURE_HEADER, ErrorResponse, GetChatLadderRankResponse, GetChatLinkedItemResponse,
+    },
+    signature::{self, HmacKey},
+};
+use shared_chat::types::{ChatChannel, ChatMessage, LinkedItemBytes};
+
+use super::{ChatCommand, CommandContext, CommandOutcome, CommandResult};
+
+// Slow answers only delay the command reply, but players should not wait too long
+const BACKEND_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct BackendClient {
+    http_client: reqwest::Client,
+    backend_url: String,
+}
+
+impl BackendClient {
+    pub fn new(backend_url: &str) -> Self {
+        Self {
+            http_client: reqwest::Client::builder()
+                .timeout(BACKEND_TIMEOUT)
+                .build()
+                .expect("failed to build http client"),
+            backend_url: backend_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn url(&self, path_segments: &[&str]) -> Result<reqwest::Url, String> {
+        let mut url = reqwest::Url::parse(&self.backend_url).map_err(|err| err.to_string())?;
+        url.path_segments_mut()
+            .map_err(|_| "invalid backend url".to_string())?
+            .pop_if_empty()
+            .extend(path_segments);
+        Ok(url)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path_segments: &[&str]) -> Result<T, String> {
+        self.send(self.http_client.get(self.url(path_segments)?))
+            .await
+    }
+
+    /// Post on behalf of an admin, the body is signed so the backend can trust the request
+    pub async fn post_signed<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path_segments: &[&str],
+        body: &B,
+        signature_key: &HmacKey,
+    ) -> Result<T, String> {
+        let body = serde_json::to_vec(body).map_err(|err| err.to_string())?;
+        self.send(
+            self.http_client
+                .post(self.url(path_segments)?)
+                .header("Content-Type", "application/json")
+                .header(
+                    CHAT_SIGNATURE_HEADER,
+                    signature::compute_hex_hmac(&body, signature_key),
+                )
+                .body(body),
+        )
+        .await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, String> {
+        let res = request.send().await.map_err(|err| {
+            tracing::error!("failed to reach backend: {err}");
+            "service unavailable".to_string()
+        })?;
+
+        if !res.status().is_success() {
+            return Err(res
+                .json::<ErrorResponse>()
+                .await
+                .map(|err| err.error)
+                .unwrap_or_else(|_| "service unavailable".to_string()));
+        }
+
+        res.json::<T>().await.map_err(|err| {
+            tracing::error!("invalid backend response: {err}");
+            "service unavailable".to_string()
+        })
+    }
+}
+
+pub struct ItemCommand {
+    backend_client: BackendClient,
+}
+
+impl ItemCommand {
+    pub fn new(backend_client: BackendClient) -> Self {
+        Self { backend_client }
+    }
+}
+
+impl ChatCommand for ItemCommand {
+    fn name(&self) -> &'static str {
+        "item"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/item <slot> [message]"
+    }
+
+    fn description(&self) -> &'static str {
+        "link the item equipped in the slot (weapon, helmet, body, gloves, boots, shield, amulet, ring, accessory)"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl ItemCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        let character_id = context
+            .message
+            .character_id
+            .ok_or("you must play a character to link its items")?;
+
+        let (item_slot, content) = args.split_once(' ').unwrap_or((args, ""));
+        if item_slot.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        let (linked_item, item_signature) = self
+            .backend_client
+            .get::<GetChatLinkedItemResponse>(&[
+                "chat",
+                "characters",
+                &character_id.to_string(),
+                "equipped",
+                item_slot,
+            ])
+            .await?
+            .linked_item
+            .ok_or("no item equipped in this slot")?;
+
+        Ok(CommandOutcome::Post(ChatMessage {
+            content: content.trim().to_string(),
+            linked_item: Some(
+                LinkedItemBytes::try_new(linked_item).map_err(|_| "this item cannot be linked")?,
+            ),
+            item_signature: Some(item_signature),
+            ..context.message.clone()
+        }))
+    }
+}
+
+pub struct LadderCommand {
+    backend_client: BackendClient,
+}
+
+impl LadderCommand {
+    pub fn new(backend_client: BackendClient) -> Self {
+        Self { backend_client }
+    }
+}
+
+impl ChatCommand for LadderCommand {
+    fn name(&self) -> &'static str {
+        "ladder"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/ladder <area>"
+    }
+
+    fn description(&self) -> &'static str {
+        "post the rank of your character in the area leaderboard"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl LadderCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        let character_id = context
+            .message
+            .character_id
+            .ok_or("you must play a character to post its rank")?;
+
+        if args.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        let response = self
+            .backend_client
+            .get::<GetChatLadderRankResponse>(&[
+                "chat",
+                "characters",
+                &character_id.to_string(),
+                "ladder",
+                args,
+            ])
+            .await?;
+
+        let Some((rank, area_level)) = response.rank else {
+            return Ok(CommandOutcome::Reply(format!(
+                "Your character is not ranked in {} yet.",
+                response.area_name
+            )));
+        };
+
+        // Posted in the system channel so players cannot fake it
+        Ok(CommandOutcome::Post(ChatMessage {
+            channel: ChatChannel::System,
+            content: format!(
+                "is ranked #{rank} in {} with level {area_level}",
+                response.area_name
+            ),
+            linked_item: None,
+            ..context.message.clone()
+        }))
+    }
+}
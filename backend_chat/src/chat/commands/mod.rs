@@ -0,0 +1,128 @@
+use futures::future::BoxFuture;
+use uuid::Uuid;
+
+use shared_chat::types::{ChatMessage, UserId};
+
+use crate::chat::chat_state::ChatState;
+
+mod admin;
+mod game;
+mod social;
+
+pub enum CommandOutcome {
+    /// Process the message as if it was sent by the user
+    Post(ChatMessage),
+    /// Feedback only sent to the user session
+    Reply(String),
+    Done,
+}
+
+/// Errors are sent back to the user session
+pub type CommandResult = Result<CommandOutcome, String>;
+
+pub struct CommandContext<'a> {
+    pub chat_state: &'a ChatState,
+    pub session_id: Uuid,
+    pub user_id: UserId,
+    pub message: &'a ChatMessage,
+}
+
+pub trait ChatCommand: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn usage(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+
+    fn admin_only(&self) -> bool {
+        false
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult>;
+}
+
+pub struct ChatCommands {
+    commands: Vec<Box<dyn ChatCommand>>,
+}
+
+impl ChatCommands {
+    pub fn new(backend_url: &str) -> Self {
+        let backend_client = game::BackendClient::new(backend_url);
+
+        Self {
+            commands: vec![
+                Box::new(social::WhisperCommand),
+                Box::new(social::ReplyCommand),
+                Box::new(social::IgnoreCommand),
+                Box::new(social::WhoCommand),
+                Box::new(social::RollCommand),
+                Box::new(game::ItemCommand::new(backend_client.clone())),
+                Box::new(game::LadderCommand::new(backend_client.clone())),
+                Box::new(admin::MuteCommand::new(backend_client.clone())),
+                Box::new(admin::AnnounceCommand::new(backend_client)),
+            ],
+        }
+    }
+
+    /// Execute the command contained in a message starting with '/'
+    pub async fn execute(
+        &self,
+        chat_state: &ChatState,
+        session_id: Uuid,
+        message: &ChatMessage,
+    ) -> CommandResult {
+        let Some(user_id) = message.user_id else {
+            return Err("commands can only be used by players".to_string());
+        };
+
+        let command_line = message.content.trim_start_matches('/');
+        let (name, args) = command_line
+            .split_once(char::is_whitespace)
+            .unwrap_or((command_line, ""));
+        let name = name.to_ascii_lowercase();
+
+        let is_admin = chat_state.admins.contains(&user_id);
+
+        if name == "help" {
+            return Ok(CommandOutcome::Reply(self.help(is_admin)));
+        }
+
+        // Admin commands are hidden from other users
+        match self
+            .commands
+            .iter()
+            .find(|command| command.name() == name && (is_admin || !command.admin_only()))
+        {
+            Some(command) => {
+                command
+                    .execute(
+                        CommandContext {
+                            chat_state,
+                            session_id,
+                            user_id,
+                            message,
+                        },
+                        args.trim(),
+                    )
+                    .await
+            }
+            None => Err(format!(
+                "unknown command '/{name}', type /help to list available commands"
+            )),
+        }
+    }
+
+    fn help(&self, is_admin: bool) -> String {
+        std::iter::once("Available commands:\n/help - list available commands".to_string())
+            .chain(
+                self.commands
+                    .iter()
+                    .filter(|command| is_admin || !command.admin_only())
+                    .map(|command| format!("{} - {}", command.usage(), command.description())),
+            )
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
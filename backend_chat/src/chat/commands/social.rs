@@ -0,0 +1,243 @@
+use chrono::{Duration, Utc};
+use futures::{FutureExt, future::BoxFuture};
+use rand::Rng;
+
+use shared_chat::types::{ChatChannel, ChatMessage};
+
+use super::{ChatCommand, CommandContext, CommandOutcome, CommandResult};
+
+const ACTIVE_USERS_WINDOW: Duration = Duration::minutes(10);
+const DEFAULT_ROLL_MAX: u32 = 100;
+const MAX_ROLL_MAX: u32 = 1_000_000;
+
+pub struct WhisperCommand;
+
+impl ChatCommand for WhisperCommand {
+    fn name(&self) -> &'static str {
+        "w"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/w <name> <message>"
+    }
+
+    fn description(&self) -> &'static str {
+        "send a private message"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl WhisperCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        if args.split_once(' ').is_none() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        Ok(CommandOutcome::Post(ChatMessage {
+            content: format!("@{args}"),
+            ..context.message.clone()
+        }))
+    }
+}
+
+pub struct ReplyCommand;
+
+impl ChatCommand for ReplyCommand {
+    fn name(&self) -> &'static str {
+        "r"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/r <message>"
+    }
+
+    fn description(&self) -> &'static str {
+        "reply to the last private message received"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl ReplyCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        if args.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        let (_, username) = context
+            .chat_state
+            .reply_targets
+            .get(&context.user_id)
+            .map(|entry| entry.value().clone())
+            .ok_or("nobody to reply to")?;
+
+        Ok(CommandOutcome::Post(ChatMessage {
+            content: format!("@{username} {args}"),
+            ..context.message.clone()
+        }))
+    }
+}
+
+pub struct IgnoreCommand;
+
+impl ChatCommand for IgnoreCommand {
+    fn name(&self) -> &'static str {
+        "ignore"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/ignore <name>"
+    }
+
+    fn description(&self) -> &'static str {
+        "ignore or stop ignoring messages from a player"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl IgnoreCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        if args.is_empty() {
+            return Err(format!("usage: {}", self.usage()));
+        }
+
+        let (ignored_user_id, username) = context
+            .chat_state
+            .usernames_map
+            .get(&args.to_ascii_lowercase())
+            .map(|entry| entry.value().clone())
+            .ok_or("unknown user or not connected")?;
+
+        let ignore_lists = &context.chat_state.ignore_lists;
+
+        if ignore_lists
+            .unignore(context.user_id, ignored_user_id)
+            .await
+        {
+            return Ok(CommandOutcome::Reply(format!(
+                "You are no longer ignoring {username}."
+            )));
+        }
+
+        ignore_lists
+            .ignore(context.user_id, ignored_user_id)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        Ok(CommandOutcome::Reply(format!(
+            "You are now ignoring {username}."
+        )))
+    }
+}
+
+pub struct WhoCommand;
+
+impl ChatCommand for WhoCommand {
+    fn name(&self) -> &'static str {
+        "who"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/who"
+    }
+
+    fn description(&self) -> &'static str {
+        "show how many players are online"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl WhoCommand {
+    async fn run(&self, context: CommandContext<'_>, _args: &str) -> CommandResult {
+        let online_users = context
+            .chat_state
+            .users_map
+            .iter()
+            .filter(|sessions| !sessions.is_empty())
+            .count();
+
+        let since = Utc::now() - ACTIVE_USERS_WINDOW;
+        let history = &context.chat_state.history;
+
+        Ok(CommandOutcome::Reply(format!(
+            "{online_users} players online. Active in the last {} minutes: {} in Global, {} in Trade.",
+            ACTIVE_USERS_WINDOW.num_minutes(),
+            history.active_users(ChatChannel::Global, since),
+            history.active_users(ChatChannel::Trade, since),
+        )))
+    }
+}
+
+pub struct RollCommand;
+
+impl ChatCommand for RollCommand {
+    fn name(&self) -> &'static str {
+        "roll"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/roll [max]"
+    }
+
+    fn description(&self) -> &'static str {
+        "roll a dice between 1 and max (100 by default)"
+    }
+
+    fn execute<'a>(
+        &'a self,
+        context: CommandContext<'a>,
+        args: &'a str,
+    ) -> BoxFuture<'a, CommandResult> {
+        self.run(context, args).boxed()
+    }
+}
+
+impl RollCommand {
+    async fn run(&self, context: CommandContext<'_>, args: &str) -> CommandResult {
+        let max = if args.is_empty() {
+            DEFAULT_ROLL_MAX
+        } else {
+            args.parse::<u32>()
+                .ok()
+                .filter(|max| (2..=MAX_ROLL_MAX).contains(max))
+                .ok_or_else(|| format!("max must be a number between 2 and {MAX_ROLL_MAX}"))?
+        };
+
+        let roll = rand::rng().random_range(1..=max);
+
+        // Posted in the system channel so players cannot fake it
+        Ok(CommandOutcome::Post(ChatMessage {
+            channel: ChatChannel::System,
+            content: format!("rolls {roll} (1-{max})"),
+            linked_item: None,
+            ..context.message.clone()
+        }))
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use shared_chat::types::UserId;
+
+use crate::chat::json_snapshot::JsonSnapshot;
+
+const MAX_IGNORED_USERS: usize = 100;
+
+/// Users ignored by each user, saved as a JSON snapshot after every change
+#[derive(Debug)]
+pub struct IgnoreListsStore {
+    ignore_lists: Mutex<HashMap<UserId, HashSet<UserId>>>,
+    snapshot: JsonSnapshot,
+}
+
+impl IgnoreListsStore {
+    pub fn load(snapshot_path: Option<PathBuf>) -> Self {
+        let snapshot = JsonSnapshot::new("chat ignore lists", snapshot_path);
+        Self {
+            ignore_lists: Mutex::new(snapshot.load()),
+            snapshot,
+        }
+    }
+
+    pub fn get(&self, user_id: UserId) -> HashSet<UserId> {
+        self.ignore_lists
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn is_ignoring(&self, user_id: UserId, other_user_id: UserId) -> bool {
+        self.ignore_lists
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .is_some_and(|ignore_list| ignore_list.contains(&other_user_id))
+    }
+
+    pub async fn ignore(&self, user_id: UserId, ignored_user_id: UserId) -> anyhow::Result<()> {
+        if user_id == ignored_user_id {
+            anyhow::bail!("cannot ignore yourself");
+        }
+
+        {
+            let mut ignore_lists = self.ignore_lists.lock().unwrap();
+            let ignore_list = ignore_lists.entry(user_id).or_default();
+            if ignore_list.len() >= MAX_IGNORED_USERS && !ignore_list.contains(&ignored_user_id) {
+                anyhow::bail!("cannot ignore more than {MAX_IGNORED_USERS} users");
+            }
+            ignore_list.insert(ignored_user_id);
+        }
+        self.save().await;
+        Ok(())
+    }
+
+    /// Return whether the user was ignored
+    pub async fn unignore(&self, user_id: UserId, ignored_user_id: UserId) -> bool {
+        let removed = {
+            let mut ignore_lists = self.ignore_lists.lock().unwrap();
+            let Some(ignore_list) = ignore_lists.get_mut(&user_id) else {
+                return false;
+            };
+
+            let removed = ignore_list.remove(&ignored_user_id);
+            if ignore_list.is_empty() {
+                ignore_lists.remove(&user_id);
+            }
+            removed
+        };
+        if removed {
+            self.save().await;
+        }
+        removed
+    }
+
+    async fn save(&self) {
+        self.snapshot
+            .save(|| serde_json::to_vec(&*self.ignore_lists.lock().unwrap()))
+            .await;
+    }
+}
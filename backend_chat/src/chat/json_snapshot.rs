@@ -0,0 +1,60 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// JSON file fully rewritten after every change, for small stores without database
+#[derive(Debug)]
+pub struct JsonSnapshot {
+    name: &'static str,
+    path: Option<PathBuf>,
+    // Serialize snapshot writes
+    save_lock: tokio::sync::Mutex<()>,
+}
+
+impl JsonSnapshot {
+    pub fn new(name: &'static str, path: Option<PathBuf>) -> Self {
+        Self {
+            name,
+            path,
+            save_lock: Default::default(),
+        }
+    }
+
+    pub fn load<T: DeserializeOwned + Default>(&self) -> T {
+        self.path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| match read_snapshot(path) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    tracing::error!("failed to load {}: {err}", self.name);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Serialization is done after acquiring the write lock so the latest state is always saved last
+    pub async fn save(&self, serialize: impl FnOnce() -> serde_json::Result<Vec<u8>>) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+
+        let _save_lock = self.save_lock.lock().await;
+        let snapshot = serialize();
+        if let Err(err) = async {
+            let tmp_path = path.with_extension("tmp");
+            tokio::fs::write(&tmp_path, snapshot?).await?;
+            tokio::fs::rename(&tmp_path, path).await?;
+            anyhow::Ok(())
+        }
+        .await
+        {
+            tracing::error!("failed to save {}: {err}", self.name);
+        }
+    }
+}
+
+fn read_snapshot<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
@@ -1,46 +1,69 @@
 use axum::body::Bytes;
 use chrono::Utc;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 
-use backend_shared::profanities_checker::ProfanitiesChecker;
+use backend_shared::{
+    profanities_checker::ProfanitiesChecker,
+    signature::{self, HmacKey},
+};
 use shared_chat::{
     messages::server::{ErrorMessage, ErrorType, ServerChatMessage, ServerWhisperFeedbackMessage},
-    ring_buffer::RingBuffer,
-    types::{ChatChannel, ChatContent, ChatMessage},
+    moderation::{ChatSanction, ChatSanctionType},
+    types::{ChatChannel, ChatContent, ChatMessage, LinkedItemBytes, LinkedItemSignature, UserId},
 };
 
-use crate::chat::chat_state::ChatState;
+use crate::chat::{
+    chat_history::ChatHistory,
+    chat_state::{ChatState, OutboundMessage},
+    commands::{ChatCommands, CommandOutcome, CommandResult},
+    ignore_lists_store::IgnoreListsStore,
+    sanctions_store::SanctionsStore,
+};
 
 pub struct MessagesProcessor {
     inbound_rx: mpsc::Receiver<(Uuid, ChatMessage)>,
+    command_results_tx: mpsc::Sender<(Uuid, CommandResult)>,
+    command_results_rx: mpsc::Receiver<(Uuid, CommandResult)>,
 
     chat_state: ChatState,
     profanities_checker: ProfanitiesChecker,
-    // item_signature_key: Arc<HmacKey>,
+    commands: Arc<ChatCommands>,
 }
 
 impl MessagesProcessor {
     pub fn new(
         profanities_checker: ProfanitiesChecker,
-        // item_signature_key: HmacKey,
+        chat_history: ChatHistory,
+        sanctions_store: SanctionsStore,
+        ignore_lists_store: IgnoreListsStore,
+        commands: ChatCommands,
+        item_signature_key: HmacKey,
     ) -> Self {
         let (inbound_tx, inbound_rx) = mpsc::channel(1000);
         let (outbound_tx, _) = broadcast::channel(500);
+        let (command_results_tx, command_results_rx) = mpsc::channel(100);
         Self {
             inbound_rx,
+            command_results_tx,
+            command_results_rx,
             chat_state: ChatState {
                 inbound_tx,
                 outbound_tx,
                 reply_map: Default::default(),
                 users_map: Default::default(),
                 usernames_map: Default::default(),
-                history: Arc::new(Mutex::new(RingBuffer::new(100))),
+                admins: Default::default(),
+                reply_targets: Default::default(),
+                ignore_lists: Arc::new(ignore_lists_store),
+                history: Arc::new(chat_history),
                 users_moderation: Default::default(),
+                sanctions: Arc::new(sanctions_store),
+                item_signature_key: Arc::new(item_signature_key),
             },
             profanities_checker,
-            // item_signature_key: Arc::new(item_signature_key),
+            commands: Arc::new(commands),
         }
     }
 
@@ -49,149 +72,241 @@ impl MessagesProcessor {
     }
 
     pub async fn run(mut self) {
-        while let Some((session_id, msg)) = self.inbound_rx.recv().await {
-            if msg.channel == ChatChannel::System && session_id != Uuid::default() {
-                send_direct_error(&self.chat_state, session_id, "cannot send to that channel")
-                    .await;
-                continue;
+        loop {
+            tokio::select! {
+                Some((session_id, msg)) = self.inbound_rx.recv() => {
+                    self.receive_message(session_id, msg).await
+                }
+                Some((session_id, result)) = self.command_results_rx.recv() => {
+                    self.complete_command(session_id, result).await
+                }
+                else => break,
             }
+        }
+    }
 
-            if let Some(user_id) = msg.user_id {
-                let mut user_moderation =
-                    self.chat_state.users_moderation.entry(user_id).or_default();
+    async fn receive_message(&mut self, session_id: Uuid, msg: ChatMessage) {
+        if msg.channel == ChatChannel::System && session_id != Uuid::default() {
+            send_direct_error(&self.chat_state, session_id, "cannot send to that channel").await;
+            return;
+        }
 
-                if user_moderation.is_muted() {
-                    send_direct_error(&self.chat_state, session_id, "you are muted").await;
-                    continue;
-                }
+        if let Some(user_id) = msg.user_id {
+            if self
+                .chat_state
+                .sanctions
+                .is_sanctioned(user_id, ChatSanctionType::Ban)
+            {
+                send_direct_error(&self.chat_state, session_id, "you are banned").await;
+                return;
+            }
 
-                if !user_moderation.allow_message() {
-                    send_direct_error(&self.chat_state, session_id, "rate limited").await;
-                    continue;
-                }
+            if self
+                .chat_state
+                .sanctions
+                .is_sanctioned(user_id, ChatSanctionType::Mute)
+            {
+                send_direct_error(&self.chat_state, session_id, "you are muted").await;
+                return;
             }
 
-            // if !verify_linked_item(
-            //     &msg.linked_item,
-            //     msg.item_signature,
-            //     &self.item_signature_key,
-            // ) {
-            //     send_direct_error(&self.chat_state, session_id, "invalid item linked").await;
-            //     continue;
-            // }
-
-            let (content, channel, target_username) =
-                if let Some((username, message)) = parse_whisper_message(&msg.content) {
-                    if let Some(entry) = self.chat_state.usernames_map.get(&username) {
-                        let (user_id, target_username) = entry.value();
-                        (
-                            message.into_inner(),
-                            ChatChannel::Whisper(*user_id),
-                            Some(target_username.clone()),
-                        )
-                    } else {
-                        send_direct_error(
-                            &self.chat_state,
-                            session_id,
-                            "unknown user or not connected",
-                        )
-                        .await;
-
-                        continue;
-                    }
-                } else {
-                    (msg.content, msg.channel, None)
-                };
+            let mut user_moderation = self.chat_state.users_moderation.entry(user_id).or_default();
 
-            let content = if let Some(profanity) = self.profanities_checker.find_profanity(&content)
-            {
-                tracing::warn!(target: "chat", channel = ?msg.channel, user_id = %msg.user_id.unwrap_or_default(), content = %&content, "moderated message");
-                // send_direct_error(
-                //     &self.chat_state,
-                //     session_id,
-                //     &format!(
-                //         "Your message has been redacted because it contains the profanity '{}': {}",
-                //         profanity, content
-                //     ),
-                // )
-                // .await;
+            if !user_moderation.allow_message() {
+                send_direct_error(&self.chat_state, session_id, "rate limited").await;
+                return;
+            }
+        }
 
-                send_direct_message(
-                    &self.chat_state,
-                    session_id,
-                    ChatMessage {
-                        channel: ChatChannel::System,
-                        sent_at: Utc::now(),
-                        user_id: None,
-                        username: None,
-                        character_id: None,
-                        character_name: None,
-                        chat_badge: None,
-                        content: format!(
-                            "Your message has been redacted because it contains the profanity '{}': \"{}\"",
-                            profanity, content
-                        ),
-                        linked_item: None,
-                    }
-                    .into(),
-                )
-                .await;
-                "***".into()
+        if msg.user_id.is_some() && msg.content.starts_with('/') {
+            // Commands may call the backend, so they must not hold the other messages
+            let commands = self.commands.clone();
+            let chat_state = self.chat_state.clone();
+            let command_results_tx = self.command_results_tx.clone();
+            tokio::spawn(async move {
+                let result = commands.execute(&chat_state, session_id, &msg).await;
+                let _ = command_results_tx.send((session_id, result)).await;
+            });
+        } else {
+            self.post_message(session_id, msg).await;
+        }
+    }
+
+    async fn complete_command(&mut self, session_id: Uuid, result: CommandResult) {
+        match result {
+            Ok(CommandOutcome::Post(msg)) => self.post_message(session_id, msg).await,
+            Ok(CommandOutcome::Reply(content)) => {
+                send_direct_message(&self.chat_state, session_id, system_message(content).into())
+                    .await
+            }
+            Ok(CommandOutcome::Done) => {}
+            Err(error) => send_direct_error(&self.chat_state, session_id, &error).await,
+        }
+    }
+
+    async fn post_message(&mut self, session_id: Uuid, msg: ChatMessage) {
+        // Forged items are still posted, but shown as unverified
+        let linked_item_verified = verify_linked_item(
+            &msg.linked_item,
+            msg.item_signature,
+            &self.chat_state.item_signature_key,
+        );
+        if msg.linked_item.is_some() && !linked_item_verified {
+            tracing::warn!(target: "chat", channel = ?msg.channel, user_id = %msg.user_id.unwrap_or_default(), "unverified linked item");
+        }
+
+        let (content, channel, target_username) =
+            if let Some((username, message)) = parse_whisper_message(&msg.content) {
+                if let Some(entry) = self.chat_state.usernames_map.get(&username) {
+                    let (user_id, target_username) = entry.value();
+                    (
+                        message.into_inner(),
+                        ChatChannel::Whisper(*user_id),
+                        Some(target_username.clone()),
+                    )
+                } else {
+                    send_direct_error(
+                        &self.chat_state,
+                        session_id,
+                        "unknown user or not connected",
+                    )
+                    .await;
+
+                    return;
+                }
             } else {
-                tracing::info!(target: "chat", channel = ?msg.channel, user_id = %msg.user_id.unwrap_or_default(), content = %&content, "message");
-                content
+                (msg.content, msg.channel, None)
             };
 
-            let chat_message = ChatMessage {
-                content,
-                channel,
-                // item_signature: None,
-                ..msg
-            };
-            let server_chat_message = ServerChatMessage::Broadcast(chat_message.clone().into());
+        let content = if let Some(profanity) = self.profanities_checker.find_profanity(&content) {
+            tracing::warn!(target: "chat", channel = ?msg.channel, user_id = %msg.user_id.unwrap_or_default(), content = %&content, "moderated message");
+            // send_direct_error(
+            //     &self.chat_state,
+            //     session_id,
+            //     &format!(
+            //         "Your message has been redacted because it contains the profanity '{}': {}",
+            //         profanity, content
+            //     ),
+            // )
+            // .await;
 
-            if let ChatChannel::Whisper(user_id) = channel {
-                if let Some(targets) = self.chat_state.users_map.get(&user_id)
-                    && !targets.is_empty()
-                {
-                    for target_session_id in targets.iter() {
-                        send_direct_message(
-                            &self.chat_state,
-                            *target_session_id,
-                            server_chat_message.clone(),
-                        )
-                        .await;
-                    }
+            send_direct_message(
+                &self.chat_state,
+                session_id,
+                system_message(format!(
+                    "Your message has been redacted because it contains the profanity '{}': \"{}\"",
+                    profanity, content
+                ))
+                .into(),
+            )
+            .await;
+            metrics::counter!("chat_messages_redacted_total").increment(1);
+            "***".into()
+        } else {
+            tracing::info!(target: "chat", channel = ?msg.channel, user_id = %msg.user_id.unwrap_or_default(), content = %&content, "message");
+            content
+        };
+
+        let chat_message = ChatMessage {
+            content,
+            channel,
+            linked_item_verified,
+            item_signature: None,
+            ..msg
+        };
+        let server_chat_message = ServerChatMessage::Broadcast(chat_message.clone().into());
+        record_message(&channel);
+
+        // Shadow muted users only receive their own messages back
+        if let Some(user_id) = chat_message.user_id
+            && self
+                .chat_state
+                .sanctions
+                .is_sanctioned(user_id, ChatSanctionType::ShadowMute)
+        {
+            tracing::info!(target: "chat", channel = ?channel, user_id = %user_id, "shadow muted message");
+            self.chat_state
+                .history
+                .push_hidden(chat_message.clone())
+                .await;
+            match channel {
+                ChatChannel::Whisper(target_user_id) => {
                     send_direct_message(
                         &self.chat_state,
                         session_id,
                         ServerWhisperFeedbackMessage {
                             target_username,
-                            target_user_id: user_id,
+                            target_user_id,
                             chat_message,
                         }
                         .into(),
                     )
-                    .await;
-                } else {
-                    send_direct_error(
+                    .await
+                }
+                _ => send_user_message(&self.chat_state, user_id, server_chat_message).await,
+            }
+            return;
+        }
+
+        if let ChatChannel::Whisper(user_id) = channel {
+            if let Some(sender_id) = chat_message.user_id
+                && self.chat_state.ignore_lists.is_ignoring(user_id, sender_id)
+            {
+                send_direct_error(
+                    &self.chat_state,
+                    session_id,
+                    "this user does not accept your whispers",
+                )
+                .await;
+                return;
+            }
+
+            let targets: Vec<_> = self
+                .chat_state
+                .users_map
+                .get(&user_id)
+                .map(|sessions| sessions.iter().copied().collect())
+                .unwrap_or_default();
+
+            if !targets.is_empty() {
+                if let (Some(sender_id), Some(sender_username)) =
+                    (chat_message.user_id, chat_message.username.clone())
+                {
+                    self.chat_state
+                        .reply_targets
+                        .insert(user_id, (sender_id, sender_username));
+                }
+
+                self.chat_state.history.push(chat_message.clone()).await;
+                for target_session_id in targets {
+                    send_direct_message(
                         &self.chat_state,
-                        session_id,
-                        "whisper target user not connected",
+                        target_session_id,
+                        server_chat_message.clone(),
                     )
                     .await;
                 }
+                send_direct_message(
+                    &self.chat_state,
+                    session_id,
+                    ServerWhisperFeedbackMessage {
+                        target_username,
+                        target_user_id: user_id,
+                        chat_message,
+                    }
+                    .into(),
+                )
+                .await;
             } else {
-                self.chat_state
-                    .history
-                    .lock()
-                    .unwrap()
-                    .push(Arc::new(chat_message));
-                if let Ok(ser_message) = rmp_serde::to_vec(&server_chat_message) {
-                    let message = Arc::new(Bytes::from(ser_message));
-                    let _ = self.chat_state.outbound_tx.send(message);
-                }
+                send_direct_error(
+                    &self.chat_state,
+                    session_id,
+                    "whisper target user not connected",
+                )
+                .await;
             }
+        } else {
+            broadcast_message(&self.chat_state, chat_message).await;
         }
     }
 }
@@ -216,6 +331,83 @@ async fn send_direct_message(chat_state: &ChatState, session_id: Uuid, msg: Serv
     }
 }
 
+pub async fn broadcast_message(chat_state: &ChatState, msg: ChatMessage) {
+    let author_id = msg.user_id;
+    let server_chat_message = ServerChatMessage::Broadcast(msg.clone().into());
+    chat_state.history.push(msg).await;
+    if let Ok(ser_message) = rmp_serde::to_vec(&server_chat_message) {
+        let _ = chat_state.outbound_tx.send(OutboundMessage {
+            author_id,
+            bytes: Arc::new(Bytes::from(ser_message)),
+        });
+    }
+}
+
+pub async fn send_user_message(chat_state: &ChatState, user_id: UserId, msg: ServerChatMessage) {
+    let session_ids: Vec<_> = chat_state
+        .users_map
+        .get(&user_id)
+        .map(|sessions| sessions.iter().copied().collect())
+        .unwrap_or_default();
+
+    for session_id in session_ids {
+        send_direct_message(chat_state, session_id, msg.clone()).await;
+    }
+}
+
+/// Shadow mutes are not notified on purpose
+pub async fn notify_sanction(chat_state: &ChatState, user_id: UserId, sanction: &ChatSanction) {
+    let duration = match sanction.until {
+        Some(until) => format!("until {until}"),
+        None => "permanently".to_string(),
+    };
+
+    match sanction.sanction_type {
+        ChatSanctionType::Mute => {
+            send_user_message(
+                chat_state,
+                user_id,
+                system_message(format!(
+                    "You have been muted {duration}: {}",
+                    sanction.reason
+                ))
+                .into(),
+            )
+            .await
+        }
+        ChatSanctionType::Ban => {
+            send_user_message(
+                chat_state,
+                user_id,
+                ErrorMessage {
+                    error_type: ErrorType::Chat,
+                    message: format!("banned from chat {duration}: {}", sanction.reason),
+                    must_disconnect: true,
+                }
+                .into(),
+            )
+            .await
+        }
+        ChatSanctionType::ShadowMute => {}
+    }
+}
+
+pub fn system_message(content: String) -> ChatMessage {
+    ChatMessage {
+        channel: ChatChannel::System,
+        sent_at: Utc::now(),
+        user_id: None,
+        username: None,
+        character_id: None,
+        character_name: None,
+        chat_badge: None,
+        content,
+        linked_item: None,
+        linked_item_verified: false,
+        item_signature: None,
+    }
+}
+
 fn parse_whisper_message(content: &str) -> Option<(String, ChatContent)> {
     if !content.starts_with('@') {
         return None;
@@ -236,19 +428,27 @@ fn parse_whisper_message(content: &str) -> Option<(String, ChatContent)> {
     ))
 }
 
-// fn verify_linked_item(
-//     linked_item: &Option<LinkedItemBytes>,
-//     item_signature: Option<HmacSignature>,
-//     key: &HmacKey,
-// ) -> bool {
-//     linked_item
-//         .as_ref()
-//         .map(|linked_item| {
-//             signature::verify_hmac(
-//                 linked_item.as_ref(),
-//                 &item_signature.unwrap_or_default(),
-//                 key,
-//             )
-//         })
-//         .unwrap_or(true)
-// }
+/// Only true if an item is linked and was signed by the game server
+pub fn verify_linked_item(
+    linked_item: &Option<LinkedItemBytes>,
+    item_signature: Option<LinkedItemSignature>,
+    key: &HmacKey,
+) -> bool {
+    match (linked_item, item_signature) {
+        (Some(linked_item), Some(item_signature)) => {
+            signature::verify_hmac(linked_item.as_ref(), &item_signature, key)
+        }
+        _ => false,
+    }
+}
+
+/// Count messages per channel, whispers are not split per recipient
+pub fn record_message(channel: &ChatChannel) {
+    let channel = match channel {
+        ChatChannel::System => "system",
+        ChatChannel::Global => "global",
+        ChatChannel::Trade => "trade",
+        ChatChannel::Whisper(_) => "whisper",
+    };
+    metrics::counter!("chat_messages_total", "channel" => channel).increment(1);
+}
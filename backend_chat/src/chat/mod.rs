@@ -1,5 +1,10 @@
 pub mod character_resolver;
+pub mod chat_history;
 pub mod chat_session;
 pub mod chat_state;
+pub mod commands;
+pub mod ignore_lists_store;
+pub mod json_snapshot;
 pub mod messages_processor;
+pub mod sanctions_store;
 pub mod user_moderation;
@@ -0,0 +1,94 @@
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
+
+use shared_chat::{
+    moderation::{ChatSanction, ChatSanctionType},
+    types::UserId,
+};
+
+use crate::chat::json_snapshot::JsonSnapshot;
+
+/// Sanctions applied by moderators, saved as a JSON snapshot after every change
+#[derive(Debug)]
+pub struct SanctionsStore {
+    sanctions: Mutex<HashMap<UserId, Vec<ChatSanction>>>,
+    snapshot: JsonSnapshot,
+}
+
+impl SanctionsStore {
+    pub fn load(snapshot_path: Option<PathBuf>) -> Self {
+        let snapshot = JsonSnapshot::new("chat sanctions", snapshot_path);
+        Self {
+            sanctions: Mutex::new(snapshot.load()),
+            snapshot,
+        }
+    }
+
+    pub fn get(&self, user_id: UserId, sanction_type: ChatSanctionType) -> Option<ChatSanction> {
+        self.sanctions
+            .lock()
+            .unwrap()
+            .get(&user_id)?
+            .iter()
+            .find(|sanction| sanction.sanction_type == sanction_type && sanction.is_active())
+            .cloned()
+    }
+
+    pub fn is_sanctioned(&self, user_id: UserId, sanction_type: ChatSanctionType) -> bool {
+        self.get(user_id, sanction_type).is_some()
+    }
+
+    pub fn active_sanctions(&self) -> Vec<(UserId, ChatSanction)> {
+        self.sanctions
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|(user_id, sanctions)| {
+                sanctions
+                    .iter()
+                    .filter(|sanction| sanction.is_active())
+                    .map(|sanction| (*user_id, sanction.clone()))
+            })
+            .collect()
+    }
+
+    /// Replace any sanction of the same type
+    pub async fn apply(&self, user_id: UserId, sanction: ChatSanction) {
+        {
+            let mut sanctions = self.sanctions.lock().unwrap();
+            let user_sanctions = sanctions.entry(user_id).or_default();
+            user_sanctions.retain(|user_sanction| {
+                user_sanction.sanction_type != sanction.sanction_type && user_sanction.is_active()
+            });
+            user_sanctions.push(sanction);
+        }
+        self.save().await;
+    }
+
+    /// Return whether there was an active sanction to revoke
+    pub async fn revoke(&self, user_id: UserId, sanction_type: ChatSanctionType) -> bool {
+        let revoked = {
+            let mut sanctions = self.sanctions.lock().unwrap();
+            let Some(user_sanctions) = sanctions.get_mut(&user_id) else {
+                return false;
+            };
+
+            let revoked = user_sanctions
+                .iter()
+                .any(|sanction| sanction.sanction_type == sanction_type && sanction.is_active());
+            user_sanctions
+                .retain(|sanction| sanction.sanction_type != sanction_type && sanction.is_active());
+            if user_sanctions.is_empty() {
+                sanctions.remove(&user_id);
+            }
+            revoked
+        };
+        self.save().await;
+        revoked
+    }
+
+    async fn save(&self) {
+        self.snapshot
+            .save(|| serde_json::to_vec(&*self.sanctions.lock().unwrap()))
+            .await;
+    }
+}
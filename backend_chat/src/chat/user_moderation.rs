@@ -1,10 +1,9 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 #[derive(Debug, Clone)]
 pub struct UserModerationState {
     tokens: u32,
     last_refill: Instant,
-    mute_until: Option<Instant>,
 }
 
 impl Default for UserModerationState {
@@ -18,7 +17,6 @@ impl UserModerationState {
         Self {
             tokens: 5,
             last_refill: Instant::now(),
-            mute_until: None,
         }
     }
 
@@ -39,15 +37,4 @@ impl UserModerationState {
         self.tokens -= 1;
         true
     }
-
-    pub fn is_muted(&self) -> bool {
-        match self.mute_until {
-            Some(t) => Instant::now() < t,
-            None => false,
-        }
-    }
-
-    pub fn mute(&mut self, duration: Duration) {
-        self.mute_until = Some(Instant::now() + duration);
-    }
 }
@@ -4,7 +4,9 @@ use axum::{
     Router,
     routing::{any, get},
 };
-use backend_shared::profanities_checker::ProfanitiesChecker;
+use backend_shared::{
+    metrics::PrometheusHandle, profanities_checker::ProfanitiesChecker, signature,
+};
 use http::{
     HeaderValue, Method,
     header::{AUTHORIZATION, CONTENT_TYPE},
@@ -19,7 +21,10 @@ use tracing_subscriber::{Layer, filter::Targets, layer::SubscriberExt, util::Sub
 
 use backend_chat::{
     app_state::{AppSettings, AppState},
-    chat::messages_processor::MessagesProcessor,
+    chat::{
+        chat_history::ChatHistory, commands::ChatCommands, ignore_lists_store::IgnoreListsStore,
+        messages_processor::MessagesProcessor, sanctions_store::SanctionsStore,
+    },
     rest, websocket,
 };
 
@@ -56,6 +61,9 @@ async fn main() {
         .with(chat_layer)
         .init();
 
+    let metrics_handle =
+        backend_shared::metrics::install_recorder().expect("failed to install metrics recorder");
+
     let tracer_layer =
         TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default().include_headers(true));
 
@@ -71,24 +79,52 @@ async fn main() {
         .allow_headers([CONTENT_TYPE, AUTHORIZATION])
         .allow_credentials(true);
 
+    let app_settings = AppSettings::from_env();
+
     let messages_processor = MessagesProcessor::new(
         ProfanitiesChecker::load_from_file(
             "profanities/strong_profanities.txt",
             "profanities/weak_profanities.txt",
         )
         .expect("failed to load profanities"),
-        // signature::load_hmac_key("HMAC_KEY"),
+        ChatHistory::load(
+            std::env::var("CHAT_HISTORY_PATH")
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(Into::into),
+        ),
+        SanctionsStore::load(
+            std::env::var("CHAT_SANCTIONS_PATH")
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(Into::into),
+        ),
+        IgnoreListsStore::load(
+            std::env::var("CHAT_IGNORE_LISTS_PATH")
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(Into::into),
+        ),
+        ChatCommands::new(&app_settings.backend_url),
+        signature::load_hmac_key("HMAC_KEY"),
     );
 
     let app_state = AppState {
-        app_settings: AppSettings::from_env(),
+        app_settings,
         chat_state: messages_processor.get_chat_state(),
     };
 
     let messages_processor_handle = tokio::spawn(MessagesProcessor::run(messages_processor));
 
+    let metrics_users_map = app_state.chat_state.users_map.clone();
     let app = Router::new()
         .route("/", get(|| async { "OK" }))
+        .route(
+            "/metrics",
+            get(move || {
+                std::future::ready(render_metrics(&metrics_handle, metrics_users_map.len()))
+            }),
+        )
         .merge(rest::routes())
         .route("/chatws", any(websocket::handler))
         .with_state(app_state.clone())
@@ -113,3 +149,8 @@ async fn main() {
 
     tracing::debug!("server has been shut down");
 }
+
+fn render_metrics(metrics_handle: &PrometheusHandle, users_connected: usize) -> String {
+    metrics::gauge!("chat_users_connected").set(users_connected as f64);
+    metrics_handle.render()
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    routing::{get, post},
+};
+
+use shared_chat::{moderation::ChatIgnoreListResponse, types::UserId};
+
+use crate::{app_state::AppState, chat::chat_state::ChatState};
+
+use super::AppError;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/ignore-lists/{user_id}", get(get_ignore_list))
+        .route(
+            "/ignore-lists/{user_id}/{ignored_user_id}",
+            post(post_ignore_user).delete(delete_ignored_user),
+        )
+}
+
+async fn get_ignore_list(
+    State(chat_state): State<ChatState>,
+    Path(user_id): Path<UserId>,
+) -> Result<Json<ChatIgnoreListResponse>, AppError> {
+    Ok(Json(ChatIgnoreListResponse {
+        ignored_user_ids: chat_state.ignore_lists.get(user_id).into_iter().collect(),
+    }))
+}
+
+async fn post_ignore_user(
+    State(chat_state): State<ChatState>,
+    Path((user_id, ignored_user_id)): Path<(UserId, UserId)>,
+) -> Result<Json<()>, AppError> {
+    chat_state
+        .ignore_lists
+        .ignore(user_id, ignored_user_id)
+        .await
+        .map_err(|err| AppError::UserError(err.to_string()))?;
+    Ok(Json(()))
+}
+
+async fn delete_ignored_user(
+    State(chat_state): State<ChatState>,
+    Path((user_id, ignored_user_id)): Path<(UserId, UserId)>,
+) -> Result<Json<()>, AppError> {
+    if !chat_state
+        .ignore_lists
+        .unignore(user_id, ignored_user_id)
+        .await
+    {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(()))
+}
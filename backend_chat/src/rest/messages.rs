@@ -2,17 +2,24 @@ use anyhow::{Result, anyhow};
 
 use axum::{
     Json, Router,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     routing::post,
 };
 use backend_shared::http::users::UserId;
 use chrono::Utc;
+use serde::Deserialize;
 use shared_chat::{
     messages::{client::ClientPostMessage, server::ServerChatMessage},
     types::ChatMessage,
 };
 
-use crate::{app_state::AppState, chat::chat_state::ChatState};
+use crate::{
+    app_state::AppState,
+    chat::{
+        chat_state::ChatState,
+        messages_processor::{record_message, verify_linked_item},
+    },
+};
 
 use super::AppError;
 
@@ -22,17 +29,32 @@ pub fn routes() -> Router<AppState> {
         .route("/message", post(post_broadcast_message))
 }
 
+#[derive(Deserialize)]
+struct PrivateMessageQuery {
+    /// User on behalf of whom the message is sent, if any
+    from_user_id: Option<UserId>,
+}
+
 async fn post_private_message(
     State(chat_state): State<ChatState>,
     Path(user_id): Path<UserId>,
+    Query(query): Query<PrivateMessageQuery>,
     Json(payload): Json<ClientPostMessage>,
 ) -> Result<Json<()>, AppError> {
+    if let Some(from_user_id) = query.from_user_id
+        && chat_state.ignore_lists.is_ignoring(user_id, from_user_id)
+    {
+        return Err(AppError::Forbidden);
+    }
+
     let user_sessions = chat_state
         .users_map
         .get(&user_id)
         .ok_or(AppError::NotFound)?;
 
-    // let (linked_item, item_signature) = payload.linked_item.unzip();
+    record_message(&payload.channel);
+
+    let (linked_item, item_signature) = payload.linked_item.unzip();
     let server_chat_message = ServerChatMessage::Broadcast(
         ChatMessage {
             channel: payload.channel,
@@ -43,8 +65,13 @@ async fn post_private_message(
             character_name: None,
             chat_badge: None, // TODO: System badge?
             content: payload.content.into_inner(),
-            linked_item: payload.linked_item,
-            // item_signature,
+            linked_item_verified: verify_linked_item(
+                &linked_item,
+                item_signature,
+                &chat_state.item_signature_key,
+            ),
+            linked_item,
+            item_signature: None,
         }
         .into(),
     );
@@ -62,7 +89,7 @@ async fn post_broadcast_message(
     State(chat_state): State<ChatState>,
     Json(payload): Json<ClientPostMessage>,
 ) -> Result<Json<()>, AppError> {
-    // let (linked_item, item_signature) = payload.linked_item.unzip();
+    let (linked_item, item_signature) = payload.linked_item.unzip();
     chat_state
         .inbound_tx
         .send((
@@ -76,8 +103,9 @@ async fn post_broadcast_message(
                 character_name: None,
                 chat_badge: None, // TODO: System badge?
                 content: payload.content.into_inner(),
-                linked_item: payload.linked_item,
-                // item_signature,
+                linked_item,
+                linked_item_verified: false,
+                item_signature,
             },
         ))
         .await
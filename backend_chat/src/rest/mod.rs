@@ -3,6 +3,7 @@ use axum::Router;
 use crate::app_state::AppState;
 
 mod app_error;
+mod ignore_lists;
 mod messages;
 mod moderation;
 
@@ -10,6 +11,7 @@ pub use app_error::AppError;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
+        .merge(ignore_lists::routes())
         .merge(messages::routes())
         .merge(moderation::routes())
 }
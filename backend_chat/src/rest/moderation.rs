@@ -1,44 +1,106 @@
-use std::time::Duration;
-
 use anyhow::Result;
+use std::sync::Arc;
 
 use axum::{
     Json, Router,
+    body::Bytes,
     extract::{Path, State},
-    routing::get,
+    routing::{delete, get, post},
 };
-use serde::{Deserialize, Serialize};
 
-use shared_chat::types::UserId;
+use shared_chat::{
+    messages::server::{ServerChatMessage, ServerDeleteMessagesMessage},
+    moderation::{
+        ChatDeleteMessagesRequest, ChatSanction, ChatSanctionType, ChatSanctionsResponse,
+        ChatUserMessagesResponse,
+    },
+    types::UserId,
+};
 
-use crate::{app_state::AppState, chat::chat_state::ChatState};
+use crate::{
+    app_state::AppState,
+    chat::{
+        chat_state::{ChatState, OutboundMessage},
+        messages_processor::notify_sanction,
+    },
+};
 
 use super::AppError;
 
+const REPORT_CONTEXT_LENGTH: usize = 20;
+
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/mute/{user_id}", get(mute_user))
+    Router::new()
+        .route("/moderation/sanctions", get(get_sanctions))
+        .route("/moderation/sanctions/{user_id}", post(post_sanction))
+        .route(
+            "/moderation/sanctions/{user_id}/{sanction_type}",
+            delete(delete_sanction),
+        )
+        .route("/moderation/messages/{user_id}", get(get_user_messages))
+        .route("/moderation/messages/delete", post(post_delete_messages))
 }
 
-async fn mute_user(
+async fn get_sanctions(
+    State(chat_state): State<ChatState>,
+) -> Result<Json<ChatSanctionsResponse>, AppError> {
+    Ok(Json(ChatSanctionsResponse {
+        sanctions: chat_state.sanctions.active_sanctions(),
+    }))
+}
+
+async fn post_sanction(
     State(chat_state): State<ChatState>,
     Path(user_id): Path<UserId>,
-    Json(payload): Json<MuteUserRequest>,
+    Json(payload): Json<ChatSanction>,
 ) -> Result<Json<()>, AppError> {
-    chat_state
-        .users_moderation
-        .entry(user_id)
-        .or_default()
-        .mute(payload.duration);
-    // TODO: Send private message to user
+    chat_state.sanctions.apply(user_id, payload.clone()).await;
+    notify_sanction(&chat_state, user_id, &payload).await;
+
+    Ok(Json(()))
+}
+
+async fn delete_sanction(
+    State(chat_state): State<ChatState>,
+    Path((user_id, sanction_type)): Path<(UserId, ChatSanctionType)>,
+) -> Result<Json<()>, AppError> {
+    if !chat_state.sanctions.revoke(user_id, sanction_type).await {
+        return Err(AppError::NotFound);
+    }
     Ok(Json(()))
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MuteUserRequest {
-    #[serde(default = "default_mute_duration")]
-    pub duration: Duration,
+async fn get_user_messages(
+    State(chat_state): State<ChatState>,
+    Path(user_id): Path<UserId>,
+) -> Result<Json<ChatUserMessagesResponse>, AppError> {
+    Ok(Json(ChatUserMessagesResponse {
+        messages: chat_state
+            .history
+            .user_messages(user_id, REPORT_CONTEXT_LENGTH),
+    }))
 }
 
-fn default_mute_duration() -> Duration {
-    Duration::from_hours(1)
+async fn post_delete_messages(
+    State(chat_state): State<ChatState>,
+    Json(payload): Json<ChatDeleteMessagesRequest>,
+) -> Result<Json<()>, AppError> {
+    chat_state
+        .history
+        .delete(payload.user_id, payload.sent_at)
+        .await;
+
+    if let Ok(ser_message) =
+        rmp_serde::to_vec(&ServerChatMessage::from(ServerDeleteMessagesMessage {
+            user_id: payload.user_id,
+            sent_at: payload.sent_at,
+        }))
+    {
+        let _ = chat_state.outbound_tx.send(OutboundMessage {
+            author_id: None,
+            bytes: Arc::new(Bytes::from(ser_message)),
+        });
+    }
+
+    Ok(Json(()))
 }
@@ -10,9 +10,12 @@ use axum::{
 };
 use axum_extra::TypedHeader;
 use backend_shared::http::users::{GetUserDetailsResponse, UserDetails};
-use shared_chat::messages::{
-    client::{ClientChatMessage, ClientConnectMessage},
-    server::{ErrorMessage, ErrorType},
+use shared_chat::{
+    messages::{
+        client::{ClientChatMessage, ClientConnectMessage},
+        server::{ErrorMessage, ErrorType},
+    },
+    moderation::ChatSanctionType,
 };
 use tokio::time::timeout;
 
@@ -119,6 +122,16 @@ async fn wait_for_connect(
 
 async fn handle_connect(app_state: &AppState, msg: ClientConnectMessage) -> Result<ChatSession> {
     let user_details = authorize_jwt(&app_state.app_settings, &msg.jwt).await?;
+    if let Some(ban) = app_state
+        .chat_state
+        .sanctions
+        .get(user_details.user.user_id, ChatSanctionType::Ban)
+    {
+        match ban.until {
+            Some(until) => anyhow::bail!("banned from chat until {until}: {}", ban.reason),
+            None => anyhow::bail!("banned from chat: {}", ban.reason),
+        }
+    }
     let character_resolver = CharacterResolver::connect(
         &app_state.app_settings.backend_url,
         user_details.user.user_id,
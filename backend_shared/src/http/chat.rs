@@ -2,6 +2,12 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use crate::signature::HmacSignature;
+
+/// Header holding the hex signature of the body of the requests made by the chat server
+/// on behalf of an admin
+pub const CHAT_SIGNATURE_HEADER: &str = "x-chat-signature";
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ErrorResponse {
     pub error: String,
@@ -12,3 +18,16 @@ impl fmt::Display for ErrorResponse {
         write!(f, "{}", self.error)
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetChatLinkedItemResponse {
+    /// Serialized item specs without signature and their signature, none if the slot is empty
+    pub linked_item: Option<(Vec<u8>, HmacSignature)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetChatLadderRankResponse {
+    pub area_name: String,
+    /// None if the character never completed the area
+    pub rank: Option<(i64, u16)>,
+}
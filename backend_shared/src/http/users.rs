@@ -21,6 +21,8 @@ pub struct UserDetails {
     pub email: Option<EmailNoValidate>,
     pub max_characters: u8,
     pub chat_badge: Option<String>,
+    #[serde(default)]
+    pub is_admin: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -1,3 +1,4 @@
 pub mod http;
+pub mod metrics;
 pub mod profanities_checker;
 pub mod signature;
@@ -35,3 +35,13 @@ pub fn verify_hmac(payload: &[u8], signature: &[u8; 32], key: &HmacKey) -> bool
     mac.update(payload);
     mac.verify_slice(signature).is_ok()
 }
+
+/// Hex encoded signature, to be sent in a header
+pub fn compute_hex_hmac(payload: &[u8], key: &HmacKey) -> String {
+    hex::encode(compute_hmac(payload, key))
+}
+
+pub fn verify_hex_hmac(payload: &[u8], signature: &str, key: &HmacKey) -> bool {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(signature, &mut bytes).is_ok() && verify_hmac(payload, &bytes, key)
+}
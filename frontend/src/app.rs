@@ -14,6 +14,7 @@ use crate::components::{
     events::provide_events_context,
     pages,
     settings::provide_settings_context,
+    shared::totp::{TotpPromptModal, provide_totp_context},
     ui::{
         confirm::{ConfirmationModal, provide_confirm_context},
         toast::{Toaster, ToasterPosition, provide_toasts},
@@ -48,10 +49,12 @@ pub fn App() -> impl IntoView {
     provide_toasts();
 
     let confirm_state = provide_confirm_context();
+    let totp_context = provide_totp_context();
 
     view! {
         <Toaster position=ToasterPosition::BottomCenter></Toaster>
         <ConfirmationModal state=confirm_state />
+        <TotpPromptModal totp_context />
         <DynamicTooltip />
         <ChatProvider url=option_env!("BACKEND_CHAT_WS_URL")
             .unwrap_or("ws://127.0.0.1:4242/chatws")
@@ -65,6 +68,7 @@ pub fn App() -> impl IntoView {
                     <Route path=path!("/signup") view=pages::SignUpPage />
                     <Route path=path!("/user-dashboard") view=pages::UserDashboardPage />
                     <Route path=path!("/reset-password") view=pages::ResetPasswordPage />
+                    <Route path=path!("/oauth-callback") view=pages::OAuthCallbackPage />
                     <Route path=path!("/town") view=pages::TownPage />
                     <Route
                         path=path!("/view-character/:character_name")
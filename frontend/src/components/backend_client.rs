@@ -5,6 +5,7 @@ use std::time::Duration;
 
 use shared::{
     data::{
+        area::AreaLevel,
         realms::Realm,
         stash::StashId,
         user::{UserCharacterId, UserId},
@@ -12,30 +13,42 @@ use shared::{
     http::{
         client::{
             AscendPassivesRequest, BrowseMarketItemsRequest, BrowseStashItemsRequest,
-            BuyBenedictionsRequest, BuyMarketItemRequest, CreateCharacterRequest,
-            EditMarketItemRequest, ExchangeGemsStashRequest, ForgeAffixRequest,
-            ForgeUpgradeRequest, ForgotPasswordRequest, GambleItemRequest, InventoryDeleteRequest,
-            InventoryEquipRequest, InventorySortRequest, InventoryUnequipRequest,
-            RejectMarketItemRequest, ResetPasswordRequest, SaveFavoriteSkillsRequest,
+            BuyBenedictionsRequest, BuyMarketItemRequest, ClaimChallengeRewardRequest,
+            CreateCharacterRequest, DeleteAccountRequest, EditMarketItemRequest,
+            ExchangeGemsStashRequest, ForgeAffixFamiliesRequest, ForgeAffixRequest,
+            ForgeCorruptRequest, ForgeDivineRequest, ForgeSocketRuneRequest,
+            ForgeUnsocketRuneRequest, ForgeUpgradeRequest, ForgotPasswordRequest,
+            GambleItemRequest, IgnoreUserRequest, InventoryDeleteRequest, InventoryEquipRequest,
+            InventorySortRequest, InventoryUnequipRequest, OAuthAuthorizeRequest,
+            OAuthCallbackRequest, RejectMarketItemRequest, ReportChatUserRequest,
+            ResetPasswordRequest, SaveAtlasModifiersRequest, SaveFavoriteSkillsRequest,
             SavePassivesRequest, SaveSkillMasteryUpgradesRequest, SellMarketItemRequest,
             SignInRequest, SignUpRequest, SocketPassiveRequest, StoreStashItemRequest,
-            TakeStashItemRequest, UpdateAccountRequest, UpdateCharacterRequest,
-            UpgradeStashRequest,
+            TakeStashItemRequest, TotpCodeRequest, TotpSignInRequest, UpdateAccountRequest,
+            UpdateCharacterRequest, UpgradeStashRequest,
         },
         server::{
             AscendPassivesResponse, BrowseMarketItemsResponse, BrowseStashItemsResponse,
-            BuyBenedictionsResponse, BuyMarketItemResponse, CreateCharacterResponse,
-            DeleteAccountResponse, DeleteCharacterResponse, EditMarketItemResponse, ErrorResponse,
-            ExchangeGemsStashResponse, ForgeAffixResponse, ForgeUpgradeResponse,
-            ForgotPasswordResponse, GambleItemResponse, GetAreasResponse, GetBenedictionsResponse,
-            GetCharacterDetailsResponse, GetDiscordInviteResponse, GetPassivesResponse,
-            GetSkillsResponse, GetStatusesResponse, GetUserCharactersResponse,
-            GetUserDetailsResponse, InventoryDeleteResponse, InventoryEquipResponse,
-            InventorySortResponse, InventoryUnequipResponse, LeaderboardResponse, NewsResponse,
-            PlayersCountResponse, RejectMarketItemResponse, ResetPasswordResponse,
-            SaveFavoriteSkillsResponse, SavePassivesResponse, SaveSkillMasteryUpgradesResponse,
-            SellMarketItemResponse, SignInResponse, SignUpResponse, SocketPassiveResponse,
-            StoreStashItemResponse, TakeStashItemResponse, UpdateAccountResponse,
+            BuyBenedictionsResponse, BuyMarketItemResponse, ClaimChallengeRewardResponse,
+            CreateCharacterResponse, DeleteAccountResponse, DeleteCharacterResponse,
+            DisableTotpResponse, EditMarketItemResponse, EnableTotpResponse, EnrollTotpResponse,
+            ErrorResponse, ExchangeGemsStashResponse, ForgeAffixFamiliesResponse,
+            ForgeAffixResponse, ForgeCorruptResponse, ForgeDivineResponse, ForgeSocketRuneResponse,
+            ForgeUnsocketRuneResponse, ForgeUpgradeResponse, ForgotPasswordResponse,
+            GambleItemResponse, GetAreaLootResponse, GetAreasResponse, GetAtlasResponse,
+            GetBenedictionsResponse, GetChallengesResponse, GetCharacterDetailsResponse,
+            GetDiscordInviteResponse, GetIgnoredUsersResponse, GetItemLootResponse,
+            GetItemSetsResponse, GetOAuthProvidersResponse, GetPassivesResponse, GetSkillsResponse,
+            GetStatusesResponse, GetTotpStatusResponse, GetUserCharactersResponse,
+            GetUserDetailsResponse, GetUserIdentitiesResponse, IgnoreUserResponse,
+            InventoryDeleteResponse, InventoryEquipResponse, InventorySortResponse,
+            InventoryUnequipResponse, LeaderboardResponse, LinkUserIdentityResponse, NewsResponse,
+            OAuthAuthorizeResponse, PlayersCountResponse, RegenerateRecoveryCodesResponse,
+            RejectMarketItemResponse, ReportChatUserResponse, ResetPasswordResponse,
+            SaveAtlasModifiersResponse, SaveFavoriteSkillsResponse, SavePassivesResponse,
+            SaveSkillMasteryUpgradesResponse, SellMarketItemResponse, SignInResponse,
+            SignUpResponse, SocketPassiveResponse, StoreStashItemResponse, TakeStashItemResponse,
+            UnignoreUserResponse, UnlinkUserIdentityResponse, UpdateAccountResponse,
             UpgradeStashResponse,
         },
     },
@@ -51,6 +64,7 @@ pub enum BackendError {
     UserError(String),
     ServerError(String),
     ServerNotResponding,
+    TotpRequired,
     OtherError,
 }
 
@@ -63,6 +77,7 @@ impl std::fmt::Display for BackendError {
             BackendError::UserError(reason) => write!(f, "{reason}"),
             BackendError::ServerError(reason) => write!(f, "Server error: {reason}"),
             BackendError::ServerNotResponding => write!(f, "Server not responding"),
+            BackendError::TotpRequired => write!(f, "Two-factor code required"),
             BackendError::OtherError => write!(f, "Unknown error"),
         }
     }
@@ -114,6 +129,19 @@ impl BackendClient {
             .await
     }
 
+    pub async fn get_challenges_leaderboard(
+        &self,
+        realm: Realm,
+        schedule_id: &str,
+    ) -> Result<LeaderboardResponse, BackendError> {
+        self.get(&format!(
+            "leaderboard/challenges?realm={}&schedule_id={}",
+            realm.realm_id(),
+            schedule_id
+        ))
+        .await
+    }
+
     pub async fn get_news(&self) -> Result<NewsResponse, BackendError> {
         self.get("news").await
     }
@@ -122,6 +150,34 @@ impl BackendClient {
         self.get("game/areas").await
     }
 
+    pub async fn get_area_loot(
+        &self,
+        area_id: &str,
+        area_level: AreaLevel,
+        boss: bool,
+    ) -> Result<GetAreaLootResponse, BackendError> {
+        self.get(&format!(
+            "game/loot/areas/{area_id}?area_level={area_level}&boss={boss}"
+        ))
+        .await
+    }
+
+    pub async fn get_item_loot(
+        &self,
+        item_id: &str,
+        item_level: Option<AreaLevel>,
+    ) -> Result<GetItemLootResponse, BackendError> {
+        match item_level {
+            Some(item_level) => {
+                self.get(&format!(
+                    "game/loot/items/{item_id}?item_level={item_level}"
+                ))
+                .await
+            }
+            None => self.get(&format!("game/loot/items/{item_id}")).await,
+        }
+    }
+
     pub async fn get_skills(&self) -> Result<GetSkillsResponse, BackendError> {
         self.get("game/skills").await
     }
@@ -130,6 +186,10 @@ impl BackendClient {
         self.get("game/statuses").await
     }
 
+    pub async fn get_item_sets(&self) -> Result<GetItemSetsResponse, BackendError> {
+        self.get("game/item-sets").await
+    }
+
     pub async fn get_passives(&self) -> Result<GetPassivesResponse, BackendError> {
         self.get("game/passives").await
     }
@@ -166,6 +226,28 @@ impl BackendClient {
         self.post_auth("game/benedictions", request).await
     }
 
+    pub async fn get_atlas(&self) -> Result<GetAtlasResponse, BackendError> {
+        self.get("game/atlas").await
+    }
+
+    pub async fn post_save_atlas_modifiers(
+        &self,
+        request: &SaveAtlasModifiersRequest,
+    ) -> Result<SaveAtlasModifiersResponse, BackendError> {
+        self.post_auth("game/atlas", request).await
+    }
+
+    pub async fn get_challenges(&self) -> Result<GetChallengesResponse, BackendError> {
+        self.get("game/challenges").await
+    }
+
+    pub async fn post_claim_challenge_reward(
+        &self,
+        request: &ClaimChallengeRewardRequest,
+    ) -> Result<ClaimChallengeRewardResponse, BackendError> {
+        self.post_auth("game/challenges/claim", request).await
+    }
+
     pub async fn post_save_favorite_skills(
         &self,
         request: &SaveFavoriteSkillsRequest,
@@ -193,6 +275,17 @@ impl BackendClient {
         request: &SignInRequest,
     ) -> Result<SignInResponse, BackendError> {
         let response: SignInResponse = self.post("account/signin", request).await?;
+        if response.totp_challenge.is_none() {
+            self.auth.set_access_token(response.jwt.clone());
+        }
+        Ok(response)
+    }
+
+    pub async fn post_totp_signin(
+        &self,
+        request: &TotpSignInRequest,
+    ) -> Result<SignInResponse, BackendError> {
+        let response: SignInResponse = self.post("account/signin/totp", request).await?;
         self.auth.set_access_token(response.jwt.clone());
         Ok(response)
     }
@@ -226,6 +319,33 @@ impl BackendClient {
         self.post("account/reset-password", request).await
     }
 
+    pub async fn get_oauth_providers(&self) -> Result<GetOAuthProvidersResponse, BackendError> {
+        self.get("account/oauth/providers").await
+    }
+
+    pub async fn post_oauth_authorize(
+        &self,
+        provider_id: &str,
+        request: &OAuthAuthorizeRequest,
+    ) -> Result<OAuthAuthorizeResponse, BackendError> {
+        self.post(&format!("account/oauth/{provider_id}/authorize"), request)
+            .await
+    }
+
+    pub async fn post_oauth_signin(
+        &self,
+        provider_id: &str,
+        request: &OAuthCallbackRequest,
+    ) -> Result<SignInResponse, BackendError> {
+        let response: SignInResponse = self
+            .post(&format!("account/oauth/{provider_id}/signin"), request)
+            .await?;
+        if response.totp_challenge.is_none() {
+            self.auth.set_access_token(response.jwt.clone());
+        }
+        Ok(response)
+    }
+
     // Account
 
     pub async fn post_update_account(
@@ -238,14 +358,99 @@ impl BackendClient {
     pub async fn delete_account(
         &self,
         user_id: &UserId,
+        request: &DeleteAccountRequest,
     ) -> Result<DeleteAccountResponse, BackendError> {
-        self.del_auth(&format!("account/{user_id}")).await
+        self.del_auth_with(&format!("account/{user_id}"), request)
+            .await
+    }
+
+    pub async fn get_totp_status(&self) -> Result<GetTotpStatusResponse, BackendError> {
+        self.get_auth("account/totp").await
+    }
+
+    pub async fn post_enroll_totp(&self) -> Result<EnrollTotpResponse, BackendError> {
+        self.post_auth("account/totp/enroll", &()).await
+    }
+
+    pub async fn post_enable_totp(
+        &self,
+        request: &TotpCodeRequest,
+    ) -> Result<EnableTotpResponse, BackendError> {
+        self.post_auth("account/totp/enable", request).await
+    }
+
+    pub async fn post_disable_totp(
+        &self,
+        request: &TotpCodeRequest,
+    ) -> Result<DisableTotpResponse, BackendError> {
+        self.post_auth("account/totp/disable", request).await
+    }
+
+    pub async fn post_regenerate_recovery_codes(
+        &self,
+        request: &TotpCodeRequest,
+    ) -> Result<RegenerateRecoveryCodesResponse, BackendError> {
+        self.post_auth("account/totp/recovery-codes", request).await
+    }
+
+    pub async fn get_user_identities(&self) -> Result<GetUserIdentitiesResponse, BackendError> {
+        self.get_auth("account/identities").await
+    }
+
+    pub async fn post_link_authorize(
+        &self,
+        provider_id: &str,
+    ) -> Result<OAuthAuthorizeResponse, BackendError> {
+        self.post_auth(&format!("account/identities/{provider_id}/authorize"), &())
+            .await
+    }
+
+    pub async fn post_link_identity(
+        &self,
+        provider_id: &str,
+        request: &OAuthCallbackRequest,
+    ) -> Result<LinkUserIdentityResponse, BackendError> {
+        self.post_auth(&format!("account/identities/{provider_id}"), request)
+            .await
+    }
+
+    pub async fn delete_user_identity(
+        &self,
+        provider_id: &str,
+    ) -> Result<UnlinkUserIdentityResponse, BackendError> {
+        self.del_auth(&format!("account/identities/{provider_id}"))
+            .await
+    }
+
+    pub async fn get_ignored_users(&self) -> Result<GetIgnoredUsersResponse, BackendError> {
+        self.get_auth("account/ignores").await
+    }
+
+    pub async fn post_ignore_user(
+        &self,
+        request: &IgnoreUserRequest,
+    ) -> Result<IgnoreUserResponse, BackendError> {
+        self.post_auth("account/ignores", request).await
+    }
+
+    pub async fn delete_ignored_user(
+        &self,
+        user_id: &UserId,
+    ) -> Result<UnignoreUserResponse, BackendError> {
+        self.del_auth(&format!("account/ignores/{user_id}")).await
     }
 
     pub async fn get_discord_invite(&self) -> Result<GetDiscordInviteResponse, BackendError> {
         self.get_auth("discord").await
     }
 
+    pub async fn post_report_chat_user(
+        &self,
+        request: &ReportChatUserRequest,
+    ) -> Result<ReportChatUserResponse, BackendError> {
+        self.post_auth("chat/report", request).await
+    }
+
     // Characters
 
     pub async fn get_user_characters(
@@ -385,6 +590,13 @@ impl BackendClient {
         self.post_auth("forge/affix", request).await
     }
 
+    pub async fn forge_families(
+        &self,
+        request: &ForgeAffixFamiliesRequest,
+    ) -> Result<ForgeAffixFamiliesResponse, BackendError> {
+        self.post_auth("forge/families", request).await
+    }
+
     pub async fn forge_upgrade(
         &self,
         request: &ForgeUpgradeRequest,
@@ -392,6 +604,34 @@ impl BackendClient {
         self.post_auth("forge/upgrade", request).await
     }
 
+    pub async fn forge_corrupt(
+        &self,
+        request: &ForgeCorruptRequest,
+    ) -> Result<ForgeCorruptResponse, BackendError> {
+        self.post_auth("forge/corrupt", request).await
+    }
+
+    pub async fn forge_divine(
+        &self,
+        request: &ForgeDivineRequest,
+    ) -> Result<ForgeDivineResponse, BackendError> {
+        self.post_auth("forge/divine", request).await
+    }
+
+    pub async fn forge_socket_rune(
+        &self,
+        request: &ForgeSocketRuneRequest,
+    ) -> Result<ForgeSocketRuneResponse, BackendError> {
+        self.post_auth("forge/socket", request).await
+    }
+
+    pub async fn forge_unsocket_rune(
+        &self,
+        request: &ForgeUnsocketRuneRequest,
+    ) -> Result<ForgeUnsocketRuneResponse, BackendError> {
+        self.post_auth("forge/unsocket", request).await
+    }
+
     pub async fn gamble_item(
         &self,
         request: &GambleItemRequest,
@@ -483,6 +723,7 @@ impl BackendClient {
                 .bearer_auth(&token)
                 .timeout(Duration::from_secs(60))
                 .json(payload)
+                .with_credentials()
                 .send()
                 .await,
         )
@@ -504,6 +745,24 @@ impl BackendClient {
         )
         .await
     }
+
+    async fn del_auth_with<T, P>(&self, endpoint: &str, payload: &P) -> Result<T, BackendError>
+    where
+        T: serde::de::DeserializeOwned,
+        P: serde::ser::Serialize,
+    {
+        let token = self.get_access_token().await?;
+        deserialize_response(
+            reqwest::Client::new()
+                .delete(format!("{}/{}", self.http_url, endpoint))
+                .timeout(Duration::from_secs(60))
+                .bearer_auth(&token)
+                .json(payload)
+                .send()
+                .await,
+        )
+        .await
+    }
 }
 
 trait RequestBuilderCredentialsExt {
@@ -544,6 +803,7 @@ where
             StatusCode::UNAUTHORIZED => BackendError::Unauthorized(err),
             StatusCode::FORBIDDEN => BackendError::Forbidden,
             StatusCode::CONFLICT => BackendError::UserError(err),
+            StatusCode::PRECONDITION_REQUIRED => BackendError::TotpRequired,
             StatusCode::INTERNAL_SERVER_ERROR => BackendError::ServerError(err),
             _ => BackendError::OtherError,
         });
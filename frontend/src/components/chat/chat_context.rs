@@ -14,7 +14,9 @@ use web_sys::CloseEvent;
 
 use shared_chat::{
     messages::{
-        client::{ClientChatMessage, ClientConnectMessage, ClientPostMessage},
+        client::{
+            ClientChatMessage, ClientConnectMessage, ClientHistoryRequest, ClientPostMessage,
+        },
         server::{ErrorType, ServerChatMessage},
     },
     types::{CharacterId, ChatChannel, ChatContent, ChatMessage, LinkedItemBytes, UserId},
@@ -27,6 +29,7 @@ use crate::components::{
 const HEARTBEAT_PERIOD: u64 = 10_000;
 const CHAT_HISTORY_CAPACITY: usize = 100;
 const CHAT_HISTORY_STORAGE_KEY: &str = "chat_history";
+const CHAT_HISTORY_REQUEST_LIMIT: u16 = 30;
 
 #[derive(Clone)]
 pub struct ChatContext {
@@ -36,7 +39,12 @@ pub struct ChatContext {
     // TODO: Split in multiple buckets to keep longer system message than global
     pub messages: RwSignal<Vec<ChatMessage>>,
     set_stored_messages: WriteSignal<Vec<ChatMessage>>,
+    // Grows when loading older messages so they are not dropped right away
+    messages_capacity: RwSignal<usize>,
     pub send: Callback<(String, Option<CharacterId>)>,
+    /// Request older messages for the selected channels and whispers
+    pub load_history: Callback<()>,
+    pub history_exhausted: RwSignal<HashSet<ChatChannel>>,
 
     pub minimized: RwSignal<bool>,
     pub opened: RwSignal<bool>,
@@ -142,6 +150,7 @@ pub fn ChatProvider(url: String, children: Children) -> impl IntoView {
     let (stored_messages, set_stored_messages, _) =
         storage::use_session_storage::<Vec<ChatMessage>, JsonSerdeCodec>(CHAT_HISTORY_STORAGE_KEY);
 
+    let send_history_request = send.clone();
     let send = Callback::new(move |(msg, character_id)| {
         if let Ok(content) = ChatContent::try_new(msg) {
             send(
@@ -151,16 +160,15 @@ pub fn ChatProvider(url: String, children: Children) -> impl IntoView {
                     character_id,
                     linked_item: linked_item.read_untracked().as_ref().and_then(
                         |linked_item: &Arc<ItemSpecs>| {
-                            // let mut item_specs = (**linked_item).clone();
-                            // item_specs.signature = Default::default();
+                            let mut item_specs = (**linked_item).clone();
+                            item_specs.signature = Default::default();
 
                             let serialized_item = LinkedItemBytes::try_new(
-                                MsgpackSerdeCodec::encode(linked_item).ok()?,
+                                MsgpackSerdeCodec::encode(&item_specs).ok()?,
                             )
                             .ok()?;
 
-                            // Some((serialized_item, linked_item.signature.clone()))
-                            Some(serialized_item)
+                            Some((serialized_item, linked_item.signature))
                         },
                     ),
                 }
@@ -170,23 +178,57 @@ pub fn ChatProvider(url: String, children: Children) -> impl IntoView {
         }
     });
 
+    let messages = RwSignal::new(normalize_messages(
+        stored_messages.get_untracked(),
+        CHAT_HISTORY_CAPACITY,
+    ));
+    let selected_channels = RwSignal::new(HashSet::from([
+        ChatChannel::Global,
+        ChatChannel::Trade,
+        ChatChannel::System,
+    ]));
+    let history_exhausted = RwSignal::new(HashSet::new());
+    let user_id = RwSignal::new(None);
+
+    let load_history = Callback::new(move |_| {
+        let channels = selected_channels
+            .get_untracked()
+            .into_iter()
+            .chain(user_id.get_untracked().map(ChatChannel::Whisper))
+            .filter(|channel| !history_exhausted.read_untracked().contains(channel))
+            .collect::<Vec<_>>();
+
+        for channel in channels {
+            let before = messages
+                .read_untracked()
+                .iter()
+                .find(|message| same_history_channel(message.channel, channel))
+                .map(|message| message.sent_at);
+
+            send_history_request(
+                &ClientHistoryRequest {
+                    channel,
+                    before,
+                    limit: CHAT_HISTORY_REQUEST_LIMIT,
+                }
+                .into(),
+            );
+        }
+    });
+
     let chat_context = ChatContext {
-        user_id: RwSignal::new(None),
+        user_id,
         send,
+        load_history,
+        history_exhausted,
         users_map: Default::default(),
-        messages: RwSignal::new(normalize_messages(
-            stored_messages.get_untracked(),
-            CHAT_HISTORY_CAPACITY,
-        )),
+        messages,
         set_stored_messages,
+        messages_capacity: RwSignal::new(CHAT_HISTORY_CAPACITY),
         // TODO: Store in storage
         minimized: RwSignal::new(true),
         opened,
-        selected_channels: RwSignal::new(HashSet::from([
-            ChatChannel::Global,
-            ChatChannel::Trade,
-            ChatChannel::System,
-        ])),
+        selected_channels,
         write_channel,
         linked_item,
     };
@@ -247,6 +289,24 @@ fn handle_message(chat_context: &ChatContext, message: ServerChatMessage) -> Con
                 .write_channel
                 .set(ChatChannel::Whisper(m.target_user_id));
         }
+        ServerChatMessage::History(m) => {
+            if !m.has_more {
+                chat_context.history_exhausted.write().insert(m.channel);
+            }
+            chat_context
+                .messages_capacity
+                .update(|capacity| *capacity += m.messages.len());
+            merge_messages(chat_context, m.messages);
+        }
+        ServerChatMessage::DeleteMessages(m) => {
+            chat_context.messages.update(|messages| {
+                messages.retain(|message| {
+                    message.user_id != Some(m.user_id)
+                        || m.sent_at.is_some_and(|sent_at| message.sent_at != sent_at)
+                })
+            });
+            persist_messages(chat_context);
+        }
     }
     ControlFlow::Continue(())
 }
@@ -284,9 +344,10 @@ fn merge_messages(
     }
 
     if changed {
-        chat_context
-            .messages
-            .set(normalize_messages(messages, CHAT_HISTORY_CAPACITY));
+        chat_context.messages.set(normalize_messages(
+            messages,
+            chat_context.messages_capacity.get_untracked(),
+        ));
         persist_messages(chat_context);
     }
 }
@@ -302,6 +363,13 @@ fn normalize_messages(mut messages: Vec<ChatMessage>, capacity: usize) -> Vec<Ch
     messages
 }
 
+fn same_history_channel(message_channel: ChatChannel, channel: ChatChannel) -> bool {
+    match (message_channel, channel) {
+        (ChatChannel::Whisper(_), ChatChannel::Whisper(_)) => true,
+        (message_channel, channel) => message_channel == channel,
+    }
+}
+
 fn message_key(message: &ChatMessage) -> (chrono::DateTime<chrono::Utc>, Option<UserId>) {
     (message.sent_at, message.user_id)
 }
@@ -4,22 +4,30 @@ use codee::{Decoder, binary::MsgpackSerdeCodec};
 use leptos::{
     ev::{mousemove, mouseup},
     prelude::*,
+    task::spawn_local,
     web_sys::wasm_bindgen::JsCast,
 };
 use leptos_use::use_resize_observer;
 
-use shared::data::{badges::UserBadge, item::ItemSpecs, user::UserCharacterId};
-use shared_chat::types::{ChatChannel, ChatMessage};
+use shared::{
+    data::{badges::UserBadge, item::ItemSpecs, user::UserCharacterId},
+    http::client::ReportChatUserRequest,
+    types::ChatContent,
+};
+use shared_chat::types::{ChatChannel, ChatMessage, UserId};
 
 use crate::{
     assets::img_asset,
     components::{
+        backend_client::BackendClient,
         chat::chat_context::ChatContext,
         events::{EventsContext, Key, keyboard_event_key},
         shared::tooltips::{ItemTooltip, item_tooltip},
         ui::{
             checkbox::Checkbox,
+            confirm::ConfirmContext,
             number::format_datetime,
+            toast::*,
             tooltip::{DynamicTooltipTarget, StaticTooltip, StaticTooltipPosition},
         },
     },
@@ -270,6 +278,21 @@ pub fn ChatPanel(#[prop(optional)] character_id: Option<UserCharacterId>) -> imp
                                     }
                                 }
                             >
+                                <Show when=move || {
+                                    let exhausted = chat_context.history_exhausted.read();
+                                    chat_context
+                                        .selected_channels
+                                        .read()
+                                        .iter()
+                                        .any(|channel| !exhausted.contains(channel))
+                                }>
+                                    <button
+                                        class="w-full text-xs text-zinc-500 hover:text-zinc-300"
+                                        on:click=move |_| chat_context.load_history.run(())
+                                    >
+                                        "Load older messages"
+                                    </button>
+                                </Show>
                                 <For
                                     each=filtered_messages
                                     key=|msg| (msg.sent_at, msg.user_id)
@@ -353,7 +376,7 @@ pub fn ChatPanel(#[prop(optional)] character_id: Option<UserCharacterId>) -> imp
                                                         >
                                                             "✕"
                                                         </button>
-                                                        <ChatItem item_specs />
+                                                        <ChatItem item_specs verified=true />
                                                     </span>
                                                 }
                                             })}
@@ -394,6 +417,26 @@ fn ChatMessageRow(msg: ChatMessage) -> impl IntoView {
     let chat_context: ChatContext = expect_context();
     let sent_title = format!("Sent at {}", format_datetime(msg.sent_at));
 
+    let report_button = msg
+        .user_id
+        .filter(|user_id| Some(*user_id) != chat_context.user_id.get_untracked())
+        .map(|user_id| {
+            let reason = format!(
+                "Reported message: {}",
+                msg.content.chars().take(180).collect::<String>()
+            );
+            let author = author_str(&msg);
+            view! {
+                <button
+                    class="invisible group-hover/message:visible text-xs text-zinc-500 hover:text-red-400 select-none"
+                    title="Report this message"
+                    on:click=move |_| report_user(user_id, author.clone(), reason.clone())
+                >
+                    "⚑"
+                </button>
+            }
+        });
+
     view! {
         <div class="group/message flex items-start gap-1.5 text-sm leading-[1.35]">
             {msg
@@ -428,14 +471,51 @@ fn ChatMessageRow(msg: ChatMessage) -> impl IntoView {
                     .linked_item
                     .and_then(|item_data| MsgpackSerdeCodec::decode(&item_data.into_inner()).ok())
                     .map(|item_specs: ItemSpecs| {
-                        view! { <ChatItem item_specs=Arc::new(item_specs) /> }
+                        view! {
+                            <ChatItem
+                                item_specs=Arc::new(item_specs)
+                                verified=msg.linked_item_verified
+                            />
+                        }
                     })}
-                <span title=sent_title>{msg.content}</span>
+                <span class="whitespace-pre-line" title=sent_title>
+                    {msg.content}
+                </span>
             </p>
+            {report_button}
         </div>
     }
 }
 
+fn report_user(user_id: UserId, author: String, reason: String) {
+    let backend: BackendClient = expect_context();
+    let toaster: Toasts = expect_context();
+    let confirm_context: ConfirmContext = expect_context();
+
+    (confirm_context.confirm)(
+        format!("Report {author} to the moderators for this message?"),
+        Arc::new(move || {
+            let reason = reason.clone();
+            spawn_local(async move {
+                let Ok(reason) = ChatContent::try_new(reason) else {
+                    return;
+                };
+                match backend
+                    .post_report_chat_user(&ReportChatUserRequest { user_id, reason })
+                    .await
+                {
+                    Ok(_) => show_toast(toaster, "Report sent", ToastVariant::Success),
+                    Err(e) => show_toast(
+                        toaster,
+                        format!("Failed to send report: {e}"),
+                        ToastVariant::Error,
+                    ),
+                }
+            });
+        }),
+    );
+}
+
 #[component]
 fn ChatBadge(badge: UserBadge) -> impl IntoView {
     let (src, badge_title, badge_description) = match badge {
@@ -487,7 +567,7 @@ fn ChatBadge(badge: UserBadge) -> impl IntoView {
 }
 
 #[component]
-fn ChatItem(item_specs: Arc<ItemSpecs>) -> impl IntoView {
+fn ChatItem(item_specs: Arc<ItemSpecs>, verified: bool) -> impl IntoView {
     let events_context: EventsContext = expect_context();
     let show_affixes = Memo::new(move |_| events_context.key_pressed(Key::Alt));
     let tooltip = {
@@ -510,7 +590,19 @@ fn ChatItem(item_specs: Arc<ItemSpecs>) -> impl IntoView {
             <span class=format!(
                 "mr-1 inline-flex select-none align-baseline items-center rounded border border-current/25 bg-zinc-950/45 px-1.5 py-[1px] font-semibold leading-[1.25] shadow-[inset_0_1px_0_rgba(255,255,255,0.06)] transition-colors hover:bg-zinc-800/80 {}",
                 item_tooltip::name_color_rarity(item_specs.modifiers.rarity),
-            )>{item_specs.modifiers.name.clone()}</span>
+            )>
+                {(!verified)
+                    .then(|| {
+                        view! {
+                            <span
+                                class="mr-1 text-amber-400"
+                                title="Unverified item: it was not issued by the game server and may be forged."
+                            >
+                                "⚠"
+                            </span>
+                        }
+                    })} {item_specs.modifiers.name.clone()}
+            </span>
         </DynamicTooltipTarget>
     }
 }
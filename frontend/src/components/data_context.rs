@@ -3,8 +3,9 @@ use leptos::prelude::*;
 use std::collections::HashMap;
 
 use shared::data::{
-    area::AreaSpecs,
+    area::{AreaSpecs, BossDrops},
     character_status::{StatusId, StatusSpecs},
+    item_set::{ItemSetId, ItemSetSpecs},
     skill::BaseSkillSpecs,
     skill_mastery::SkillMasterySpecs,
 };
@@ -14,18 +15,22 @@ use crate::components::backend_client::{BackendClient, BackendError};
 #[derive(Clone, Copy)]
 pub struct DataContext {
     pub areas_specs: RwSignal<HashMap<String, AreaSpecs>>,
+    pub areas_bosses_drops: RwSignal<HashMap<String, Vec<BossDrops>>>,
     pub skill_specs: RwSignal<HashMap<String, BaseSkillSpecs>>,
     pub skill_mastery_specs: RwSignal<IndexMap<String, SkillMasterySpecs>>,
     pub statuses_specs: RwSignal<HashMap<StatusId, StatusSpecs>>,
+    pub item_sets_specs: RwSignal<HashMap<ItemSetId, ItemSetSpecs>>,
     pub loaded: RwSignal<bool>,
 }
 
 pub fn provide_data_context() {
     provide_context(DataContext {
         areas_specs: RwSignal::new(Default::default()),
+        areas_bosses_drops: RwSignal::new(Default::default()),
         skill_specs: RwSignal::new(Default::default()),
         skill_mastery_specs: RwSignal::new(Default::default()),
         statuses_specs: RwSignal::new(Default::default()),
+        item_sets_specs: RwSignal::new(Default::default()),
         loaded: RwSignal::new(false),
     });
 }
@@ -36,17 +41,21 @@ impl DataContext {
             return Ok(());
         }
 
-        let (areas, skills, statuses) = futures::join!(
+        let (areas, skills, statuses, item_sets) = futures::join!(
             backend_client.get_areas(),
             backend_client.get_skills(),
-            backend_client.get_statuses()
+            backend_client.get_statuses(),
+            backend_client.get_item_sets()
         );
 
-        self.areas_specs.set(areas?.areas);
+        let areas = areas?;
+        self.areas_specs.set(areas.areas);
+        self.areas_bosses_drops.set(areas.bosses_drops);
         let skills = skills?;
         self.skill_specs.set(skills.skills);
         self.skill_mastery_specs.set(skills.skill_masteries);
         self.statuses_specs.set(statuses?.statuses);
+        self.item_sets_specs.set(item_sets?.item_sets);
 
         self.loaded.set(true);
 
@@ -10,6 +10,7 @@ use shared::data::{
     game_stats::GameStats,
     item::ItemSpecs,
     loot::QueuedLoot,
+    minion::{MinionSpecs, MinionState},
     monster::{MonsterSpecs, MonsterState},
     passive::{PassivesTreeSpecs, PassivesTreeState, PurchasedNodes},
     player::{PlayerBaseSpecs, PlayerInventory, PlayerResources, PlayerSpecs, PlayerState},
@@ -55,6 +56,9 @@ pub struct GameContext {
     pub monster_specs: RwSignal<Vec<MonsterSpecs>>,
     pub monster_states: RwSignal<Vec<MonsterState>>,
 
+    pub minion_specs: RwSignal<Vec<MinionSpecs>>,
+    pub minion_states: RwSignal<Vec<MinionState>>,
+
     pub queued_loot: Syncable<Vec<QueuedLoot>>,
     pub quest_rewards: RwSignal<Option<QuestRewards>>,
 
@@ -108,6 +112,8 @@ impl GameContext {
             // monster_wave: RwSignal::new(0),
             monster_specs: RwSignal::new(Vec::new()),
             monster_states: RwSignal::new(Vec::new()),
+            minion_specs: RwSignal::new(Vec::new()),
+            minion_states: RwSignal::new(Vec::new()),
 
             queued_loot: Default::default(),
             quest_rewards: RwSignal::new(None),
@@ -122,8 +122,29 @@ fn handle_message(
     match message {
         ServerMessage::Connect(_) => {}
         ServerMessage::ServerDown(message) => {
+            // Running games are closed by the server, the connection is resumed once back
+            game_context.started.set(false);
             server_down_until.set(Some(message.expected_launch_time));
         }
+        ServerMessage::Maintenance(message) => {
+            let toaster = expect_context::<Toasts>();
+            match message.scheduled {
+                Some(maintenance) => show_toast(
+                    toaster,
+                    format!(
+                        "Server maintenance at {}, the game should be back by {}.",
+                        format_local_time(maintenance.starts_at),
+                        format_local_time(maintenance.expected_launch_time),
+                    ),
+                    ToastVariant::Warning,
+                ),
+                None => show_toast(
+                    toaster,
+                    "The scheduled maintenance has been cancelled.",
+                    ToastVariant::Info,
+                ),
+            }
+        }
         ServerMessage::InitGame(m) => {
             init_game(game_context, *m);
         }
@@ -203,6 +224,8 @@ fn sync_game(game_context: &GameContext, sync_message: SyncGameStateMessage) {
         player_stamina,
         monster_specs,
         monster_states,
+        minion_specs,
+        minion_states,
         queued_loot,
         game_stats,
         quest_rewards,
@@ -227,6 +250,10 @@ fn sync_game(game_context: &GameContext, sync_message: SyncGameStateMessage) {
         game_context.monster_specs.set(monster_specs);
     }
     game_context.monster_states.set(monster_states);
+    if let Some(minion_specs) = minion_specs {
+        game_context.minion_specs.set(minion_specs);
+    }
+    game_context.minion_states.set(minion_states);
     game_context.queued_loot.sync(queued_loot);
     game_context.game_stats.set(game_stats);
     if let Some(quest_rewards) = quest_rewards {
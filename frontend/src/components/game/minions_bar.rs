@@ -0,0 +1,141 @@
+use leptos::{html::*, prelude::*};
+
+use crate::{
+    assets::img_asset,
+    components::ui::{
+        number::format_number,
+        progress_bars::HorizontalProgressBar,
+        tooltip::{StaticTooltip, StaticTooltipPosition},
+    },
+};
+
+use super::GameContext;
+
+#[component]
+pub fn MinionsBar() -> impl IntoView {
+    let game_context = expect_context::<GameContext>();
+
+    let alive_minions = Memo::new(move |_| {
+        game_context.minion_states.with(|minion_states| {
+            minion_states
+                .iter()
+                .enumerate()
+                .filter(|(_, minion_state)| minion_state.character_state.is_alive)
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    move || {
+        (!alive_minions.read().is_empty()).then(|| {
+            view! {
+                <div class="flex flex-wrap gap-1 xl:gap-2">
+                    <For each=move || alive_minions.get() key=|index| *index let(index)>
+                        <Minion index />
+                    </For>
+                </div>
+            }
+        })
+    }
+}
+
+#[component]
+fn Minion(index: usize) -> impl IntoView {
+    let game_context = expect_context::<GameContext>();
+
+    let minion_static = Memo::new(move |_| {
+        game_context.minion_specs.with(|minion_specs| {
+            minion_specs
+                .get(index)
+                .map(|minion_specs| {
+                    (
+                        minion_specs.character_specs.character_static.name.clone(),
+                        minion_specs
+                            .character_specs
+                            .character_static
+                            .portrait
+                            .clone(),
+                        minion_specs.character_specs.character_attrs.max_life.get(),
+                        minion_specs.lifetime.get(),
+                    )
+                })
+                .unwrap_or_default()
+        })
+    });
+
+    let state = Memo::new(move |_| {
+        game_context.minion_states.with(|minion_states| {
+            minion_states
+                .get(index)
+                .map(|minion_state| {
+                    (
+                        minion_state.character_state.life.get(),
+                        minion_state.elapsed_lifetime.get(),
+                    )
+                })
+                .unwrap_or_default()
+        })
+    });
+
+    let life_percent = Signal::derive(move || {
+        let (_, _, max_life, _) = minion_static.get();
+        if max_life > 0.0 {
+            ((state.get().0 / max_life).clamp(0.0, 1.0) * 100.0) as f32
+        } else {
+            0.0
+        }
+    });
+
+    let lifetime_percent = Signal::derive(move || {
+        let (_, _, _, lifetime) = minion_static.get();
+        if lifetime > 0.0 {
+            ((1.0 - state.get().1 / lifetime).clamp(0.0, 1.0) * 100.0) as f32
+        } else {
+            100.0
+        }
+    });
+
+    let tooltip = move || {
+        let (name, _, max_life, lifetime) = minion_static.get();
+        let (life, elapsed_lifetime) = state.get();
+        view! {
+            <div class="flex flex-col text-sm">
+                <span class="font-semibold text-white">{name}</span>
+                <span>"Life: " {format_number(life)} "/" {format_number(max_life)}</span>
+                {(lifetime > 0.0)
+                    .then(|| {
+                        view! {
+                            <span>
+                                "Remaining: "
+                                {format!("{:.1}s", (lifetime - elapsed_lifetime).max(0.0))}
+                            </span>
+                        }
+                    })}
+            </div>
+        }
+    };
+
+    view! {
+        <StaticTooltip tooltip position=StaticTooltipPosition::Top>
+            <div class="flex items-center gap-1 w-20 xl:w-28">
+                <img
+                    src=move || img_asset(&minion_static.read().1)
+                    alt=move || minion_static.read().0.clone()
+                    class="w-6 h-6 xl:w-8 xl:h-8 rounded-sm ring-1 ring-zinc-950 object-cover"
+                />
+                <div class="flex-1 flex flex-col gap-0.5">
+                    <HorizontalProgressBar
+                        class="h-1.5 xl:h-2"
+                        bar_color="bg-gradient-to-b from-[#c44a3d] to-[#6b221d]"
+                        value=life_percent
+                    />
+                    <HorizontalProgressBar
+                        class="h-1 xl:h-1.5"
+                        bar_color="bg-gradient-to-b from-neutral-300 to-neutral-500"
+                        value=lifetime_percent
+                    />
+                </div>
+            </div>
+        </StaticTooltip>
+    }
+}
@@ -3,6 +3,7 @@ pub mod game_instance;
 pub mod header_menu;
 pub mod local_stats;
 pub mod loot_queue;
+pub mod minions_bar;
 pub mod monsters_grid;
 pub mod panels;
 pub mod player_card;
@@ -35,7 +35,7 @@ use crate::components::{
 };
 
 use super::GameContext;
-use super::portrait::CharacterPortrait;
+use super::portrait::{CastBar, CharacterPortrait, cast_progress};
 
 #[component]
 pub fn MonstersGrid() -> impl IntoView {
@@ -394,6 +394,17 @@ fn MonsterCard(specs: MonsterSpecs, index: usize) -> impl IntoView {
             .unwrap_or_default()
     });
 
+    let cast = Signal::derive({
+        let skills_specs = specs.character_specs.skills_specs.clone();
+        move || {
+            game_context
+                .monster_states
+                .read()
+                .get(index)
+                .and_then(|x| cast_progress(&skills_specs, &x.character_state.skills_states))
+        }
+    });
+
     let resource_reward = RwSignal::<ResourceReward>::new(Default::default());
 
     Effect::new(move |_| {
@@ -474,7 +485,7 @@ fn MonsterCard(specs: MonsterSpecs, index: usize) -> impl IntoView {
                         <span class=title_style>{monster_name}</span>
                     </HorizontalProgressBar>
                 </StaticTooltip>
-                <div class="flex-1 min-h-0">
+                <div class="relative flex-1 min-h-0">
                     <CharacterPortrait
                         image_uri=specs.character_specs.character_static.portrait.clone()
                         character_name=specs.character_specs.character_static.name.clone()
@@ -486,7 +497,8 @@ fn MonsterCard(specs: MonsterSpecs, index: usize) -> impl IntoView {
                         is_dead=is_dead
                         statuses=statuses
                     />
-                // enable_blink=true
+                    // enable_blink=true
+                    <CastBar cast_progress=cast />
                 </div>
                 <MonsterFeedbackOverlay damage_ticks resource_reward />
             </div>
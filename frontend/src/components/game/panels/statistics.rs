@@ -529,6 +529,37 @@ pub fn StatisticsPanel(open: RwSignal<bool>) -> impl IntoView {
                                             }
                                         })
                                 }}
+                                {move || {
+                                    let (max_ward, ward_recharge, ward_recharge_delay) = game_context
+                                        .player_specs
+                                        .with(|player_specs| {
+                                            let character_attrs = &player_specs
+                                                .character_specs
+                                                .character_attrs;
+                                            (
+                                                character_attrs.max_ward.get(),
+                                                *character_attrs.ward_recharge,
+                                                character_attrs.ward_recharge_delay.get(),
+                                            )
+                                        });
+                                    (max_ward != 0.0)
+                                        .then(move || {
+                                            view! {
+                                                <Stat
+                                                    label="Maximum Ward"
+                                                    value=move || format_number(max_ward)
+                                                />
+                                                <Stat
+                                                    label="Ward Recharge per second"
+                                                    value=move || format!("{:.0}%", ward_recharge)
+                                                />
+                                                <Stat
+                                                    label="Ward Recharge Delay"
+                                                    value=move || format!("{:.1}s", ward_recharge_delay)
+                                                />
+                                            }
+                                        })
+                                }}
                                 {move || {
                                     let take_from_mana_before_life = game_context
                                         .player_specs
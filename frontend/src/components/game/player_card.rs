@@ -31,7 +31,11 @@ use crate::components::{
     },
 };
 
-use super::{GameContext, portrait::CharacterPortrait};
+use super::{
+    GameContext,
+    minions_bar::MinionsBar,
+    portrait::{CastBar, CharacterPortrait, cast_progress},
+};
 
 #[component]
 pub fn PlayerCard() -> impl IntoView {
@@ -48,12 +52,31 @@ pub fn PlayerCard() -> impl IntoView {
     });
     let life = Signal::derive(move || game_context.player_state.read().character_state.life.get());
 
+    let max_ward = Memo::new(move |_| {
+        game_context
+            .player_specs
+            .with(|player_specs| player_specs.character_specs.character_attrs.max_ward.get())
+    });
+    let ward = Signal::derive(move || game_context.player_state.read().character_state.ward.get());
+
     let life_tooltip = move || {
         view! {
             "Life: "
             {format_number(life.get())}
             "/"
             {format_number(max_life.get())}
+            {move || {
+                (max_ward.get() > 0.0)
+                    .then(|| {
+                        view! {
+                            <br />
+                            "Ward: "
+                            {format_number(ward.get())}
+                            "/"
+                            {format_number(max_ward.get())}
+                        }
+                    })
+            }}
         }
     };
 
@@ -66,6 +89,15 @@ pub fn PlayerCard() -> impl IntoView {
         }
     });
 
+    let ward_percent = Signal::derive(move || {
+        let max_ward = max_ward.get();
+        if max_ward > 0.0 {
+            quantize_ratio(ward.get() / max_ward)
+        } else {
+            0.0
+        }
+    });
+
     let max_mana = Memo::new(move |_| {
         game_context
             .player_specs
@@ -150,6 +182,19 @@ pub fn PlayerCard() -> impl IntoView {
             .clone()
     });
 
+    let cast = Signal::derive(move || {
+        game_context.player_specs.with(|player_specs| {
+            cast_progress(
+                &player_specs.character_specs.skills_specs,
+                &game_context
+                    .player_state
+                    .read()
+                    .character_state
+                    .skills_states,
+            )
+        })
+    });
+
     let just_leveled_up = RwSignal::new(false);
 
     let conn = expect_context::<WebsocketContext>();
@@ -300,10 +345,19 @@ pub fn PlayerCard() -> impl IntoView {
                         class="w-6 xl:w-8"
                         bar_color="bg-gradient-to-l from-[#6b221d] to-[#c44a3d]"
                         value=life_percent
-                    />
+                    >
+                        <div
+                            class="h-full w-full origin-bottom transition-progress-bar"
+                            style=move || {
+                                format!("transform: scaleY({});", ward_percent.get())
+                            }
+                        >
+                            <div class="w-full h-full bg-[#8fd3f4] opacity-35"></div>
+                        </div>
+                    </VerticalProgressBar>
                 </StaticTooltip>
                 <div class="flex flex-col gap-1 xl:gap-2">
-                    <div class="flex-1 min-h-0">
+                    <div class="relative flex-1 min-h-0">
                         <CharacterPortrait
                             image_uri=game_context
                                 .player_base_specs
@@ -320,7 +374,8 @@ pub fn PlayerCard() -> impl IntoView {
                             statuses=statuses
                             character_triggers
                         />
-                    // enable_blink=false
+                        // enable_blink=false
+                        <CastBar cast_progress=cast />
                     </div>
                     <FancyButton disabled=disable_level_up on:click=level_up>
                         <span class="text-base xl:text-lg">
@@ -368,6 +423,8 @@ pub fn PlayerCard() -> impl IntoView {
                 </StaticTooltip>
             </div>
 
+            <MinionsBar />
+
             <StaticTooltip tooltip=xp_tooltip position=StaticTooltipPosition::Top>
                 <HorizontalProgressBar
                     class="h-2 xl:h-4"
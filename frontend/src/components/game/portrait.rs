@@ -1,12 +1,15 @@
 use leptos::{html::*, prelude::*};
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
 
 use shared::data::{
     chance::ChanceRange,
     character_status::{StatusId, StatusMap, StatusSpecs},
     modifier::ModifiableValue,
     monster::MonsterRarity,
-    skill::DamageType,
+    skill::{DamageType, SkillCastState, SkillSpecs, SkillState},
     trigger::TriggerEffect,
     values::NonNegative,
 };
@@ -17,7 +20,10 @@ use crate::{
         data_context::DataContext,
         settings::{GraphicsQuality, SettingsContext},
         shared::tooltips::status_tooltip::format_status_effects,
-        ui::tooltip::{StaticTooltip, StaticTooltipPosition},
+        ui::{
+            progress_bars::HorizontalProgressBar,
+            tooltip::{StaticTooltip, StaticTooltipPosition},
+        },
     },
 };
 
@@ -32,7 +38,7 @@ pub fn CharacterPortrait(
     #[prop(into)] just_evaded: Signal<bool>,
     #[prop(into)] is_dead: Signal<bool>,
     #[prop(into)] statuses: Signal<StatusMap>,
-    #[prop(optional)] character_triggers: Option<Memo<HashMap<String, TriggerEffect>>>,
+    #[prop(optional)] character_triggers: Option<Memo<BTreeMap<String, TriggerEffect>>>,
 ) -> impl IntoView {
     let settings: SettingsContext = expect_context();
     let heavy_effects = move || settings.uses_heavy_effects();
@@ -556,7 +562,7 @@ fn StatusIcon(
     status_specs: Option<StatusSpecs>,
     stack: Signal<(usize, f64)>,
     tooltip_position: StaticTooltipPosition,
-    character_triggers: Option<Memo<HashMap<String, TriggerEffect>>>,
+    character_triggers: Option<Memo<BTreeMap<String, TriggerEffect>>>,
 ) -> impl IntoView {
     let status_name = {
         let status_id = status_id.clone();
@@ -635,3 +641,74 @@ fn status_value_range(value: f64) -> ChanceRange<ModifiableValue<NonNegative>> {
         lucky_chance: Default::default(),
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CastProgress {
+    pub skill_name: String,
+    pub progress: f64,
+    pub channelling: bool,
+}
+
+pub fn cast_progress(
+    skills_specs: &[SkillSpecs],
+    skills_states: &[SkillState],
+) -> Option<CastProgress> {
+    skills_specs
+        .iter()
+        .zip(skills_states.iter())
+        .find_map(|(skill_specs, skill_state)| {
+            skill_state
+                .cast_state
+                .progress(skill_specs)
+                .map(|progress| CastProgress {
+                    skill_name: skill_specs.name.clone(),
+                    progress,
+                    channelling: matches!(
+                        skill_state.cast_state,
+                        SkillCastState::Channelling { .. }
+                    ),
+                })
+        })
+}
+
+#[component]
+pub fn CastBar(#[prop(into)] cast_progress: Signal<Option<CastProgress>>) -> impl IntoView {
+    let is_casting = Memo::new(move |_| cast_progress.read().is_some());
+
+    let skill_name = Memo::new(move |_| {
+        cast_progress
+            .read()
+            .as_ref()
+            .map(|cast_progress| cast_progress.skill_name.clone())
+            .unwrap_or_default()
+    });
+
+    // Casts fill up while channels drain down
+    let value = Signal::derive(move || {
+        cast_progress
+            .read()
+            .as_ref()
+            .map(|cast_progress| {
+                if cast_progress.channelling {
+                    (1.0 - cast_progress.progress) * 100.0
+                } else {
+                    cast_progress.progress * 100.0
+                }
+            })
+            .unwrap_or_default() as f32
+    });
+
+    view! {
+        <Show when=move || is_casting.get()>
+            <div class="absolute inset-x-1 xl:inset-x-2 bottom-1 xl:bottom-2 z-3 pointer-events-none">
+                <HorizontalProgressBar
+                    class="h-3 xl:h-4"
+                    bar_color="bg-gradient-to-b from-[#e0b85a] to-[#8a5a12]"
+                    value
+                >
+                    <span class="text-[10px] xl:text-xs">{skill_name}</span>
+                </HorizontalProgressBar>
+            </div>
+        </Show>
+    }
+}
@@ -16,7 +16,13 @@ use crate::{
         captcha::*,
         events::keyboard_event_key,
         settings::SettingsContext,
-        shared::{leaderboard::LeaderboardPanel, news::NewsInset, player_count::PlayerCount},
+        shared::{
+            leaderboard::LeaderboardPanel,
+            news::NewsInset,
+            oauth::OAuthSignInButtons,
+            player_count::PlayerCount,
+            totp::{TotpContext, complete_sign_in},
+        },
         ui::{
             ALink,
             buttons::MenuButton,
@@ -79,6 +85,7 @@ fn MainMenu() -> impl IntoView {
     let do_signin = {
         let toaster = expect_context::<Toasts>();
         let backend = expect_context::<BackendClient>();
+        let totp_context = expect_context::<TotpContext>();
         let navigate = use_navigate();
 
         move |username, password| {
@@ -86,14 +93,15 @@ fn MainMenu() -> impl IntoView {
             let navigate = navigate.clone();
             spawn_local({
                 async move {
-                    match backend
+                    let response = backend
                         .post_signin(&SignInRequest {
                             captcha_token: captcha_token.get_untracked().unwrap_or_default(),
                             username,
                             password,
                         })
-                        .await
-                    {
+                        .await;
+
+                    match complete_sign_in(backend, totp_context, response).await {
                         Ok(_) => {
                             navigate("/user-dashboard", Default::default());
                         }
@@ -247,6 +255,7 @@ fn MainMenu() -> impl IntoView {
                                                 if connecting.get() { "Connecting..." } else { "Connect" }
                                             }}
                                         </MenuButton>
+                                        <OAuthSignInButtons accepted_terms=false />
                                     </div>
                                 </div>
                             </div>
@@ -1,5 +1,6 @@
 mod game;
 mod main_menu;
+mod oauth_callback;
 pub mod privacy;
 mod reset_password;
 mod signup;
@@ -11,6 +12,7 @@ mod view_character;
 pub use game::GamePage;
 pub use main_menu::MainMenuPage;
 pub(crate) use main_menu::{LogoCog, LogoWord};
+pub use oauth_callback::OAuthCallbackPage;
 pub use reset_password::ResetPasswordPage;
 pub use signup::SignUpPage;
 pub use town::TownPage;
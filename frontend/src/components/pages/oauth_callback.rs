@@ -0,0 +1,92 @@
+use leptos::{Params, prelude::*, task::spawn_local};
+use leptos_router::{
+    hooks::{use_navigate, use_query},
+    params::Params,
+};
+
+use shared::http::client::OAuthCallbackRequest;
+
+use crate::components::{
+    backend_client::BackendClient,
+    shared::{
+        oauth::take_pending_oauth,
+        totp::{TotpContext, complete_sign_in},
+    },
+    ui::{loading_screen::LoadingScreen, toast::*},
+};
+
+#[derive(Params, PartialEq, Clone, Default, Debug)]
+struct OAuthCallbackParams {
+    code: Option<String>,
+    state: Option<String>,
+}
+
+#[component]
+pub fn OAuthCallbackPage() -> impl IntoView {
+    let toaster = expect_context::<Toasts>();
+    let backend = expect_context::<BackendClient>();
+    let totp_context = expect_context::<TotpContext>();
+    let navigate = use_navigate();
+
+    let query = use_query::<OAuthCallbackParams>()
+        .get_untracked()
+        .unwrap_or_default();
+
+    spawn_local(async move {
+        // The state must match the one we started with, to prevent login CSRF
+        let pending_oauth =
+            take_pending_oauth().filter(|pending| Some(&pending.state) == query.state.as_ref());
+
+        let (Some(pending_oauth), Some(code)) = (pending_oauth, query.code) else {
+            show_toast(
+                toaster,
+                "Authentication error: invalid or expired request".to_string(),
+                ToastVariant::Error,
+            );
+            navigate("/", Default::default());
+            return;
+        };
+
+        let request = OAuthCallbackRequest {
+            code,
+            state: pending_oauth.state,
+        };
+
+        if pending_oauth.link {
+            match backend
+                .post_link_identity(&pending_oauth.provider_id, &request)
+                .await
+            {
+                Ok(_) => show_toast(
+                    toaster,
+                    "Account linked!".to_string(),
+                    ToastVariant::Success,
+                ),
+                Err(e) => show_toast(
+                    toaster,
+                    format!("Link account error: {e}"),
+                    ToastVariant::Error,
+                ),
+            }
+            navigate("/user-dashboard", Default::default());
+        } else {
+            let response = backend
+                .post_oauth_signin(&pending_oauth.provider_id, &request)
+                .await;
+
+            match complete_sign_in(backend, totp_context, response).await {
+                Ok(_) => navigate("/user-dashboard", Default::default()),
+                Err(e) => {
+                    show_toast(
+                        toaster,
+                        format!("Authentication error: {e}"),
+                        ToastVariant::Error,
+                    );
+                    navigate("/", Default::default());
+                }
+            }
+        }
+    });
+
+    view! { <LoadingScreen title="Signing in..." detail="Waiting for the identity provider." /> }
+}
@@ -9,6 +9,7 @@ use crate::components::{
     backend_client::BackendClient,
     captcha::Captcha,
     pages::{privacy::PrivacyContent, terms::TermsContent},
+    shared::oauth::OAuthSignInButtons,
     ui::{
         buttons::MenuButton,
         card::{Card, CardInset, CardTitle},
@@ -176,6 +177,10 @@ pub fn SignUpPage() -> impl IntoView {
                 <MenuButton class="w-full" on:click=on_submit disabled=disable_submit>
                     "Confirm"
                 </MenuButton>
+                <OAuthSignInButtons
+                    accepted_terms
+                    disabled=Signal::derive(move || !accepted_terms.get())
+                />
 
                 <p class="mt-6 text-xs text-zinc-400 text-left">
                     "By signing up, you consent to the storage and processing of your data in accordance with GDPR. You can request data deletion at any time via the account page."
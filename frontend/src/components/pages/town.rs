@@ -17,6 +17,7 @@ use crate::components::{
         TownContext,
         header_menu::HeaderMenu,
         panels::{
+            atlas::AtlasPanel,
             forge::ForgePanel,
             inventory::TownInventoryPanel,
             market::MarketPanel,
@@ -61,6 +62,16 @@ pub fn TownPage() -> impl IntoView {
         }
     });
 
+    let atlas_specs = LocalResource::new({
+        move || async move {
+            backend
+                .get_atlas()
+                .await
+                .map(|response| response.atlas_specs)
+                .unwrap_or_default()
+        }
+    });
+
     let data_load = LocalResource::new({
         move || async move {
             if data_context.load_data(backend).await.is_err() {
@@ -82,6 +93,7 @@ pub fn TownPage() -> impl IntoView {
                     ascension,
                     passives_build,
                     benedictions,
+                    atlas,
                     // last_grind,
                     character_stash,
                     user_stash,
@@ -98,6 +110,7 @@ pub fn TownPage() -> impl IntoView {
                     town_context.passives_tree_ascension.set(ascension);
                     town_context.passives_tree_build.set(passives_build);
                     town_context.player_benedictions.set(benedictions);
+                    town_context.atlas.set(atlas);
                     town_context.player_skill_masteries.set(skill_masteries);
                     town_context
                         .skill_mastery_skill_specs
@@ -133,6 +146,7 @@ pub fn TownPage() -> impl IntoView {
                     initial_load.await;
                     town_context.passives_tree_specs.set(passives_tree_specs.await);
                     town_context.benedictions_specs.set(benedictions_specs.await);
+                    town_context.atlas_specs.set(atlas_specs.await);
                     view! {
                         <HeaderMenu />
                         <div class="relative flex-1">
@@ -144,6 +158,7 @@ pub fn TownPage() -> impl IntoView {
                             <MarketPanel open=town_context.open_market />
                             <StashPanel open=town_context.open_stash />
                             <PassivesPanel open=town_context.open_ascend />
+                            <AtlasPanel open=town_context.open_atlas />
                             <ForgePanel open=town_context.open_forge />
                             <TownInventoryPanel open=town_context.open_inventory />
                             <SettingsModal open=town_context.open_settings />
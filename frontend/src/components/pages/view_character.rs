@@ -94,6 +94,7 @@ pub fn ViewCharacterPage() -> impl IntoView {
                         inventory,
                         ascension,
                         benedictions,
+                        atlas: _,
                         // last_grind,
                         passives_build,
                         character_stash: _,
@@ -5,10 +5,18 @@ use leptos::{html::*, prelude::*, task::spawn_local};
 use leptos_router::hooks::use_navigate;
 use leptos_use::storage;
 
-use shared::{data::user::UserId, http::client::UpdateAccountRequest, types::Username};
+use shared::{
+    data::user::UserId,
+    http::client::{DeleteAccountRequest, IgnoreUserRequest, UpdateAccountRequest},
+    types::Username,
+};
 
 use crate::components::{
     backend_client::BackendClient,
+    shared::{
+        oauth::LinkedAccountsSettings,
+        totp::{TotpContext, TwoFactorSettings, with_totp},
+    },
     ui::{
         buttons::{MenuButton, MenuButtonRed},
         card::{CardHeader, CardInset, MenuCard},
@@ -22,6 +30,7 @@ use crate::components::{
 pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>) -> impl IntoView {
     let toaster = expect_context::<Toasts>();
     let backend = expect_context::<BackendClient>();
+    let totp_context = expect_context::<TotpContext>();
 
     let (get_username_storage, set_username_storage, _) =
         storage::use_local_storage::<Option<_>, JsonSerdeCodec>("username");
@@ -98,12 +107,16 @@ pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>
         move |_| {
             spawn_local({
                 async move {
-                    match backend
-                        .post_update_account(&UpdateAccountRequest {
-                            email: Some(email.get_untracked().unwrap()),
-                            ..Default::default()
-                        })
-                        .await
+                    match with_totp(totp_context, |totp_code| async move {
+                        backend
+                            .post_update_account(&UpdateAccountRequest {
+                                email: Some(email.get_untracked().unwrap()),
+                                totp_code,
+                                ..Default::default()
+                            })
+                            .await
+                    })
+                    .await
                     {
                         Ok(_) => {
                             init_email.set(email.get_untracked());
@@ -127,11 +140,9 @@ pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>
         }
     };
 
+    // Accounts created through an identity provider have no old password
     let disable_password_submit = Signal::derive(move || {
-        old_password.read().is_none()
-            || password.read().is_none()
-            || passwords_mismatch.get()
-            || processing.get()
+        password.read().is_none() || passwords_mismatch.get() || processing.get()
     });
     let on_update_password = {
         move |_| {
@@ -139,7 +150,7 @@ pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>
                 async move {
                     match backend
                         .post_update_account(&UpdateAccountRequest {
-                            old_password: Some(old_password.get().unwrap()),
+                            old_password: old_password.get(),
                             password: Some(password.get().unwrap()),
                             ..Default::default()
                         })
@@ -254,6 +265,12 @@ pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>
                         </MenuButton>
                     </CardInset>
 
+                    <LinkedAccountsSettings />
+
+                    <TwoFactorSettings />
+
+                    <IgnoredUsersSettings />
+
                     <CardInset>
                         <p class="text-sm text-red-400">
                             "Deleting your account is irreversible. All game progress will be lost."
@@ -269,6 +286,105 @@ pub fn AccountSettingsPanel(open: RwSignal<bool>, refresh_trigger: RwSignal<u64>
     }
 }
 
+#[component]
+fn IgnoredUsersSettings() -> impl IntoView {
+    let toaster = expect_context::<Toasts>();
+    let backend = expect_context::<BackendClient>();
+
+    let refresh_trigger = RwSignal::new(0u64);
+    let ignored_users = LocalResource::new(move || {
+        let _ = refresh_trigger.read();
+        async move {
+            backend
+                .get_ignored_users()
+                .await
+                .map(|r| r.ignored_users)
+                .unwrap_or_default()
+        }
+    });
+
+    let ignored_username = RwSignal::new(None);
+    let processing = RwSignal::new(false);
+
+    let disable_ignore_submit =
+        Signal::derive(move || ignored_username.read().is_none() || processing.get());
+    let on_ignore_user = move |_| {
+        let Some(username) = ignored_username.get_untracked() else {
+            return;
+        };
+        processing.set(true);
+        spawn_local(async move {
+            match backend
+                .post_ignore_user(&IgnoreUserRequest { username })
+                .await
+            {
+                Ok(_) => {
+                    ignored_username.set(None);
+                    *refresh_trigger.write() += 1;
+                }
+                Err(e) => {
+                    show_toast(
+                        toaster,
+                        format!("Ignore player error: {e}"),
+                        ToastVariant::Error,
+                    );
+                }
+            }
+            processing.set(false);
+        });
+    };
+
+    let on_unignore_user = move |user_id: UserId| {
+        spawn_local(async move {
+            match backend.delete_ignored_user(&user_id).await {
+                Ok(_) => *refresh_trigger.write() += 1,
+                Err(e) => {
+                    show_toast(
+                        toaster,
+                        format!("Unignore player error: {e}"),
+                        ToastVariant::Error,
+                    );
+                }
+            }
+        });
+    };
+
+    view! {
+        <CardInset>
+            <ValidatedInput
+                label="Ignore Player"
+                id="ignored-username"
+                input_type="text"
+                placeholder="Enter the name of a player to hide in chat"
+                bind=ignored_username
+            />
+            <MenuButton on:click=on_ignore_user disabled=disable_ignore_submit>
+                "Ignore Player"
+            </MenuButton>
+            <ul class="text-left text-sm space-y-1">
+                {move || {
+                    ignored_users
+                        .get()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|ignored_user| {
+                            let user_id = ignored_user.user_id;
+                            view! {
+                                <li class="flex items-center justify-between gap-2">
+                                    <span class="text-gray-300">{ignored_user.username}</span>
+                                    <MenuButton on:click=move |_| on_unignore_user(user_id)>
+                                        "Unignore"
+                                    </MenuButton>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+        </CardInset>
+    }
+}
+
 fn validate_delete(s: &str) -> anyhow::Result<()> {
     if s.eq_ignore_ascii_case("delete") {
         Ok(())
@@ -302,6 +418,7 @@ pub fn ConfirmAccountDeletionModal(
     let do_delete = {
         let toaster = expect_context::<Toasts>();
         let backend = expect_context::<BackendClient>();
+        let totp_context = expect_context::<TotpContext>();
         let navigate = use_navigate();
 
         move |_| {
@@ -309,7 +426,13 @@ pub fn ConfirmAccountDeletionModal(
             spawn_local({
                 let navigate = navigate.clone();
                 async move {
-                    match backend.delete_account(&user_id).await {
+                    match with_totp(totp_context, |totp_code| async move {
+                        backend
+                            .delete_account(&user_id, &DeleteAccountRequest { totp_code })
+                            .await
+                    })
+                    .await
+                    {
                         Ok(_) => {
                             if get_username_storage.get_untracked()
                                 == get_guest_username_storage.get_untracked()
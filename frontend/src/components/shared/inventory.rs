@@ -18,7 +18,7 @@ use crate::{
         shared::{
             item_card::ItemCard,
             resources::{ResourceReward, ResourceRewardOverlay},
-            tooltips::ItemTooltip,
+            tooltips::{ItemTooltip, item_tooltip::EquippedItemSetsContext},
         },
         ui::{
             buttons::{CloseButton, MenuButton},
@@ -72,6 +72,10 @@ pub struct InventoryConfig {
 pub fn Inventory(inventory: InventoryConfig, open: RwSignal<bool>) -> impl IntoView {
     let sell_queue = SellQueue::default();
     provide_context(sell_queue);
+    let player_inventory = inventory.player_inventory;
+    provide_context(EquippedItemSetsContext(Signal::derive(move || {
+        player_inventory.read().equipped_item_sets()
+    })));
 
     Effect::new(move || {
         if !open.get() {
@@ -4,9 +4,11 @@ pub mod item_card;
 pub mod leaderboard;
 pub mod loot_filter;
 pub mod news;
+pub mod oauth;
 pub mod passives;
 pub mod player_count;
 pub mod resources;
 pub mod settings;
 pub mod skills;
+pub mod totp;
 pub mod tooltips;
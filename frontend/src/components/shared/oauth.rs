@@ -0,0 +1,253 @@
+use leptos::{html::*, prelude::*, task::spawn_local};
+use serde::{Deserialize, Serialize};
+
+use shared::http::{client::OAuthAuthorizeRequest, server::OAuthAuthorizeResponse};
+
+use crate::components::{
+    backend_client::{BackendClient, BackendError},
+    ui::{buttons::MenuButton, card::CardInset, number::format_datetime, toast::*},
+};
+
+const PENDING_OAUTH_KEY: &str = "pending_oauth";
+
+/// Kept until the identity provider redirects back to the callback page
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PendingOAuth {
+    pub provider_id: String,
+    pub state: String,
+    pub link: bool,
+}
+
+// Written directly as the page is left right after
+fn store_pending_oauth(pending_oauth: &PendingOAuth) {
+    if let Ok(Some(storage)) = window().session_storage()
+        && let Ok(value) = serde_json::to_string(pending_oauth)
+    {
+        let _ = storage.set_item(PENDING_OAUTH_KEY, &value);
+    }
+}
+
+pub fn take_pending_oauth() -> Option<PendingOAuth> {
+    let storage = window().session_storage().ok().flatten()?;
+    let value = storage.get_item(PENDING_OAUTH_KEY).ok().flatten();
+    let _ = storage.remove_item(PENDING_OAUTH_KEY);
+    serde_json::from_str(&value?).ok()
+}
+
+fn redirect_to_provider(
+    provider_id: String,
+    link: bool,
+    response: Result<OAuthAuthorizeResponse, BackendError>,
+) -> Result<(), BackendError> {
+    let response = response?;
+
+    store_pending_oauth(&PendingOAuth {
+        provider_id,
+        state: response.state,
+        link,
+    });
+
+    window()
+        .location()
+        .set_href(&response.authorize_url)
+        .map_err(|_| BackendError::OtherError)
+}
+
+#[component]
+pub fn OAuthSignInButtons(
+    #[prop(into)] accepted_terms: Signal<bool>,
+    #[prop(optional, into)] disabled: Option<Signal<bool>>,
+) -> impl IntoView {
+    let toaster = expect_context::<Toasts>();
+    let backend = expect_context::<BackendClient>();
+
+    let providers = LocalResource::new(move || async move {
+        backend
+            .get_oauth_providers()
+            .await
+            .map(|r| r.providers)
+            .unwrap_or_default()
+    });
+
+    let redirecting = RwSignal::new(false);
+    let disable_buttons =
+        Signal::derive(move || redirecting.get() || disabled.is_some_and(|d| d.get()));
+
+    let on_sign_in = move |provider_id: String| {
+        redirecting.set(true);
+        spawn_local(async move {
+            let response = backend
+                .post_oauth_authorize(
+                    &provider_id,
+                    &OAuthAuthorizeRequest {
+                        accepted_terms: accepted_terms.get_untracked(),
+                    },
+                )
+                .await;
+
+            if let Err(e) = redirect_to_provider(provider_id, false, response) {
+                show_toast(
+                    toaster,
+                    format!("Authentication error: {e}"),
+                    ToastVariant::Error,
+                );
+                redirecting.set(false);
+            }
+        });
+    };
+
+    view! {
+        <div class="flex flex-col gap-2 mt-2 empty:hidden">
+            {move || {
+                providers
+                    .get()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|provider| {
+                        let provider_id = provider.provider_id.clone();
+                        view! {
+                            <MenuButton
+                                on:click=move |_| on_sign_in(provider_id.clone())
+                                disabled=disable_buttons
+                                class="w-full"
+                            >
+                                {format!("Continue with {}", provider.name)}
+                            </MenuButton>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn LinkedAccountsSettings() -> impl IntoView {
+    let toaster = expect_context::<Toasts>();
+    let backend = expect_context::<BackendClient>();
+
+    let providers = LocalResource::new(move || async move {
+        backend
+            .get_oauth_providers()
+            .await
+            .map(|r| r.providers)
+            .unwrap_or_default()
+    });
+
+    let refresh_trigger = RwSignal::new(0u64);
+    let identities = LocalResource::new(move || {
+        let _ = refresh_trigger.read();
+        async move { backend.get_user_identities().await.unwrap_or_default() }
+    });
+
+    let processing = RwSignal::new(false);
+
+    let on_link = move |provider_id: String| {
+        processing.set(true);
+        spawn_local(async move {
+            let response = backend.post_link_authorize(&provider_id).await;
+            if let Err(e) = redirect_to_provider(provider_id, true, response) {
+                show_toast(
+                    toaster,
+                    format!("Link account error: {e}"),
+                    ToastVariant::Error,
+                );
+                processing.set(false);
+            }
+        });
+    };
+
+    let on_unlink = move |provider_id: String| {
+        processing.set(true);
+        spawn_local(async move {
+            match backend.delete_user_identity(&provider_id).await {
+                Ok(_) => *refresh_trigger.write() += 1,
+                Err(e) => {
+                    show_toast(
+                        toaster,
+                        format!("Unlink account error: {e}"),
+                        ToastVariant::Error,
+                    );
+                }
+            }
+            processing.set(false);
+        });
+    };
+
+    view! {
+        <CardInset>
+            <p class="text-left text-sm text-gray-300">"Linked Accounts"</p>
+            <ul class="text-left text-sm space-y-1">
+                {move || {
+                    let identities = identities.get().unwrap_or_default();
+                    providers
+                        .get()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|provider| {
+                            let identity = identities
+                                .identities
+                                .iter()
+                                .find(|identity| {
+                                    identity.provider.provider_id == provider.provider_id
+                                })
+                                .cloned();
+                            let provider_id = provider.provider_id.clone();
+                            view! {
+                                <li class="flex items-center justify-between gap-2">
+                                    <span class="text-gray-300">
+                                        {provider.name}
+                                        {identity
+                                            .as_ref()
+                                            .map(|identity| {
+                                                format!(
+                                                    " - {} (linked {})",
+                                                    identity
+                                                        .provider_username
+                                                        .clone()
+                                                        .unwrap_or_default(),
+                                                    format_datetime(identity.linked_at),
+                                                )
+                                            })}
+                                    </span>
+                                    {if identity.is_some() {
+                                        view! {
+                                            <MenuButton
+                                                on:click=move |_| on_unlink(provider_id.clone())
+                                                disabled=processing
+                                            >
+                                                "Unlink"
+                                            </MenuButton>
+                                        }
+                                            .into_any()
+                                    } else {
+                                        view! {
+                                            <MenuButton
+                                                on:click=move |_| on_link(provider_id.clone())
+                                                disabled=processing
+                                            >
+                                                "Link"
+                                            </MenuButton>
+                                        }
+                                            .into_any()
+                                    }}
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+            <Show when=move || {
+                identities
+                    .get()
+                    .is_some_and(|identities| {
+                        !identities.has_password && identities.identities.len() <= 1
+                    })
+            }>
+                <p class="text-left text-xs text-zinc-400">
+                    "Set a password to be able to unlink your last account."
+                </p>
+            </Show>
+        </CardInset>
+    }
+}
@@ -35,6 +35,8 @@ pub fn skill_specs_from_base(skill_id: String, base_skill_specs: &BaseSkillSpecs
         skill_type: base_skill_specs.skill_type,
         cooldown: base_skill_specs.cooldown.into(),
         mana_cost: base_skill_specs.mana_cost.into(),
+        cast_time: base_skill_specs.cast_time.into(),
+        channel: base_skill_specs.channel,
         targets: base_skill_specs.targets.clone(),
         triggers: base_skill_specs.triggers.clone(),
         level_modifier: 0,
@@ -291,12 +291,14 @@ pub fn stat_skill_effect_type_str(effect_type: Option<&StatSkillEffectType>) ->
                 format!("Apply {}", status_filter_str(&status_filter))
             }
             StatSkillEffectType::RefreshStatus => "Refresh Statuses".into(),
+            StatSkillEffectType::Cleanse => "Cleanse".into(),
             StatSkillEffectType::Restore { restore_type } => {
                 format!("Restore{}", restore_type_str(*restore_type))
             }
             StatSkillEffectType::Resurrect => "Resurrect".into(),
             StatSkillEffectType::Kill => "Kill".into(),
             StatSkillEffectType::RefreshCooldown => "Refresh Skills".into(),
+            StatSkillEffectType::Summon => "Summon".into(),
         },
         None => "All Skill Effects".into(),
     }
@@ -306,6 +308,7 @@ pub fn scope_str(scope: AffixEffectScope) -> &'static str {
     match scope {
         AffixEffectScope::Local => "Local",
         AffixEffectScope::Global => "Global",
+        AffixEffectScope::Player => "Player",
     }
 }
 
@@ -468,6 +471,9 @@ pub fn format_multiplier_stat_name(stat: &StatType) -> String {
         StatType::LifeRegen => "Life Regeneration".to_string(),
         StatType::Mana => "Maximum Mana".to_string(),
         StatType::ManaRegen => "Mana Regeneration".to_string(),
+        StatType::Ward => "Maximum Ward".to_string(),
+        StatType::WardRecharge => "Ward Recharge Rate".to_string(),
+        StatType::WardRechargeDelay => "Ward Recharge Delay".to_string(),
         StatType::ManaCost { skill_filter } => {
             format!("{}Mana Cost", skill_filter_str(skill_filter, "", false))
         }
@@ -606,6 +612,7 @@ pub fn format_multiplier_stat_name(stat: &StatType) -> String {
         StatType::ItemAreaChance => "Chance to Find Area Items".to_string(),
         StatType::ItemLevel => "Items Power Level".to_string(),
         StatType::GemsFind => "Gems Find".to_string(),
+        StatType::ChampionChance => "Champion Chance".to_string(),
         StatType::PowerLevel => "Power Level".to_string(),
         StatType::RestoreOnHit {
             restore_type,
@@ -697,6 +704,41 @@ pub fn format_multiplier_stat_name(stat: &StatType) -> String {
                 )
             }
         }
+        StatType::MinionLifetime(skill_filter) => format!(
+            "Minion Duration{}",
+            skill_filter_str(skill_filter, " with ", true)
+        ),
+        StatType::MaxMinions(skill_filter) => format!(
+            "Maximum Minions{}",
+            skill_filter_str(skill_filter, " with ", true)
+        ),
+        StatType::MinionModifier { stat, skill_filter } => format!(
+            "Minion {}{}",
+            format_multiplier_stat_name(stat),
+            skill_filter_str(skill_filter, " from ", true)
+        ),
+        StatType::DamageConversion {
+            skill_filter,
+            from,
+            to,
+            is_extra,
+        } => {
+            if *is_extra {
+                format!(
+                    "Gain {}Damage as Extra {}Damage{}",
+                    damage_type_str(Some(*from)),
+                    damage_type_str(Some(*to)),
+                    skill_filter_str(skill_filter, " with ", true)
+                )
+            } else {
+                format!(
+                    "Convert {}Damage to {}Damage{}",
+                    damage_type_str(Some(*from)),
+                    damage_type_str(Some(*to)),
+                    skill_filter_str(skill_filter, " with ", true)
+                )
+            }
+        }
         StatType::Description(description) | StatType::Description2(description) => {
             description.clone()
         }
@@ -715,6 +757,15 @@ pub fn format_flat_stat(stat: &StatType, value: Option<f64>) -> String {
             "{} Mana Regeneration per second",
             format_adds_removes(value.map(|value| value * 0.1), true, "%")
         ),
+        StatType::Ward => format!("{} Maximum Ward", format_adds_removes(value, false, "")),
+        StatType::WardRecharge => format!(
+            "{} Ward Recharge per second",
+            format_adds_removes(value, true, "%")
+        ),
+        StatType::WardRechargeDelay => format!(
+            "{}s Ward Recharge Delay",
+            format_adds_removes(value, true, "")
+        ),
         StatType::ManaCost { skill_filter } => format!(
             "{} Mana Cost{}",
             format_adds_removes(value, false, ""),
@@ -915,6 +966,9 @@ pub fn format_flat_stat(stat: &StatType, value: Option<f64>) -> String {
             "Adds {} Gems per Champion Kill",
             format_flat_number(value, false)
         ),
+        StatType::ChampionChance => {
+            format!("Adds {}% Champion Chance", format_flat_number(value, false))
+        }
         StatType::ItemRarity => format!("Adds {}% Items Rarity", format_flat_number(value, false)),
         StatType::ItemAreaChance => format!(
             "Adds {}% Chance to Find Area Items",
@@ -991,6 +1045,24 @@ pub fn format_flat_stat(stat: &StatType, value: Option<f64>) -> String {
                 format_multiplier_stat_name(&stat_converter_specs.stat)
             )
         }
+        StatType::DamageConversion {
+            skill_filter,
+            from,
+            to,
+            is_extra,
+        } => {
+            let extra_str = match is_extra {
+                true => "gained as Extra",
+                false => "converted to",
+            };
+            format!(
+                "{}% of {}Damage{} {extra_str} {}Damage",
+                format_flat_number(value, false),
+                damage_type_str(Some(*from)),
+                skill_filter_str(skill_filter, " with ", true),
+                damage_type_str(Some(*to)),
+            )
+        }
         StatType::SuccessChance {
             skill_filter,
             effect_type,
@@ -1130,6 +1202,21 @@ pub fn format_flat_stat(stat: &StatType, value: Option<f64>) -> String {
             conditions_tooltip::format_skill_modifier_conditions_pre(conditions, ""),
             conditions_tooltip::format_skill_modifier_conditions_post(conditions, "")
         ),
+        StatType::MinionLifetime(skill_filter) => format!(
+            "{} seconds to Minion Duration{}",
+            format_adds_removes(value, true, ""),
+            skill_filter_str(skill_filter, " with ", true)
+        ),
+        StatType::MaxMinions(skill_filter) => format!(
+            "{} Maximum Minions{}",
+            format_adds_removes(value, false, ""),
+            skill_filter_str(skill_filter, " with ", true)
+        ),
+        StatType::MinionModifier { stat, skill_filter } => format!(
+            "Minions{} have {}",
+            skill_filter_str(skill_filter, " from ", true),
+            format_flat_stat(stat, value)
+        ),
         StatType::StatConditionalModifier {
             stat,
             conditions,
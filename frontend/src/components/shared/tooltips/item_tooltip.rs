@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use strum::IntoEnumIterator;
 
 use leptos::{html::*, prelude::*};
@@ -7,6 +7,7 @@ use shared::data::{
     area::AreaLevel,
     item::{ItemCategory, ItemRarity, ItemSlot, ItemSpecs, SkillRange, SkillShape},
     item_affix::{AffixEffectScope, AffixTag, AffixType, ItemAffix},
+    item_set::ItemSetId,
     skill::DamageType,
 };
 
@@ -23,6 +24,10 @@ use crate::components::{
 
 use super::effects_tooltip;
 
+/// Amount of different items equipped per set, used to display set progress
+#[derive(Clone, Copy)]
+pub struct EquippedItemSetsContext(pub Signal<HashMap<ItemSetId, usize>>);
+
 pub enum ComparableType {
     NotComparable,
     Comparable,
@@ -71,9 +76,14 @@ pub fn ItemTooltipContent(
         let suffixes = formatted_affixes_list(&item_specs.modifiers.affixes, AffixType::Suffix);
         let upgrade_affixes =
             formatted_affixes_list(&item_specs.modifiers.affixes, AffixType::Upgrade);
+        let corruption_affixes =
+            formatted_affixes_list(&item_specs.modifiers.affixes, AffixType::Corruption);
         // let effects = formatted_affixes_list(&item_specs.modifiers.affixes);
         (
-            (!base_affixes.is_empty() || !prefixes.is_empty() || !suffixes.is_empty()),
+            (!base_affixes.is_empty()
+                || !prefixes.is_empty()
+                || !suffixes.is_empty()
+                || !corruption_affixes.is_empty()),
             view! {
                 {(!base_affixes.is_empty())
                     .then(|| {
@@ -111,6 +121,7 @@ pub fn ItemTooltipContent(
                             {upgrade_affixes}
                         }
                     })}
+                {(!corruption_affixes.is_empty()).then(|| view! { {corruption_affixes} })}
             }
             .into_any(),
         )
@@ -127,15 +138,41 @@ pub fn ItemTooltipContent(
                 .aggregate_effects(AffixEffectScope::Global, false))
                 .into(),
         ));
-        (!effects.is_empty(), view! { {effects} }.into_any())
+        let player_effects = effects_tooltip::formatted_effects_list(
+            (&item_specs
+                .modifiers
+                .aggregate_effects(AffixEffectScope::Player, false))
+                .into(),
+        );
+        (
+            !effects.is_empty() || !player_effects.is_empty(),
+            view! {
+                {effects}
+                {(!player_effects.is_empty())
+                    .then(|| {
+                        view! {
+                            <li class="text-zinc-400 text-xs">"Player:"</li>
+                            {player_effects}
+                        }
+                    })}
+            }
+            .into_any(),
+        )
     };
 
     let (has_triggers, triggers) = {
         let trigger_lines = item_specs
             .base
             .triggers
-            .clone()
-            .into_iter()
+            .iter()
+            .chain(
+                item_specs
+                    .modifiers
+                    .affixes
+                    .iter()
+                    .flat_map(|affix| affix.triggers.iter()),
+            )
+            .cloned()
             .map(|trigger| format_trigger(trigger, show_affixes, None, None))
             .collect::<Vec<_>>();
 
@@ -238,6 +275,23 @@ pub fn ItemTooltipContent(
                             </li>
                         }
                     })}
+                {(item_specs.modifiers.rarity == ItemRarity::Unique
+                    && item_specs.modifiers.base_version < item_specs.base.version)
+                    .then(|| {
+                        view! {
+                            <li class="text-xs xl:text-sm italic text-amber-500">
+                                "Legacy Unique, divine it to update"
+                            </li>
+                        }
+                    })}
+                {item_specs
+                    .modifiers
+                    .corrupted
+                    .then(|| {
+                        view! {
+                            <li class="text-xs xl:text-sm font-bold text-red-500">"Corrupted"</li>
+                        }
+                    })}
                 <QualityTooltip item_specs=item_specs.clone() />
                 <ArmorTooltip item_specs=item_specs.clone() />
                 <WeaponTooltip item_specs=item_specs.clone() />
@@ -260,7 +314,13 @@ pub fn ItemTooltipContent(
                         <span class="text-xs xl:text-sm text-zinc-400">"Empower effects:"</span>
                         <ul class="list-none xl:space-y-1 text-xs xl:text-sm">{upgrades}</ul>
                     }
-                })} <Separator /> <ul class="list-none xl:space-y-1">
+                })}
+            {item_specs
+                .base
+                .item_set
+                .clone()
+                .map(|item_set_id| view! { <ItemSetTooltip item_set_id /> })}
+            <SocketsTooltip item_specs=item_specs.clone() show_affixes /> <Separator /> <ul class="list-none xl:space-y-1">
                 <li class="text-xs xl:text-sm text-zinc-400">
                     "Required Power Level: "
                     <span class=move || {
@@ -317,6 +377,117 @@ pub fn ItemTooltipContent(
     }
 }
 
+#[component]
+fn ItemSetTooltip(item_set_id: ItemSetId) -> impl IntoView {
+    let data_context: DataContext = expect_context();
+    let equipped_item_sets = use_context::<EquippedItemSetsContext>();
+
+    let item_set = data_context
+        .item_sets_specs
+        .read_untracked()
+        .get(&item_set_id)
+        .cloned();
+
+    item_set.map(|item_set| {
+        let pieces = Signal::derive(move || {
+            equipped_item_sets
+                .and_then(|equipped_item_sets| {
+                    equipped_item_sets.0.read().get(&item_set_id).copied()
+                })
+                .unwrap_or_default()
+        });
+
+        let max_pieces = item_set
+            .bonuses
+            .iter()
+            .map(|bonus| bonus.pieces)
+            .max()
+            .unwrap_or_default();
+
+        let bonuses: Vec<_> = item_set
+            .bonuses
+            .into_iter()
+            .map(|bonus| {
+                let bonus_pieces = bonus.pieces;
+                let triggers: Vec<_> = bonus
+                    .triggers
+                    .into_iter()
+                    .map(|trigger| format_trigger(trigger, false, None, None))
+                    .collect();
+                view! {
+                    <ul class=move || {
+                        if pieces.get() >= bonus_pieces {
+                            "list-none xl:space-y-1"
+                        } else {
+                            "list-none xl:space-y-1 opacity-50"
+                        }
+                    }>
+                        <li class="text-zinc-400 text-xs">{format!("({bonus_pieces}) Set:")}</li>
+                        {effects_tooltip::formatted_effects_list(bonus.effects)}
+                        {triggers}
+                    </ul>
+                }
+            })
+            .collect();
+
+        view! {
+            <Separator />
+            <div class="text-xs xl:text-sm">
+                <span class="font-bold text-emerald-400">
+                    {item_set.name} " (" {move || pieces.get()} "/" {max_pieces} ")"
+                </span>
+                {bonuses}
+            </div>
+        }
+    })
+}
+
+#[component]
+fn SocketsTooltip(item_specs: Arc<ItemSpecs>, show_affixes: bool) -> impl IntoView {
+    let sockets = item_specs.base.sockets as usize;
+
+    (sockets > 0).then(|| {
+        let socketed_runes: Vec<_> = item_specs
+            .modifiers
+            .socketed_runes
+            .iter()
+            .map(|rune| {
+                let effects = show_affixes.then(|| {
+                    let mut effects = effects_tooltip::formatted_effects_list(
+                        (&rune.aggregate_effects(AffixEffectScope::Local, false)).into(),
+                    );
+                    effects.extend(effects_tooltip::formatted_effects_list(
+                        (&rune.aggregate_effects(AffixEffectScope::Global, false)).into(),
+                    ));
+                    effects
+                });
+                view! {
+                    <li class=format!("font-semibold {}", name_color_rarity(rune.rarity))>
+                        {rune.name.clone()}
+                    </li>
+                    {effects}
+                }
+            })
+            .collect();
+        let empty_sockets = sockets.saturating_sub(socketed_runes.len());
+
+        view! {
+            <Separator />
+            <ul class="list-none xl:space-y-1 text-xs xl:text-sm">
+                <li class="text-zinc-400">
+                    "Sockets: " <span class="text-stone-100">
+                        {item_specs.modifiers.socketed_runes.len()} "/" {sockets}
+                    </span>
+                </li>
+                {socketed_runes}
+                {(0..empty_sockets)
+                    .map(|_| view! { <li class="text-zinc-500 italic">"Empty Socket"</li> })
+                    .collect_view()}
+            </ul>
+        }
+    })
+}
+
 pub fn name_color_rarity(item_rarity: ItemRarity) -> &'static str {
     match item_rarity {
         ItemRarity::Normal => "text-stone-100",
@@ -698,7 +869,7 @@ pub fn formatted_affixes_list(
                 .map(|e| e.scope)
                 .unwrap_or(AffixEffectScope::Global);
             let affix_meta = match affix_type {
-                AffixType::Unique | AffixType::Upgrade => view! {
+                AffixType::Unique | AffixType::Upgrade | AffixType::Corruption => view! {
                     <li class="text-zinc-400 text-xs -mb-1">
                         {affix_type_str(affix.affix_type)}" – "{scope_str(scope)}
                     </li>
@@ -742,6 +913,7 @@ fn affix_type_str(affix_type: AffixType) -> &'static str {
         AffixType::Suffix => "Suffix",
         AffixType::Unique => "Base affix",
         AffixType::Upgrade => "Empowering affix",
+        AffixType::Corruption => "Corrupted affix",
     }
 }
 
@@ -221,6 +221,30 @@ pub fn SkillTooltip(
                             </span>
                         }
                     })}
+                {(skill_specs.cast_time.get() > 0.0)
+                    .then(|| {
+                        view! {
+                            " | Cast Time: "
+                            <span class="text-stone-100">
+                                {format!("{:.1}s", skill_specs.cast_time.get())}
+                            </span>
+                        }
+                    })}
+                {skill_specs
+                    .channel
+                    .map(|channel| {
+                        view! {
+                            <br />
+                            "Channelled for "
+                            <span class="text-stone-100">
+                                {format!("{:.1}s", channel.duration.get())}
+                            </span>
+                            ", every "
+                            <span class="text-stone-100">
+                                {format!("{:.1}s", channel.tick_rate.get())}
+                            </span>
+                        }
+                    })}
             </p>
 
             {(!skill_specs.auto_use_conditions.is_empty())
@@ -680,6 +704,10 @@ pub fn format_skill_effect(
                 .into_any(),
             }
         }
+        SkillEffectType::Cleanse { status_filter } => {
+            let status_filter_str = effects_tooltip::status_filter_str(&status_filter);
+            view! { <EffectLi>{success_chance}"Cleanse "{status_filter_str}</EffectLi> }.into_any()
+        }
         SkillEffectType::RefreshStatus {
             status_filter,
             value,
@@ -770,6 +798,37 @@ pub fn format_skill_effect(
         SkillEffectType::Resurrect => {
             view! { <EffectLi>{success_chance}"Resurrect"</EffectLi> }.into_any()
         }
+        SkillEffectType::Summon {
+            minion_id: _,
+            minion_name,
+            lifetime,
+            max_minions,
+            minion_effects,
+        } => {
+            let lifetime = lifetime.get();
+            view! {
+                <EffectLi>
+                    {success_chance}"Summon "<span class="font-semibold">{minion_name}</span>" "
+                    {(lifetime > 0.0).then(|| format_duration_values(lifetime, lifetime))}
+                </EffectLi>
+                <EffectLi>
+                    "Maximum Minions: "<span class="font-semibold">{*max_minions}</span>
+                </EffectLi>
+                {formatted_effects_list(
+                    minion_effects
+                        .into_iter()
+                        .map(|effect| StatEffect {
+                            stat: StatType::MinionModifier {
+                                stat: Box::new(effect.stat),
+                                skill_filter: Default::default(),
+                            },
+                            ..effect
+                        })
+                        .collect(),
+                )}
+            }
+            .into_any()
+        }
         SkillEffectType::Kill => view! { <EffectLi>{success_chance}"Kill"</EffectLi> }.into_any(),
         SkillEffectType::RefreshCooldown {
             skill_filter,
@@ -1060,8 +1119,13 @@ pub fn skill_effect_text(
             },
             effects_tooltip::status_filter_str(&status_filter),
         ),
+        SkillEffectType::Cleanse { status_filter } => format!(
+            "Cleanse {}",
+            effects_tooltip::status_filter_str(&status_filter)
+        ),
         SkillEffectType::Resurrect => "Resurrect".into(),
         SkillEffectType::Kill => "Kill".into(),
+        SkillEffectType::Summon { minion_name, .. } => format!("Summon {minion_name}"),
         SkillEffectType::Restore { restore_type, .. } => {
             format!("Restore {}", restore_type_str(Some(restore_type)))
         }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, matches};
+use std::{collections::BTreeMap, matches};
 
 use leptos::{html::*, prelude::*};
 
@@ -27,7 +27,7 @@ pub fn format_status_effects(
     stacks: usize,
     modifiers: Option<&[TriggerEffectModifier]>,
     // effects_map: Option<&EffectsMap>,
-    character_triggers: Option<&HashMap<String, TriggerEffect>>,
+    character_triggers: Option<&BTreeMap<String, TriggerEffect>>,
 ) -> Option<impl IntoView + use<>> {
     // let value_factor = effects_map.map(|effects_map| {
     //     stats_computations::compute_stats_effects_status_value(
@@ -55,7 +55,9 @@ pub fn format_status_effects(
                 character_triggers,
                 stacks,
             )
+            .map(|line| line.into_any())
         })
+        .chain(format_status_interactions(&status_specs))
         .collect::<Vec<_>>();
 
     let grant_str = if status_specs.debuff {
@@ -85,6 +87,43 @@ pub fn format_status_effects(
     }
 }
 
+fn format_status_interactions(status_specs: &StatusSpecs) -> Vec<AnyView> {
+    status_specs
+        .consumes
+        .iter()
+        .map(|status_consumption| {
+            let status_filter_str =
+                effects_tooltip::status_filter_str(&status_consumption.status_filter);
+            let burst_factor = status_consumption.burst_factor.get();
+            if burst_factor == 100.0 {
+                view! { "Consumes "{status_filter_str}", dealing their remaining Damage" }
+                    .into_any()
+            } else {
+                view! {
+                    "Consumes "{status_filter_str}", dealing "
+                    <span class="font-semibold">{format_number(burst_factor)}"%"</span>
+                    " of their remaining Damage"
+                }
+                .into_any()
+            }
+        })
+        .chain(status_specs.excludes.iter().map(|status_filter| {
+            let status_filter_str = effects_tooltip::status_filter_str(status_filter);
+            view! { "Removes "{status_filter_str} }.into_any()
+        }))
+        .chain((status_specs.immunity_duration.get() > 0.0).then(|| {
+            view! {
+                "Cannot be applied again for "
+                <span class="font-semibold">
+                    {format!("{:.1}s", status_specs.immunity_duration.get())}
+                </span>
+                " after expiring"
+            }
+            .into_any()
+        }))
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn format_status_effect_line(
     status_name: &str,
@@ -93,7 +132,7 @@ fn format_status_effect_line(
     value_factor: Option<f64>,
     modifiers: Option<&[TriggerEffectModifier]>,
     // effects_map: Option<&EffectsMap>,
-    character_triggers: Option<&HashMap<String, TriggerEffect>>,
+    character_triggers: Option<&BTreeMap<String, TriggerEffect>>,
     stacks: usize,
 ) -> Option<impl IntoView + use<>> {
     let value = computed_status_effect_value(&status_effect, skill_value, stacks);
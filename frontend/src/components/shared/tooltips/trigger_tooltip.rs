@@ -335,6 +335,13 @@ fn format_trigger_event(event_trigger: &EventTrigger) -> String {
                 _ => format!("On Affected by {}", format_status_trigger(status_trigger)),
             },
         },
+        EventTrigger::OnRemoveStatus(status_trigger) => {
+            format!(
+                "On Removing {}{}",
+                format_status_trigger(status_trigger),
+                format_target_conditions(&status_trigger.conditions, " from ", " Enemies")
+            )
+        }
         EventTrigger::OnRestored(restore_trigger) => format_restore_trigger(restore_trigger),
     }
 }
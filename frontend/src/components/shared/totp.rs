@@ -0,0 +1,355 @@
+use futures::channel::oneshot;
+use leptos::{html::*, prelude::*, task::spawn_local};
+use leptos_use::on_click_outside;
+
+use shared::http::{
+    client::{TotpCodeRequest, TotpSignInRequest},
+    server::{EnrollTotpResponse, SignInResponse},
+};
+
+use crate::components::{
+    backend_client::{BackendClient, BackendError},
+    events::keyboard_event_key,
+    ui::{
+        buttons::{MenuButton, MenuButtonRed},
+        card::{CardInset, CardTitle},
+        input::Input,
+        toast::*,
+    },
+};
+
+/// Ask the user for a TOTP or recovery code through a modal
+#[derive(Clone, Copy)]
+pub struct TotpContext {
+    pending: RwSignal<Option<oneshot::Sender<String>>>,
+}
+
+impl TotpContext {
+    /// Return None if the user cancelled
+    pub async fn request_code(self) -> Option<String> {
+        let (sender, receiver) = oneshot::channel();
+        self.pending.set(Some(sender));
+        receiver.await.ok()
+    }
+}
+
+pub fn provide_totp_context() -> TotpContext {
+    let context = TotpContext {
+        pending: RwSignal::new(None),
+    };
+    provide_context(context);
+    context
+}
+
+/// Run the request, asking for a code and retrying if the backend requires one
+pub async fn with_totp<T, F, Fut>(totp_context: TotpContext, request: F) -> Result<T, BackendError>
+where
+    F: Fn(Option<String>) -> Fut,
+    Fut: Future<Output = Result<T, BackendError>>,
+{
+    match request(None).await {
+        Err(BackendError::TotpRequired) => match totp_context.request_code().await {
+            Some(code) => request(Some(code)).await,
+            None => Err(BackendError::TotpRequired),
+        },
+        response => response,
+    }
+}
+
+/// Complete the sign in with a code if the first factor wasn't enough
+pub async fn complete_sign_in(
+    backend: BackendClient,
+    totp_context: TotpContext,
+    response: Result<SignInResponse, BackendError>,
+) -> Result<(), BackendError> {
+    let Some(challenge) = response?.totp_challenge else {
+        return Ok(());
+    };
+
+    let code = totp_context
+        .request_code()
+        .await
+        .ok_or(BackendError::TotpRequired)?;
+
+    backend
+        .post_totp_signin(&TotpSignInRequest { challenge, code })
+        .await
+        .map(|_| ())
+}
+
+#[component]
+pub fn TotpPromptModal(totp_context: TotpContext) -> impl IntoView {
+    let code = RwSignal::new(None::<String>);
+    let node_ref = NodeRef::new();
+
+    // Dropping the sender cancels the request
+    let cancel = move || {
+        totp_context.pending.set(None);
+        code.set(None);
+    };
+    let _ = on_click_outside(node_ref, move |_| cancel());
+
+    let disable_confirm = Signal::derive(move || {
+        code.read()
+            .as_ref()
+            .is_none_or(|code| code.trim().is_empty())
+    });
+    let confirm = move || {
+        if disable_confirm.get_untracked() {
+            return;
+        }
+        if let Some(sender) = totp_context.pending.write().take() {
+            let _ = sender.send(code.get_untracked().unwrap_or_default());
+        }
+        code.set(None);
+    };
+
+    view! {
+        <Show when=move || totp_context.pending.read().is_some()>
+            <div class="fixed inset-0 z-50 flex items-center justify-center bg-black/70 backdrop-blur-sm px-4">
+                <div
+                    node_ref=node_ref
+                    class="bg-zinc-900 ring-1 ring-zinc-700 rounded-lg p-6 w-full max-w-md shadow-xl text-gray-200 space-y-4"
+                >
+                    <CardTitle>"Two-Factor Authentication"</CardTitle>
+                    <p class="text-zinc-400 text-sm leading-relaxed">
+                        "Enter the code from your authenticator app, or one of your recovery codes."
+                    </p>
+
+                    <Input
+                        id="totp-code"
+                        input_type="text"
+                        placeholder="123456"
+                        bind=code
+                        on:keydown=move |ev: leptos::ev::KeyboardEvent| {
+                            if keyboard_event_key(&ev).as_deref() == Some("Enter") {
+                                confirm();
+                            }
+                        }
+                    />
+
+                    <div class="flex justify-between gap-2 pt-2">
+                        <MenuButtonRed on:click=move |_| cancel()>"Cancel"</MenuButtonRed>
+                        <MenuButton on:click=move |_| confirm() disabled=disable_confirm>
+                            "Confirm"
+                        </MenuButton>
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+pub fn TwoFactorSettings() -> impl IntoView {
+    let toaster = expect_context::<Toasts>();
+    let backend = expect_context::<BackendClient>();
+    let totp_context = expect_context::<TotpContext>();
+
+    let refresh_trigger = RwSignal::new(0u64);
+    let totp_status = LocalResource::new(move || {
+        let _ = refresh_trigger.read();
+        async move { backend.get_totp_status().await.unwrap_or_default() }
+    });
+
+    let enrolment = RwSignal::new(None::<EnrollTotpResponse>);
+    let enrolment_code = RwSignal::new(None::<String>);
+    let recovery_codes = RwSignal::new(None::<Vec<String>>);
+    let processing = RwSignal::new(false);
+
+    let on_enroll = move |_| {
+        processing.set(true);
+        spawn_local(async move {
+            match backend.post_enroll_totp().await {
+                Ok(response) => enrolment.set(Some(response)),
+                Err(e) => show_toast(
+                    toaster,
+                    format!("Two-factor authentication error: {e}"),
+                    ToastVariant::Error,
+                ),
+            }
+            processing.set(false);
+        });
+    };
+
+    let disable_enable =
+        Signal::derive(move || enrolment_code.read().is_none() || processing.get());
+    let on_enable = move |_| {
+        processing.set(true);
+        spawn_local(async move {
+            match backend
+                .post_enable_totp(&TotpCodeRequest {
+                    code: enrolment_code.get_untracked().unwrap_or_default(),
+                })
+                .await
+            {
+                Ok(response) => {
+                    enrolment.set(None);
+                    enrolment_code.set(None);
+                    recovery_codes.set(Some(response.recovery_codes));
+                    *refresh_trigger.write() += 1;
+                    show_toast(
+                        toaster,
+                        "Two-factor authentication enabled!".to_string(),
+                        ToastVariant::Success,
+                    );
+                }
+                Err(e) => show_toast(
+                    toaster,
+                    format!("Two-factor authentication error: {e}"),
+                    ToastVariant::Error,
+                ),
+            }
+            processing.set(false);
+        });
+    };
+
+    let on_regenerate = move |_| {
+        processing.set(true);
+        spawn_local(async move {
+            if let Some(code) = totp_context.request_code().await {
+                match backend
+                    .post_regenerate_recovery_codes(&TotpCodeRequest { code })
+                    .await
+                {
+                    Ok(response) => {
+                        recovery_codes.set(Some(response.recovery_codes));
+                        *refresh_trigger.write() += 1;
+                    }
+                    Err(e) => show_toast(
+                        toaster,
+                        format!("Recovery codes error: {e}"),
+                        ToastVariant::Error,
+                    ),
+                }
+            }
+            processing.set(false);
+        });
+    };
+
+    let on_disable = move |_| {
+        processing.set(true);
+        spawn_local(async move {
+            if let Some(code) = totp_context.request_code().await {
+                match backend.post_disable_totp(&TotpCodeRequest { code }).await {
+                    Ok(_) => {
+                        recovery_codes.set(None);
+                        *refresh_trigger.write() += 1;
+                        show_toast(
+                            toaster,
+                            "Two-factor authentication disabled.".to_string(),
+                            ToastVariant::Warning,
+                        );
+                    }
+                    Err(e) => show_toast(
+                        toaster,
+                        format!("Two-factor authentication error: {e}"),
+                        ToastVariant::Error,
+                    ),
+                }
+            }
+            processing.set(false);
+        });
+    };
+
+    let totp_enabled = move || totp_status.get().is_some_and(|status| status.enabled);
+
+    view! {
+        <CardInset>
+            <p class="text-left text-sm text-gray-300">"Two-Factor Authentication"</p>
+
+            <Show
+                when=totp_enabled
+                fallback=move || {
+                    view! {
+                        <Show
+                            when=move || enrolment.read().is_some()
+                            fallback=move || {
+                                view! {
+                                    <p class="text-left text-xs text-zinc-400">
+                                        "Protect your account with an authenticator app. A code will be asked when signing in and for sensitive actions."
+                                    </p>
+                                    <MenuButton
+                                        class:justify-self-end
+                                        on:click=on_enroll
+                                        disabled=processing
+                                    >
+                                        "Enable Two-Factor"
+                                    </MenuButton>
+                                }
+                            }
+                        >
+                            {move || {
+                                enrolment
+                                    .get()
+                                    .map(|enrolment| {
+                                        view! {
+                                            <p class="text-left text-xs text-zinc-400">
+                                                "Scan this QR code with your authenticator app, then enter the generated code."
+                                            </p>
+                                            <img
+                                                class="mx-auto w-48 h-48 bg-white p-2 rounded"
+                                                src=format!("data:image/png;base64,{}", enrolment.qr_code)
+                                                alt=enrolment.provisioning_uri
+                                            />
+                                            <p class="text-center text-xs text-zinc-400 break-all select-all font-mono">
+                                                {enrolment.secret}
+                                            </p>
+                                        }
+                                    })
+                            }}
+                            <Input
+                                id="totp-enrolment-code"
+                                input_type="text"
+                                placeholder="Enter the generated code"
+                                bind=enrolment_code
+                            />
+                            <MenuButton
+                                class:justify-self-end
+                                on:click=on_enable
+                                disabled=disable_enable
+                            >
+                                "Confirm"
+                            </MenuButton>
+                        </Show>
+                    }
+                }
+            >
+                <p class="text-left text-xs text-zinc-400">
+                    {move || {
+                        format!(
+                            "Enabled, {} recovery codes left.",
+                            totp_status.get().map(|status| status.recovery_codes_left).unwrap_or_default(),
+                        )
+                    }}
+                </p>
+                <div class="flex justify-end gap-2">
+                    <MenuButton on:click=on_regenerate disabled=processing>
+                        "New Recovery Codes"
+                    </MenuButton>
+                    <MenuButtonRed on:click=on_disable disabled=Signal::from(processing)>
+                        "Disable"
+                    </MenuButtonRed>
+                </div>
+            </Show>
+
+            {move || {
+                recovery_codes
+                    .get()
+                    .map(|recovery_codes| {
+                        view! {
+                            <p class="text-left text-xs text-amber-300">
+                                "Save these recovery codes somewhere safe, they will not be shown again. Each can be used once instead of a code."
+                            </p>
+                            <ul class="grid grid-cols-2 gap-1 text-sm font-mono text-gray-200 select-all">
+                                {recovery_codes
+                                    .into_iter()
+                                    .map(|recovery_code| view! { <li>{recovery_code}</li> })
+                                    .collect_view()}
+                            </ul>
+                        }
+                    })
+            }}
+        </CardInset>
+    }
+}
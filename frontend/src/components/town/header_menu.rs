@@ -41,6 +41,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_temple.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.equip_filter.set(InventoryEquipFilter::Slot);
     };
 
@@ -60,6 +61,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_temple.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.open_inventory.set(false);
     };
 
@@ -78,6 +80,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_temple.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.open_inventory.set(false);
         town_context.open_stash.set(false);
     };
@@ -97,6 +100,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_temple.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.open_inventory.set(false);
         town_context.open_stash.set(false);
     };
@@ -116,6 +120,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_temple.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.open_inventory.set(false);
         town_context.open_stash.set(false);
     };
@@ -137,6 +142,7 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_stash.set(false);
         town_context.open_skill_masteries.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
     };
 
     Effect::new(move || {
@@ -154,10 +160,31 @@ pub fn HeaderMenu() -> impl IntoView {
         town_context.open_forge.set(false);
         town_context.open_inventory.set(false);
         town_context.open_skill_mastery_details.set(false);
+        town_context.open_atlas.set(false);
         town_context.open_stash.set(false);
         town_context.open_temple.set(false);
     };
 
+    let open_atlas = move || {
+        town_context
+            .open_atlas
+            .set(!town_context.open_atlas.get_untracked());
+        town_context.open_ascend.set(false);
+        town_context.open_market.set(false);
+        town_context.open_forge.set(false);
+        town_context.open_inventory.set(false);
+        town_context.open_stash.set(false);
+        town_context.open_temple.set(false);
+        town_context.open_skill_masteries.set(false);
+        town_context.open_skill_mastery_details.set(false);
+    };
+
+    Effect::new(move || {
+        if events_context.key_pressed(Key::Character('a')) {
+            open_atlas()
+        }
+    });
+
     view! {
         <BaseHeaderMenu>
             <div class="flex justify-start space-x-1 xl:space-x-2">
@@ -214,6 +241,9 @@ pub fn HeaderMenu() -> impl IntoView {
                 <MenuButton on:click=move |_| open_temple() disabled=disable_panels>
                     "Temple"
                 </MenuButton>
+                <MenuButton on:click=move |_| open_atlas() disabled=disable_panels>
+                    "Atlas"
+                </MenuButton>
                 <MenuButton
                     on:click=move |_| open_skill_masteries()
                     disabled=move || {
@@ -0,0 +1,611 @@
+use codee::string::JsonSerdeCodec;
+use leptos::{prelude::*, task::spawn_local};
+use leptos_router::hooks::use_navigate;
+use leptos_use::storage;
+
+use std::sync::Arc;
+
+use shared::{
+    data::{
+        area::StartAreaConfig,
+        atlas::AtlasSpecs,
+        challenge::{ActiveChallenge, ChallengePeriod},
+        passive::{
+            PassiveConnection, PassiveNodeId, PassiveNodeSpecs, PassiveNodeType, PassivesTreeSpecs,
+            PurchasedNodes,
+        },
+    },
+    http::client::{ClaimChallengeRewardRequest, SaveAtlasModifiersRequest},
+};
+
+use crate::components::{
+    backend_client::BackendClient,
+    shared::passives::{Connection, MetaStatus, Node, NodeStatus, PurchaseStatus},
+    town::TownContext,
+    ui::{
+        buttons::{MenuButton, TabButton},
+        card::{CardHeader, CardInset, MenuCard},
+        confirm::ConfirmContext,
+        menu_panel::MenuPanel,
+        number::{NumberInset, format_datetime},
+        pannable::Pannable,
+        toast::*,
+    },
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AtlasTab {
+    Maps,
+    Modifiers,
+    Challenges,
+}
+
+#[component]
+pub fn AtlasPanel(open: RwSignal<bool>) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    let active_tab = RwSignal::new(AtlasTab::Maps);
+
+    let purchased_modifiers = RwSignal::new(PurchasedNodes::default());
+    Effect::new(move || {
+        purchased_modifiers.set(town_context.atlas.read().purchased_modifiers.clone());
+    });
+
+    let points_available = Memo::new(move |_| {
+        town_context.atlas_specs.with(|atlas_specs| {
+            town_context.atlas.with(|atlas| {
+                atlas_specs.total_points(atlas) as i32 - purchased_modifiers.read().len() as i32
+            })
+        })
+    });
+
+    view! {
+        <MenuPanel open=open>
+            <div class="w-full h-full">
+                <MenuCard>
+                    <CardHeader title="Atlas" on_close=move || open.set(false)>
+                        <div class="flex self-end justify-center h-full ml-2 xl:ml-4 gap-2 xl:gap-4 -mb-2 overflow-clip">
+                            <TabButton
+                                is_active=Signal::derive(move || active_tab.get() == AtlasTab::Maps)
+                                on:click=move |_| { active_tab.set(AtlasTab::Maps) }
+                            >
+                                <span class="mx-2">"Maps"</span>
+                            </TabButton>
+                            <TabButton
+                                is_active=Signal::derive(move || {
+                                    active_tab.get() == AtlasTab::Modifiers
+                                })
+                                on:click=move |_| { active_tab.set(AtlasTab::Modifiers) }
+                            >
+                                <span class="mx-2">"Modifiers"</span>
+                            </TabButton>
+                            <TabButton
+                                is_active=Signal::derive(move || {
+                                    active_tab.get() == AtlasTab::Challenges
+                                })
+                                on:click=move |_| { active_tab.set(AtlasTab::Challenges) }
+                            >
+                                <span class="mx-2">"Challenges"</span>
+                            </TabButton>
+                        </div>
+
+                        <div class="flex-1"></div>
+
+                        <div class="text-sm xl:text-base text-zinc-400 flex items-center gap-1">
+                            "Atlas Points:"
+                            <NumberInset>
+                                <div class="w-[3ch] font-semibold text-white text-right">
+                                    {move || points_available.get()}
+                                </div>
+                            </NumberInset>
+                        </div>
+
+                        <div class="flex-1"></div>
+
+                        {move || {
+                            (active_tab.get() == AtlasTab::Modifiers)
+                                .then(|| {
+                                    view! {
+                                        <ModifiersPanelHeader
+                                            purchased_modifiers
+                                            points_available
+                                        />
+                                    }
+                                })
+                        }}
+                    </CardHeader>
+                    <CardInset pad=false class="relative">
+                        {move || match active_tab.get() {
+                            AtlasTab::Maps => view! { <AtlasMap /> }.into_any(),
+                            AtlasTab::Modifiers => {
+                                view! { <AtlasModifiersTree purchased_modifiers points_available /> }
+                                    .into_any()
+                            }
+                            AtlasTab::Challenges => view! { <ChallengesList /> }.into_any(),
+                        }}
+                    </CardInset>
+                </MenuCard>
+            </div>
+        </MenuPanel>
+    }
+}
+
+#[component]
+fn ModifiersPanelHeader(
+    purchased_modifiers: RwSignal<PurchasedNodes>,
+    points_available: Memo<i32>,
+) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    let cancel = move || {
+        purchased_modifiers.set(
+            town_context
+                .atlas
+                .read_untracked()
+                .purchased_modifiers
+                .clone(),
+        );
+    };
+
+    let reset = move || {
+        purchased_modifiers.set(Default::default());
+    };
+
+    let has_changed = Memo::new(move |_| {
+        town_context.atlas.with(|atlas| {
+            purchased_modifiers
+                .read()
+                .iter()
+                .ne(atlas.purchased_modifiers.iter())
+        })
+    });
+
+    view! {
+        <div class="flex items-center gap-2">
+            <MenuButton on:click=move |_| reset()>"Reset"</MenuButton>
+            <MenuButton
+                on:click=move |_| cancel()
+                disabled=Signal::derive(move || !has_changed.get())
+            >
+                "Cancel"
+            </MenuButton>
+            <ConfirmModifiersButton purchased_modifiers points_available has_changed />
+        </div>
+    }
+}
+
+#[component]
+fn ConfirmModifiersButton(
+    purchased_modifiers: RwSignal<PurchasedNodes>,
+    points_available: Memo<i32>,
+    has_changed: Memo<bool>,
+) -> impl IntoView {
+    let do_save = Arc::new({
+        let backend = expect_context::<BackendClient>();
+        let town_context = expect_context::<TownContext>();
+        let toaster = expect_context::<Toasts>();
+
+        let character_id = town_context.character.read_untracked().character_id;
+        move || {
+            spawn_local({
+                async move {
+                    match backend
+                        .post_save_atlas_modifiers(&SaveAtlasModifiersRequest {
+                            character_id,
+                            purchased_modifiers: purchased_modifiers.get_untracked(),
+                        })
+                        .await
+                    {
+                        Ok(response) => {
+                            town_context.atlas.set(response.atlas);
+                            show_toast(toaster, "Atlas modifiers saved!", ToastVariant::Success);
+                        }
+                        Err(e) => show_toast(
+                            toaster,
+                            format!("Failed to save atlas modifiers: {e}"),
+                            ToastVariant::Error,
+                        ),
+                    }
+                }
+            });
+        }
+    });
+
+    let try_save = {
+        let confirm_context = expect_context::<ConfirmContext>();
+        move |_| {
+            (confirm_context.confirm)(
+                "Do you confirm saving your atlas modifiers?".into(),
+                do_save.clone(),
+            );
+        }
+    };
+
+    let disabled = Signal::derive(move || !has_changed.get() || points_available.get() < 0);
+
+    view! {
+        <MenuButton on:click=try_save disabled=disabled>
+            "Confirm"
+        </MenuButton>
+    }
+}
+
+// Maps
+// ----
+
+#[component]
+fn AtlasMap() -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    // Display the atlas through the passive tree components
+    let atlas_tree_specs = RwSignal::new(PassivesTreeSpecs::default());
+    Effect::new(move || {
+        atlas_tree_specs.set(town_context.atlas_specs.with(atlas_to_tree_specs));
+    });
+
+    view! {
+        <Pannable>
+            <For
+                each=move || town_context.atlas_specs.read().connections.clone().into_iter()
+                key=|conn| (conn.from, conn.to)
+                let(conn)
+            >
+                <AtlasConnection connection=conn atlas_tree_specs />
+            </For>
+            <For
+                each=move || atlas_tree_specs.read().nodes.clone().into_iter()
+                key=|(id, _)| *id
+                let((id, node))
+            >
+                <AtlasNode node_id=id node_specs=node />
+            </For>
+        </Pannable>
+    }
+}
+
+#[component]
+fn AtlasNode(node_id: PassiveNodeId, node_specs: PassiveNodeSpecs) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    let node_status = Memo::new(move |_| {
+        let completed = town_context.atlas.read().completed_nodes.contains(&node_id);
+        let unlocked = town_context.atlas_specs.with(|atlas_specs| {
+            town_context
+                .atlas
+                .with(|atlas| atlas_specs.is_node_unlocked(atlas, &node_id))
+        });
+
+        NodeStatus {
+            purchase_status: if completed {
+                PurchaseStatus::Purchased
+            } else if unlocked {
+                PurchaseStatus::Purchaseable
+            } else {
+                PurchaseStatus::Inactive
+            },
+            meta_status: if completed || unlocked {
+                MetaStatus::Normal
+            } else {
+                MetaStatus::Locked
+            },
+        }
+    });
+
+    let node_level = Memo::new(|_| 0);
+
+    view! {
+        <Node
+            node_specs
+            node_status
+            node_level
+            on_click=|| {}
+            on_right_click=|| {}
+            show_upgrade=false
+        />
+    }
+}
+
+#[component]
+fn AtlasConnection(
+    connection: PassiveConnection,
+    atlas_tree_specs: RwSignal<PassivesTreeSpecs>,
+) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    let amount_connections = Memo::new(move |_| {
+        town_context.atlas.with(|atlas| {
+            atlas.completed_nodes.contains(&connection.from) as usize
+                + atlas.completed_nodes.contains(&connection.to) as usize
+        })
+    });
+
+    let node_levels = Memo::new(|_| (0, 0));
+
+    view! {
+        <Connection
+            connection
+            passives_tree_specs=atlas_tree_specs
+            amount_connections
+            node_levels
+        />
+    }
+}
+
+fn atlas_to_tree_specs(atlas_specs: &AtlasSpecs) -> PassivesTreeSpecs {
+    PassivesTreeSpecs {
+        nodes: atlas_specs
+            .nodes
+            .iter()
+            .map(|(node_id, node_specs)| {
+                (
+                    *node_id,
+                    PassiveNodeSpecs {
+                        name: format!(
+                            "{} (Level {}, {} Atlas Points)",
+                            node_specs.name, node_specs.completion_level, node_specs.atlas_points
+                        ),
+                        icon: "passives/third_eye.svg".into(),
+                        x: node_specs.x,
+                        y: node_specs.y,
+                        size: 1,
+                        root_node: node_specs.root_node,
+                        node_type: PassiveNodeType::Utility,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect(),
+        connections: atlas_specs.connections.clone(),
+    }
+}
+
+// Modifiers
+// ---------
+
+#[component]
+fn AtlasModifiersTree(
+    purchased_modifiers: RwSignal<PurchasedNodes>,
+    points_available: Memo<i32>,
+) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+
+    let modifiers_tree_specs = RwSignal::new(PassivesTreeSpecs::default());
+    Effect::new(move || {
+        modifiers_tree_specs.set(town_context.atlas_specs.read().modifiers_tree.clone());
+    });
+
+    view! {
+        <Pannable>
+            <For
+                each=move || modifiers_tree_specs.read().connections.clone().into_iter()
+                key=|conn| (conn.from, conn.to)
+                let(conn)
+            >
+                <ModifierConnection connection=conn modifiers_tree_specs purchased_modifiers />
+            </For>
+            <For
+                each=move || modifiers_tree_specs.read().nodes.clone().into_iter()
+                key=|(id, _)| *id
+                let((id, node))
+            >
+                <ModifierNode
+                    node_id=id
+                    node_specs=node
+                    modifiers_tree_specs
+                    purchased_modifiers
+                    points_available
+                />
+            </For>
+        </Pannable>
+    }
+}
+
+#[component]
+fn ModifierNode(
+    node_id: PassiveNodeId,
+    node_specs: PassiveNodeSpecs,
+    modifiers_tree_specs: RwSignal<PassivesTreeSpecs>,
+    purchased_modifiers: RwSignal<PurchasedNodes>,
+    points_available: Memo<i32>,
+) -> impl IntoView {
+    let root_node = node_specs.root_node;
+
+    let connected = Memo::new(move |_| {
+        root_node
+            || modifiers_tree_specs
+                .read()
+                .connections
+                .iter()
+                .any(|connection| {
+                    (connection.from == node_id
+                        && purchased_modifiers.read().contains(&connection.to))
+                        || (connection.to == node_id
+                            && purchased_modifiers.read().contains(&connection.from))
+                })
+    });
+
+    let node_status = Memo::new(move |_| NodeStatus {
+        purchase_status: if purchased_modifiers.read().contains(&node_id) {
+            PurchaseStatus::Purchased
+        } else if points_available.get() > 0 && connected.get() {
+            PurchaseStatus::Purchaseable
+        } else {
+            PurchaseStatus::Inactive
+        },
+        meta_status: MetaStatus::Normal,
+    });
+
+    let node_level = Memo::new(move |_| purchased_modifiers.read().contains(&node_id) as u8);
+
+    let purchase = move || {
+        if node_status.get_untracked().purchase_status == PurchaseStatus::Purchaseable {
+            purchased_modifiers.write().insert(node_id);
+        }
+    };
+
+    // Only allow refunding the last allocated node, to keep the tree connected
+    let refund = move || {
+        if purchased_modifiers.read_untracked().last() == Some(&node_id) {
+            purchased_modifiers.write().pop();
+        }
+    };
+
+    view! {
+        <Node
+            node_specs
+            node_status
+            node_level
+            on_click=purchase
+            on_right_click=refund
+            show_upgrade=false
+        />
+    }
+}
+
+#[component]
+fn ModifierConnection(
+    connection: PassiveConnection,
+    modifiers_tree_specs: RwSignal<PassivesTreeSpecs>,
+    purchased_modifiers: RwSignal<PurchasedNodes>,
+) -> impl IntoView {
+    let amount_connections = Memo::new(move |_| {
+        purchased_modifiers.read().contains(&connection.from) as usize
+            + purchased_modifiers.read().contains(&connection.to) as usize
+    });
+
+    let node_levels = Memo::new(|_| (0, 0));
+
+    view! {
+        <Connection
+            connection
+            passives_tree_specs=modifiers_tree_specs
+            amount_connections
+            node_levels
+        />
+    }
+}
+
+#[component]
+fn ChallengesList() -> impl IntoView {
+    let challenges = LocalResource::new({
+        let backend = expect_context::<BackendClient>();
+        move || async move {
+            let mut challenges: Vec<_> = backend
+                .get_challenges()
+                .await
+                .unwrap_or_default()
+                .challenges
+                .into_values()
+                .collect();
+            challenges.sort_by_key(|challenge| challenge.period.duration_secs());
+            challenges
+        }
+    });
+
+    view! {
+        <div class="flex flex-col gap-2 xl:gap-4 p-2 xl:p-4 overflow-y-auto h-full">
+            <Transition fallback=move || {
+                view! { <p class="text-zinc-400">"Loading..."</p> }
+            }>
+                {move || {
+                    Suspend::new(async move {
+                        let challenges = challenges.await;
+                        view! {
+                            <For
+                                each=move || challenges.clone()
+                                key=|challenge| challenge.challenge_id()
+                                children=move |challenge| {
+                                    view! { <ChallengeCard challenge /> }
+                                }
+                            />
+                        }
+                    })
+                }}
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn ChallengeCard(challenge: ActiveChallenge) -> impl IntoView {
+    let town_context = expect_context::<TownContext>();
+    let backend = expect_context::<BackendClient>();
+    let toaster = expect_context::<Toasts>();
+
+    let (_, set_area_config_storage, _) =
+        storage::use_session_storage::<Option<StartAreaConfig>, JsonSerdeCodec>("area_config");
+
+    let period = match challenge.period {
+        ChallengePeriod::Daily => "Daily Challenge",
+        ChallengePeriod::Weekly => "Weekly Challenge",
+    };
+
+    let start = {
+        let navigate = use_navigate();
+        let schedule_id = challenge.schedule_id.clone();
+        let area_id = challenge.specs.area_id.clone();
+        move |_| {
+            set_area_config_storage.set(Some(StartAreaConfig {
+                area_id: area_id.clone(),
+                map_item_index: None,
+                challenge_id: Some(schedule_id.clone()),
+            }));
+            navigate("/game", Default::default());
+        }
+    };
+
+    let claim = {
+        let schedule_id = challenge.schedule_id.clone();
+        move |_| {
+            let character_id = town_context.character.read_untracked().character_id;
+            let schedule_id = schedule_id.clone();
+            spawn_local(async move {
+                match backend
+                    .post_claim_challenge_reward(&ClaimChallengeRewardRequest {
+                        character_id,
+                        schedule_id,
+                    })
+                    .await
+                {
+                    Ok(response) => {
+                        town_context.character.write().resource_gems = response.resource_gems;
+                        town_context.character.write().resource_shards = response.resource_shards;
+                        show_toast(toaster, "Challenge reward claimed!", ToastVariant::Success);
+                    }
+                    Err(e) => show_toast(
+                        toaster,
+                        format!("Failed to claim reward: {e}"),
+                        ToastVariant::Error,
+                    ),
+                }
+            });
+        }
+    };
+
+    view! {
+        <div class="flex items-center justify-between gap-4 p-3 xl:p-4
+        rounded-[10px] border border-[#5f5137]/60 bg-zinc-900/80">
+            <div class="flex flex-col gap-1">
+                <span class="text-xs text-gray-500 uppercase tracking-[0.08em]">{period}</span>
+                <span class="text-amber-300 font-semibold text-base font-display">
+                    {challenge.specs.name.clone()}
+                </span>
+                <span class="text-sm text-zinc-400">
+                    {format!(
+                        "Reach Area Level {} to earn {} Gems and {} Power Shards",
+                        challenge.specs.completion_level,
+                        challenge.specs.reward.gems,
+                        challenge.specs.reward.shards,
+                    )}
+                </span>
+                <span class="text-xs text-zinc-500">
+                    {format!("Ends on {}", format_datetime(challenge.ends_at))}
+                </span>
+            </div>
+            <div class="flex items-center gap-2">
+                <MenuButton on:click=claim>"Claim Reward"</MenuButton>
+                <MenuButton on:click=start>"Start Challenge"</MenuButton>
+            </div>
+        </div>
+    }
+}
@@ -1,17 +1,22 @@
 use chrono::Utc;
+use indexmap::IndexMap;
 use leptos::{prelude::*, task::spawn_local};
 use shared::{
     computations,
     constants::GAMBLE_ITEM_CATEGORIES,
     data::{
-        forge,
+        forge::{self, CorruptionOutcome},
         item::{ItemCategory, ItemRarity},
         item_affix::AffixType,
-        player::EquippedSlot,
+        player::{EquippedSlot, PlayerInventory},
     },
-    http::client::{
-        ForgeAffixOperation, ForgeAffixRequest, ForgeUpgradeRequest, GambleItemRequest,
-        InventoryDeleteRequest,
+    http::{
+        client::{
+            ForgeAffixFamiliesRequest, ForgeAffixOperation, ForgeAffixRequest, ForgeCorruptRequest,
+            ForgeDivineRequest, ForgeSocketRuneRequest, ForgeUnsocketRuneRequest,
+            ForgeUpgradeRequest, GambleItemRequest, InventoryDeleteRequest,
+        },
+        server::ForgeAffixFamily,
     },
 };
 use std::sync::Arc;
@@ -31,7 +36,7 @@ use crate::components::{
         buttons::{MenuButton, MenuButtonRed, TabButton},
         card::{CardHeader, CardInset, CardInsetTitle, MenuCard},
         confirm::ConfirmContext,
-        dropdown::SearchableDropdownMenu,
+        dropdown::{DropdownMenu, SearchableDropdownMenu},
         menu_panel::MenuPanel,
         toast::*,
     },
@@ -40,8 +45,11 @@ use crate::components::{
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ForgeTab {
     Affix,
+    Refine,
+    Corrupt,
     Gamble,
     UniqueUpgrade,
+    Sockets,
 }
 
 #[component]
@@ -78,6 +86,15 @@ pub fn ForgePanel(open: RwSignal<bool>) -> impl IntoView {
                         >
                             <div class="mx-1">"Craft"</div>
                         </TabButton>
+                        <TabButton
+                            is_active=Signal::derive(move || {
+                                active_tab.get() == ForgeTab::Refine
+                            })
+                            on:click=move |_| { switch_tab(ForgeTab::Refine) }
+                            class:py-3
+                        >
+                            <div class="mx-1">"Refine"</div>
+                        </TabButton>
                         <TabButton
                             is_active=Signal::derive(move || {
                                 active_tab.get() == ForgeTab::UniqueUpgrade
@@ -87,6 +104,24 @@ pub fn ForgePanel(open: RwSignal<bool>) -> impl IntoView {
                         >
                             <div class="mx-1">"Empower"</div>
                         </TabButton>
+                        <TabButton
+                            is_active=Signal::derive(move || {
+                                active_tab.get() == ForgeTab::Sockets
+                            })
+                            on:click=move |_| { switch_tab(ForgeTab::Sockets) }
+                            class:py-3
+                        >
+                            <div class="mx-1">"Sockets"</div>
+                        </TabButton>
+                        <TabButton
+                            is_active=Signal::derive(move || {
+                                active_tab.get() == ForgeTab::Corrupt
+                            })
+                            on:click=move |_| { switch_tab(ForgeTab::Corrupt) }
+                            class:py-3
+                        >
+                            <div class="mx-1">"Corrupt"</div>
+                        </TabButton>
                         <TabButton
                             is_active=Signal::derive(move || {
                                 active_tab.get() == ForgeTab::Gamble
@@ -104,7 +139,11 @@ pub fn ForgePanel(open: RwSignal<bool>) -> impl IntoView {
                     <CardInset class="w-full" pad=false>
 
                         {move || match active_tab.get() {
-                            ForgeTab::Affix | ForgeTab::UniqueUpgrade => {
+                            ForgeTab::Affix
+                            | ForgeTab::Refine
+                            | ForgeTab::Corrupt
+                            | ForgeTab::UniqueUpgrade
+                            | ForgeTab::Sockets => {
                                 view! {
                                     <InventoryBrowser
                                         selected_item
@@ -124,9 +163,18 @@ pub fn ForgePanel(open: RwSignal<bool>) -> impl IntoView {
                             ForgeTab::Affix => {
                                 view! { <ForgeAffixDetails selected_item /> }.into_any()
                             }
+                            ForgeTab::Refine => {
+                                view! { <ForgeRefineDetails selected_item /> }.into_any()
+                            }
                             ForgeTab::UniqueUpgrade => {
                                 view! { <UpgradeUniqueDetails selected_item /> }.into_any()
                             }
+                            ForgeTab::Corrupt => {
+                                view! { <CorruptItemDetails selected_item /> }.into_any()
+                            }
+                            ForgeTab::Sockets => {
+                                view! { <SocketsDetails selected_item /> }.into_any()
+                            }
                             ForgeTab::Gamble => {
                                 view! { <GambleDetails selected_item gamble_category /> }.into_any()
                             }
@@ -236,9 +284,7 @@ fn InventoryBrowser(selected_item: RwSignal<SelectedItem>, filter_unique: bool)
 
 #[component]
 pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
-    let backend: BackendClient = expect_context();
     let town_context: TownContext = expect_context();
-    let toaster: Toasts = expect_context();
     let confirm_context: ConfirmContext = expect_context();
 
     let user_gems = move || town_context.character.read().resource_gems;
@@ -250,63 +296,7 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
         })
     };
 
-    let do_affix_operation = {
-        let character_id = town_context.character.read_untracked().character_id;
-        move |operation| {
-            if let SelectedItem::InMarket(item) = selected_item.get() {
-                spawn_local({
-                    async move {
-                        match backend
-                            .forge_affix(&ForgeAffixRequest {
-                                character_id,
-                                item_index: item.index as u32,
-                                operation,
-                            })
-                            .await
-                        {
-                            Ok(response) => {
-                                let updated_item_specs = if item.index < 9 {
-                                    response
-                                        .inventory
-                                        .equipped
-                                        .get(&item.index.try_into().unwrap())
-                                        .cloned()
-                                        .and_then(|equipped_item| match equipped_item {
-                                            EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
-                                            _ => None,
-                                        })
-                                } else {
-                                    response
-                                        .inventory
-                                        .bag
-                                        .get(item.index.saturating_sub(9))
-                                        .cloned()
-                                };
-
-                                if let Some(updated_item_specs) = updated_item_specs {
-                                    selected_item.try_set(SelectedItem::InMarket(
-                                        SelectedMarketItem {
-                                            item_specs: Arc::new(updated_item_specs),
-                                            ..item
-                                        },
-                                    ));
-                                }
-
-                                town_context.inventory.set(response.inventory);
-                                town_context.character.write().resource_gems =
-                                    response.resource_gems;
-                            }
-                            Err(e) => show_toast(
-                                toaster,
-                                format!("Failed to forge item: {e}"),
-                                ToastVariant::Error,
-                            ),
-                        }
-                    }
-                });
-            }
-        }
-    };
+    let do_affix_operation = use_affix_operation(selected_item);
 
     let try_add_affix = {
         let confirm_context = confirm_context.clone();
@@ -351,8 +341,8 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
                     return None;
                 }
                 forge::affix_operation_price(
-                    ForgeAffixOperation::Add(None),
-                    item.item_specs.modifiers.count_nonunique_affixes(),
+                    &ForgeAffixOperation::Add(None),
+                    &item.item_specs.modifiers,
                     &item.item_specs.base,
                 )
             }
@@ -372,8 +362,8 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
 
                 if prefixes == suffixes {
                     forge::affix_operation_price(
-                        ForgeAffixOperation::Add(Some(AffixType::Prefix)),
-                        prefixes + suffixes,
+                        &ForgeAffixOperation::Add(Some(AffixType::Prefix)),
+                        &item.item_specs.modifiers,
                         &item.item_specs.base,
                     )
                 } else {
@@ -396,8 +386,8 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
 
                 if suffixes == prefixes {
                     forge::affix_operation_price(
-                        ForgeAffixOperation::Add(Some(AffixType::Suffix)),
-                        prefixes + suffixes,
+                        &ForgeAffixOperation::Add(Some(AffixType::Suffix)),
+                        &item.item_specs.modifiers,
                         &item.item_specs.base,
                     )
                 } else {
@@ -411,8 +401,8 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
     let remove_price = move || {
         selected_item.with(|selected_item| match selected_item {
             SelectedItem::InMarket(item) => forge::affix_operation_price(
-                ForgeAffixOperation::Remove,
-                item.item_specs.modifiers.count_nonunique_affixes(),
+                &ForgeAffixOperation::Remove,
+                &item.item_specs.modifiers,
                 &item.item_specs.base,
             ),
             _ => None,
@@ -529,94 +519,161 @@ pub fn ForgeAffixDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView
 }
 
 #[component]
-pub fn UpgradeUniqueDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
+pub fn ForgeRefineDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
     let backend: BackendClient = expect_context();
     let town_context: TownContext = expect_context();
     let toaster: Toasts = expect_context();
+    let confirm_context: ConfirmContext = expect_context();
 
     let user_gems = move || town_context.character.read().resource_gems;
 
-    let wrong_item = move || {
-        selected_item.with(|selected_item| match selected_item {
-            SelectedItem::InMarket(item) => item.item_specs.base.upgrade_levels.is_empty(),
-            _ => true,
-        })
-    };
+    let do_affix_operation = use_affix_operation(selected_item);
 
-    let do_upgrade_item = {
+    let chosen_affix = RwSignal::new(None);
+    let chosen_family = RwSignal::new(None);
+    let families = RwSignal::new(Vec::<ForgeAffixFamily>::new());
+
+    Effect::new({
         let character_id = town_context.character.read_untracked().character_id;
         move || {
-            if let SelectedItem::InMarket(item) = selected_item.get() {
-                spawn_local({
-                    async move {
+            chosen_family.set(None);
+            match selected_item.get() {
+                SelectedItem::InMarket(item)
+                    if item.item_specs.base.rarity != ItemRarity::Unique =>
+                {
+                    spawn_local(async move {
                         match backend
-                            .forge_upgrade(&ForgeUpgradeRequest {
+                            .forge_families(&ForgeAffixFamiliesRequest {
                                 character_id,
                                 item_index: item.index as u32,
                             })
                             .await
                         {
                             Ok(response) => {
-                                let updated_item_specs = if item.index < 9 {
-                                    response
-                                        .inventory
-                                        .equipped
-                                        .get(&item.index.try_into().unwrap())
-                                        .cloned()
-                                        .and_then(|equipped_item| match equipped_item {
-                                            EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
-                                            _ => None,
-                                        })
-                                } else {
-                                    response
-                                        .inventory
-                                        .bag
-                                        .get(item.index.saturating_sub(9))
-                                        .cloned()
-                                };
-
-                                if let Some(updated_item_specs) = updated_item_specs {
-                                    selected_item.try_set(SelectedItem::InMarket(
-                                        SelectedMarketItem {
-                                            item_specs: Arc::new(updated_item_specs),
-                                            ..item
-                                        },
-                                    ));
-                                }
-
-                                town_context.inventory.set(response.inventory);
-                                town_context.character.write().resource_gems =
-                                    response.resource_gems;
+                                families.try_set(response.families);
                             }
                             Err(e) => show_toast(
                                 toaster,
-                                format!("Failed to forge item: {e}"),
+                                format!("Failed to get affix families: {e}"),
                                 ToastVariant::Error,
                             ),
                         }
-                    }
-                });
+                    });
+                }
+                _ => families.set(Vec::new()),
             }
         }
-    };
+    });
 
-    let is_equipped = move || {
+    let item_level = move || {
         selected_item.with(|selected_item| match selected_item {
-            SelectedItem::InMarket(selected_item) => selected_item.recipient.is_some(),
-            _ => false,
+            SelectedItem::InMarket(item) => item.item_specs.modifiers.level,
+            _ => 0,
         })
     };
 
-    let upgrade_price = move || {
+    let try_affix_operation = move |operation: ForgeAffixOperation| {
+        let do_operation = Arc::new(move || do_affix_operation(operation.clone()));
+        if town_context.character.read_untracked().max_area_level < item_level() {
+            (confirm_context
+                    .confirm)(
+                    "Your Character Power Level is lower than this item's level. Forging an Affix may make it unusable for your character. Continue?"
+                        .to_string(),
+                    do_operation,
+                );
+        } else {
+            do_operation();
+        }
+    };
+
+    let operation_price = move |operation: &ForgeAffixOperation| {
         selected_item.with(|selected_item| match selected_item {
-            SelectedItem::InMarket(item) => computations::upgrade_item_price(&item.item_specs),
+            SelectedItem::InMarket(item) if item.item_specs.base.rarity != ItemRarity::Unique => {
+                forge::affix_operation_price(
+                    operation,
+                    &item.item_specs.modifiers,
+                    &item.item_specs.base,
+                )
+            }
             _ => None,
         })
     };
 
+    let reroll_price = move || operation_price(&ForgeAffixOperation::Reroll { locked_affix: None });
+
+    let lock_reroll_price = move || {
+        chosen_affix.get().and_then(|affix_index| {
+            operation_price(&ForgeAffixOperation::Reroll {
+                locked_affix: Some(affix_index),
+            })
+        })
+    };
+
+    let upgrade_tier_price = move || {
+        chosen_affix
+            .get()
+            .and_then(|affix_index| operation_price(&ForgeAffixOperation::UpgradeTier(affix_index)))
+    };
+
+    let family_price = move || {
+        chosen_family
+            .get()
+            .and_then(|family| operation_price(&ForgeAffixOperation::AddFamily(family)))
+    };
+
+    let affix_options = move || -> IndexMap<Option<usize>, String> {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) => item
+                .item_specs
+                .modifiers
+                .affixes
+                .iter()
+                .enumerate()
+                .filter(|(affix_index, _)| {
+                    forge::is_forgeable_affix(&item.item_specs.modifiers, *affix_index)
+                })
+                .map(|(affix_index, affix)| {
+                    (
+                        Some(affix_index),
+                        format!("{} (Tier {})", affix.name, affix.tier),
+                    )
+                })
+                .collect(),
+            _ => Default::default(),
+        })
+    };
+
+    let family_options = move || -> IndexMap<Option<String>, String> {
+        families.with(|families| {
+            families
+                .iter()
+                .map(|family| {
+                    (
+                        Some(family.family.clone()),
+                        format!(
+                            "{} ({})",
+                            family.name,
+                            match family.affix_type {
+                                AffixType::Prefix => "Prefix",
+                                _ => "Suffix",
+                            }
+                        ),
+                    )
+                })
+                .collect()
+        })
+    };
+
+    let is_equipped = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(selected_item) => selected_item.recipient.is_some(),
+            _ => false,
+        })
+    };
+
     view! {
         <div class="w-full h-full flex flex-col justify-between relative">
-            <CardInsetTitle>"Empower Unique Item"</CardInsetTitle>
+            <CardInsetTitle>"Refine Item"</CardInsetTitle>
 
             <div class="flex flex-col">
                 <span class="text-pink-400 font-bold text-sm xl:text-base">
@@ -627,42 +684,832 @@ pub fn UpgradeUniqueDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoV
 
             <div class="flex flex-col gap-1 xl:gap-2">
                 <MenuButton
-                    on:click=move |_| do_upgrade_item()
+                    on:click=move |_| do_affix_operation(ForgeAffixOperation::Reroll {
+                        locked_affix: None,
+                    })
                     disabled=Signal::derive({
-                        move || upgrade_price().map(|price| price > user_gems()).unwrap_or(true)
+                        move || reroll_price().map(|price| price > user_gems()).unwrap_or(true)
                     })
                     class:mb-1
                     class:xl:mb-2
                 >
                     <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        "Reroll all" <span class="text-white font-bold">"Values"</span>
                         {move || {
-                            if wrong_item() {
-                                view! { "Item cannot be empowered" }.into_any()
-                            } else if selected_item.read().is_empty() {
-                                view! { "Select a Unique item to empower" }.into_any()
-                            } else {
-                                let upgrade_price = upgrade_price();
-                                match upgrade_price {
-                                    Some(upgrade_price) => {
-                                        view! {
-                                            "Empower for "
-                                            <span class="text-fuchsia-300 font-bold">
-                                                {upgrade_price}
-                                            </span>
-                                            <GemsIcon />
-                                        }
-                                            .into_any()
+                            reroll_price()
+                                .map(|price| {
+                                    view! {
+                                        "for "
+                                        <span class="text-fuchsia-300 font-bold">{price}</span>
+                                        <GemsIcon />
                                     }
-                                    None => {
-                                        view! { "Item has reached its maximum potential" }
-                                            .into_any()
+                                })
+                        }}
+                    </div>
+                </MenuButton>
+
+                <div class="w-full flex items-center gap-2 text-zinc-400 text-sm">
+                    <span>"Affix:"</span>
+                    {move || {
+                        view! {
+                            <DropdownMenu
+                                options=affix_options()
+                                chosen_option=chosen_affix
+                                missing_text="Select an affix"
+                            />
+                        }
+                    }}
+                </div>
+                <MenuButton
+                    on:click=move |_| {
+                        if let Some(affix_index) = chosen_affix.get() {
+                            do_affix_operation(ForgeAffixOperation::Reroll {
+                                locked_affix: Some(affix_index),
+                            })
+                        }
+                    }
+                    disabled=Signal::derive({
+                        move || lock_reroll_price().map(|price| price > user_gems()).unwrap_or(true)
+                    })
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        "Lock and reroll" <span class="text-white font-bold">"Others"</span>
+                        {move || {
+                            lock_reroll_price()
+                                .map(|price| {
+                                    view! {
+                                        "for "
+                                        <span class="text-fuchsia-300 font-bold">{price}</span>
+                                        <GemsIcon />
                                     }
-                                }
+                                })
+                        }}
+                    </div>
+                </MenuButton>
+                <MenuButton
+                    on:click={
+                        let try_affix_operation = try_affix_operation.clone();
+                        move |_| {
+                            if let Some(affix_index) = chosen_affix.get() {
+                                try_affix_operation(ForgeAffixOperation::UpgradeTier(affix_index))
                             }
+                        }
+                    }
+                    disabled=Signal::derive({
+                        move || {
+                            upgrade_tier_price().map(|price| price > user_gems()).unwrap_or(true)
+                        }
+                    })
+                    class:mb-1
+                    class:xl:mb-2
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        "Upgrade" <span class="text-white font-bold">"Tier"</span>
+                        {move || {
+                            upgrade_tier_price()
+                                .map(|price| {
+                                    view! {
+                                        "for "
+                                        <span class="text-fuchsia-300 font-bold">{price}</span>
+                                        <GemsIcon />
+                                    }
+                                })
                         }}
                     </div>
                 </MenuButton>
 
+                <div class="w-full flex items-center gap-2 text-zinc-400 text-sm">
+                    <span>"Family:"</span>
+                    {move || {
+                        view! {
+                            <SearchableDropdownMenu
+                                options=family_options()
+                                chosen_option=chosen_family
+                                missing_text="Select a family"
+                            />
+                        }
+                    }}
+                </div>
+                <MenuButton
+                    on:click=move |_| {
+                        if let Some(family) = chosen_family.get() {
+                            try_affix_operation(ForgeAffixOperation::AddFamily(family))
+                        }
+                    }
+                    disabled=Signal::derive({
+                        move || family_price().map(|price| price > user_gems()).unwrap_or(true)
+                    })
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        "Add chosen" <span class="text-white font-bold">"Family"</span>
+                        {move || {
+                            family_price()
+                                .map(|price| {
+                                    view! {
+                                        "for "
+                                        <span class="text-fuchsia-300 font-bold">{price}</span>
+                                        <GemsIcon />
+                                    }
+                                })
+                        }}
+                    </div>
+                </MenuButton>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn CorruptItemDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
+    let backend: BackendClient = expect_context();
+    let town_context: TownContext = expect_context();
+    let toaster: Toasts = expect_context();
+    let confirm_context: ConfirmContext = expect_context();
+
+    let user_gems = move || town_context.character.read().resource_gems;
+
+    let do_corrupt_item = {
+        let character_id = town_context.character.read_untracked().character_id;
+        move || {
+            if let SelectedItem::InMarket(item) = selected_item.get() {
+                spawn_local({
+                    async move {
+                        match backend
+                            .forge_corrupt(&ForgeCorruptRequest {
+                                character_id,
+                                item_index: item.index as u32,
+                            })
+                            .await
+                        {
+                            Ok(response) => {
+                                let updated_item_specs = if item.index < 9 {
+                                    response
+                                        .inventory
+                                        .equipped
+                                        .get(&item.index.try_into().unwrap())
+                                        .cloned()
+                                        .and_then(|equipped_item| match equipped_item {
+                                            EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
+                                            _ => None,
+                                        })
+                                } else {
+                                    response
+                                        .inventory
+                                        .bag
+                                        .get(item.index.saturating_sub(9))
+                                        .cloned()
+                                };
+
+                                if let Some(updated_item_specs) = updated_item_specs {
+                                    selected_item.try_set(SelectedItem::InMarket(
+                                        SelectedMarketItem {
+                                            item_specs: Arc::new(updated_item_specs),
+                                            ..item
+                                        },
+                                    ));
+                                }
+
+                                town_context.inventory.set(response.inventory);
+                                town_context.character.write().resource_gems =
+                                    response.resource_gems;
+
+                                show_toast(
+                                    toaster,
+                                    corruption_outcome_str(response.outcome),
+                                    match response.outcome {
+                                        CorruptionOutcome::Brick => ToastVariant::Warning,
+                                        _ => ToastVariant::Success,
+                                    },
+                                );
+                            }
+                            Err(e) => show_toast(
+                                toaster,
+                                format!("Failed to corrupt item: {e}"),
+                                ToastVariant::Error,
+                            ),
+                        }
+                    }
+                });
+            }
+        }
+    };
+
+    let try_corrupt_item = move || {
+        (confirm_context.confirm)(
+            "Corruption is unpredictable and may ruin your item. A corrupted item cannot be forged anymore. Continue?"
+                .to_string(),
+            Arc::new(do_corrupt_item),
+        );
+    };
+
+    let is_equipped = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(selected_item) => selected_item.recipient.is_some(),
+            _ => false,
+        })
+    };
+
+    let corrupt_price = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) if !item.item_specs.modifiers.corrupted => {
+                Some(forge::corrupt_price(item.item_specs.modifiers.level))
+            }
+            _ => None,
+        })
+    };
+
+    view! {
+        <div class="w-full h-full flex flex-col justify-between relative">
+            <CardInsetTitle>"Corrupt Item"</CardInsetTitle>
+
+            <div class="flex flex-col">
+                <span class="text-pink-400 font-bold text-sm xl:text-base">
+                    {move || is_equipped().then_some("Equipped Item")}
+                </span>
+                <ItemDetails selected_item show_affixes=true />
+            </div>
+
+            <div class="flex flex-col gap-1 xl:gap-2">
+                <MenuButtonRed
+                    on:click=move |_| try_corrupt_item()
+                    disabled=Signal::derive({
+                        move || corrupt_price().map(|price| price > user_gems()).unwrap_or(true)
+                    })
+                    class:mb-1
+                    class:xl:mb-2
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        {move || {
+                            if selected_item.read().is_empty() {
+                                view! { "Select an item to corrupt" }.into_any()
+                            } else {
+                                match corrupt_price() {
+                                    Some(price) => {
+                                        view! {
+                                            "Corrupt for "
+                                            <span class="text-fuchsia-300 font-bold">{price}</span>
+                                            <GemsIcon />
+                                        }
+                                            .into_any()
+                                    }
+                                    None => view! { "Item is already corrupted" }.into_any(),
+                                }
+                            }
+                        }}
+                    </div>
+                </MenuButtonRed>
+            </div>
+        </div>
+    }
+}
+
+fn corruption_outcome_str(outcome: CorruptionOutcome) -> &'static str {
+    match outcome {
+        CorruptionOutcome::ImplicitAffix => "The corruption granted a new affix!",
+        CorruptionOutcome::UpgradeAffixTier => "The corruption empowered an affix!",
+        CorruptionOutcome::UniqueReroll => "The corruption transformed the item into a Unique!",
+        CorruptionOutcome::Brick => "The corruption ruined the item...",
+    }
+}
+
+fn use_affix_operation(
+    selected_item: RwSignal<SelectedItem>,
+) -> impl Fn(ForgeAffixOperation) + Copy + Send + Sync + 'static {
+    let backend: BackendClient = expect_context();
+    let town_context: TownContext = expect_context();
+    let toaster: Toasts = expect_context();
+
+    let character_id = town_context.character.read_untracked().character_id;
+    move |operation| {
+        if let SelectedItem::InMarket(item) = selected_item.get() {
+            spawn_local({
+                async move {
+                    match backend
+                        .forge_affix(&ForgeAffixRequest {
+                            character_id,
+                            item_index: item.index as u32,
+                            operation,
+                        })
+                        .await
+                    {
+                        Ok(response) => {
+                            let updated_item_specs = if item.index < 9 {
+                                response
+                                    .inventory
+                                    .equipped
+                                    .get(&item.index.try_into().unwrap())
+                                    .cloned()
+                                    .and_then(|equipped_item| match equipped_item {
+                                        EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
+                                        _ => None,
+                                    })
+                            } else {
+                                response
+                                    .inventory
+                                    .bag
+                                    .get(item.index.saturating_sub(9))
+                                    .cloned()
+                            };
+
+                            if let Some(updated_item_specs) = updated_item_specs {
+                                selected_item.try_set(SelectedItem::InMarket(SelectedMarketItem {
+                                    item_specs: Arc::new(updated_item_specs),
+                                    ..item
+                                }));
+                            }
+
+                            town_context.inventory.set(response.inventory);
+                            town_context.character.write().resource_gems = response.resource_gems;
+                        }
+                        Err(e) => show_toast(
+                            toaster,
+                            format!("Failed to forge item: {e}"),
+                            ToastVariant::Error,
+                        ),
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[component]
+pub fn UpgradeUniqueDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
+    let backend: BackendClient = expect_context();
+    let town_context: TownContext = expect_context();
+    let toaster: Toasts = expect_context();
+
+    let user_gems = move || town_context.character.read().resource_gems;
+
+    let wrong_item = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) => {
+                item.item_specs.base.upgrade_levels.is_empty()
+                    || item.item_specs.modifiers.corrupted
+            }
+            _ => true,
+        })
+    };
+
+    let do_upgrade_item = {
+        let character_id = town_context.character.read_untracked().character_id;
+        move || {
+            if let SelectedItem::InMarket(item) = selected_item.get() {
+                spawn_local({
+                    async move {
+                        match backend
+                            .forge_upgrade(&ForgeUpgradeRequest {
+                                character_id,
+                                item_index: item.index as u32,
+                            })
+                            .await
+                        {
+                            Ok(response) => {
+                                let updated_item_specs = if item.index < 9 {
+                                    response
+                                        .inventory
+                                        .equipped
+                                        .get(&item.index.try_into().unwrap())
+                                        .cloned()
+                                        .and_then(|equipped_item| match equipped_item {
+                                            EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
+                                            _ => None,
+                                        })
+                                } else {
+                                    response
+                                        .inventory
+                                        .bag
+                                        .get(item.index.saturating_sub(9))
+                                        .cloned()
+                                };
+
+                                if let Some(updated_item_specs) = updated_item_specs {
+                                    selected_item.try_set(SelectedItem::InMarket(
+                                        SelectedMarketItem {
+                                            item_specs: Arc::new(updated_item_specs),
+                                            ..item
+                                        },
+                                    ));
+                                }
+
+                                town_context.inventory.set(response.inventory);
+                                town_context.character.write().resource_gems =
+                                    response.resource_gems;
+                            }
+                            Err(e) => show_toast(
+                                toaster,
+                                format!("Failed to forge item: {e}"),
+                                ToastVariant::Error,
+                            ),
+                        }
+                    }
+                });
+            }
+        }
+    };
+
+    let do_divine_item = {
+        let character_id = town_context.character.read_untracked().character_id;
+        move || {
+            if let SelectedItem::InMarket(item) = selected_item.get() {
+                spawn_local({
+                    async move {
+                        match backend
+                            .forge_divine(&ForgeDivineRequest {
+                                character_id,
+                                item_index: item.index as u32,
+                            })
+                            .await
+                        {
+                            Ok(response) => {
+                                let updated_item_specs = if item.index < 9 {
+                                    response
+                                        .inventory
+                                        .equipped
+                                        .get(&item.index.try_into().unwrap())
+                                        .cloned()
+                                        .and_then(|equipped_item| match equipped_item {
+                                            EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
+                                            _ => None,
+                                        })
+                                } else {
+                                    response
+                                        .inventory
+                                        .bag
+                                        .get(item.index.saturating_sub(9))
+                                        .cloned()
+                                };
+
+                                if let Some(updated_item_specs) = updated_item_specs {
+                                    selected_item.try_set(SelectedItem::InMarket(
+                                        SelectedMarketItem {
+                                            item_specs: Arc::new(updated_item_specs),
+                                            ..item
+                                        },
+                                    ));
+                                }
+
+                                town_context.inventory.set(response.inventory);
+                                town_context.character.write().resource_gems =
+                                    response.resource_gems;
+                            }
+                            Err(e) => show_toast(
+                                toaster,
+                                format!("Failed to divine item: {e}"),
+                                ToastVariant::Error,
+                            ),
+                        }
+                    }
+                });
+            }
+        }
+    };
+
+    let divine_price = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item)
+                if item.item_specs.modifiers.rarity == ItemRarity::Unique
+                    && !item.item_specs.modifiers.corrupted =>
+            {
+                Some(forge::divine_price(item.item_specs.modifiers.level))
+            }
+            _ => None,
+        })
+    };
+
+    let is_equipped = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(selected_item) => selected_item.recipient.is_some(),
+            _ => false,
+        })
+    };
+
+    let upgrade_price = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) => computations::upgrade_item_price(&item.item_specs),
+            _ => None,
+        })
+    };
+
+    view! {
+        <div class="w-full h-full flex flex-col justify-between relative">
+            <CardInsetTitle>"Empower Unique Item"</CardInsetTitle>
+
+            <div class="flex flex-col">
+                <span class="text-pink-400 font-bold text-sm xl:text-base">
+                    {move || is_equipped().then_some("Equipped Item")}
+                </span>
+                <ItemDetails selected_item show_affixes=true />
+            </div>
+
+            <div class="flex flex-col gap-1 xl:gap-2">
+                <MenuButton
+                    on:click=move |_| do_upgrade_item()
+                    disabled=Signal::derive({
+                        move || upgrade_price().map(|price| price > user_gems()).unwrap_or(true)
+                    })
+                    class:mb-1
+                    class:xl:mb-2
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        {move || {
+                            if wrong_item() {
+                                view! { "Item cannot be empowered" }.into_any()
+                            } else if selected_item.read().is_empty() {
+                                view! { "Select a Unique item to empower" }.into_any()
+                            } else {
+                                let upgrade_price = upgrade_price();
+                                match upgrade_price {
+                                    Some(upgrade_price) => {
+                                        view! {
+                                            "Empower for "
+                                            <span class="text-fuchsia-300 font-bold">
+                                                {upgrade_price}
+                                            </span>
+                                            <GemsIcon />
+                                        }
+                                            .into_any()
+                                    }
+                                    None => {
+                                        view! { "Item has reached its maximum potential" }
+                                            .into_any()
+                                    }
+                                }
+                            }
+                        }}
+                    </div>
+                </MenuButton>
+                <MenuButton
+                    on:click=move |_| do_divine_item()
+                    disabled=Signal::derive({
+                        move || divine_price().map(|price| price > user_gems()).unwrap_or(true)
+                    })
+                    class:mb-1
+                    class:xl:mb-2
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        {move || match divine_price() {
+                            Some(divine_price) => {
+                                view! {
+                                    "Divine" <span class="text-white font-bold">"Unique Affixes"</span>
+                                    "for "
+                                    <span class="text-fuchsia-300 font-bold">{divine_price}</span>
+                                    <GemsIcon />
+                                }
+                                    .into_any()
+                            }
+                            None => view! { "Item cannot be divined" }.into_any(),
+                        }}
+                    </div>
+                </MenuButton>
+
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn SocketsDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
+    let backend: BackendClient = expect_context();
+    let town_context: TownContext = expect_context();
+    let toaster: Toasts = expect_context();
+    let confirm_context: ConfirmContext = expect_context();
+
+    let user_gems = move || town_context.character.read().resource_gems;
+    let chosen_rune = RwSignal::new(None);
+
+    let rune_options = move || -> IndexMap<Option<u8>, String> {
+        town_context.inventory.with(|inventory| {
+            inventory
+                .bag
+                .iter()
+                .enumerate()
+                .filter(|(_, item_specs)| {
+                    item_specs
+                        .base
+                        .rune_specs
+                        .as_ref()
+                        .map(|rune_specs| !rune_specs.root_node)
+                        .unwrap_or_default()
+                })
+                .map(|(index, item_specs)| (Some(index as u8), item_specs.modifiers.name.clone()))
+                .collect()
+        })
+    };
+
+    let free_sockets = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) if !item.item_specs.modifiers.corrupted => {
+                (item.item_specs.base.sockets as usize)
+                    .saturating_sub(item.item_specs.modifiers.socketed_runes.len())
+            }
+            _ => 0,
+        })
+    };
+
+    let update_selected_item =
+        move |item: SelectedMarketItem, index: usize, inventory: &PlayerInventory| {
+            let updated_item_specs = if index < 9 {
+                inventory
+                    .equipped
+                    .get(&index.try_into().unwrap())
+                    .cloned()
+                    .and_then(|equipped_item| match equipped_item {
+                        EquippedSlot::MainSlot(item_specs) => Some(*item_specs),
+                        _ => None,
+                    })
+            } else {
+                inventory.bag.get(index.saturating_sub(9)).cloned()
+            };
+
+            if let Some(updated_item_specs) = updated_item_specs {
+                selected_item.try_set(SelectedItem::InMarket(SelectedMarketItem {
+                    index,
+                    item_specs: Arc::new(updated_item_specs),
+                    ..item
+                }));
+            }
+        };
+
+    let do_socket_rune = {
+        let character_id = town_context.character.read_untracked().character_id;
+        move || {
+            if let (SelectedItem::InMarket(item), Some(rune_index)) =
+                (selected_item.get(), chosen_rune.get())
+            {
+                spawn_local({
+                    async move {
+                        match backend
+                            .forge_socket_rune(&ForgeSocketRuneRequest {
+                                character_id,
+                                item_index: item.index as u32,
+                                rune_index,
+                            })
+                            .await
+                        {
+                            Ok(response) => {
+                                // The rune was removed from the bag, shifting the following items
+                                let index = if item.index > rune_index as usize + 9 {
+                                    item.index - 1
+                                } else {
+                                    item.index
+                                };
+                                update_selected_item(item, index, &response.inventory);
+                                chosen_rune.set(None);
+                                town_context.inventory.set(response.inventory);
+                            }
+                            Err(e) => show_toast(
+                                toaster,
+                                format!("Failed to socket rune: {e}"),
+                                ToastVariant::Error,
+                            ),
+                        }
+                    }
+                });
+            }
+        }
+    };
+
+    let do_unsocket_rune = {
+        let character_id = town_context.character.read_untracked().character_id;
+        move |socket_index: u8| {
+            if let SelectedItem::InMarket(item) = selected_item.get() {
+                spawn_local({
+                    async move {
+                        match backend
+                            .forge_unsocket_rune(&ForgeUnsocketRuneRequest {
+                                character_id,
+                                item_index: item.index as u32,
+                                socket_index,
+                            })
+                            .await
+                        {
+                            Ok(response) => {
+                                update_selected_item(item.clone(), item.index, &response.inventory);
+                                town_context.inventory.set(response.inventory);
+                                town_context.character.write().resource_gems =
+                                    response.resource_gems;
+
+                                if response.rune_destroyed {
+                                    show_toast(
+                                        toaster,
+                                        "The rune shattered during removal...",
+                                        ToastVariant::Warning,
+                                    );
+                                } else {
+                                    show_toast(
+                                        toaster,
+                                        "The rune was moved to your bag.",
+                                        ToastVariant::Success,
+                                    );
+                                }
+                            }
+                            Err(e) => show_toast(
+                                toaster,
+                                format!("Failed to remove rune: {e}"),
+                                ToastVariant::Error,
+                            ),
+                        }
+                    }
+                });
+            }
+        }
+    };
+
+    let try_unsocket_rune = move |socket_index: u8| {
+        (confirm_context.confirm)(
+            format!(
+                "Removing a rune may destroy it ({:.0}% chance). Continue?",
+                forge::UNSOCKET_RUNE_DESTROY_CHANCE * 100.0
+            ),
+            Arc::new(move || do_unsocket_rune(socket_index)),
+        );
+    };
+
+    let socketed_runes = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(item) if !item.item_specs.modifiers.corrupted => item
+                .item_specs
+                .modifiers
+                .socketed_runes
+                .iter()
+                .enumerate()
+                .map(|(socket_index, rune)| {
+                    let price = forge::unsocket_rune_price(rune.level);
+                    let rune_name = rune.name.clone();
+                    let socket_index = socket_index as u8;
+                    let try_unsocket_rune = try_unsocket_rune.clone();
+                    view! {
+                        <MenuButtonRed
+                            on:click=move |_| try_unsocket_rune(socket_index)
+                            disabled=Signal::derive(move || price > user_gems())
+                        >
+                            <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                                "Remove" <span class="text-white font-bold">{rune_name}</span>
+                                "for " <span class="text-fuchsia-300 font-bold">{price}</span>
+                                <GemsIcon />
+                            </div>
+                        </MenuButtonRed>
+                    }
+                })
+                .collect_view()
+                .into_any(),
+            _ => ().into_any(),
+        })
+    };
+
+    let is_equipped = move || {
+        selected_item.with(|selected_item| match selected_item {
+            SelectedItem::InMarket(selected_item) => selected_item.recipient.is_some(),
+            _ => false,
+        })
+    };
+
+    view! {
+        <div class="w-full h-full flex flex-col justify-between relative">
+            <CardInsetTitle>"Socket Runes"</CardInsetTitle>
+
+            <div class="flex flex-col">
+                <span class="text-pink-400 font-bold text-sm xl:text-base">
+                    {move || is_equipped().then_some("Equipped Item")}
+                </span>
+                <ItemDetails selected_item show_affixes=true />
+            </div>
+
+            <div class="flex flex-col gap-1 xl:gap-2">
+                {socketed_runes}
+                <div class="w-full flex items-center gap-2 text-zinc-400 text-sm">
+                    <span>"Rune:"</span>
+                    {move || {
+                        view! {
+                            <DropdownMenu
+                                options=rune_options()
+                                chosen_option=chosen_rune
+                                missing_text="Select a rune"
+                            />
+                        }
+                    }}
+                </div>
+                <MenuButton
+                    on:click=move |_| do_socket_rune()
+                    disabled=Signal::derive(move || {
+                        free_sockets() == 0 || chosen_rune.read().is_none()
+                    })
+                    class:mb-1
+                    class:xl:mb-2
+                >
+                    <div class="w-full flex justify-center items-center gap-1 text-zinc-400 h-[2em]">
+                        {move || {
+                            if selected_item.read().is_empty() {
+                                "Select an item with sockets"
+                            } else if free_sockets() == 0 {
+                                "No free socket"
+                            } else {
+                                "Socket chosen Rune"
+                            }
+                        }}
+                    </div>
+                </MenuButton>
             </div>
         </div>
     }
@@ -30,6 +30,7 @@ use crate::components::{
         inventory::{InventoryEquipFilter, loot_filter_category_to_str},
         resources::{GemsCounter, GemsIcon},
         tooltips::effects_tooltip::{format_flat_stat, format_multiplier_stat_name},
+        totp::{TotpContext, with_totp},
     },
     town::{
         TownContext,
@@ -489,6 +490,7 @@ pub fn BuyDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
     let town_context = expect_context::<TownContext>();
     let chat_context: ChatContext = expect_context();
     let toaster = expect_context::<Toasts>();
+    let totp_context = expect_context::<TotpContext>();
 
     let own_item = move || {
         selected_item.with(|selected_item| match selected_item {
@@ -553,14 +555,20 @@ pub fn BuyDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
         let character_id = town_context.character.read_untracked().character_id;
         move |_| {
             if let SelectedItem::InMarket(item) = selected_item.get() {
+                let item_index = item.index as u32;
                 spawn_local({
                     async move {
-                        match backend
-                            .buy_market_item(&BuyMarketItemRequest {
-                                character_id,
-                                item_index: item.index as u32,
-                            })
-                            .await
+                        // Large purchases require a two-factor code when enabled
+                        match with_totp(totp_context, |totp_code| async move {
+                            backend
+                                .buy_market_item(&BuyMarketItemRequest {
+                                    character_id,
+                                    item_index,
+                                    totp_code,
+                                })
+                                .await
+                        })
+                        .await
                         {
                             Ok(response) => {
                                 town_context.inventory.set(response.inventory);
@@ -878,6 +886,7 @@ pub fn ListingDetails(selected_item: RwSignal<SelectedItem>) -> impl IntoView {
                             .buy_market_item(&BuyMarketItemRequest {
                                 character_id,
                                 item_index: item.index as u32,
+                                totp_code: None,
                             })
                             .await
                         {
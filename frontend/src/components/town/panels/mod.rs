@@ -1,3 +1,4 @@
+pub mod atlas;
 pub mod forge;
 pub mod inventory;
 pub mod market;
@@ -3,6 +3,7 @@ use leptos::prelude::*;
 use std::collections::HashMap;
 
 use shared::data::{
+    atlas::{AtlasSpecs, AtlasState},
     passive::{PassivesTreeAscension, PassivesTreeSpecs, PurchasedNodes},
     player::PlayerInventory,
     skill::SkillSpecs,
@@ -31,6 +32,9 @@ pub struct TownContext {
     pub benedictions_specs: RwSignal<IndexMap<String, BenedictionsCategory>>,
     pub player_benedictions: RwSignal<PlayerBenedictions>,
 
+    pub atlas_specs: RwSignal<AtlasSpecs>,
+    pub atlas: RwSignal<AtlasState>,
+
     pub player_skill_masteries: RwSignal<PlayerSkillMasteries>,
     pub skill_mastery_skill_specs: RwSignal<HashMap<String, SkillSpecs>>,
     pub selected_skill_mastery: RwSignal<Option<String>>,
@@ -47,6 +51,7 @@ pub struct TownContext {
     pub open_market: RwSignal<bool>,
     pub open_forge: RwSignal<bool>,
     pub open_temple: RwSignal<bool>,
+    pub open_atlas: RwSignal<bool>,
     pub open_skill_masteries: RwSignal<bool>,
     pub open_skill_mastery_details: RwSignal<bool>,
     pub open_settings: RwSignal<bool>,
@@ -75,6 +80,8 @@ impl Default for TownContext {
             passives_tree_build: Default::default(),
             benedictions_specs: Default::default(),
             player_benedictions: Default::default(),
+            atlas_specs: Default::default(),
+            atlas: Default::default(),
             player_skill_masteries: Default::default(),
             skill_mastery_skill_specs: Default::default(),
             selected_skill_mastery: Default::default(),
@@ -87,6 +94,7 @@ impl Default for TownContext {
             open_market: Default::default(),
             open_forge: Default::default(),
             open_temple: Default::default(),
+            open_atlas: Default::default(),
             open_skill_masteries: Default::default(),
             open_skill_mastery_details: Default::default(),
             open_settings: Default::default(),
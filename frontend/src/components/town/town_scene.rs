@@ -6,7 +6,7 @@ use leptos_router::hooks::use_navigate;
 use leptos_use::storage;
 
 use shared::data::{
-    area::StartAreaConfig,
+    area::{BossDrop, BossDropType, StartAreaConfig},
     item::{ItemCategory, ItemSpecs},
     user::UserGrindArea,
 };
@@ -504,6 +504,20 @@ pub fn StartGrindPanel(
         })
     };
 
+    let bosses_drops = move || {
+        selected_area
+            .read()
+            .as_ref()
+            .and_then(|selected_area| {
+                data_context
+                    .areas_bosses_drops
+                    .read()
+                    .get(&selected_area.area_id)
+                    .cloned()
+            })
+            .unwrap_or_default()
+    };
+
     let max_item_level = Signal::derive(move || town_context.character.read().max_area_level);
 
     let selected_map = Signal::derive(move || {
@@ -617,6 +631,36 @@ pub fn StartGrindPanel(
                                             view! { <li>"No Power Shards in this Grind"</li> }
                                                 .into_any()
                                         }}
+                                        {bosses_drops()
+                                            .into_iter()
+                                            .filter(|boss_drops| !boss_drops.drops.is_empty())
+                                            .map(|boss_drops| {
+                                                view! {
+                                                    <li>
+                                                        {match boss_drops.interval {
+                                                            Some(interval) => {
+                                                                format!(
+                                                                    "Boss Drops (Level {}, every {}): ",
+                                                                    boss_drops.level,
+                                                                    interval,
+                                                                )
+                                                            }
+                                                            None => {
+                                                                format!("Boss Drops (Level {}): ", boss_drops.level)
+                                                            }
+                                                        }}
+                                                        <span class="font-semibold text-white">
+                                                            {boss_drops
+                                                                .drops
+                                                                .iter()
+                                                                .map(format_boss_drop)
+                                                                .collect::<Vec<_>>()
+                                                                .join(", ")}
+                                                        </span>
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()}
 
                                     </ul>
 
@@ -670,6 +714,7 @@ pub fn StartGrindPanel(
                                                                         map_item_index: town_context
                                                                             .selected_item_index
                                                                             .get_untracked(),
+                                                                        challenge_id: None,
                                                                     }),
                                                                 );
                                                             navigate("/game", Default::default());
@@ -690,3 +735,12 @@ pub fn StartGrindPanel(
         </MenuPanel>
     }
 }
+
+fn format_boss_drop(boss_drop: &BossDrop) -> String {
+    match boss_drop.drop_type {
+        BossDropType::LootTable => boss_drop.name.clone(),
+        BossDropType::Guaranteed => format!("{} (guaranteed)", boss_drop.name),
+        BossDropType::Chance(chance) => format!("{} ({chance:.0}%)", boss_drop.name),
+        BossDropType::FirstKill => format!("{} (first kill)", boss_drop.name),
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
 use shared::data::{
     character_status::StatusId,
@@ -278,7 +278,7 @@ pub fn compute_stats_effects_success_chance_value(
 
 fn filter_effects(
     effects_map: &EffectsMap,
-    ignore_stat_effects: &HashSet<StatType>,
+    ignore_stat_effects: &BTreeSet<StatType>,
 ) -> impl Iterator<Item = StatEffect> {
     effects_map.iter().filter(|stat_effect| {
         !ignore_stat_effects
@@ -289,7 +289,7 @@ fn filter_effects(
 
 pub fn compute_stats_effects_status_value(
     effects_map: &EffectsMap,
-    ignore_stat_effects: &HashSet<StatType>,
+    ignore_stat_effects: &BTreeSet<StatType>,
     skill_id: Option<&String>,
     skill_type: Option<SkillType>,
     status_id: &StatusId,
@@ -334,7 +334,7 @@ pub fn compute_stats_effects_status_value(
 
 pub fn compute_stats_effects_status_duration(
     effects_map: &EffectsMap,
-    ignore_stat_effects: &HashSet<StatType>,
+    ignore_stat_effects: &BTreeSet<StatType>,
     skill_id: Option<&String>,
     skill_type: Option<SkillType>,
     status_id: &StatusId,
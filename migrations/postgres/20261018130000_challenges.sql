@@ -0,0 +1,10 @@
+CREATE TABLE
+    challenges_rewards (
+        character_id UUID NOT NULL,
+        challenge_id TEXT NOT NULL,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT now (),
+        --
+        PRIMARY KEY (character_id, challenge_id),
+        FOREIGN KEY (character_id) REFERENCES characters (character_id) ON DELETE CASCADE
+    );
@@ -0,0 +1,32 @@
+CREATE TABLE
+    chat_reports (
+        report_id BIGSERIAL NOT NULL PRIMARY KEY,
+        reporter_id UUID NOT NULL,
+        reported_user_id UUID NOT NULL,
+        reason TEXT NOT NULL,
+        context_messages JSONB NOT NULL,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        resolved_at TIMESTAMPTZ,
+        resolved_by UUID,
+        --
+        FOREIGN KEY (reporter_id) REFERENCES users (user_id) ON DELETE CASCADE,
+        FOREIGN KEY (reported_user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
+
+CREATE INDEX idx_chat_reports_resolved_at ON chat_reports (resolved_at, created_at);
+
+CREATE TABLE
+    chat_moderation_logs (
+        log_id BIGSERIAL NOT NULL PRIMARY KEY,
+        moderator_id UUID NOT NULL,
+        target_user_id UUID,
+        action TEXT NOT NULL,
+        details JSONB NOT NULL,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX idx_chat_moderation_logs_created_at ON chat_moderation_logs (created_at);
+
+CREATE INDEX idx_chat_moderation_logs_target_user_id ON chat_moderation_logs (target_user_id, created_at);
@@ -0,0 +1,16 @@
+CREATE TABLE
+    user_identities (
+        provider TEXT NOT NULL,
+        subject TEXT NOT NULL,
+        --
+        user_id UUID NOT NULL,
+        provider_username TEXT,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        PRIMARY KEY (provider, subject),
+        UNIQUE (user_id, provider),
+        FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
+
+CREATE INDEX idx_user_identities_user_id ON user_identities (user_id);
@@ -0,0 +1,26 @@
+CREATE TABLE
+    user_totp (
+        user_id UUID NOT NULL PRIMARY KEY,
+        secret_crypt BYTEA NOT NULL,
+        -- Pending until the first code is confirmed
+        enabled_at TIMESTAMPTZ,
+        --
+        last_used_step BIGINT NOT NULL DEFAULT 0,
+        failed_attempts BIGINT NOT NULL DEFAULT 0,
+        last_failed_at TIMESTAMPTZ,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    user_recovery_codes (
+        user_id UUID NOT NULL,
+        code_hash BYTEA NOT NULL,
+        --
+        created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        PRIMARY KEY (user_id, code_hash),
+        FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
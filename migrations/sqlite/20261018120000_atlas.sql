@@ -0,0 +1,2 @@
+ALTER TABLE characters_data
+ADD COLUMN atlas_data BLOB;
\ No newline at end of file
@@ -0,0 +1,10 @@
+CREATE TABLE
+    challenges_rewards (
+        character_id TEXT NOT NULL,
+        challenge_id TEXT NOT NULL,
+        --
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        PRIMARY KEY (character_id, challenge_id),
+        FOREIGN KEY (character_id) REFERENCES characters (character_id) ON DELETE CASCADE
+    );
@@ -0,0 +1,32 @@
+CREATE TABLE
+    chat_reports (
+        report_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        reporter_id TEXT NOT NULL,
+        reported_user_id TEXT NOT NULL,
+        reason TEXT NOT NULL,
+        context_messages TEXT NOT NULL,
+        --
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        resolved_at TIMESTAMP,
+        resolved_by TEXT,
+        --
+        FOREIGN KEY (reporter_id) REFERENCES users (user_id) ON DELETE CASCADE,
+        FOREIGN KEY (reported_user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
+
+CREATE INDEX idx_chat_reports_resolved_at ON chat_reports (resolved_at, created_at);
+
+CREATE TABLE
+    chat_moderation_logs (
+        log_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        moderator_id TEXT NOT NULL,
+        target_user_id TEXT,
+        action TEXT NOT NULL,
+        details TEXT NOT NULL,
+        --
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX idx_chat_moderation_logs_created_at ON chat_moderation_logs (created_at);
+
+CREATE INDEX idx_chat_moderation_logs_target_user_id ON chat_moderation_logs (target_user_id, created_at);
@@ -0,0 +1,26 @@
+CREATE TABLE
+    user_totp (
+        user_id TEXT NOT NULL PRIMARY KEY,
+        secret_crypt BLOB NOT NULL,
+        -- Pending until the first code is confirmed
+        enabled_at TIMESTAMP,
+        --
+        last_used_step INTEGER NOT NULL DEFAULT 0,
+        failed_attempts INTEGER NOT NULL DEFAULT 0,
+        last_failed_at TIMESTAMP,
+        --
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    user_recovery_codes (
+        user_id TEXT NOT NULL,
+        code_hash BLOB NOT NULL,
+        --
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        --
+        PRIMARY KEY (user_id, code_hash),
+        FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+    );
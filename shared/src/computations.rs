@@ -148,6 +148,10 @@ pub fn item_gold_price(item_level: AreaLevel, item_rarity: ItemRarity) -> f64 {
 }
 
 pub fn upgrade_item_price(item_specs: &ItemSpecs) -> Option<f64> {
+    if item_specs.modifiers.corrupted {
+        return None;
+    }
+
     item_specs
         .base
         .upgrade_levels
@@ -47,6 +47,8 @@ pub struct AreaSpecs {
     pub gems_find: ModifiableValue<f64>,
     #[serde(default = "default_item_rarity")]
     pub gold_find: ModifiableValue<f64>,
+    #[serde(default = "default_item_rarity")]
+    pub champion_chance: ModifiableValue<f64>,
     #[serde(default)]
     pub max_power_shard_level: AreaLevel,
 
@@ -54,6 +56,8 @@ pub struct AreaSpecs {
     pub effects: EffectsMap,
     #[serde(default)]
     pub triggers: TriggersMap,
+    #[serde(default)]
+    pub player_effects: EffectsMap,
 }
 
 impl AreaSpecs {
@@ -113,4 +117,30 @@ pub struct AreaThreat {
 pub struct StartAreaConfig {
     pub area_id: String,
     pub map_item_index: Option<u8>,
+    // Schedule id of the challenge to start, overriding area and map
+    #[serde(default)]
+    pub challenge_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BossDrops {
+    pub level: AreaLevel,
+    pub interval: Option<AreaLevel>,
+    pub drops: Vec<BossDrop>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BossDrop {
+    pub item_id: String,
+    pub name: String,
+    pub drop_type: BossDropType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum BossDropType {
+    /// Can be rolled from the boss loot table
+    LootTable,
+    Guaranteed,
+    Chance(f32),
+    FirstKill,
 }
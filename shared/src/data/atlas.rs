@@ -0,0 +1,85 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+
+use crate::data::{
+    area::AreaLevel,
+    passive::{PassiveConnection, PassiveNodeId, PassivesTreeSpecs, PurchasedNodes},
+};
+
+pub type AtlasNodeId = PassiveNodeId;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AtlasSpecs {
+    pub nodes: HashMap<AtlasNodeId, AtlasNodeSpecs>,
+    pub connections: Vec<PassiveConnection>,
+
+    // Global map modifiers bought with atlas points
+    #[serde(default)]
+    pub modifiers_tree: PassivesTreeSpecs,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AtlasNodeSpecs {
+    pub name: String,
+    pub area_id: String,
+
+    pub x: f64,
+    pub y: f64,
+
+    #[serde(default)]
+    pub root_node: bool,
+
+    // Minimum area level to reach with a map to complete the node
+    pub completion_level: AreaLevel,
+    #[serde(default = "default_atlas_points")]
+    pub atlas_points: u16,
+}
+
+fn default_atlas_points() -> u16 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AtlasState {
+    pub completed_nodes: HashSet<AtlasNodeId>,
+    pub purchased_modifiers: PurchasedNodes,
+}
+
+impl AtlasSpecs {
+    /// A node is unlocked if it is a root or a neighbour of a completed node
+    pub fn is_node_unlocked(&self, atlas_state: &AtlasState, node_id: &AtlasNodeId) -> bool {
+        self.nodes
+            .get(node_id)
+            .map(|node_specs| node_specs.root_node)
+            .unwrap_or_default()
+            || self.connections.iter().any(|connection| {
+                (connection.from == *node_id
+                    && atlas_state.completed_nodes.contains(&connection.to))
+                    || (connection.to == *node_id
+                        && atlas_state.completed_nodes.contains(&connection.from))
+            })
+    }
+
+    pub fn unlocked_nodes(&self, atlas_state: &AtlasState) -> IndexSet<AtlasNodeId> {
+        self.nodes
+            .keys()
+            .filter(|node_id| self.is_node_unlocked(atlas_state, node_id))
+            .copied()
+            .collect()
+    }
+
+    pub fn total_points(&self, atlas_state: &AtlasState) -> u16 {
+        atlas_state
+            .completed_nodes
+            .iter()
+            .filter_map(|node_id| self.nodes.get(node_id))
+            .map(|node_specs| node_specs.atlas_points)
+            .sum()
+    }
+
+    pub fn available_points(&self, atlas_state: &AtlasState) -> i32 {
+        self.total_points(atlas_state) as i32 - atlas_state.purchased_modifiers.len() as i32
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::area::AreaLevel;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChallengePeriod {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl ChallengePeriod {
+    pub fn duration_secs(&self) -> i64 {
+        match self {
+            ChallengePeriod::Daily => 86_400,
+            ChallengePeriod::Weekly => 7 * 86_400,
+        }
+    }
+
+    // Unix epoch is a Thursday, shift so weeks start on Monday
+    fn offset_secs(&self) -> i64 {
+        match self {
+            ChallengePeriod::Daily => 0,
+            ChallengePeriod::Weekly => 3 * 86_400,
+        }
+    }
+
+    pub fn period_index(&self, now: DateTime<Utc>) -> i64 {
+        (now.timestamp() + self.offset_secs()).div_euclid(self.duration_secs())
+    }
+
+    pub fn period_end(&self, period_index: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(
+            (period_index + 1) * self.duration_secs() - self.offset_secs(),
+            0,
+        )
+        .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChallengeSchedule {
+    pub period: ChallengePeriod,
+    /// Challenges are picked in order, one per period, looping over the list
+    pub rotation: Vec<ChallengeSpecs>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChallengeSpecs {
+    pub name: String,
+    pub area_id: String,
+
+    pub map_item_id: String,
+    pub map_level: AreaLevel,
+    #[serde(default)]
+    pub map_affixes: Vec<ChallengeMapAffix>,
+
+    pub seed: u64,
+
+    // Area level to reach to be able to claim the reward
+    pub completion_level: AreaLevel,
+    #[serde(default)]
+    pub reward: ChallengeReward,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChallengeMapAffix {
+    pub family: String,
+    pub tier: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChallengeReward {
+    #[serde(default)]
+    pub gems: f64,
+    #[serde(default)]
+    pub shards: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ActiveChallenge {
+    pub schedule_id: String,
+    pub period: ChallengePeriod,
+    pub period_index: i64,
+    pub ends_at: DateTime<Utc>,
+
+    pub specs: ChallengeSpecs,
+}
+
+impl ActiveChallenge {
+    /// Unique identifier of the challenge for the period, used as leaderboard area id
+    pub fn challenge_id(&self) -> String {
+        format!("challenge:{}:{}", self.schedule_id, self.period_index)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.specs.seed.wrapping_add(self.period_index as u64)
+    }
+}
@@ -20,6 +20,7 @@ pub use super::skill::{SkillSpecs, SkillState};
 pub enum CharacterId {
     Player,
     Monster(usize),
+    Minion(usize),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
@@ -98,6 +99,13 @@ pub struct CharacterAttrs {
     #[serde(default)]
     pub take_from_life_before_mana: ModifiableValue<Percent>,
 
+    #[serde(default)]
+    pub max_ward: ModifiableValue<NonNegative>,
+    #[serde(default)]
+    pub ward_recharge: ModifiableValue<f64>,
+    #[serde(default)]
+    pub ward_recharge_delay: ModifiableValue<NonNegative>,
+
     #[serde(default)]
     pub armor: HashMap<DamageType, ModifiableValue<f64>>,
 
@@ -126,8 +134,13 @@ pub struct CharacterAttrs {
 pub struct CharacterState {
     pub life: NonNegative,
     pub mana: NonNegative,
+    #[serde(default)]
+    pub ward: NonNegative,
 
     pub statuses: StatusMap,
+    // Remaining duration before statuses can be applied again
+    #[serde(default)]
+    pub status_immunities: HashMap<StatusId, NonNegative>,
     pub skills_states: Vec<SkillState>,
 
     pub is_alive: bool,
@@ -145,6 +158,8 @@ pub struct CharacterState {
     pub repeated_skills: Vec<RepeatedSkillEffect>,
     #[serde(default, skip_serializing, skip_deserializing)]
     pub resurrected: bool,
+    #[serde(default, skip_serializing, skip_deserializing)]
+    pub ward_recharge_elapsed: f64,
 }
 
 // This shouldn't be here
@@ -11,7 +11,10 @@ use crate::data::{
     values::{NonNegative, Percent},
 };
 
-use super::{skill::DamageType, stat_effect::StatType};
+use super::{
+    skill::DamageType,
+    stat_effect::{StatStatusFilter, StatType},
+};
 
 pub type StatusId = IndexedKey<String>;
 
@@ -60,6 +63,29 @@ pub struct StatusSpecs {
     pub escalation: NonNegative,
 
     pub effects: Vec<StatusEffect>,
+
+    /// Applying this status removes matching statuses from the target
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excludes: Vec<StatStatusFilter>,
+    /// Applying this status consumes matching statuses, dealing their remaining damage at once
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consumes: Vec<StatusConsumption>,
+    /// Once expired, the status cannot be applied again for this duration
+    #[serde(default)]
+    pub immunity_duration: NonNegative,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StatusConsumption {
+    #[serde(flatten)]
+    pub status_filter: StatStatusFilter,
+    /// Percent of the remaining damage over time dealt as burst
+    #[serde(default = "default_100_percent")]
+    pub burst_factor: NonNegative,
+}
+
+fn default_100_percent() -> NonNegative {
+    100.0.into()
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
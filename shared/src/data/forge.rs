@@ -1,11 +1,34 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    data::{item::ItemBase, item_affix::AffixType},
+    data::{
+        area::AreaLevel,
+        item::{ItemBase, ItemModifiers},
+        item_affix::AffixType,
+    },
     http::client::ForgeAffixOperation,
 };
 
 pub const MAX_AFFIXES: usize = 5;
 pub const PREFIX_PRICE_FACTOR: f64 = 2.0;
 pub const SUFFIX_PRICE_FACTOR: f64 = 2.0;
+pub const FAMILY_PRICE_FACTOR: f64 = 5.0;
+pub const REROLL_PRICE_PER_AFFIX: f64 = 2.0;
+pub const LOCK_PRICE_FACTOR: f64 = 3.0;
+/// Chance for a rune to be destroyed when removed from an item socket
+pub const UNSOCKET_RUNE_DESTROY_CHANCE: f64 = 0.25;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorruptionOutcome {
+    /// Add an implicit corruption affix
+    ImplicitAffix,
+    /// Upgrade the tier of a random affix
+    UpgradeAffixTier,
+    /// Reroll the item into a unique of the same base
+    UniqueReroll,
+    /// Strip all crafted affixes and empowerment from the item
+    Brick,
+}
 
 fn affix_price(amount: usize, is_edict: bool) -> Option<f64> {
     match amount {
@@ -22,11 +45,55 @@ fn remove_price(amount: usize) -> Option<f64> {
     if amount > 0 { Some(10.0) } else { None }
 }
 
+fn reroll_price(amount: usize, locked: bool) -> Option<f64> {
+    if amount > 0 {
+        Some(amount as f64 * REROLL_PRICE_PER_AFFIX * if locked { LOCK_PRICE_FACTOR } else { 1.0 })
+    } else {
+        None
+    }
+}
+
+fn upgrade_tier_price(tier: u8) -> f64 {
+    match tier {
+        0 | 1 => 5.0,
+        2 => 10.0,
+        3 => 25.0,
+        4 => 50.0,
+        _ => 100.0,
+    }
+}
+
+pub fn corrupt_price(item_level: AreaLevel) -> f64 {
+    (item_level as f64 / 5.0).floor() + 50.0
+}
+
+pub fn divine_price(item_level: AreaLevel) -> f64 {
+    (item_level as f64 / 10.0).floor() + 20.0
+}
+
+pub fn unsocket_rune_price(rune_level: AreaLevel) -> f64 {
+    (rune_level as f64 / 10.0).floor() + 10.0
+}
+
+/// Return whether the affix at given index can be targeted by a forge operation
+pub fn is_forgeable_affix(item_modifiers: &ItemModifiers, affix_index: usize) -> bool {
+    item_modifiers
+        .affixes
+        .get(affix_index)
+        .map(|affix| matches!(affix.affix_type, AffixType::Prefix | AffixType::Suffix))
+        .unwrap_or_default()
+}
+
 pub fn affix_operation_price(
-    operation: ForgeAffixOperation,
-    affixes_amount: usize,
+    operation: &ForgeAffixOperation,
+    item_modifiers: &ItemModifiers,
     item_base: &ItemBase,
 ) -> Option<f64> {
+    if item_modifiers.corrupted {
+        return None;
+    }
+
+    let affixes_amount = item_modifiers.count_nonunique_affixes();
     match operation {
         ForgeAffixOperation::Add(affix_type) => {
             affix_price(affixes_amount, item_base.map_specs.is_some()).map(|price| {
@@ -38,6 +105,157 @@ pub fn affix_operation_price(
                     }
             })
         }
+        ForgeAffixOperation::AddFamily(_) => {
+            affix_price(affixes_amount, item_base.map_specs.is_some())
+                .map(|price| price * FAMILY_PRICE_FACTOR)
+        }
         ForgeAffixOperation::Remove => remove_price(affixes_amount),
+        ForgeAffixOperation::Reroll { locked_affix } => match locked_affix {
+            Some(locked_affix) => is_forgeable_affix(item_modifiers, *locked_affix)
+                .then(|| reroll_price(affixes_amount.saturating_sub(1), true))
+                .flatten(),
+            None => reroll_price(affixes_amount, false),
+        },
+        ForgeAffixOperation::UpgradeTier(affix_index) => item_modifiers
+            .affixes
+            .get(*affix_index)
+            .filter(|_| is_forgeable_affix(item_modifiers, *affix_index))
+            .map(|affix| upgrade_tier_price(affix.tier)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::{
+        item::{ItemRarity, MapSpecs},
+        item_affix::ItemAffix,
+    };
+
+    use super::*;
+
+    fn item_modifiers(affix_types: &[AffixType]) -> ItemModifiers {
+        ItemModifiers {
+            base_item_id: "sword".into(),
+            name: "Sword".into(),
+            rarity: ItemRarity::Rare,
+            level: 10,
+            affixes: affix_types
+                .iter()
+                .enumerate()
+                .map(|(index, affix_type)| ItemAffix {
+                    name: format!("affix {index}"),
+                    family: format!("family_{index}"),
+                    tags: Default::default(),
+                    affix_type: *affix_type,
+                    tier: 1,
+                    effects: Vec::new(),
+                    item_level: 1,
+                    triggers: Vec::new(),
+                })
+                .collect(),
+            quality: 0.0,
+            upgrade_level: 0,
+            corrupted: false,
+            socketed_runes: Vec::new(),
+            base_version: 0,
+        }
+    }
+
+    #[test]
+    fn test_add_affix_price_grows_with_affixes() {
+        let item_base = ItemBase::default();
+        let price = |operation: ForgeAffixOperation, affix_types: &[AffixType]| {
+            affix_operation_price(&operation, &item_modifiers(affix_types), &item_base)
+        };
+        let prefixes = [AffixType::Prefix; 3];
+
+        assert_eq!(price(ForgeAffixOperation::Add(None), &[]), Some(1.0));
+        assert_eq!(price(ForgeAffixOperation::Add(None), &prefixes), Some(10.0));
+        assert_eq!(
+            price(ForgeAffixOperation::Add(Some(AffixType::Prefix)), &prefixes),
+            Some(10.0 * PREFIX_PRICE_FACTOR)
+        );
+        assert_eq!(
+            price(ForgeAffixOperation::AddFamily("family".into()), &prefixes),
+            Some(10.0 * FAMILY_PRICE_FACTOR)
+        );
+        assert_eq!(
+            price(
+                ForgeAffixOperation::Add(None),
+                &[AffixType::Prefix; MAX_AFFIXES]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unique_affixes_are_not_counted_nor_forgeable() {
+        let item_base = ItemBase::default();
+        let item_modifiers = item_modifiers(&[AffixType::Unique, AffixType::Suffix]);
+
+        assert_eq!(
+            affix_operation_price(&ForgeAffixOperation::Add(None), &item_modifiers, &item_base),
+            Some(3.0)
+        );
+        assert_eq!(
+            affix_operation_price(
+                &ForgeAffixOperation::UpgradeTier(0),
+                &item_modifiers,
+                &item_base
+            ),
+            None
+        );
+        assert_eq!(
+            affix_operation_price(
+                &ForgeAffixOperation::Reroll {
+                    locked_affix: Some(0)
+                },
+                &item_modifiers,
+                &item_base
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_locked_reroll_costs_more_per_affix() {
+        let item_base = ItemBase::default();
+        let item_modifiers = item_modifiers(&[AffixType::Prefix; 3]);
+
+        assert_eq!(
+            affix_operation_price(
+                &ForgeAffixOperation::Reroll { locked_affix: None },
+                &item_modifiers,
+                &item_base
+            ),
+            Some(3.0 * REROLL_PRICE_PER_AFFIX)
+        );
+        assert_eq!(
+            affix_operation_price(
+                &ForgeAffixOperation::Reroll {
+                    locked_affix: Some(1)
+                },
+                &item_modifiers,
+                &item_base
+            ),
+            Some(2.0 * REROLL_PRICE_PER_AFFIX * LOCK_PRICE_FACTOR)
+        );
+    }
+
+    #[test]
+    fn test_edict_last_affix_is_cheaper() {
+        let item_base = ItemBase {
+            map_specs: Some(MapSpecs::default()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            affix_operation_price(
+                &ForgeAffixOperation::Add(None),
+                &item_modifiers(&[AffixType::Prefix; 4]),
+                &item_base
+            ),
+            Some(20.0)
+        );
     }
 }
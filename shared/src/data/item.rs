@@ -7,6 +7,7 @@ use strum_macros::EnumIter;
 use crate::data::{
     chance::Chance,
     item_affix::{AffixEffect, AffixType},
+    item_set::ItemSetId,
     modifier::ModifiableValue,
     skill::DamageType,
     stat_effect::{ArmorStatType, StatType},
@@ -21,7 +22,7 @@ use super::{
     stat_effect::{DamageMap, EffectsMap},
 };
 
-// pub type ItemSignature = [u8; 32];
+pub type ItemSignature = [u8; 32];
 
 #[derive(
     Serialize,
@@ -157,6 +158,11 @@ pub struct ItemBase {
     pub affixes: Vec<AffixEffectBlueprint>,
     #[serde(default)]
     pub triggers: Vec<TriggerSpecs>,
+    #[serde(default)]
+    pub item_set: Option<ItemSetId>,
+    /// Version of the Unique definition, to bump whenever its affixes change
+    #[serde(default)]
+    pub version: u16,
 
     #[serde(default)]
     pub weapon_specs: Option<WeaponSpecs>,
@@ -167,6 +173,10 @@ pub struct ItemBase {
     #[serde(default)]
     pub map_specs: Option<MapSpecs>,
 
+    /// Amount of runes that can be socketed into the item
+    #[serde(default)]
+    pub sockets: u8,
+
     #[serde(default)]
     pub ignore_quality: bool,
 
@@ -191,6 +201,16 @@ pub struct ItemModifiers {
 
     #[serde(default)]
     pub upgrade_level: u8,
+
+    #[serde(default)]
+    pub corrupted: bool,
+
+    #[serde(default)]
+    pub socketed_runes: Vec<ItemModifiers>,
+
+    /// Version of the Unique definition the item was rolled with
+    #[serde(default)]
+    pub base_version: u16,
 }
 
 // #[cfg(feature = "modifiable")]
@@ -208,8 +228,9 @@ pub struct ItemSpecs {
 
     #[serde(default)]
     pub required_level: AreaLevel,
-    // #[serde(default)]
-    // pub signature: ItemSignature,
+    /// Signed by the server to prove the item was not forged, computed with an empty signature
+    #[serde(default)]
+    pub signature: ItemSignature,
     #[serde(default)]
     pub gold_price: f64,
 }
@@ -279,9 +300,17 @@ pub struct MapSpecs {
 }
 
 impl ItemModifiers {
+    /// Affixes of the item, including the ones of its socketed runes
+    pub fn all_affixes(&self) -> impl Iterator<Item = &ItemAffix> {
+        self.affixes.iter().chain(
+            self.socketed_runes
+                .iter()
+                .flat_map(|rune| rune.affixes.iter()),
+        )
+    }
+
     pub fn aggregate_effects(&self, scope: AffixEffectScope, split_elements: bool) -> EffectsMap {
-        self.affixes
-            .iter()
+        self.all_affixes()
             .flat_map(|affix| affix.effects.iter())
             .filter(|e| e.scope == scope)
             .fold(EffectsMap::default(), |mut effects_map, effect| {
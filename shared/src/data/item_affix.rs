@@ -10,6 +10,7 @@ use super::{
     area::AreaLevel,
     item::ItemCategory,
     stat_effect::{StatEffect, StatType},
+    trigger::TriggerSpecs,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
@@ -18,6 +19,7 @@ pub enum AffixType {
     Suffix,
     Unique,
     Upgrade,
+    Corruption,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
@@ -51,6 +53,7 @@ pub enum AffixEffectScope {
     #[default]
     Local,
     Global,
+    Player, // Only used by maps, to penalize the player while in the area
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -69,8 +72,8 @@ pub struct ItemAffixBlueprint {
 
     #[serde(default)]
     pub effects: Vec<AffixEffectBlueprint>,
-    // #[serde(default)]
-    // pub triggers: Vec<TriggeredEffect>, // TODO
+    #[serde(default)]
+    pub triggers: Vec<TriggerSpecs>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -99,7 +102,8 @@ pub struct ItemAffix {
     pub effects: Vec<AffixEffect>,
     #[serde(default)] // For retro compatibility
     pub item_level: AreaLevel,
-    // pub triggers: Vec<TriggeredEffect>, // TODO
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<TriggerSpecs>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::{stat_effect::StatEffect, trigger::TriggerSpecs};
+
+pub type ItemSetId = String;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemSetSpecs {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+
+    pub bonuses: Vec<ItemSetBonus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemSetBonus {
+    /// Amount of different set items to equip to unlock the bonus
+    pub pieces: usize,
+
+    #[serde(default)]
+    pub effects: Vec<StatEffect>,
+    #[serde(default)]
+    pub triggers: Vec<TriggerSpecs>,
+}
+
+impl ItemSetSpecs {
+    pub fn active_bonuses(&self, pieces: usize) -> impl Iterator<Item = &ItemSetBonus> {
+        self.bonuses
+            .iter()
+            .filter(move |bonus| bonus.pieces <= pieces)
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-use super::item::ItemSpecs;
+use super::{
+    area::AreaLevel,
+    item::{ItemRarity, ItemSpecs},
+    item_affix::AffixType,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct QueuedLoot {
@@ -23,3 +27,53 @@ impl LootState {
         matches!(self, LootState::Sold | LootState::HasDisappeared)
     }
 }
+
+/// Chances are given in percent
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AreaLootChances {
+    pub item_level: AreaLevel,
+    pub rarity_chances: Vec<RarityChance>,
+    pub items: Vec<ItemDropChance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RarityChance {
+    pub rarity: ItemRarity,
+    pub chance: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemDropChance {
+    pub item_id: String,
+    pub name: String,
+    pub rarity: ItemRarity,
+    pub chance: f64,
+}
+
+/// Chance for an affix to be picked when adding a new affix of its type to an empty item
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AffixRollChance {
+    pub name: String,
+    pub family: String,
+    pub affix_type: AffixType,
+    pub tier: u8,
+    pub item_level: AreaLevel,
+    pub chance: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ItemDropArea {
+    /// None if the map can be used in any area
+    pub area_id: Option<String>,
+    pub source: ItemDropSource,
+    pub min_area_level: AreaLevel,
+    pub max_area_level: Option<AreaLevel>,
+    pub boss_only: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ItemDropSource {
+    Area,
+    Boss,
+    Map { item_id: String, name: String },
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use crate::data::{
+    player::{CharacterSpecs, CharacterState},
+    values::NonNegative,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinionSpecs {
+    pub character_specs: CharacterSpecs,
+
+    pub minion_id: String,
+    // Skill that summoned the minion, used to enforce max minions
+    pub skill_id: String,
+    pub lifetime: NonNegative,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct MinionState {
+    pub character_state: CharacterState,
+
+    pub elapsed_lifetime: NonNegative,
+}
@@ -1,5 +1,7 @@
 pub mod area;
+pub mod atlas;
 pub mod badges;
+pub mod challenge;
 pub mod chance;
 pub mod character;
 pub mod character_status;
@@ -9,8 +11,10 @@ pub mod game_stats;
 pub mod indexed_key;
 pub mod item;
 pub mod item_affix;
+pub mod item_set;
 pub mod loot;
 pub mod market;
+pub mod minion;
 pub mod modifier;
 pub mod monster;
 pub mod passive;
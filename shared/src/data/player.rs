@@ -7,6 +7,7 @@ use strum::IntoEnumIterator;
 use crate::data::{
     area::AreaLevel,
     character::{CharacterAttrs, CharacterStatic},
+    item_set::ItemSetId,
     skill::BaseSkillSpecs,
     skill_mastery::PlayerSkillMasteries,
     stat_effect::StatEffect,
@@ -120,6 +121,23 @@ impl PlayerInventory {
     //         })
     // }
 
+    /// Count the different items equipped for each item set
+    pub fn equipped_item_sets(&self) -> HashMap<ItemSetId, usize> {
+        let mut item_sets: HashMap<ItemSetId, HashSet<&str>> = HashMap::new();
+        for (_, item_specs) in self.equipped_items() {
+            if let Some(item_set) = &item_specs.base.item_set {
+                item_sets
+                    .entry(item_set.clone())
+                    .or_default()
+                    .insert(&item_specs.modifiers.base_item_id);
+            }
+        }
+        item_sets
+            .into_iter()
+            .map(|(item_set, items)| (item_set, items.len()))
+            .collect()
+    }
+
     pub fn equipped_items_mut(&mut self) -> impl Iterator<Item = (ItemSlot, &mut Box<ItemSpecs>)> {
         self.equipped
             .iter_mut()
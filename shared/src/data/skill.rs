@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
@@ -37,6 +37,11 @@ pub struct BaseSkillSpecs {
     pub cooldown: NonNegative,
     #[serde(default)]
     pub mana_cost: NonNegative,
+    /// Time to charge the skill before it resolves, can be interrupted by stun
+    #[serde(default)]
+    pub cast_time: NonNegative,
+    #[serde(default)]
+    pub channel: Option<SkillChannel>,
     #[serde(default)]
     pub required_item: Option<SkillRequiredItem>,
 
@@ -57,7 +62,7 @@ pub struct BaseSkillSpecs {
     pub auto_use_conditions: Vec<Condition>,
 
     #[serde(default)]
-    pub ignore_stat_effects: HashSet<StatType>,
+    pub ignore_stat_effects: BTreeSet<StatType>,
 
     #[serde(default)]
     pub hidden: bool,
@@ -86,13 +91,15 @@ pub struct SkillSpecs {
     pub usable: bool,
     pub cooldown: ModifiableValue<NonNegative>,
     pub mana_cost: ModifiableValue<NonNegative>,
+    pub cast_time: ModifiableValue<NonNegative>,
+    pub channel: Option<SkillChannel>,
 
     pub targets: Vec<SkillTargetsGroup>,
     pub triggers: Vec<TriggerSpecs>,
 
     pub level_modifier: u16,
 
-    pub ignore_stat_effects: HashSet<StatType>,
+    pub ignore_stat_effects: BTreeSet<StatType>,
     pub extra_modifier_effects: Vec<ModifierEffect>,
     pub auto_use_conditions: Vec<Condition>,
 }
@@ -103,6 +110,57 @@ pub struct SkillState {
 
     pub is_ready: bool,
     pub just_triggered: bool,
+
+    #[serde(default)]
+    pub cast_state: SkillCastState,
+}
+
+/// Channelled skills keep applying their effects after being cast,
+/// spending the mana cost again on every tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SkillChannel {
+    pub duration: NonNegative,
+    pub tick_rate: ModifiableValue<NonNegative>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum SkillCastState {
+    #[default]
+    Idle,
+    Casting {
+        elapsed: NonNegative,
+    },
+    Channelling {
+        elapsed: NonNegative,
+        elapsed_tick: NonNegative,
+    },
+}
+
+impl SkillCastState {
+    pub fn is_active(&self) -> bool {
+        !matches!(self, SkillCastState::Idle)
+    }
+
+    /// Ratio of the cast or channel already elapsed, if any
+    pub fn progress(&self, skill_specs: &SkillSpecs) -> Option<f64> {
+        let (elapsed, total) = match self {
+            SkillCastState::Idle => return None,
+            SkillCastState::Casting { elapsed } => (elapsed.get(), skill_specs.cast_time.get()),
+            SkillCastState::Channelling { elapsed, .. } => (
+                elapsed.get(),
+                skill_specs
+                    .channel
+                    .map(|channel| channel.duration.get())
+                    .unwrap_or_default(),
+            ),
+        };
+
+        Some(if total > 0.0 {
+            (elapsed / total).clamp(0.0, 1.0)
+        } else {
+            1.0
+        })
+    }
 }
 
 #[derive(
@@ -232,7 +290,7 @@ pub struct SkillEffect {
     pub effect_type: SkillEffectType,
 
     #[serde(default)]
-    pub ignore_stat_effects: HashSet<StatType>,
+    pub ignore_stat_effects: BTreeSet<StatType>,
 
     #[serde(default)]
     pub conditional_modifiers: Vec<ConditionalModifier>,
@@ -257,7 +315,7 @@ pub enum SkillEffectType {
     FlatDamage {
         damage: DamageMap,
         #[serde(default)]
-        damage_factors: HashMap<DamageType, f64>, // For tooltip purposes
+        damage_factors: BTreeMap<DamageType, f64>, // For tooltip purposes
         #[serde(default)]
         crit_chance: Chance,
         #[serde(default)]
@@ -292,7 +350,7 @@ pub enum SkillEffectType {
         replace_on_value_only: bool,
 
         #[serde(default, skip_serializing_if = "Option::is_none")]
-        computed_status_triggers: Option<HashMap<String, TriggerEffect>>,
+        computed_status_triggers: Option<BTreeMap<String, TriggerEffect>>,
     },
     RefreshStatus {
         #[serde(flatten)]
@@ -300,6 +358,10 @@ pub enum SkillEffectType {
         value: ChanceRange<ModifiableValue<f64>>,
         modifier: RestoreModifier,
     },
+    Cleanse {
+        #[serde(flatten)]
+        status_filter: StatStatusFilter,
+    },
     Restore {
         restore_type: RestoreType,
         value: ChanceRange<ModifiableValue<f64>>,
@@ -313,6 +375,16 @@ pub enum SkillEffectType {
         value: ChanceRange<ModifiableValue<f64>>,
         modifier: RestoreModifier,
     },
+    Summon {
+        minion_id: String,
+        minion_name: String, // For tooltip purposes
+        lifetime: ModifiableValue<NonNegative>,
+        max_minions: ModifiableValue<u8>,
+
+        // On skill computed specs, effects granted to the minions
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        minion_effects: Vec<StatEffect>,
+    },
 }
 
 #[derive(
@@ -175,6 +175,7 @@ impl StatStatusFilter {
 pub enum StatType {
     Description(String),
     GemsFind,
+    ChampionChance,
     ItemRarity,
     ItemAreaChance,
     ItemLevel,
@@ -264,6 +265,9 @@ pub enum StatType {
     LifeRegen,
     Mana,
     ManaRegen,
+    Ward,
+    WardRecharge,
+    WardRechargeDelay,
     ManaCost {
         #[serde(flatten)]
         skill_filter: StatSkillFilter,
@@ -291,6 +295,14 @@ pub enum StatType {
         conditions_duration: u32,
     },
     StatConverter(StatConverterSpecs),
+    DamageConversion {
+        #[serde(flatten)]
+        skill_filter: StatSkillFilter,
+        from: DamageType,
+        to: DamageType,
+        #[serde(default)]
+        is_extra: bool,
+    },
     SkillTargetModifier {
         #[serde(flatten)]
         skill_filter: StatSkillFilter,
@@ -319,6 +331,13 @@ pub enum StatType {
         #[serde(flatten)]
         skill_filter: StatSkillFilter,
     },
+    MinionLifetime(#[serde(default)] StatSkillFilter),
+    MaxMinions(#[serde(default)] StatSkillFilter),
+    MinionModifier {
+        stat: Box<StatType>,
+        #[serde(flatten)]
+        skill_filter: StatSkillFilter,
+    },
     GoldFind,
     PowerLevel,
     Description2(String),
@@ -466,7 +485,35 @@ impl Matchable for StatType {
             ) => {
                 compare_options(status_id, status_id_2) && compare_options(skill_type, skill_type_2)
             }
-            (SkillLevel(first), SkillLevel(second)) => first.is_match(second),
+            (SkillLevel(first), SkillLevel(second))
+            | (MinionLifetime(first), MinionLifetime(second))
+            | (MaxMinions(first), MaxMinions(second)) => first.is_match(second),
+            (
+                MinionModifier { stat, skill_filter },
+                MinionModifier {
+                    stat: stat_2,
+                    skill_filter: skill_filter_2,
+                },
+            ) => stat.is_match(stat_2) && skill_filter.is_match(skill_filter_2),
+            (
+                DamageConversion {
+                    skill_filter,
+                    from,
+                    to,
+                    is_extra,
+                },
+                DamageConversion {
+                    skill_filter: skill_filter_2,
+                    from: from_2,
+                    to: to_2,
+                    is_extra: is_extra_2,
+                },
+            ) => {
+                skill_filter.is_match(skill_filter_2)
+                    && from == from_2
+                    && to == to_2
+                    && is_extra == is_extra_2
+            }
             (
                 SkillConditionalModifier { skill_filter, .. },
                 SkillConditionalModifier {
@@ -529,6 +576,7 @@ pub enum StatSkillEffectType {
         debuff: Option<bool>,
     },
     RefreshStatus,
+    Cleanse,
     Restore {
         #[serde(default)]
         restore_type: Option<RestoreType>,
@@ -536,6 +584,7 @@ pub enum StatSkillEffectType {
     Resurrect,
     Kill,
     RefreshCooldown,
+    Summon,
 }
 
 impl Matchable for StatSkillEffectType {
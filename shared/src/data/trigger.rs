@@ -95,6 +95,7 @@ pub enum EventTrigger {
     OnDeath(TargetType),
     OnApplyStatus(StatusTrigger),
     OnReceiveStatus(StatusTrigger),
+    OnRemoveStatus(StatusTrigger),
     OnRestored(RestoreTrigger),
 }
 
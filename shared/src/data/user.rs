@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,22 @@ pub struct UserDetails {
     pub email: Option<Email>,
     pub chat_badge: Option<String>,
     pub max_characters: u8,
+    #[serde(default)]
+    pub is_admin: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OAuthProviderInfo {
+    pub provider_id: String,
+    pub name: String,
+}
+
+/// External account linked to the user
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UserIdentity {
+    pub provider: OAuthProviderInfo,
+    pub provider_username: Option<String>,
+    pub linked_at: DateTime<Utc>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -1,10 +1,14 @@
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use shared_chat::moderation::ChatSanctionType;
+
 use crate::{
     data::{
-        item::{InventorySortType, ItemCategory, ItemSlot},
+        area::AreaLevel,
+        item::{InventorySortType, ItemCategory, ItemRarity, ItemSlot},
         item_affix::AffixType,
         market::MarketFilters,
         passive::{PassiveNodeId, PurchasedNodes},
@@ -13,7 +17,7 @@ use crate::{
         temple::PlayerBenedictions,
         user::{UserCharacterId, UserId},
     },
-    types::{AssetName, Email, ItemPrice, PaginationLimit, Password, Username},
+    types::{AssetName, ChatContent, Email, ItemPrice, PaginationLimit, Password, Username},
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -34,6 +38,12 @@ pub struct SignInRequest {
     pub password: Password,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TotpSignInRequest {
+    pub challenge: String,
+    pub code: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ForgotPasswordRequest {
     pub captcha_token: String,
@@ -50,6 +60,18 @@ pub struct ResetPasswordRequest {
     pub password_token: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct OAuthAuthorizeRequest {
+    /// Required to create an account if none is linked to the identity yet
+    pub accepted_terms: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OAuthCallbackRequest {
+    pub code: String,
+    pub state: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct UpdateAccountRequest {
     pub username: Option<Username>,
@@ -57,6 +79,33 @@ pub struct UpdateAccountRequest {
 
     pub old_password: Option<Password>,
     pub password: Option<Password>,
+
+    /// Required to change the email when two-factor authentication is enabled
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DeleteAccountRequest {
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+/// TOTP or recovery code
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IgnoreUserRequest {
+    pub username: Username,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReportChatUserRequest {
+    pub user_id: UserId,
+    pub reason: ChatContent,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -82,6 +131,22 @@ pub struct BuyBenedictionsRequest {
     pub player_benedictions: PlayerBenedictions,
 }
 
+// Atlas
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SaveAtlasModifiersRequest {
+    pub character_id: UserCharacterId,
+    pub purchased_modifiers: PurchasedNodes,
+}
+
+// Challenges
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ClaimChallengeRewardRequest {
+    pub character_id: UserCharacterId,
+    pub schedule_id: String,
+}
+
 // Skill Masteries
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -154,6 +219,10 @@ pub struct EditMarketItemRequest {
 pub struct BuyMarketItemRequest {
     pub character_id: UserCharacterId,
     pub item_index: u32,
+
+    /// Required for large purchases when two-factor authentication is enabled
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -206,10 +275,18 @@ pub struct TakeStashItemRequest {
 
 // Forge
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum ForgeAffixOperation {
     Add(Option<AffixType>),
+    /// Add an affix from the given family
+    AddFamily(String),
     Remove,
+    /// Reroll values of all affixes within their tier, except the locked one
+    Reroll {
+        locked_affix: Option<usize>,
+    },
+    /// Upgrade the affix at given index to its next tier
+    UpgradeTier(usize),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -219,12 +296,45 @@ pub struct ForgeAffixRequest {
     pub operation: ForgeAffixOperation,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeAffixFamiliesRequest {
+    pub character_id: UserCharacterId,
+    pub item_index: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeCorruptRequest {
+    pub character_id: UserCharacterId,
+    pub item_index: u32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ForgeUpgradeRequest {
     pub character_id: UserCharacterId,
     pub item_index: u32,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeDivineRequest {
+    pub character_id: UserCharacterId,
+    pub item_index: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeSocketRuneRequest {
+    pub character_id: UserCharacterId,
+    pub item_index: u32,
+    /// Index of the rune in the bag
+    pub rune_index: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeUnsocketRuneRequest {
+    pub character_id: UserCharacterId,
+    pub item_index: u32,
+    pub socket_index: u8,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GambleItemRequest {
     pub character_id: UserCharacterId,
@@ -256,3 +366,72 @@ pub struct InventorySortRequest {
     pub character_id: UserCharacterId,
     pub sort_type: InventorySortType,
 }
+
+// Admin
+
+/// Resources are set to the given values, missing ones are left untouched
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminCharacterResourcesRequest {
+    pub resource_gems: Option<f64>,
+    pub resource_shards: Option<f64>,
+    pub resource_gold: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminDeleteCharacterItemsRequest {
+    pub item_indexes: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminGrantItemRequest {
+    pub base_item_id: String,
+    /// Ignored for Unique items
+    #[serde(default)]
+    pub rarity: ItemRarity,
+    pub level: AreaLevel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminBroadcastRequest {
+    pub message: shared_chat::types::ChatContent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminMaintenanceRequest {
+    /// Delay before closing the game sessions
+    pub countdown_secs: i64,
+    pub duration_secs: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminDrainRequest {
+    /// Delay before clients can reconnect
+    #[serde(default = "default_drain_reconnect_delay_secs")]
+    pub reconnect_delay_secs: i64,
+}
+
+fn default_drain_reconnect_delay_secs() -> i64 {
+    15
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatSanctionRequest {
+    pub user_id: UserId,
+    pub sanction_type: ChatSanctionType,
+    /// Permanent if none
+    pub duration_secs: Option<i64>,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminRevokeChatSanctionRequest {
+    pub user_id: UserId,
+    pub sanction_type: ChatSanctionType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminDeleteChatMessagesRequest {
+    pub user_id: UserId,
+    /// Delete all recent messages of the user if none
+    pub sent_at: Option<DateTime<Utc>>,
+}
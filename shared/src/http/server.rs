@@ -4,9 +4,20 @@ use std::{collections::HashMap, fmt, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
+use shared_chat::{
+    moderation::{ChatReportId, ChatSanction},
+    types::ChatMessage,
+};
+
 use crate::data::{
-    area::{AreaLevel, AreaSpecs},
+    area::{AreaLevel, AreaSpecs, BossDrops},
+    atlas::{AtlasSpecs, AtlasState},
+    challenge::ActiveChallenge,
     character_status::{StatusId, StatusSpecs},
+    forge::CorruptionOutcome,
+    item_affix::AffixType,
+    item_set::{ItemSetId, ItemSetSpecs},
+    loot::{AffixRollChance, AreaLootChances, ItemDropArea},
     market::MarketItem,
     passive::{PassivesTreeAscension, PassivesTreeSpecs, PurchasedNodes},
     player::PlayerInventory,
@@ -15,7 +26,10 @@ use crate::data::{
     skill_mastery::{PlayerSkillMasteries, SkillMasterySpecs, SkillMasteryState},
     stash::{Stash, StashItem},
     temple::{BenedictionsCategory, PlayerBenedictions},
-    user::{User, UserCharacter, UserCharacterId, UserDetails, UserGrindArea, UserId},
+    user::{
+        OAuthProviderInfo, User, UserCharacter, UserCharacterId, UserDetails, UserGrindArea,
+        UserId, UserIdentity,
+    },
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -75,6 +89,9 @@ pub struct SignUpResponse {}
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct SignInResponse {
     pub jwt: String,
+    /// Set instead of the jwt when a TOTP code is required to complete the sign in
+    #[serde(default)]
+    pub totp_challenge: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -88,6 +105,58 @@ pub struct GetUserResponse {
     pub user: User,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetOAuthProvidersResponse {
+    pub providers: Vec<OAuthProviderInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct OAuthAuthorizeResponse {
+    pub authorize_url: String,
+    /// Must be checked against the state returned by the provider
+    pub state: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetUserIdentitiesResponse {
+    pub identities: Vec<UserIdentity>,
+    pub has_password: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct LinkUserIdentityResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UnlinkUserIdentityResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetTotpStatusResponse {
+    pub enabled: bool,
+    pub recovery_codes_left: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EnrollTotpResponse {
+    pub provisioning_uri: String,
+    /// Base64 encoded PNG
+    pub qr_code: String,
+    /// Base32 encoded, for manual entry
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct EnableTotpResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DisableTotpResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RegenerateRecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GetUserDetailsResponse {
     pub user_details: UserDetails,
@@ -99,6 +168,20 @@ pub struct UpdateAccountResponse {}
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DeleteAccountResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetIgnoredUsersResponse {
+    pub ignored_users: Vec<User>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct IgnoreUserResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UnignoreUserResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ReportChatUserResponse {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GetDiscordInviteResponse {
     pub code: String,
@@ -124,6 +207,7 @@ pub struct GetCharacterDetailsResponse {
     pub ascension: PassivesTreeAscension,
     pub passives_build: PurchasedNodes,
     pub benedictions: PlayerBenedictions,
+    pub atlas: AtlasState,
     pub skill_masteries: PlayerSkillMasteries,
     pub skill_mastery_skill_specs: HashMap<String, SkillSpecs>,
 
@@ -144,6 +228,19 @@ pub struct DeleteCharacterResponse {}
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GetAreasResponse {
     pub areas: HashMap<String, AreaSpecs>,
+    #[serde(default)]
+    pub bosses_drops: HashMap<String, Vec<BossDrops>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetAreaLootResponse {
+    pub loot: AreaLootChances,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetItemLootResponse {
+    pub drop_areas: Vec<ItemDropArea>,
+    pub affixes: Vec<AffixRollChance>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -157,11 +254,21 @@ pub struct GetStatusesResponse {
     pub statuses: HashMap<StatusId, StatusSpecs>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetItemSetsResponse {
+    pub item_sets: HashMap<ItemSetId, ItemSetSpecs>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GetPassivesResponse {
     pub passives_tree_specs: PassivesTreeSpecs,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetAtlasResponse {
+    pub atlas_specs: AtlasSpecs,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GetBenedictionsResponse {
     pub benedictions_specs: IndexMap<String, BenedictionsCategory>,
@@ -188,6 +295,22 @@ pub struct BuyBenedictionsResponse {
     pub player_benedictions: PlayerBenedictions,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SaveAtlasModifiersResponse {
+    pub atlas: AtlasState,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetChallengesResponse {
+    pub challenges: HashMap<String, ActiveChallenge>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ClaimChallengeRewardResponse {
+    pub resource_gems: f64,
+    pub resource_shards: f64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct SaveFavoriteSkillsResponse {
     pub favorite_skills: Vec<String>,
@@ -264,12 +387,49 @@ pub struct ForgeAffixResponse {
     pub inventory: PlayerInventory,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeAffixFamiliesResponse {
+    pub families: Vec<ForgeAffixFamily>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ForgeAffixFamily {
+    pub family: String,
+    pub name: String,
+    pub affix_type: AffixType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeCorruptResponse {
+    pub resource_gems: f64,
+    pub inventory: PlayerInventory,
+    pub outcome: CorruptionOutcome,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ForgeUpgradeResponse {
     pub resource_gems: f64,
     pub inventory: PlayerInventory,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeDivineResponse {
+    pub resource_gems: f64,
+    pub inventory: PlayerInventory,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeSocketRuneResponse {
+    pub inventory: PlayerInventory,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ForgeUnsocketRuneResponse {
+    pub resource_gems: f64,
+    pub inventory: PlayerInventory,
+    pub rune_destroyed: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GambleItemResponse {
     pub resource_gems: f64,
@@ -301,3 +461,110 @@ pub struct InventoryDeleteResponse {
 pub struct InventorySortResponse {
     pub inventory: PlayerInventory,
 }
+
+// Admin
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminSession {
+    pub character_id: UserCharacterId,
+    pub area_id: String,
+    pub area_level: AreaLevel,
+    pub last_active: DateTime<Utc>,
+    /// Sessions kept in memory after the player disconnected are not connected
+    pub connected: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminSessionsResponse {
+    pub sessions: Vec<AdminSession>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminCharacterResponse {
+    pub character_id: UserCharacterId,
+    pub user_id: UserId,
+    pub character_name: String,
+    /// Area the character is grinding, if any
+    pub area_id: Option<String>,
+    pub resource_gems: f64,
+    pub resource_shards: f64,
+    pub resource_gold: f64,
+    pub inventory: PlayerInventory,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminCharacterResourcesResponse {
+    pub resource_gems: f64,
+    pub resource_shards: f64,
+    pub resource_gold: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminInventoryResponse {
+    pub inventory: PlayerInventory,
+}
+
+/// Item that doesn't match the current items definitions anymore
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminLegacyItem {
+    pub location: String,
+    pub base_item_id: String,
+    pub name: String,
+    pub issues: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminInventoriesLegacyItemsResponse {
+    pub items: Vec<AdminLegacyItem>,
+    /// Character to continue the scan after, none once all inventories were checked
+    pub next_character_id: Option<UserCharacterId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminStashLegacyItemsResponse {
+    pub items: Vec<AdminLegacyItem>,
+    /// Stash item to continue the scan after, none once all stashes were checked
+    pub next_stash_item_id: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminMaintenanceResponse {
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatSanctionsResponse {
+    pub sanctions: Vec<(UserId, ChatSanction)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatReport {
+    pub report_id: ChatReportId,
+    pub reporter_id: UserId,
+    pub reported_user_id: UserId,
+    pub reason: String,
+    pub context_messages: Vec<ChatMessage>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolved_by: Option<UserId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatReportsResponse {
+    pub reports: Vec<AdminChatReport>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatModerationLog {
+    pub moderator_id: UserId,
+    pub target_user_id: Option<UserId>,
+    pub action: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminChatModerationLogsResponse {
+    pub logs: Vec<AdminChatModerationLog>,
+}
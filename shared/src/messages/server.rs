@@ -8,6 +8,7 @@ use crate::data::{
     game_stats::GameStats,
     item::ItemSpecs,
     loot::QueuedLoot,
+    minion::{MinionSpecs, MinionState},
     monster::{MonsterSpecs, MonsterState},
     passive::{PassivesTreeSpecs, PassivesTreeState, PurchasedNodes},
     player::{PlayerBaseSpecs, PlayerInventory, PlayerResources, PlayerSpecs, PlayerState},
@@ -24,6 +25,7 @@ impl_into_message! {
     pub enum ServerMessage {
         Connect(ConnectMessage),
         ServerDown(ServerDownMessage),
+        Maintenance(MaintenanceMessage),
         Error(ErrorMessage),
         InitGame(InitGameMessage),
         UpdateGame(SyncGameStateMessage),
@@ -39,6 +41,19 @@ pub struct ServerDownMessage {
     pub expected_launch_time: DateTime<Utc>,
 }
 
+/// Sent to running games when a maintenance is scheduled or cancelled
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MaintenanceMessage {
+    /// None if the maintenance was cancelled
+    pub scheduled: Option<ScheduledMaintenance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ScheduledMaintenance {
+    pub starts_at: DateTime<Utc>,
+    pub expected_launch_time: DateTime<Utc>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ErrorMessage {
     pub error_type: ErrorType,
@@ -85,6 +100,8 @@ pub struct SyncGameStateMessage {
     pub player_stamina: Duration,
     pub monster_specs: Option<Vec<MonsterSpecs>>,
     pub monster_states: Vec<MonsterState>,
+    pub minion_specs: Option<Vec<MinionSpecs>>,
+    pub minion_states: Vec<MinionState>,
     pub queued_loot: Option<Vec<QueuedLoot>>,
     pub game_stats: GameStats,
     pub quest_rewards: Option<Option<QuestRewards>>,
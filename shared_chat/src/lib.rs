@@ -1,3 +1,4 @@
 pub mod messages;
+pub mod moderation;
 pub mod ring_buffer;
 pub mod types;
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{CharacterId, ChatChannel, ChatContent, LinkedItemBytes};
+use crate::types::{CharacterId, ChatChannel, ChatContent, LinkedItemBytes, LinkedItemSignature};
 
 use super::macros::impl_into_message;
 
@@ -12,6 +13,7 @@ impl_into_message! {
         Connect(ClientConnectMessage),
         // Disconnect(ClientDisconnectMessage),
         PostMessage(ClientPostMessage),
+        RequestHistory(ClientHistoryRequest),
     }
 }
 
@@ -36,6 +38,15 @@ pub struct ClientPostMessage {
     pub channel: ChatChannel,
     pub content: ChatContent,
     pub character_id: Option<CharacterId>,
-    pub linked_item: Option<LinkedItemBytes>,
-    // pub linked_item: Option<(LinkedItemBytes, [u8; 32])>,
+    /// Serialized item specs without their signature, and the signature
+    pub linked_item: Option<(LinkedItemBytes, LinkedItemSignature)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClientHistoryRequest {
+    /// For whispers, the history of the connected user is always returned
+    pub channel: ChatChannel,
+    /// Only return messages sent strictly before this time
+    pub before: Option<DateTime<Utc>>,
+    pub limit: u16,
 }
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{ChatMessage, UserId};
+use crate::types::{ChatChannel, ChatMessage, UserId};
 
 use super::macros::impl_into_message;
 
@@ -13,6 +14,8 @@ impl_into_message! {
 
         Broadcast(ChatMessage),
         WhisperFeedback(ServerWhisperFeedbackMessage),
+        History(ServerHistoryMessage),
+        DeleteMessages(ServerDeleteMessagesMessage),
     }
 }
 
@@ -46,3 +49,18 @@ pub struct ServerWhisperFeedbackMessage {
     pub target_user_id: UserId,
     pub chat_message: ChatMessage,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServerHistoryMessage {
+    pub channel: ChatChannel,
+    /// Most recent messages first
+    pub messages: Vec<ChatMessage>,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ServerDeleteMessagesMessage {
+    pub user_id: UserId,
+    /// Delete all messages of the user if none
+    pub sent_at: Option<DateTime<Utc>>,
+}
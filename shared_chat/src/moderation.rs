@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{ChatContent, ChatMessage, UserId};
+
+pub type ChatReportId = i64;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChatSanctionType {
+    /// Cannot post messages
+    Mute,
+    /// Messages are only visible to the user themselves
+    ShadowMute,
+    /// Cannot connect to the chat
+    Ban,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatSanction {
+    pub sanction_type: ChatSanctionType,
+    /// Permanent if none
+    pub until: Option<DateTime<Utc>>,
+    pub reason: String,
+}
+
+impl ChatSanction {
+    pub fn is_active(&self) -> bool {
+        self.until.is_none_or(|until| Utc::now() < until)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatSanctionsResponse {
+    pub sanctions: Vec<(UserId, ChatSanction)>,
+}
+
+/// Sanction given with an admin chat command, applied and logged by the backend
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatCommandSanctionRequest {
+    pub moderator_id: UserId,
+    pub user_id: UserId,
+    pub sanction: ChatSanction,
+}
+
+/// Announcement made with an admin chat command, broadcast and logged by the backend
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatCommandBroadcastRequest {
+    pub moderator_id: UserId,
+    pub message: ChatContent,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatDeleteMessagesRequest {
+    pub user_id: UserId,
+    /// Delete all messages of the user if none
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatUserMessagesResponse {
+    pub messages: Vec<ChatMessage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ChatIgnoreListResponse {
+    pub ignored_user_ids: Vec<UserId>,
+}
@@ -34,6 +34,10 @@ impl<T> RingBuffer<T> {
         }
     }
 
+    pub fn retain(&mut self, f: impl FnMut(&T) -> bool) {
+        self.buf.retain(f);
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.buf.iter()
     }
@@ -42,6 +46,10 @@ impl<T> RingBuffer<T> {
         self.buf.iter().rev()
     }
 
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn len(&self) -> usize {
         self.buf.len()
     }
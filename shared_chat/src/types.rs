@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 pub type UserId = uuid::Uuid;
 pub type CharacterId = uuid::Uuid;
+pub type LinkedItemSignature = [u8; 32];
 const MAX_LINKED_ITEM_SIZE: usize = 4096;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Hash)]
@@ -27,8 +28,12 @@ pub struct ChatMessage {
 
     pub content: String,
     pub linked_item: Option<LinkedItemBytes>,
-    // #[serde(default, skip_serializing, skip_deserializing)]
-    // pub item_signature: Option<[u8; 32]>,
+    /// Whether the signature of the linked item was checked against the game server key
+    #[serde(default)]
+    pub linked_item_verified: bool,
+    /// Only used internally by the chat server to verify the linked item
+    #[serde(default, skip_serializing, skip_deserializing)]
+    pub item_signature: Option<LinkedItemSignature>,
 }
 
 #[nutype(
@@ -48,6 +48,7 @@ pub fn App() -> impl IntoView {
                 <Route path=path!("/ui_tests") view=pages::UiTestsPage />
                 <Route path=path!("/items") view=pages::ItemsPage />
                 <Route path=path!("/logo") view=pages::LogoPage />
+                <Route path=path!("/loot") view=pages::LootPage />
             </Routes>
         </Router>
     }
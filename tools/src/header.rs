@@ -33,6 +33,13 @@ pub fn HeaderMenu() -> impl IntoView {
         }
     };
 
+    let navigate_to_loot = {
+        let navigate = use_navigate();
+        move |_| {
+            navigate("/loot", Default::default());
+        }
+    };
+
     view! {
         <div class="relative z-50 flex justify-between items-center p-1 xl:p-2
         bg-zinc-800 border-b-1 border-zinc-900/50 shadow-md/30 h-auto">
@@ -40,6 +47,7 @@ pub fn HeaderMenu() -> impl IntoView {
                 <MenuButton on:click=navigate_to_passives>"Passives"</MenuButton>
                 <MenuButton on:click=navigate_to_items>"Items"</MenuButton>
                 <MenuButton on:click=navigate_to_skills>"Skills"</MenuButton>
+                <MenuButton on:click=navigate_to_loot>"Loot"</MenuButton>
                 <MenuButton on:click=navigate_to_ui_tests>"Ui Tests"</MenuButton>
             </div>
         </div>
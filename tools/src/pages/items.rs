@@ -258,6 +258,7 @@ fn ItemPreview(
                             },
                         }]),
                         item_level: 0,
+                        triggers: Vec::new(),
                     })
                     .collect();
                 let modifiers = ItemModifiers {
@@ -268,6 +269,9 @@ fn ItemPreview(
                     affixes: unique_effects,
                     quality: 0.0,
                     upgrade_level: upgrade_level.get().unwrap_or_default(),
+                    corrupted: false,
+                    socketed_runes: Vec::new(),
+                    base_version: item_base.version,
                 };
                 Arc::new(create_item_specs(item_base, modifiers, true))
             })
@@ -313,6 +317,7 @@ pub fn create_item_specs(
         modifiers,
         old_game,
         gold_price: 0.0,
+        signature: Default::default(),
     }
 }
 
@@ -477,6 +482,7 @@ fn compute_upgrade_effects(base: &ItemBase, item_modifiers: &mut ItemModifiers)
                         .get(item_modifiers.upgrade_level.saturating_sub(1) as usize)
                         .copied()
                         .unwrap_or_default(),
+                    triggers: Vec::new(),
                 }
             }));
     }
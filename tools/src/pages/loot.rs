@@ -0,0 +1,349 @@
+use indexmap::IndexMap;
+
+use leptos::prelude::*;
+use shared::data::{
+    area::AreaLevel,
+    item::ItemRarity,
+    item_affix::AffixType,
+    loot::{AffixRollChance, AreaLootChances, ItemDropArea, ItemDropSource},
+};
+
+use frontend::components::{
+    backend_client::BackendClient,
+    data_context::DataContext,
+    shared::tooltips::item_tooltip::name_color_rarity,
+    ui::{
+        buttons::MenuButton,
+        card::{Card, CardInset, CardTitle},
+        dropdown::SearchableDropdownMenu,
+        input::ValidatedInput,
+    },
+};
+
+use crate::header::HeaderMenu;
+
+#[component]
+pub fn LootPage() -> impl IntoView {
+    let backend: BackendClient = expect_context();
+    let data_context: DataContext = expect_context();
+
+    let _data_load = LocalResource::new({
+        move || async move {
+            let _ = data_context.load_data(backend).await;
+        }
+    });
+
+    let selected_item = RwSignal::new(Some(String::new()));
+
+    view! {
+        <main class="my-0 mx-auto w-full text-center overflow-x-hidden flex flex-col min-h-screen">
+            <HeaderMenu />
+            <div class="relative flex-1">
+                <div class="absolute inset-0 flex p-1 xl:p-4 gap-4">
+                    <div class="flex-1 h-full">
+                        <AreaLootCard selected_item />
+                    </div>
+                    <div class="flex-1 h-full">
+                        <ItemLootCard selected_item />
+                    </div>
+                </div>
+            </div>
+        </main>
+    }
+}
+
+#[component]
+fn AreaLootCard(selected_item: RwSignal<Option<String>>) -> impl IntoView {
+    let backend: BackendClient = expect_context();
+    let data_context: DataContext = expect_context();
+
+    let area_id = RwSignal::new(String::new());
+    let area_level = RwSignal::new(Some(1 as AreaLevel));
+    let boss = RwSignal::new(false);
+
+    let areas_options = move || {
+        let mut areas: IndexMap<_, _> = data_context
+            .areas_specs
+            .read()
+            .iter()
+            .map(|(area_id, area_specs)| (area_id.clone(), area_specs.name.clone()))
+            .collect();
+        areas.sort_by(|_, a, _, b| a.cmp(b));
+        areas
+    };
+
+    let area_loot = LocalResource::new(move || {
+        let (area_id, area_level, boss) = (area_id.get(), area_level.get(), boss.get());
+        async move {
+            match area_level {
+                Some(area_level) if !area_id.is_empty() => backend
+                    .get_area_loot(&area_id, area_level, boss)
+                    .await
+                    .ok()
+                    .map(|response| response.loot),
+                _ => None,
+            }
+        }
+    });
+
+    view! {
+        <Card>
+            <div class="flex justify-between mx-4 items-center gap-2">
+                <CardTitle>"Area Loot"</CardTitle>
+                <div class="flex-1" />
+                <MenuButton on:click=move |_| boss.update(|boss| *boss = !*boss)>
+                    {move || if boss.get() { "Boss Level" } else { "Normal Level" }}
+                </MenuButton>
+            </div>
+            <div class="flex gap-2 mx-4 items-end">
+                <div class="flex-1">
+                    {move || {
+                        view! {
+                            <SearchableDropdownMenu
+                                options=areas_options()
+                                chosen_option=area_id
+                                missing_text="Select an area"
+                            />
+                        }
+                    }}
+                </div>
+                <ValidatedInput
+                    id="area_level"
+                    label="Area Level"
+                    input_type="number"
+                    step="1"
+                    bind=area_level
+                />
+            </div>
+            <CardInset class="flex-1 text-left">
+                <Transition fallback=move || {
+                    view! { <p class="text-zinc-400">"Loading..."</p> }
+                }>
+                    {move || Suspend::new(async move {
+                        area_loot
+                            .await
+                            .map(|loot| view! { <AreaLootDetails loot selected_item /> })
+                    })}
+                </Transition>
+            </CardInset>
+        </Card>
+    }
+}
+
+#[component]
+fn AreaLootDetails(
+    loot: AreaLootChances,
+    selected_item: RwSignal<Option<String>>,
+) -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-2 text-sm">
+            <div class="text-zinc-400">
+                "Item Level: " <span class="font-semibold text-white">{loot.item_level}</span>
+            </div>
+            <div class="flex gap-4 text-zinc-400">
+                {loot
+                    .rarity_chances
+                    .into_iter()
+                    .map(|rarity_chance| {
+                        view! {
+                            <span class=name_color_rarity(rarity_chance.rarity)>
+                                {format!(
+                                    "{}: {:.2}%",
+                                    rarity_str(rarity_chance.rarity),
+                                    rarity_chance.chance,
+                                )}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <table class="w-full">
+                <tbody>
+                    {loot
+                        .items
+                        .into_iter()
+                        .map(|item| {
+                            let item_id = item.item_id.clone();
+                            view! {
+                                <tr
+                                    class="cursor-pointer hover:bg-zinc-800"
+                                    on:click=move |_| selected_item.set(Some(item_id.clone()))
+                                >
+                                    <td class=name_color_rarity(item.rarity)>{item.name}</td>
+                                    <td class="text-zinc-500">{item.item_id}</td>
+                                    <td class="text-right">{format!("{:.4}%", item.chance)}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn ItemLootCard(selected_item: RwSignal<Option<String>>) -> impl IntoView {
+    let backend: BackendClient = expect_context();
+
+    let item_level = RwSignal::new(None::<AreaLevel>);
+
+    let item_loot = LocalResource::new(move || {
+        let (item_id, item_level) = (selected_item.get().unwrap_or_default(), item_level.get());
+        async move {
+            if item_id.is_empty() {
+                None
+            } else {
+                backend.get_item_loot(&item_id, item_level).await.ok()
+            }
+        }
+    });
+
+    view! {
+        <Card>
+            <div class="flex justify-between mx-4 items-center">
+                <CardTitle>"Item Loot"</CardTitle>
+            </div>
+            <div class="flex gap-2 mx-4 items-end">
+                <div class="flex-1">
+                    <ValidatedInput
+                        id="item_id"
+                        label="Item Id"
+                        input_type="text"
+                        placeholder="Base item id"
+                        bind=selected_item
+                    />
+                </div>
+                <ValidatedInput
+                    id="item_level"
+                    label="Item Level"
+                    input_type="number"
+                    step="1"
+                    bind=item_level
+                />
+            </div>
+            <CardInset class="flex-1 text-left">
+                <Transition fallback=move || {
+                    view! { <p class="text-zinc-400">"Loading..."</p> }
+                }>
+                    {move || Suspend::new(async move {
+                        item_loot
+                            .await
+                            .map(|item_loot| {
+                                view! {
+                                    <DropAreasList drop_areas=item_loot.drop_areas />
+                                    <AffixesList affixes=item_loot.affixes />
+                                }
+                            })
+                    })}
+                </Transition>
+            </CardInset>
+        </Card>
+    }
+}
+
+#[component]
+fn DropAreasList(drop_areas: Vec<ItemDropArea>) -> impl IntoView {
+    let data_context: DataContext = expect_context();
+
+    let area_name = move |area_id: &Option<String>| {
+        area_id
+            .as_ref()
+            .map(|area_id| {
+                data_context
+                    .areas_specs
+                    .read_untracked()
+                    .get(area_id)
+                    .map(|area_specs| area_specs.name.clone())
+                    .unwrap_or(area_id.clone())
+            })
+            .unwrap_or("Any Area".into())
+    };
+
+    view! {
+        <div class="flex flex-col gap-1 text-sm mb-4">
+            <span class="font-semibold text-amber-200">"Drop Areas"</span>
+            {if drop_areas.is_empty() {
+                view! { <span class="text-zinc-400">"Cannot drop"</span> }.into_any()
+            } else {
+                drop_areas
+                    .iter()
+                    .map(|drop_area| {
+                        let source = match &drop_area.source {
+                            ItemDropSource::Area => "Area".to_string(),
+                            ItemDropSource::Boss => "Boss".to_string(),
+                            ItemDropSource::Map { name, .. } => format!("Edict '{name}'"),
+                        };
+                        view! {
+                            <div class="flex gap-2 text-zinc-300">
+                                <span class="flex-1">{area_name(&drop_area.area_id)}</span>
+                                <span class="text-zinc-500">{source}</span>
+                                <span>
+                                    {match drop_area.max_area_level {
+                                        Some(max_area_level) => {
+                                            format!(
+                                                "Levels {}-{}",
+                                                drop_area.min_area_level,
+                                                max_area_level,
+                                            )
+                                        }
+                                        None => format!("Levels {}+", drop_area.min_area_level),
+                                    }}
+                                    {drop_area.boss_only.then_some(" (Boss only)")}
+                                </span>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+                    .into_any()
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn AffixesList(affixes: Vec<AffixRollChance>) -> impl IntoView {
+    view! {
+        <div class="flex flex-col gap-1 text-sm">
+            <span class="font-semibold text-amber-200">"Affix Roll Chances"</span>
+            <table class="w-full">
+                <tbody>
+                    {affixes
+                        .into_iter()
+                        .map(|affix| {
+                            view! {
+                                <tr class="text-zinc-300">
+                                    <td>
+                                        {match affix.affix_type {
+                                            AffixType::Prefix => "Prefix",
+                                            AffixType::Suffix => "Suffix",
+                                            _ => "",
+                                        }}
+                                    </td>
+                                    <td>{affix.name}</td>
+                                    <td class="text-zinc-500">
+                                        {format!("{} T{}", affix.family, affix.tier)}
+                                    </td>
+                                    <td class="text-zinc-500">
+                                        {format!("Level {}", affix.item_level)}
+                                    </td>
+                                    <td class="text-right">{format!("{:.2}%", affix.chance)}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn rarity_str(rarity: ItemRarity) -> &'static str {
+    match rarity {
+        ItemRarity::Normal => "Normal",
+        ItemRarity::Magic => "Magic",
+        ItemRarity::Rare => "Rare",
+        ItemRarity::Masterwork => "Masterwork",
+        ItemRarity::Unique => "Unique",
+    }
+}
@@ -1,6 +1,7 @@
 mod home;
 mod items;
 mod logo;
+mod loot;
 mod passives;
 mod skills;
 mod ui_tests;
@@ -8,6 +9,7 @@ mod ui_tests;
 pub use home::HomePage;
 pub use items::ItemsPage;
 pub use logo::LogoPage;
+pub use loot::LootPage;
 pub use passives::PassivesPage;
 pub use skills::SkillsPage;
 pub use ui_tests::UiTestsPage;
@@ -319,6 +319,8 @@ fn new_skill_specs() -> BaseSkillSpecs {
         skill_type: SkillType::Other,
         cooldown: NonNegative::new(1.0),
         mana_cost: NonNegative::default(),
+        cast_time: NonNegative::default(),
+        channel: None,
         upgrade_cost: 0.0,
         upgrade_effects: Default::default(),
         modifier_effects: Default::default(),